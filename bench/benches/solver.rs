@@ -0,0 +1,79 @@
+//! Benchmarks of the phases of a search, across bitwidths, numbers of threads and backends, run from the `bench` directory with :
+//!
+//! ```sh
+//! cargo bench --features grumpkin,secp256k1 -- end_to_end/grumpkin
+//! ```
+//!
+//! The groups are the build of the table of baby steps (`table`, the baby phase), the giant steps of a search of the largest plaintext
+//! (`giant`, the giant phase), and the end-to-end computation of [`compute_dlog_on`], table included, on each curve enabled by the
+//! features (`end_to_end`). Their benchmarks are named `<group>/<backend>/bits=<bits>,threads=<threads>`, so that the usual filters
+//! and options of criterion apply, e.g `-- giant/baby-jubjub/bits=24 --save-baseline main` then `--baseline main` to compare two
+//! revisions. The throughput is the number of steps of the phase.
+
+use babygiant_alt_bn128::{compute_dlog_on, embed_plaintext, Curve, SolveConfig, Solver};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+const BITS: [u64; 3] = [16, 20, 24];
+const THREADS: [u64; 3] = [1, 2, 4];
+
+/// Plaintext found by the last giant step of a worker, the worst case of a search.
+fn largest(bits: u64) -> u64 {
+    (1u64 << bits) - 1
+}
+
+fn id(backend: &str, bits: u64, threads: u64) -> BenchmarkId {
+    BenchmarkId::new(backend, format!("bits={},threads={}", bits, threads))
+}
+
+fn table(c: &mut Criterion) {
+    let mut group = c.benchmark_group("table");
+    for bits in BITS {
+        let table_bits = bits / 2;
+        group.throughput(Throughput::Elements(1 << table_bits));
+        for threads in THREADS {
+            group.bench_function(id(Curve::BabyJubJub.name(), bits, threads), |b| {
+                b.iter(|| Solver::with_table_bitwidth(bits, table_bits, threads))
+            });
+        }
+    }
+    group.finish();
+}
+
+fn giant(c: &mut Criterion) {
+    let mut group = c.benchmark_group("giant");
+    for bits in BITS {
+        let table_bits = bits / 2;
+        let (x, y) = embed_plaintext(largest(bits));
+        group.throughput(Throughput::Elements(1 << (bits - table_bits)));
+        for threads in THREADS {
+            let solver = Solver::with_table_bitwidth(bits, table_bits, threads);
+            group.bench_function(id(Curve::BabyJubJub.name(), bits, threads), |b| {
+                b.iter(|| assert_eq!(solver.solve(&x, &y), Ok(largest(bits))))
+            });
+        }
+    }
+    group.finish();
+}
+
+fn end_to_end(c: &mut Criterion) {
+    let mut group = c.benchmark_group("end_to_end");
+    // a search of 24 bits builds its table every time, so the default 100 samples would take minutes
+    group.sample_size(10);
+    for bits in BITS {
+        let table_bits = bits / 2;
+        group.throughput(Throughput::Elements((1 << table_bits) + (1 << (bits - table_bits))));
+        for threads in THREADS {
+            let config = SolveConfig { max_bitwidth: bits, table_bitwidth: table_bits, num_threads: threads };
+            for curve in Curve::all() {
+                let (x, y) = curve.embed_plaintext(largest(bits));
+                group.bench_function(id(curve.name(), bits, threads), |b| {
+                    b.iter(|| assert_eq!(compute_dlog_on(curve, &x, &y, &config), Ok(largest(bits))))
+                });
+            }
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, table, giant, end_to_end);
+criterion_main!(benches);
@@ -0,0 +1,16 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    #[cfg(feature = "grpc")]
+    {
+        println!("cargo:rerun-if-changed=proto");
+        let file_descriptors = protox::compile(["proto/babygiant.proto"], ["proto"]).unwrap();
+        tonic_build::configure().compile_fds(file_descriptors).unwrap();
+    }
+    // without the service, only the messages are generated, in the same file
+    #[cfg(all(feature = "protobuf", not(feature = "grpc")))]
+    {
+        println!("cargo:rerun-if-changed=proto");
+        let file_descriptors = protox::compile(["proto/messages.proto"], ["proto"]).unwrap();
+        prost_build::Config::new().compile_fds(file_descriptors).unwrap();
+    }
+}
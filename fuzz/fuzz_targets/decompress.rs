@@ -0,0 +1,23 @@
+//! Fuzzes the decoding of the packed points of EIP-2494 and of the containers of ciphertexts, checking that the points which are
+//! decoded are encoded back into the same bytes.
+
+#![no_main]
+
+use babygiant_alt_bn128::container::Container;
+use babygiant_alt_bn128::curve::Point;
+use babygiant_alt_bn128::{pack_point, unpack_point};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(packed) = <[u8; 32]>::try_from(data) {
+        if let Ok(point) = Point::from_bytes(&packed) {
+            assert_eq!(point.to_bytes(), packed);
+        }
+        if let Ok((x, y)) = unpack_point(&packed) {
+            assert_eq!(pack_point(&x, &y), Ok(packed));
+        }
+    }
+    if let Ok(container) = Container::decode(data) {
+        assert_eq!(container.encode(), data);
+    }
+});
@@ -0,0 +1,42 @@
+//! Fuzzes the parsers of the coordinates given as strings or byte slices, e.g by the callers of an HTTP endpoint : the hexadecimal
+//! parsers of both parsing modes, and the conversions of the base64 strings and of the `Field`s of Noir. The input is split into the
+//! two coordinates at its first `,`.
+
+#![no_main]
+
+use babygiant_alt_bn128::{base64_to_hex, noir_field_to_hex, validate_embedding, ParseMode, Solver};
+use libfuzzer_sys::fuzz_target;
+use std::sync::OnceLock;
+
+fn solvers() -> &'static [Solver; 2] {
+    static SOLVERS: OnceLock<[Solver; 2]> = OnceLock::new();
+    SOLVERS.get_or_init(|| [Solver::new(8, 1), Solver::new(8, 1).with_parse_mode(ParseMode::Strict)])
+}
+
+fuzz_target!(|data: &[u8]| {
+    let (x, y) = match data.iter().position(|&b| b == b',') {
+        Some(i) => (&data[..i], &data[i + 1..]),
+        None => (data, data),
+    };
+    let [lenient, strict] = solvers();
+    let solved = lenient.solve_hex_bytes(x, y);
+    if strict.solve_hex_bytes(x, y).is_ok() {
+        assert_eq!(solved, strict.solve_hex_bytes(x, y));
+    }
+
+    let (Ok(x), Ok(y)) = (std::str::from_utf8(x), std::str::from_utf8(y)) else {
+        return;
+    };
+    let report = validate_embedding(x, y);
+    assert_eq!(report.is_valid(), report.error().is_none());
+    if solved.is_ok() {
+        assert!(report.is_valid(), "{}", report);
+    }
+    if let Ok(converted) = base64_to_hex(x) {
+        assert_eq!(ParseMode::Strict.check(&converted), Ok(()));
+    }
+    if let Ok(converted) = noir_field_to_hex(x) {
+        assert_eq!(ParseMode::Strict.check(&converted), Ok(()));
+        assert_eq!(noir_field_to_hex(&converted), Ok(converted));
+    }
+});
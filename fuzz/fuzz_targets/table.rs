@@ -0,0 +1,21 @@
+//! Fuzzes the readers of the table and checkpoint files, which should reject the truncated, corrupted or hostile files without
+//! allocating more memory than their size, however many entries their header claims.
+
+#![no_main]
+
+use babygiant_alt_bn128::table::inspect_table;
+use babygiant_alt_bn128::{Checkpoint, Solver};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let inspected = inspect_table(data);
+    let loaded = Solver::from_table(data, 32, 1);
+    if loaded.is_ok() {
+        assert!(inspected.is_ok());
+    }
+    if let Ok(checkpoint) = Checkpoint::read_from(data) {
+        let mut bytes = Vec::new();
+        checkpoint.write_to(&mut bytes).unwrap();
+        assert_eq!(Checkpoint::read_from(bytes.as_slice()), Ok(checkpoint));
+    }
+});
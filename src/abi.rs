@@ -0,0 +1,183 @@
+//! Decoding of the points and ciphertexts stored on-chain, from the calldata of a transaction or the data of an event log, and their
+//! encoding into calldata, so that relayers build the inputs of contracts and parse their outputs with the same layout.
+//!
+//! Solidity encodes a `(uint256 x, uint256 y)` pair, as well as a packed `(bytes32, bytes32)` pair, as two consecutive 32 bytes words,
+//! each of them a big-endian integer left-padded with zeros. Calldata starts with the 4 bytes selector of the called function, which is skipped.
+//! The structs `Point { uint256 x; uint256 y; }` and `Ciphertext { Point c1; Point c2; }` of the Solidity examples of noir-elgamal are
+//! static, so they are encoded in place as the words `x, y` and `c1.x, c1.y, c2.x, c2.y`, both as arguments and as return values.
+//! Contracts storing the little-endian serialization of the coordinates (e.g as produced by arkworks) are supported with [`Endianness::Little`].
+
+use crate::point::HexPoint;
+use crate::{decrypt_to_u64, Ciphertext, Error, Solver};
+
+/// Size of an ABI word, in bytes.
+const WORD_BYTES: usize = 32;
+/// Size of the selector at the start of calldata, in bytes.
+const SELECTOR_BYTES: usize = 4;
+
+/// Byte order of the coordinates in the ABI words.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Endianness {
+    /// Big-endian integers, as encoded by Solidity for `uint256` values.
+    #[default]
+    Big,
+    /// Little-endian integers, e.g the serialization of field elements by arkworks stored as `bytes32`.
+    Little,
+}
+
+/// Splits ABI-encoded `data` into its words, as `0x`-prefixed big-endian hexadecimal strings accepted as coordinates by the other functions of this crate.
+///
+/// `data` is an hexadecimal string, with or without the `0x` prefix. The selector of calldata, whose length is 4 bytes more than a multiple of a word, is skipped.
+pub fn decode_words(data: &str, endianness: Endianness) -> Result<Vec<String>, Error> {
+    let digits = data.trim().trim_start_matches("0x");
+    let bytes = hex::decode(digits).map_err(|e| Error::InvalidAbi(format!("{} is not an hexadecimal string : {}", data, e)))?;
+    let words = match bytes.len() % WORD_BYTES {
+        0 => &bytes[..],
+        SELECTOR_BYTES => &bytes[SELECTOR_BYTES..],
+        _ => return Err(Error::InvalidAbi(format!("{} bytes is neither a multiple of {} bytes, nor calldata with a selector", bytes.len(), WORD_BYTES))),
+    };
+    Ok(words
+        .chunks_exact(WORD_BYTES)
+        .map(|word| {
+            let mut word = word.to_vec();
+            if endianness == Endianness::Little {
+                word.reverse();
+            }
+            format!("0x{}", hex::encode(word))
+        })
+        .collect())
+}
+
+/// Decodes the point whose coordinates `x` and `y` are the words `offset` and `offset + 1` of `data`, see [`decode_words`].
+pub fn decode_point(data: &str, offset: usize, endianness: Endianness) -> Result<HexPoint, Error> {
+    let words = decode_words(data, endianness)?;
+    match words.get(offset..offset.saturating_add(2)) {
+        Some([x, y]) => Ok((x.clone(), y.clone())),
+        _ => Err(Error::InvalidAbi(format!("a point at word {} needs {} words, but there are only {}", offset, offset.saturating_add(2), words.len()))),
+    }
+}
+
+/// Decodes the ciphertext `(C1, C2)` whose coordinates `C1.x, C1.y, C2.x, C2.y` are the four words starting at `offset` in `data`, see [`decode_words`].
+pub fn decode_ciphertext(data: &str, offset: usize, endianness: Endianness) -> Result<(HexPoint, HexPoint), Error> {
+    let c1 = decode_point(data, offset, endianness)?;
+    let c2 = decode_point(data, offset.saturating_add(2), endianness)?;
+    Ok((c1, c2))
+}
+
+/// Decrypts the ciphertext whose coordinates `C1.x, C1.y, C2.x, C2.y` are the four words starting at `offset` in `words`, e.g the data of
+/// an encrypted-balance event returned by `eth_getLogs` split into its 32 bytes words, with `private_key` down to its plaintext, see
+/// [`crate::decrypt_to_u64`].
+pub fn decrypt_event(private_key: &str, words: &[[u8; WORD_BYTES]], offset: usize, endianness: Endianness, solver: &Solver) -> Result<u64, Error> {
+    let coordinates = words
+        .get(offset..offset.saturating_add(4))
+        .ok_or_else(|| Error::InvalidAbi(format!("a ciphertext at word {} needs {} words, but there are only {}", offset, offset.saturating_add(4), words.len())))?;
+    let data: String = coordinates.iter().map(hex::encode).collect();
+    let (c1, c2) = decode_ciphertext(&data, 0, endianness)?;
+    let ciphertext = Ciphertext::new((&c1.0, &c1.1), (&c2.0, &c2.1))?;
+    decrypt_to_u64(private_key, &ciphertext, solver)
+}
+
+/// Encodes `words`, `0x`-prefixed hexadecimal integers of at most 32 bytes such as the coordinates returned by the other functions of
+/// this crate, as consecutive ABI words, returned as a `0x`-prefixed hexadecimal string.
+pub fn encode_words(words: &[&str], endianness: Endianness) -> Result<String, Error> {
+    let mut encoded = String::from("0x");
+    for word in words {
+        let digits = word.strip_prefix("0x").ok_or_else(|| Error::InvalidAbi(format!("{} should be a 0x-prefixed hexadecimal string", word)))?;
+        let padded = format!("{:0>width$}", digits, width = 2 * WORD_BYTES);
+        let mut bytes = hex::decode(&padded).map_err(|e| Error::InvalidAbi(format!("{} is not an hexadecimal string : {}", word, e)))?;
+        if bytes.len() != WORD_BYTES {
+            return Err(Error::InvalidAbi(format!("{} is larger than a word of {} bytes", word, WORD_BYTES)));
+        }
+        if endianness == Endianness::Little {
+            bytes.reverse();
+        }
+        encoded.push_str(&hex::encode(bytes));
+    }
+    Ok(encoded)
+}
+
+/// Encodes the point `(x, y)` as a `Point` struct, i.e the two words `x, y`, the inverse of [`decode_point`].
+pub fn encode_point(point: (&str, &str), endianness: Endianness) -> Result<String, Error> {
+    encode_words(&[point.0, point.1], endianness)
+}
+
+/// Encodes the ciphertext `(C1, C2)` as a `Ciphertext` struct, i.e the four words `C1.x, C1.y, C2.x, C2.y`, the inverse of [`decode_ciphertext`].
+pub fn encode_ciphertext(c1: (&str, &str), c2: (&str, &str), endianness: Endianness) -> Result<String, Error> {
+    encode_words(&[c1.0, c1.1, c2.0, c2.1], endianness)
+}
+
+/// Prepends the 4 bytes `selector` of a function, e.g `0xa9059cbb`, to its `arguments` encoded by the functions above, giving the calldata of its call.
+pub fn encode_calldata(selector: &str, arguments: &str) -> Result<String, Error> {
+    let selector_digits = selector.strip_prefix("0x").unwrap_or(selector);
+    if selector_digits.len() != 2 * SELECTOR_BYTES || hex::decode(selector_digits).is_err() {
+        return Err(Error::InvalidAbi(format!("{} is not a selector of {} bytes", selector, SELECTOR_BYTES)));
+    }
+    Ok(format!("0x{}{}", selector_digits, arguments.strip_prefix("0x").unwrap_or(arguments)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const X: &str = "0x06184da392a17823e9c1d38cb50980b17150ffa411965b03f0b0200d9557daa9";
+    const Y: &str = "0x244a710118db92636e46e3f97bd80093ba7026ff97ca32d387145337e250549c";
+
+    #[test]
+    fn test_decode_point() {
+        let expected = (X.to_string(), Y.to_string());
+        let log_data = format!("{}{}", X, &Y[2..]);
+        assert_eq!(decode_point(&log_data, 0, Endianness::Big), Ok(expected.clone()));
+        // calldata of a function taking (uint256 id, uint256 x, uint256 y)
+        let calldata = format!("0x12345678{:064x}{}{}", 7, &X[2..], &Y[2..]);
+        assert_eq!(decode_point(&calldata, 1, Endianness::Big), Ok(expected.clone()));
+        assert_eq!(decode_words(&calldata, Endianness::Big).unwrap()[0], format!("0x{:064x}", 7));
+
+        let reversed = |s: &str| hex::encode(hex::decode(&s[2..]).unwrap().into_iter().rev().collect::<Vec<u8>>());
+        let packed_le = format!("{}{}", reversed(X), reversed(Y));
+        assert_eq!(decode_point(&packed_le, 0, Endianness::Little), Ok(expected));
+
+        assert!(matches!(decode_point(&log_data, 1, Endianness::Big), Err(Error::InvalidAbi(_))));
+        assert!(matches!(decode_point(&log_data[..64], 0, Endianness::Big), Err(Error::InvalidAbi(_))));
+        assert!(matches!(decode_point("0xzz", 0, Endianness::Big), Err(Error::InvalidAbi(_))));
+    }
+
+    #[test]
+    fn test_decode_ciphertext() {
+        let words: Vec<String> = (1..=4).map(|i| format!("{:064x}", i)).collect();
+        let (c1, c2) = decode_ciphertext(&words.concat(), 0, Endianness::Big).unwrap();
+        assert_eq!((c1.0, c2.1), (format!("0x{:064x}", 1), format!("0x{:064x}", 4)));
+        assert!(decode_ciphertext(&words.concat(), 1, Endianness::Big).is_err());
+    }
+
+    #[test]
+    fn test_encode() {
+        let point = encode_point((X, Y), Endianness::Big).unwrap();
+        assert_eq!(point, format!("{}{}", X, &Y[2..]));
+        assert_eq!(decode_point(&point, 0, Endianness::Little), decode_point(&encode_point((X, Y), Endianness::Little).unwrap(), 0, Endianness::Big));
+
+        let ciphertext = encode_ciphertext(("0x1", "0x2"), (X, Y), Endianness::Big).unwrap();
+        let calldata = encode_calldata("0x12345678", &ciphertext).unwrap();
+        assert_eq!(&calldata[..10], "0x12345678");
+        let expected = ((format!("0x{:064x}", 1), format!("0x{:064x}", 2)), (X.to_string(), Y.to_string()));
+        assert_eq!(decode_ciphertext(&calldata, 0, Endianness::Big), Ok(expected));
+
+        assert!(matches!(encode_words(&["1"], Endianness::Big), Err(Error::InvalidAbi(_))));
+        assert!(matches!(encode_words(&[&format!("{}00", X)], Endianness::Big), Err(Error::InvalidAbi(_))));
+        assert!(matches!(encode_words(&["0xzz"], Endianness::Big), Err(Error::InvalidAbi(_))));
+        assert!(matches!(encode_calldata("0x1234", &ciphertext), Err(Error::InvalidAbi(_))));
+    }
+
+    #[test]
+    fn test_decrypt_event() {
+        let sk = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        let pk = crate::priv_to_pub_key(sk).unwrap();
+        let (c1, c2) = crate::exp_elgamal_encrypt((&pk.0, &pk.1), 1234, "0x05").unwrap();
+        // event EncryptedBalance(address indexed owner, uint256 epoch, Ciphertext balance)
+        let data = format!("{:064x}{}", 7, &encode_ciphertext((&c1.0, &c1.1), (&c2.0, &c2.1), Endianness::Big).unwrap()[2..]);
+        let words: Vec<[u8; 32]> = hex::decode(data).unwrap().chunks_exact(32).map(|word| word.try_into().unwrap()).collect();
+        let solver = Solver::new(16, 1);
+        assert_eq!(decrypt_event(sk, &words, 1, Endianness::Big, &solver), Ok(1234));
+        assert!(matches!(decrypt_event(sk, &words, 2, Endianness::Big, &solver), Err(Error::InvalidAbi(_))));
+        assert!(decrypt_event(sk, &words, 0, Endianness::Big, &solver).is_err());
+    }
+}
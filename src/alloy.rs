@@ -0,0 +1,112 @@
+//! Conversions between the values of this crate and the [`U256`] and [`B256`] of [alloy](https://github.com/alloy-rs/core), so that
+//! indexers and relayers written with alloy can pass the words of calldata and event logs straight through, without formatting them
+//! as hexadecimal strings first.
+//!
+//! - A `Field`, e.g a coordinate, is a [`U256`] smaller than the modulus of the base field, see [`field_from_u256`] and [`field_to_u256`].
+//! - A [`Point`] is the pair `(x, y)` of the [`U256`]s of its Twisted Edwards coordinates, or the [`B256`] of its packed encoding of
+//!   EIP-2494, as stored in a `bytes32` by noir-elgamal, see [`crate::pack_point`].
+//! - A [`Ciphertext`] is the array `[C1.x, C1.y, C2.x, C2.y]` of the [`U256`]s of its coordinates, in the order of their ABI words.
+//!
+//! The conversions from alloy check their values like the other inputs of this crate : a [`Point`] should be on the curve, and the points
+//! of a [`Ciphertext`] in its prime subgroup.
+
+use alloy_primitives::{B256, U256};
+use ark_ed_on_bn254::Fq;
+use ark_ff::{BigInteger256, PrimeField};
+use crate::curve::Point;
+use crate::point::{field_to_hex, parse_field, point_coordinates, point_from_coordinates};
+use crate::{Ciphertext, Error};
+
+/// Parses the `Field` `value`, as a `0x`-prefixed hexadecimal string accepted by the other functions of this crate.
+pub fn field_from_u256(value: U256) -> Result<String, Error> {
+    Ok(field_to_hex(&to_field(value)?))
+}
+
+/// Converts the `Field` `f`, in the same format as the inputs of [`crate::do_compute_dlog`], to a [`U256`].
+pub fn field_to_u256(f: &str) -> Result<U256, Error> {
+    Ok(from_field(&parse_field(f)?))
+}
+
+fn to_field(value: U256) -> Result<Fq, Error> {
+    Fq::from_repr(BigInteger256::new(value.into_limbs())).ok_or_else(|| Error::InvalidFormat(format!("{:#x}", value)))
+}
+
+fn from_field(f: &Fq) -> U256 {
+    U256::from_limbs(f.into_repr().0)
+}
+
+impl TryFrom<(U256, U256)> for Point {
+    type Error = Error;
+
+    fn try_from((x, y): (U256, U256)) -> Result<Self, Error> {
+        Point::from_coordinates(to_field(x)?, to_field(y)?)
+    }
+}
+
+impl From<Point> for (U256, U256) {
+    fn from(point: Point) -> Self {
+        let (x, y) = point.coordinates();
+        (from_field(&x), from_field(&y))
+    }
+}
+
+impl TryFrom<B256> for Point {
+    type Error = Error;
+
+    fn try_from(packed: B256) -> Result<Self, Error> {
+        Point::from_bytes(&packed.0)
+    }
+}
+
+impl From<Point> for B256 {
+    fn from(point: Point) -> Self {
+        B256::new(point.to_bytes())
+    }
+}
+
+impl TryFrom<[U256; 4]> for Ciphertext {
+    type Error = Error;
+
+    fn try_from([c1x, c1y, c2x, c2y]: [U256; 4]) -> Result<Self, Error> {
+        let c1 = point_from_coordinates(to_field(c1x)?, to_field(c1y)?)?;
+        let c2 = point_from_coordinates(to_field(c2x)?, to_field(c2y)?)?;
+        Ok(Ciphertext { c1, c2 })
+    }
+}
+
+impl From<Ciphertext> for [U256; 4] {
+    fn from(ciphertext: Ciphertext) -> Self {
+        let [c1x, c1y] = point_coordinates(&ciphertext.c1);
+        let [c2x, c2y] = point_coordinates(&ciphertext.c2);
+        [from_field(&c1x), from_field(&c1y), from_field(&c2x), from_field(&c2y)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{exp_elgamal_encrypt, pack_point, priv_to_pub_key};
+
+    #[test]
+    fn test_alloy_conversions() {
+        let pk = priv_to_pub_key("0x05").unwrap();
+        let x = field_to_u256(&pk.0).unwrap();
+        assert_eq!(format!("{:#066x}", x), pk.0);
+        assert_eq!(field_from_u256(x), Ok(pk.0.clone()));
+        let modulus = U256::from_str_radix("30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001", 16).unwrap();
+        assert!(matches!(field_from_u256(modulus), Err(Error::InvalidFormat(_))));
+
+        let point = Point::try_from(&pk).unwrap();
+        let words: (U256, U256) = point.into();
+        assert_eq!(Point::try_from(words), Ok(point));
+        assert_eq!(Point::try_from((words.1, words.0)), Err(Error::NotOnCurve));
+        let packed = B256::from(point);
+        assert_eq!(packed.0, pack_point(&pk.0, &pk.1).unwrap());
+        assert_eq!(Point::try_from(packed), Ok(point));
+
+        let ciphertext: Ciphertext = exp_elgamal_encrypt((&pk.0, &pk.1), 42, "0x03").unwrap().try_into().unwrap();
+        let words: [U256; 4] = ciphertext.into();
+        assert_eq!(format!("{:#066x}", words[3]), ciphertext.c2().1);
+        assert_eq!(Ciphertext::try_from(words), Ok(ciphertext));
+    }
+}
@@ -0,0 +1,155 @@
+//! Audit trail of the searches, written as one JSON object per line so that the decryptions run by `babygiant` and `babygiant-server`
+//! are ingested by a log aggregator out of the box : who ran them, on what, when and for how long.
+//!
+//! A [`Record`] is written as `{"timestamp": "2024-05-01T12:00:00.123Z", "actor": "203.0.113.7", "operation": "dlog", "coordinates":
+//! ["0x...", "0x..."], "duration_ms": 12.5, "ok": true, "dlog": 42}`, with `"error": "..."` instead of `"dlog"` for the failed ones.
+//! The actor is the IP address of the client of the service, or the user running the command-line interface, and the coordinates are
+//! the ones of the embedded plaintext or the ciphertext given as input : the private keys are never recorded.
+
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use crate::Error;
+
+/// Entry of the audit trail, see the [module documentation](self).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    /// Start of the operation, in RFC 3339 format and in UTC, with milliseconds.
+    pub timestamp: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+    pub operation: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub coordinates: Vec<String>,
+    pub duration_ms: f64,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dlog: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Sink of the [`Record`]s, shared by the threads of a process.
+pub struct AuditLog {
+    out: Mutex<Box<dyn Write + Send>>,
+}
+
+impl AuditLog {
+    pub fn new<W: Write + Send + 'static>(out: W) -> Self {
+        AuditLog { out: Mutex::new(Box::new(out)) }
+    }
+
+    /// Appends the records to the file `path`, created if it does not exist.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        Ok(AuditLog::new(OpenOptions::new().create(true).append(true).open(path)?))
+    }
+
+    /// Writes `record` on its own line, flushed so that it is not lost if the process is killed.
+    pub fn write(&self, record: &Record) -> io::Result<()> {
+        let line = serde_json::to_string(record).expect("audit records are serializable");
+        let mut out = self.out.lock().unwrap();
+        writeln!(out, "{}", line)?;
+        out.flush()
+    }
+
+    /// Runs the search `f` of `operation` on `coordinates`, on behalf of `actor`, and records it with its duration and its result.
+    ///
+    /// The result is returned as is, even if the record could not be written, whose error is only reported on the standard error.
+    pub fn record(&self, actor: Option<&str>, operation: &str, coordinates: &[&str], f: impl FnOnce() -> Result<u64, Error>) -> Result<u64, Error> {
+        let (timestamp, start) = (SystemTime::now(), Instant::now());
+        let result = f();
+        let record = Record::new(actor, operation, coordinates, timestamp, start.elapsed(), result.as_ref().map(|dlog| Some(*dlog)).map_err(Error::to_string));
+        if let Err(e) = self.write(&record) {
+            eprintln!("Error: unable to write the audit record : {}", e);
+        }
+        result
+    }
+}
+
+impl Record {
+    /// Record of an operation started at `timestamp` and taking `duration`, whose result is a Discrete Logarithm, nothing for the
+    /// operations giving several results, e.g a batch, or the message of an error.
+    pub fn new(
+        actor: Option<&str>,
+        operation: &str,
+        coordinates: &[&str],
+        timestamp: SystemTime,
+        duration: Duration,
+        result: Result<Option<u64>, String>,
+    ) -> Self {
+        Record {
+            timestamp: rfc3339(timestamp),
+            actor: actor.map(str::to_string),
+            operation: operation.to_string(),
+            coordinates: coordinates.iter().map(|c| c.to_string()).collect(),
+            duration_ms: duration.as_secs_f64() * 1000.0,
+            ok: result.is_ok(),
+            dlog: result.as_ref().ok().copied().flatten(),
+            error: result.err(),
+        }
+    }
+}
+
+/// Formats `time` as `YYYY-MM-DDTHH:MM:SS.mmmZ`, converting the days since the epoch into a date of the proleptic Gregorian calendar.
+fn rfc3339(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let (days, seconds) = (since_epoch.as_secs() / 86400, since_epoch.as_secs() % 86400);
+    // the eras of 400 years start on the 1st of March, so that the leap day is the last day of their years
+    let days = days + 719468;
+    let (era, day_of_era) = (days / 146097, days % 146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 { month_from_march + 3 } else { month_from_march - 9 };
+    let year = era * 400 + year_of_era + u64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year, month, day, seconds / 3600, seconds / 60 % 60, seconds % 60, since_epoch.subsec_millis()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    /// Shared buffer of the records written by a log.
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_audit_log() {
+        assert_eq!(rfc3339(UNIX_EPOCH), "1970-01-01T00:00:00.000Z");
+        assert_eq!(rfc3339(UNIX_EPOCH + Duration::from_millis(951_782_400_123)), "2000-02-29T00:00:00.123Z");
+        assert_eq!(rfc3339(UNIX_EPOCH + Duration::from_secs(1_735_689_599)), "2024-12-31T23:59:59.000Z");
+
+        let buffer = Buffer::default();
+        let log = AuditLog::new(buffer.clone());
+        let p = crate::embed_plaintext(42);
+        let solver = crate::Solver::new(16, 1);
+        assert_eq!(log.record(Some("127.0.0.1"), "dlog", &[&p.0, &p.1], || solver.solve(&p.0, &p.1)), Ok(42));
+        assert_eq!(log.record(None, "dlog", &["0x1", "0x1"], || solver.solve("0x1", "0x1")), Err(Error::NotOnCurve));
+        let contents = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let records: Vec<Record> = contents.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!((records[0].actor.as_deref(), records[0].operation.as_str(), records[0].coordinates.clone()), (Some("127.0.0.1"), "dlog", vec![p.0, p.1]));
+        assert_eq!((records[0].ok, records[0].dlog, records[0].error.as_deref()), (true, Some(42), None));
+        assert_eq!((records[1].ok, records[1].dlog, records[1].error.clone()), (false, None, Some(Error::NotOnCurve.to_string())));
+        assert!(records[0].timestamp.ends_with('Z') && records[0].duration_ms >= 0.0);
+        let line: serde_json::Value = serde_json::from_str(contents.lines().nth(1).unwrap()).unwrap();
+        assert!(line.get("actor").is_none() && line.get("dlog").is_none());
+    }
+}
@@ -0,0 +1,165 @@
+//! Exponential ElGamal on Bandersnatch, the Twisted Edwards curve embedded in BLS12-381 with an efficient endomorphism, on which several
+//! newer protocols standardize, e.g the Verkle trees of Ethereum, with the same Baby-step Giant-step search as Baby Jubjub.
+//!
+//! Bandersnatch is the curve `-5x^2 + y^2 = 1 + d·x^2y^2` over the scalar field of BLS12-381, with the parameters and the generator
+//! of `ed_on_bls12_381_bandersnatch` of arkworks, see [`GENERATOR`]. Its group has the order `4·r`, and the inputs are checked to be in
+//! its prime subgroup of order `r`, see [`SUBGROUP_ORDER`]. Points are encoded as their affine coordinates `(x, y)` in `0x`-prefixed
+//! hexadecimal strings, and scalars, i.e private keys and the encryption randomness, are elements of the base field taken modulo `r`,
+//! like the scalars of Baby Jubjub.
+
+use ark_ec::models::twisted_edwards_extended::GroupAffine;
+use ark_ec::models::{ModelParameters, MontgomeryModelParameters, TEModelParameters};
+use ark_ec::AffineCurve;
+use ark_ed_on_bls12_381::Fq;
+use ark_ff::biginteger::BigInteger256 as BigInteger;
+use ark_ff::fields::{FftParameters, Fp256, Fp256Parameters, FpParameters};
+use ark_ff::{field_new, PrimeField};
+use crate::dlog::{self, DlogCurve};
+use crate::point::field_to_hex;
+use crate::table::ArkworksCurve;
+use crate::{Error, HexPoint};
+
+/// Coordinates of the generator `G` of the prime subgroup of Bandersnatch used by arkworks.
+pub const GENERATOR: (&str, &str) = (
+    "0x29c132cc2c0b34c5743711777bbe42f32b79c022ad998465e1e71866a252ae18",
+    "0x2a6c669eda123e0f157d8b50badcd586358cad81eee464605e3167b6cc974166",
+);
+
+/// Order `r` of the prime subgroup of Bandersnatch.
+pub const SUBGROUP_ORDER: &str = "0x1cfb69d4ca675f520cce760202687600ff8f87007419047174fd06b52876e7e1";
+
+/// Scalar field of Bandersnatch, i.e the integers modulo [`SUBGROUP_ORDER`].
+pub(crate) type Fr = Fp256<FrParameters>;
+
+pub(crate) struct FrParameters;
+
+impl Fp256Parameters for FrParameters {}
+
+impl FftParameters for FrParameters {
+    type BigInt = BigInteger;
+
+    const TWO_ADICITY: u32 = 5;
+
+    const TWO_ADIC_ROOT_OF_UNITY: BigInteger = BigInteger([0x4b263b9a8d79c573, 0xeadb3d0a007af1fd, 0xa54c8a4668832589, 0x0610860c4254fb9d]);
+}
+
+impl FpParameters for FrParameters {
+    const MODULUS: BigInteger = BigInteger([0x74fd06b52876e7e1, 0xff8f870074190471, 0x0cce760202687600, 0x1cfb69d4ca675f52]);
+
+    const MODULUS_BITS: u32 = 253;
+
+    const CAPACITY: u32 = Self::MODULUS_BITS - 1;
+
+    const REPR_SHAVE_BITS: u32 = 3;
+
+    const R: BigInteger = BigInteger([0x5817ca56bc48c0f8, 0x0383c7fc5f37dc74, 0x998c4fefecbc4ff8, 0x1824b159acc5056f]);
+
+    const R2: BigInteger = BigInteger([0xdbb4f5d658db47cb, 0x40fa7ca27fecb938, 0xaa9e6daec0055cea, 0x0ae793ddb14aec7d]);
+
+    const INV: u64 = 0xf19f22295cc063df;
+
+    /// `7`, in Montgomery form.
+    const GENERATOR: BigInteger = BigInteger([0x1fb566d55baabf63, 0x1accd4e45609f0f7, 0xf2cde1856d1be1c3, 0x1817c84bc55e4972]);
+
+    const MODULUS_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([0xba7e835a943b73f0, 0x7fc7c3803a0c8238, 0x06673b0101343b00, 0x0e7db4ea6533afa9]);
+
+    const T: BigInteger = BigInteger([0x8ba7e835a943b73f, 0x07fc7c3803a0c823, 0x906673b0101343b0, 0x00e7db4ea6533afa]);
+
+    const T_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([0xc5d3f41ad4a1db9f, 0x03fe3e1c01d06411, 0x483339d80809a1d8, 0x0073eda753299d7d]);
+}
+
+#[derive(Clone, Default, PartialEq, Eq)]
+pub(crate) struct Parameters;
+
+impl ModelParameters for Parameters {
+    type BaseField = Fq;
+    type ScalarField = Fr;
+}
+
+impl TEModelParameters for Parameters {
+    const COEFF_A: Fq = field_new!(Fq, "-5");
+    const COEFF_D: Fq = field_new!(Fq, "45022363124591815672509500913686876175488063829319466900776701791074614335719");
+    const COFACTOR: &'static [u64] = &[4];
+    const COFACTOR_INV: Fr = field_new!(Fr, "9831726595336160714896451345284868594481866920080427688839802480047265754601");
+    const AFFINE_GENERATOR_COEFFS: (Fq, Fq) = (
+        field_new!(Fq, "18886178867200960497001835917649091219057080094937609519140440539760939937304"),
+        field_new!(Fq, "19188667384257783945677642223292697773471335439753913231509108946878080696678"),
+    );
+
+    type MontgomeryModelParameters = Parameters;
+}
+
+impl MontgomeryModelParameters for Parameters {
+    /// `2·(a + d) / (a - d)`
+    const COEFF_A: Fq = field_new!(Fq, "29978822694968839326280996386011761570173833766074948509196803838190355340952");
+    /// `4 / (a - d)`
+    const COEFF_B: Fq = field_new!(Fq, "25465760566081946422412445027709227188579564747101592991722834452325077642517");
+
+    type TEModelParameters = Parameters;
+}
+
+pub(crate) type Bandersnatch = GroupAffine<Parameters>;
+
+impl ArkworksCurve for Bandersnatch {
+    fn x_low_bits(&self) -> u64 {
+        self.x.into_repr().0[0]
+    }
+}
+
+impl DlogCurve for Bandersnatch {
+    type Scalar = Fr;
+
+    fn generator() -> Self {
+        Self::prime_subgroup_generator()
+    }
+
+    fn mul_scalar(&self, s: &Fr) -> <Self as AffineCurve>::Projective {
+        self.mul(*s)
+    }
+
+    fn parse_point(x: &str, y: &str) -> Result<Self, Error> {
+        dlog::edwards_point(x, y)
+    }
+
+    fn to_hex(&self) -> HexPoint {
+        (field_to_hex(&self.x), field_to_hex(&self.y))
+    }
+
+    fn parse_scalar(s: &str) -> Result<Fr, Error> {
+        dlog::reduced_scalar::<Fq, Fr>(s)
+    }
+}
+
+dlog::curve_module!(Bandersnatch, edwards);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ec::{AffineCurve};
+    use ark_ff::{One, Zero};
+
+    #[test]
+    fn test_bandersnatch() {
+        let g = Bandersnatch::prime_subgroup_generator();
+        assert!(g.is_on_curve() && g.is_in_correct_subgroup_assuming_on_curve());
+        assert!(g.mul(FrParameters::MODULUS).is_zero() && !g.is_zero());
+        assert_eq!(g.to_hex(), (GENERATOR.0.to_string(), GENERATOR.1.to_string()));
+        assert_eq!(field_to_hex(&-Fr::one()), format!("{}0", &SUBGROUP_ORDER[..65]));
+
+        let private_key = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        let pk = priv_to_pub_key(private_key).unwrap();
+        let (c1, c2) = exp_elgamal_encrypt((&pk.0, &pk.1), 65535, "0x05").unwrap();
+        assert_eq!(exp_elgamal_decrypt(private_key, (&c1.0, &c1.1), (&c2.0, &c2.1)), Ok(embed_plaintext(65535)));
+        let solver = Solver::new(16, 2);
+        assert_eq!(decrypt_to_u64(private_key, (&c1.0, &c1.1), (&c2.0, &c2.1), &solver), Ok(65535));
+        for plaintext in [0, 1, 255, 256] {
+            let p = embed_plaintext(plaintext);
+            assert_eq!(solver.solve(&p.0, &p.1), Ok(plaintext));
+        }
+        // the scalars are taken modulo the order of the prime subgroup
+        assert_eq!(priv_to_pub_key(SUBGROUP_ORDER), Ok(embed_plaintext(0)));
+        // (0, -1) is on the curve, of order 2
+        assert_eq!(Bandersnatch::parse_point("0x00", &field_to_hex(&-Fq::one())), Err(Error::NotInSubgroup));
+        assert_eq!(Bandersnatch::parse_point("0x01", "0x01"), Err(Error::NotOnCurve));
+    }
+}
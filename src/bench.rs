@@ -0,0 +1,152 @@
+//! Measurements of the cost of the Baby-step Giant-step algorithm on the current machine, to choose the number of threads and the size of the table.
+//!
+//! A search of `b` bits with a table of `2^t` baby steps costs `2^t` additions to build the table, then up to `2^(b - t)` giant steps,
+//! each of them being a subtraction, a conversion to affine coordinates and a lookup in the table. The rates at which these operations run
+//! are measured on small tables and extrapolated to the target bitwidth by [`recommend`].
+
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ff::BigInteger256;
+use std::time::{Duration, Instant};
+use crate::point::{base_point, point_to_hex};
+use crate::Solver;
+
+/// Approximate size of an entry of the table of baby steps, including the overhead of the `HashMap`.
+pub const TABLE_ENTRY_BYTES: u64 = 26;
+
+/// Cost of the elementary operations of a search on one thread, in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OpCosts {
+    /// Addition of two points in affine coordinates, done for each baby step.
+    pub affine_addition_ns: f64,
+    /// Subtraction of a point in projective coordinates, done for each giant step.
+    pub projective_subtraction_ns: f64,
+    /// Conversion from projective to affine coordinates, done for each giant step.
+    pub affine_conversion_ns: f64,
+}
+
+/// Measures the cost of the elementary operations, each of them repeated `samples` times.
+pub fn measure_op_costs(samples: u32) -> OpCosts {
+    let g = base_point();
+    let per_op = |elapsed: Duration| elapsed.as_nanos() as f64 / f64::from(samples);
+
+    let mut v = g;
+    let start = Instant::now();
+    for _ in 0..samples {
+        v += &g;
+    }
+    let affine_addition_ns = per_op(start.elapsed());
+
+    let mut gamma = v.into_projective();
+    let am = g.mul(BigInteger256::from(samples as u64));
+    let start = Instant::now();
+    for _ in 0..samples {
+        gamma -= &am;
+    }
+    let projective_subtraction_ns = per_op(start.elapsed());
+
+    let start = Instant::now();
+    for _ in 0..samples {
+        // keep the conversions from being optimized away
+        let _ = std::hint::black_box(std::hint::black_box(&gamma).into_affine());
+    }
+    let affine_conversion_ns = per_op(start.elapsed());
+
+    OpCosts { affine_addition_ns, projective_subtraction_ns, affine_conversion_ns }
+}
+
+/// Throughput of a [`Solver`] using `threads` threads.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThreadMeasurement {
+    pub threads: u64,
+    /// Baby steps computed per second when building the table.
+    pub build_rate: f64,
+    /// Giant steps done per second by a search.
+    pub step_rate: f64,
+}
+
+/// Measures the throughput of a solver with a table of `2^sample_bitwidth` baby steps, running `2^sample_bitwidth` giant steps.
+pub fn measure_threads(threads: u64, sample_bitwidth: u64) -> ThreadMeasurement {
+    let start = Instant::now();
+    let solver = Solver::with_table_bitwidth(2 * sample_bitwidth, sample_bitwidth, threads);
+    let build_rate = (1u64 << sample_bitwidth) as f64 / start.elapsed().as_secs_f64();
+
+    // a point out of the range, so that all the giant steps are done
+    let (x, y) = point_to_hex(&base_point().mul(BigInteger256::from(u64::MAX)).into_affine());
+    let search = solver.search(&x, &y).unwrap();
+    let start = Instant::now();
+    let _ = search.run();
+    let step_rate = solver.giant_steps() as f64 / start.elapsed().as_secs_f64();
+
+    ThreadMeasurement { threads, build_rate, step_rate }
+}
+
+/// Configuration chosen by [`recommend`], with its expected costs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Recommendation {
+    pub threads: u64,
+    pub table_bitwidth: u64,
+    /// Expected time to build the table.
+    pub build_time: Duration,
+    /// Expected time of a search, i.e half of its worst case.
+    pub search_time: Duration,
+    /// Expected memory used by the table, in bytes.
+    pub table_bytes: u64,
+}
+
+/// Chooses the number of threads and the size of the table minimizing the total time of `solves` searches of `max_bitwidth` bits,
+/// whose table uses at most `max_memory` bytes.
+///
+/// The table is built once and shared by all the searches, so a larger `solves` leads to a larger table.
+pub fn recommend(measurements: &[ThreadMeasurement], max_bitwidth: u64, max_memory: u64, solves: u64) -> Option<Recommendation> {
+    let mut best: Option<(f64, Recommendation)> = None;
+    for m in measurements {
+        // a search of 64 bits needs a table of at least 1 bit, see `Solver::with_table_bitwidth`
+        for table_bitwidth in max_bitwidth.saturating_sub(63)..=max_bitwidth.min(32) {
+            let table_bytes = TABLE_ENTRY_BYTES << table_bitwidth;
+            if table_bytes > max_memory {
+                break;
+            }
+            let build_time = (1u64 << table_bitwidth) as f64 / m.build_rate;
+            let search_time = (1u64 << (max_bitwidth - table_bitwidth)) as f64 / m.step_rate / 2.0;
+            let total = build_time + solves as f64 * search_time;
+            if best.map_or(true, |(t, _)| total < t) {
+                let recommendation = Recommendation {
+                    threads: m.threads,
+                    table_bitwidth,
+                    build_time: Duration::from_secs_f64(build_time),
+                    search_time: Duration::from_secs_f64(search_time),
+                    table_bytes,
+                };
+                best = Some((total, recommendation));
+            }
+        }
+    }
+    best.map(|(_, r)| r)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recommend() {
+        let measurements = [
+            ThreadMeasurement { threads: 1, build_rate: 1e6, step_rate: 1e6 },
+            ThreadMeasurement { threads: 2, build_rate: 2e6, step_rate: 1.5e6 },
+        ];
+        let r = recommend(&measurements, 40, u64::MAX, 1).unwrap();
+        assert_eq!((r.threads, r.table_bitwidth), (2, 20));
+        // the table grows with the number of searches sharing it, until it reaches the memory limit
+        assert_eq!(recommend(&measurements, 40, u64::MAX, 1000).unwrap().table_bitwidth, 25);
+        assert_eq!(recommend(&measurements, 40, TABLE_ENTRY_BYTES << 22, 1000).unwrap().table_bitwidth, 22);
+        assert_eq!(recommend(&measurements, 40, 0, 1), None);
+    }
+
+    #[test]
+    fn test_measure() {
+        let costs = measure_op_costs(1000);
+        assert!(costs.affine_addition_ns > 0.0 && costs.affine_conversion_ns > 0.0);
+        let m = measure_threads(2, 8);
+        assert!(m.build_rate > 0.0 && m.step_rate > 0.0);
+    }
+}
@@ -0,0 +1,36 @@
+//! Coordinator of a search sharded between several `babygiant-server` workers, see [`babygiant_alt_bn128::server::shard`].
+
+use babygiant_alt_bn128::server::shard::Coordinator;
+use clap::Parser;
+use std::process;
+
+#[derive(Parser)]
+#[command(name = "babygiant-coordinator", version, about = "Compute the Discrete Logarithm of a noir-elgamal embedded plaintext on several babygiant-server workers")]
+struct Args {
+    /// x coordinate of the embedded plaintext, in Twisted Edwards form
+    x: String,
+    /// y coordinate of the embedded plaintext, in Twisted Edwards form
+    y: String,
+    /// Base URL of a worker, e.g http://10.0.0.1:8080 (repeat for each worker)
+    #[arg(long = "worker", value_name = "URL", required = true)]
+    workers: Vec<String>,
+    /// Maximum bitwidth of the embedded plaintext, which must be the one of all the workers
+    #[arg(long, default_value_t = 40, value_parser = clap::value_parser!(u64).range(1..=64))]
+    bits: u64,
+    /// Number of shards sent to each worker
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u64).range(1..))]
+    shards_per_worker: u64,
+}
+
+#[tokio::main]
+async fn main() {
+    let args = Args::parse();
+    let coordinator = Coordinator::new(args.workers, args.bits).with_shards_per_worker(args.shards_per_worker);
+    match coordinator.solve(&args.x, &args.y).await {
+        Ok(dlog) => println!("{}", dlog),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    }
+}
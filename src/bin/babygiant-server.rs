@@ -0,0 +1,162 @@
+//! HTTP decryption service sharing one table of baby steps between all the requests, see [`babygiant_alt_bn128::server`].
+
+use babygiant_alt_bn128::audit::AuditLog;
+use babygiant_alt_bn128::config::Config;
+use babygiant_alt_bn128::events::JsonLines;
+use babygiant_alt_bn128::server::limits::{Limits, RateLimit};
+use babygiant_alt_bn128::server::{self, cache::Cache, Backend};
+use babygiant_alt_bn128::{Error, ParseMode, Solver};
+use clap::Parser;
+use std::fs::File;
+use std::io::BufReader;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+#[derive(Parser)]
+#[command(name = "babygiant-server", version, about = "HTTP service computing Discrete Logarithms of noir-elgamal embedded plaintexts")]
+struct Args {
+    /// TOML configuration file providing the default value of the options, see the `config` module of the library
+    #[arg(long, value_name = "FILE", env = "BABYGIANT_CONFIG")]
+    config: Option<PathBuf>,
+    /// Address the service listens on [default: 127.0.0.1:8080]
+    #[arg(long, env = "BABYGIANT_SERVER_BIND")]
+    bind: Option<SocketAddr>,
+    /// Number of threads used by each search [default: number of available cores]
+    #[arg(long, env = "BABYGIANT_THREADS", value_parser = clap::value_parser!(u64).range(1..))]
+    threads: Option<u64>,
+    /// Maximum bitwidth of the embedded plaintexts [default: 40]
+    #[arg(long, env = "BABYGIANT_BITS", value_parser = clap::value_parser!(u64).range(1..=64))]
+    bits: Option<u64>,
+    /// Bitwidth of the table of baby steps, see `babygiant bench` [default: half of --bits]
+    #[arg(long, env = "BABYGIANT_TABLE_BITS", value_parser = clap::value_parser!(u64).range(0..=32))]
+    table_bits: Option<u64>,
+    /// Load the table of baby steps from a file written by `babygiant table build`, instead of building it
+    #[arg(long, value_name = "FILE", env = "BABYGIANT_TABLE", conflicts_with = "table_bits")]
+    table: Option<PathBuf>,
+    /// Only accept the coordinates written as 0x followed by 64 hexadecimal digits [default: the parse_mode of the configuration file, or lenient]
+    #[arg(long)]
+    strict: bool,
+    /// Append a record of every search, with the IP address of its client, to this file of JSON lines
+    #[arg(long, value_name = "FILE", env = "BABYGIANT_AUDIT_LOG")]
+    audit_log: Option<PathBuf>,
+    /// Write an event of each worker of the searches every STEPS giant steps, as JSON lines on the standard error
+    #[arg(long, value_name = "STEPS", env = "BABYGIANT_PROGRESS_INTERVAL", value_parser = clap::value_parser!(u64).range(1..))]
+    progress_interval: Option<u64>,
+    /// Directory of a persistent cache of the results, created if it does not exist
+    #[arg(long, value_name = "PATH", env = "BABYGIANT_CACHE_PATH")]
+    cache: Option<PathBuf>,
+    /// Requests per second allowed for each client IP address
+    #[arg(long, value_name = "PER_SECOND", env = "BABYGIANT_SERVER_RATE_LIMIT", value_parser = parse_rate)]
+    rate_limit: Option<f64>,
+    /// Number of requests each client can send at once before being rate limited [default: the rate limit, at least 1]
+    #[arg(long, env = "BABYGIANT_SERVER_BURST")]
+    burst: Option<u32>,
+    /// Maximum number of requests solved at the same time, the other ones waiting in a queue
+    #[arg(long, env = "BABYGIANT_SERVER_MAX_CONCURRENT_SOLVES", value_parser = clap::value_parser!(u64).range(1..))]
+    max_concurrent_solves: Option<u64>,
+    /// Maximum number of requests waiting in the queue, beyond which they are rejected [default: unbounded]
+    #[arg(long, env = "BABYGIANT_SERVER_MAX_QUEUED")]
+    max_queued: Option<usize>,
+    /// Address of the gRPC service, served alongside the HTTP one
+    #[cfg(feature = "grpc")]
+    #[arg(long, value_name = "ADDR", env = "BABYGIANT_SERVER_GRPC_BIND")]
+    grpc_bind: Option<SocketAddr>,
+}
+
+impl Args {
+    /// Takes the options which are not set on the command line from the configuration file.
+    fn with_config(mut self, config: Config) -> Self {
+        self.bind = self.bind.or(config.server.bind);
+        self.threads = self.threads.or(config.threads);
+        self.bits = self.bits.or(config.bits);
+        if self.table_bits.is_none() {
+            self.table = self.table.or(config.table);
+        }
+        self.table_bits = self.table_bits.or(config.table_bits);
+        self.strict = self.strict || config.parse_mode == Some(ParseMode::Strict);
+        self.audit_log = self.audit_log.or(config.audit_log);
+        self.progress_interval = self.progress_interval.or(config.progress_interval);
+        self.cache = self.cache.or(config.cache.path);
+        self.rate_limit = self.rate_limit.or(config.server.rate_limit);
+        self.burst = self.burst.or(config.server.burst);
+        self.max_concurrent_solves = self.max_concurrent_solves.or(config.server.max_concurrent_solves);
+        self.max_queued = self.max_queued.or(config.server.max_queued);
+        #[cfg(feature = "grpc")]
+        {
+            self.grpc_bind = self.grpc_bind.or(config.server.grpc_bind);
+        }
+        self
+    }
+}
+
+fn parse_rate(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(rate) if rate > 0.0 && rate.is_finite() => Ok(rate),
+        _ => Err(format!("{:?} is not a positive number", s)),
+    }
+}
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let args = Args::parse();
+    let config = Config::load(args.config.as_deref()).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+    let args = args.with_config(config);
+    let num_threads = args.threads.unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get() as u64));
+    let bits = args.bits.unwrap_or(40);
+    let table_bits = args.table_bits.unwrap_or(bits / 2).clamp(bits.saturating_sub(63), bits);
+    let solver = match args.table.clone() {
+        Some(path) => {
+            eprintln!("Loading the table of baby steps from {}...", path.display());
+            let load = move || File::open(&path).map_err(|e| Error::InvalidTable(format!("{} : {}", path.display(), e))).and_then(|f| Solver::from_table(BufReader::new(f), bits, num_threads));
+            tokio::task::spawn_blocking(load).await.unwrap().map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?
+        }
+        None => {
+            eprintln!("Building the table of baby steps for {} bits...", bits);
+            tokio::task::spawn_blocking(move || Solver::with_table_bitwidth(bits, table_bits, num_threads)).await.unwrap()
+        }
+    };
+    let mut solver = solver.with_parse_mode(if args.strict { ParseMode::Strict } else { ParseMode::Lenient });
+    if let Some(giant_steps) = args.progress_interval {
+        JsonLines::install().map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        solver = solver.with_progress_interval(giant_steps);
+    }
+    let mut backend = Backend::new(Arc::new(solver));
+    if let Some(path) = &args.cache {
+        let cache = Cache::open(path).map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        eprintln!("Opened the cache of results {} ({} points)", path.display(), cache.len());
+        backend = backend.with_cache(Arc::new(cache));
+    }
+    if let Some(path) = &args.audit_log {
+        let audit_log = AuditLog::open(path).map_err(|e| std::io::Error::new(e.kind(), format!("{} : {}", path.display(), e)))?;
+        eprintln!("Recording the searches in {}", path.display());
+        backend = backend.with_audit_log(Arc::new(audit_log));
+    }
+    let listener = tokio::net::TcpListener::bind(args.bind.unwrap_or(SocketAddr::from(([127, 0, 0, 1], 8080)))).await?;
+    eprintln!("Listening on http://{}", listener.local_addr()?);
+    if args.burst.is_some() && args.rate_limit.is_none() {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "burst requires a rate limit"));
+    }
+    if args.max_queued.is_some() && args.max_concurrent_solves.is_none() {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "max_queued requires a maximum number of concurrent solves"));
+    }
+    let limits = Limits {
+        rate: args.rate_limit.map(|per_second| RateLimit { per_second, burst: args.burst.map_or(per_second.ceil().max(1.0), f64::from) }),
+        max_concurrent_solves: args.max_concurrent_solves.map(|n| n as usize),
+        max_queued: args.max_queued,
+    };
+    // the limits are shared by the HTTP and gRPC services
+    let backend = backend.with_limits(limits);
+    let router = server::router(backend.clone());
+    let http = axum::serve(listener, router.into_make_service_with_connect_info::<SocketAddr>());
+    #[cfg(feature = "grpc")]
+    if let Some(grpc_bind) = args.grpc_bind {
+        eprintln!("Listening on grpc://{}", grpc_bind);
+        let grpc = tonic::transport::Server::builder()
+            .add_service(babygiant_alt_bn128::grpc::BabygiantService::new(backend).into_server())
+            .serve(grpc_bind);
+        let grpc = async { grpc.await.map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e)) };
+        return tokio::try_join!(std::future::IntoFuture::into_future(http), grpc).map(|_| ());
+    }
+    http.await
+}
@@ -0,0 +1,108 @@
+//! Queue worker processing decryption jobs from Redis or NATS, see [`babygiant_alt_bn128::worker`].
+
+use babygiant_alt_bn128::config::Config;
+use babygiant_alt_bn128::events::JsonLines;
+use babygiant_alt_bn128::worker::{self, NatsConfig, RedisConfig};
+use babygiant_alt_bn128::{Error, ParseMode, Solver};
+use clap::{Parser, Subcommand};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+#[derive(Parser)]
+#[command(name = "babygiant-worker", version, about = "Queue worker computing Discrete Logarithms of noir-elgamal embedded plaintexts")]
+struct Cli {
+    #[command(subcommand)]
+    queue: Queue,
+    /// TOML configuration file providing the number of threads and the bitwidths, see the `config` module of the library
+    #[arg(long, global = true, value_name = "FILE", env = "BABYGIANT_CONFIG")]
+    config: Option<PathBuf>,
+    /// Number of threads used by each search [default: number of available cores]
+    #[arg(long, global = true, env = "BABYGIANT_THREADS", value_parser = clap::value_parser!(u64).range(1..))]
+    threads: Option<u64>,
+    /// Maximum bitwidth of the embedded plaintexts [default: 40]
+    #[arg(long, global = true, env = "BABYGIANT_BITS", value_parser = clap::value_parser!(u64).range(1..=64))]
+    bits: Option<u64>,
+    /// Bitwidth of the table of baby steps, see `babygiant bench` [default: half of --bits]
+    #[arg(long, global = true, env = "BABYGIANT_TABLE_BITS", value_parser = clap::value_parser!(u64).range(0..=32))]
+    table_bits: Option<u64>,
+    /// Load the table of baby steps from a file written by `babygiant table build`, instead of building it
+    #[arg(long, global = true, value_name = "FILE", env = "BABYGIANT_TABLE", conflicts_with = "table_bits")]
+    table: Option<PathBuf>,
+    /// Only accept the coordinates written as 0x followed by 64 hexadecimal digits [default: the parse_mode of the configuration file, or lenient]
+    #[arg(long, global = true)]
+    strict: bool,
+    /// Write an event of each worker of the searches every STEPS giant steps, as JSON lines on the standard error
+    #[arg(long, global = true, value_name = "STEPS", env = "BABYGIANT_PROGRESS_INTERVAL", value_parser = clap::value_parser!(u64).range(1..))]
+    progress_interval: Option<u64>,
+}
+
+#[derive(Subcommand)]
+enum Queue {
+    /// Pop the jobs from a Redis list and push the results to another one
+    Redis {
+        #[arg(long, default_value = "redis://127.0.0.1:6379")]
+        url: String,
+        #[arg(long, default_value = "babygiant:jobs")]
+        jobs: String,
+        #[arg(long, default_value = "babygiant:results")]
+        results: String,
+        /// Identifier of this worker, which must be unique among the workers of the same list
+        #[arg(long, default_value = "0")]
+        worker_id: String,
+    },
+    /// Consume the jobs of a NATS JetStream subject and publish the results on another one
+    Nats {
+        #[arg(long, default_value = "nats://127.0.0.1:4222")]
+        url: String,
+        #[arg(long, default_value = "BABYGIANT")]
+        stream: String,
+        #[arg(long, default_value = "babygiant.jobs")]
+        jobs: String,
+        #[arg(long, default_value = "babygiant.results")]
+        results: String,
+        #[arg(long, default_value = "babygiant-worker")]
+        consumer: String,
+        #[arg(long, default_value = "babygiant-dlogs")]
+        bucket: String,
+    },
+}
+
+#[tokio::main]
+async fn main() -> Result<(), worker::BoxError> {
+    let cli = Cli::parse();
+    let config = Config::load(cli.config.as_deref())?;
+    let num_threads = cli.threads.or(config.threads).unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get() as u64));
+    let bits = cli.bits.or(config.bits).unwrap_or(40);
+    let table = if cli.table_bits.is_none() { cli.table.or(config.table) } else { None };
+    let table_bits = cli.table_bits.or(config.table_bits).unwrap_or(bits / 2).clamp(bits.saturating_sub(63), bits);
+    let solver = match table {
+        Some(path) => {
+            eprintln!("Loading the table of baby steps from {}...", path.display());
+            let load = move || File::open(&path).map_err(|e| Error::InvalidTable(format!("{} : {}", path.display(), e))).and_then(|f| Solver::from_table(BufReader::new(f), bits, num_threads));
+            tokio::task::spawn_blocking(load).await??
+        }
+        None => {
+            eprintln!("Building the table of baby steps for {} bits...", bits);
+            tokio::task::spawn_blocking(move || Solver::with_table_bitwidth(bits, table_bits, num_threads)).await?
+        }
+    };
+    let parse_mode = if cli.strict { ParseMode::Strict } else { config.parse_mode.unwrap_or_default() };
+    let mut solver = solver.with_parse_mode(parse_mode);
+    if let Some(giant_steps) = cli.progress_interval.or(config.progress_interval) {
+        JsonLines::install()?;
+        solver = solver.with_progress_interval(giant_steps);
+    }
+    let solver = Arc::new(solver);
+    match cli.queue {
+        Queue::Redis { url, jobs, results, worker_id } => {
+            eprintln!("Waiting for jobs on the Redis list {}", jobs);
+            worker::run_redis(solver, &RedisConfig { url, jobs, results, worker_id }).await
+        }
+        Queue::Nats { url, stream, jobs, results, consumer, bucket } => {
+            eprintln!("Waiting for jobs on the NATS subject {}", jobs);
+            worker::run_nats(solver, &NatsConfig { url, stream, jobs, results, consumer, bucket }).await
+        }
+    }
+}
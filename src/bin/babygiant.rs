@@ -0,0 +1,948 @@
+//! Command-line interface to the baby-step giant-step solver, for recovering plaintexts encrypted with
+//! [noir-elgamal](https://github.com/jat9292/noir-elgamal) without running a Noir program.
+
+use babygiant_alt_bn128::abi::{self, Endianness};
+use babygiant_alt_bn128::audit::{self, AuditLog};
+use babygiant_alt_bn128::bench::{self, ThreadMeasurement};
+use babygiant_alt_bn128::config::Config;
+use babygiant_alt_bn128::conformance::Fixture;
+use babygiant_alt_bn128::csv::{Columns, Record};
+use babygiant_alt_bn128::curve;
+use babygiant_alt_bn128::events::JsonLines;
+use babygiant_alt_bn128::hd::ExtendedPrivateKey;
+use babygiant_alt_bn128::jsonl;
+use babygiant_alt_bn128::nargo;
+use babygiant_alt_bn128::profile::Profile;
+use babygiant_alt_bn128::table::{inspect_table, verify_table, TableInfo};
+use babygiant_alt_bn128::vectors;
+use babygiant_alt_bn128::watch::Watcher;
+use babygiant_alt_bn128::zkay::{self, ZkaySolver};
+use babygiant_alt_bn128::{base64_to_hex, exp_elgamal_decrypt, exp_elgamal_encrypt, format_noir_point, generate_private_key, generate_randomness, noir_field_to_hex, priv_to_pub_key, private_key_from_seed, validate_point, Checkpoint, Error, HexPoint, ParseMode, Solver};
+use clap::{Parser, Subcommand, ValueEnum};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use num_bigint::BigUint;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+use std::{process, thread};
+
+#[derive(Parser)]
+#[command(name = "babygiant", version, about = "Exponential ElGamal decryption on the Baby Jubjub curve, compatible with noir-elgamal")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// TOML configuration file providing the default value of the options, see the `config` module of the library
+    #[arg(long, global = true, value_name = "FILE", env = "BABYGIANT_CONFIG")]
+    config: Option<PathBuf>,
+    /// Only accept the coordinates written as 0x followed by 64 hexadecimal digits [default: the parse_mode of the configuration file, or lenient]
+    #[arg(long, global = true)]
+    strict: bool,
+    /// Append a record of the dlog, decrypt and batch commands, with the user running them, to this file of JSON lines
+    #[arg(long, global = true, value_name = "FILE", env = "BABYGIANT_AUDIT_LOG")]
+    audit_log: Option<PathBuf>,
+    /// Write an event of each worker of the searches every STEPS giant steps, as JSON lines on the standard error
+    #[arg(long, global = true, value_name = "STEPS", env = "BABYGIANT_PROGRESS_INTERVAL", value_parser = clap::value_parser!(u64).range(1..))]
+    progress_interval: Option<u64>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Compute the discrete logarithm of an embedded plaintext point, as returned by the exp_elgamal_decrypt Noir function
+    Dlog {
+        /// x coordinate of the embedded plaintext, in Twisted Edwards form
+        #[arg(long, required_unless_present_any = ["resume", "nargo", "abi"])]
+        x: Option<String>,
+        /// y coordinate of the embedded plaintext, in Twisted Edwards form
+        #[arg(long, required_unless_present_any = ["resume", "nargo", "abi"])]
+        y: Option<String>,
+        /// Resume an interrupted search from its checkpoint file, instead of starting a new one
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["x", "y", "nargo", "abi"])]
+        resume: Option<PathBuf>,
+        /// Read the embedded plaintext from a Prover.toml or Verifier.toml file, or from the output of `nargo execute` (`-` for the standard input)
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["x", "y", "abi"])]
+        nargo: Option<PathBuf>,
+        /// Name of the embedded plaintext in the TOML file given to --nargo, e.g `decrypted_point` or `ciphertext.c2`
+        #[arg(long, value_name = "NAME", default_value = nargo::RETURN_FIELD, requires = "nargo")]
+        field: String,
+        /// Generator and encoding of the embedded plaintext [default: the profile of the configuration file, or noir]
+        #[arg(long, value_enum, conflicts_with = "resume")]
+        profile: Option<KeyProfile>,
+        /// Form of the curve of the coordinates of the embedded plaintext, which are converted to the Twisted Edwards form of Noir
+        #[arg(long, value_enum, default_value_t = PointForm::Twisted, conflicts_with = "resume")]
+        form: PointForm,
+        /// The coordinates given to --x and --y are the base64 encodings of their big-endian bytes, standard or URL-safe, instead of hexadecimal strings
+        #[arg(long, requires = "x")]
+        base64: bool,
+        /// The coordinates given to --x and --y are Fields as displayed by Noir, i.e decimal without the 0x prefix, e.g copied from a Prover.toml
+        #[arg(long, requires = "x", conflicts_with = "base64")]
+        noir_field: bool,
+        #[command(flatten)]
+        abi: AbiArgs,
+        #[command(flatten)]
+        solver: SolverArgs,
+    },
+    /// Decrypt a ciphertext (C1, C2) with a private key: recover the embedded plaintext C2 - sk·C1, then its discrete logarithm
+    Decrypt {
+        /// Private key, as an hexadecimal string
+        #[arg(long)]
+        sk: String,
+        /// Convention of the private key, generator and encoding of the ciphertext [default: the profile of the configuration file, or noir]
+        #[arg(long, value_enum)]
+        profile: Option<KeyProfile>,
+        /// x coordinate of the first point C1 of the ciphertext
+        #[arg(long, required_unless_present_any = ["abi", "nargo"])]
+        c1x: Option<String>,
+        /// y coordinate of the first point C1 of the ciphertext
+        #[arg(long, required_unless_present_any = ["abi", "nargo"])]
+        c1y: Option<String>,
+        /// x coordinate of the second point C2 of the ciphertext
+        #[arg(long, required_unless_present_any = ["abi", "nargo"])]
+        c2x: Option<String>,
+        /// y coordinate of the second point C2 of the ciphertext
+        #[arg(long, required_unless_present_any = ["abi", "nargo"])]
+        c2y: Option<String>,
+        /// The coordinates of C1 and C2 are the base64 encodings of their big-endian bytes, standard or URL-safe, instead of hexadecimal strings
+        #[arg(long, requires = "c1x")]
+        base64: bool,
+        /// The private key and the coordinates of C1 and C2 are Fields as displayed by Noir, i.e decimal without the 0x prefix
+        #[arg(long, requires = "c1x", conflicts_with = "base64")]
+        noir_field: bool,
+        /// Read the ciphertext from a TOML file with the points c1 and c2, e.g written by `babygiant encrypt` or a Prover.toml (`-` for the standard input)
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["c1x", "c1y", "c2x", "c2y", "abi"])]
+        nargo: Option<PathBuf>,
+        /// Name of the ciphertext in the TOML file given to --nargo, e.g `ciphertext`, instead of the top level of the file
+        #[arg(long, value_name = "NAME", default_value = "", requires = "nargo")]
+        field: String,
+        #[command(flatten)]
+        abi: AbiArgs,
+        /// Only print the embedded plaintext C2 - sk·C1, formatted like Noir prints the result of exp_elgamal_decrypt, instead of solving it
+        #[arg(long)]
+        point_only: bool,
+        #[command(flatten)]
+        solver: SolverArgs,
+    },
+    /// Generate a Baby Jubjub key pair, usable with the priv_to_pub_key, exp_elgamal_encrypt and exp_elgamal_decrypt Noir functions
+    Keygen {
+        /// Derive the private key deterministically as SHA-512(SEED) modulo the subgroup order, instead of generating it randomly
+        #[arg(long, value_name = "SEED")]
+        from_seed: Option<String>,
+        /// Derive the key at this BIP32-style path, such as m/44'/1'/0, from the master key of SEED instead
+        #[arg(long, requires = "from_seed")]
+        path: Option<String>,
+        /// Output format of the key pair
+        #[arg(long, value_enum, default_value_t = KeyFormat::Noir)]
+        format: KeyFormat,
+    },
+    /// Encrypt a plaintext for a public key, exactly like the exp_elgamal_encrypt Noir function, e.g to generate test vectors
+    Encrypt {
+        /// Public key, as its x and y coordinates separated by a comma
+        #[arg(long, value_name = "X,Y", value_parser = parse_coordinates)]
+        pk: HexPoint,
+        /// Plaintext to encrypt, which should be a u40 for the ciphertext to be decryptable
+        #[arg(long)]
+        plaintext: u64,
+        /// Randomness used for the encryption, as an hexadecimal string [default: freshly generated]
+        #[arg(long)]
+        randomness: Option<String>,
+    },
+    /// Compute the discrete logarithms of many embedded plaintexts with one table of baby steps, solving several of them at the same time
+    Batch {
+        /// File of embedded plaintexts, one per line as their x and y coordinates separated by a comma (`-` for the standard input)
+        input: PathBuf,
+        /// Number of embedded plaintexts solved at the same time
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+        jobs: u64,
+        /// Number of threads of each search [default: number of available cores divided by --jobs]
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        threads_per_job: Option<u64>,
+        /// Maximum bitwidth of the embedded plaintexts [default: 40]
+        #[arg(long, env = "BABYGIANT_BITS", value_parser = clap::value_parser!(u64).range(1..=64))]
+        bits: Option<u64>,
+        /// Bitwidth of the table of baby steps, see `babygiant bench` [default: half of --bits]
+        #[arg(long, env = "BABYGIANT_TABLE_BITS", value_parser = clap::value_parser!(u64).range(0..=32))]
+        table_bits: Option<u64>,
+        /// Load the table of baby steps from a file written by `babygiant table build`, instead of building it
+        #[arg(long, value_name = "FILE", env = "BABYGIANT_TABLE", conflicts_with = "table_bits")]
+        table: Option<PathBuf>,
+        /// Read the input as a CSV file with a header row, taking the coordinates from the columns X,Y of the embedded plaintexts, or C1X,C1Y,C2X,C2Y of ciphertexts decrypted with --sk
+        #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+        columns: Option<Vec<String>>,
+        /// Delimiter of the fields of the CSV file given with --columns
+        #[arg(long, default_value_t = ',', requires = "columns")]
+        delimiter: char,
+        /// Private key decrypting the ciphertexts of the CSV file, as an hexadecimal string
+        #[arg(long, env = "BABYGIANT_PRIVATE_KEY", hide_env_values = true, requires = "columns")]
+        sk: Option<String>,
+        /// Stream a JSON-lines file of embedded plaintexts and ciphertexts of any size, see the `jsonl` module of the library, solving them one after the other with all the threads and printing their results as they are found
+        #[arg(long, conflicts_with_all = ["columns", "jobs"])]
+        jsonl: bool,
+        /// Do not display the number of embedded plaintexts solved
+        #[arg(long, short)]
+        quiet: bool,
+    },
+    /// Watch a directory for files of ciphertexts, one JSON object per line, and write the plaintexts to companion result files
+    Watch {
+        /// Directory of the files, see the `watch` module of the library for their format
+        dir: PathBuf,
+        /// Private key decrypting the ciphertexts, as an hexadecimal string
+        #[arg(long, env = "BABYGIANT_PRIVATE_KEY", hide_env_values = true)]
+        sk: Option<String>,
+        /// File holding the private key decrypting the ciphertexts, so that it does not appear in the command line [takes precedence over --sk]
+        #[arg(long, value_name = "FILE")]
+        sk_file: Option<PathBuf>,
+        /// Interval between two scans of the directory, in seconds
+        #[arg(long, value_name = "SECONDS", default_value_t = 1.0)]
+        interval: f64,
+        /// Time since their last modification after which files are processed, in seconds
+        #[arg(long, value_name = "SECONDS", default_value_t = 1.0)]
+        settle_time: f64,
+        /// Process the files which are ready, and exit instead of watching the directory
+        #[arg(long)]
+        once: bool,
+        #[command(flatten)]
+        solver: SolverArgs,
+    },
+    /// Build, inspect or verify the files of tables of baby steps loaded with --table
+    Table {
+        #[command(subcommand)]
+        command: TableCommand,
+    },
+    /// Measure the speed of the algorithm on this machine, and print the optimal number of threads and size of the table
+    Bench {
+        /// Maximum bitwidth of the embedded plaintexts the configuration is optimized for
+        #[arg(long, default_value_t = 40, value_parser = clap::value_parser!(u64).range(1..=64))]
+        bits: u64,
+        /// Largest number of threads measured [default: number of available cores]
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        max_threads: Option<u64>,
+        /// Bitwidth of the table used for the measurements
+        #[arg(long, default_value_t = 18, value_parser = clap::value_parser!(u64).range(8..=24))]
+        sample_bits: u64,
+        /// Number of searches sharing one table, e.g the number of ciphertexts decrypted by a server before it restarts
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+        solves: u64,
+        /// Maximum memory used by the table, in MiB
+        #[arg(long, value_name = "MIB", default_value_t = 4096)]
+        max_memory: u64,
+        /// Save the optimal configuration to this TOML file
+        #[arg(long, value_name = "FILE")]
+        save: Option<PathBuf>,
+    },
+    /// Compute the discrete logarithm of a point written by zkay, or decrypt a ciphertext of zkay with --sk, with the generator and formats of zkay
+    Zkay {
+        /// x coordinate of the embedded plaintext, or of the first point C1 of the ciphertext with --sk, as 32 little-endian bytes in hexadecimal
+        #[arg(long)]
+        x: String,
+        /// y coordinate of the embedded plaintext, or of the first point C1 of the ciphertext with --sk
+        #[arg(long)]
+        y: String,
+        /// Private key of zkay, as an hexadecimal string, to decrypt the ciphertext (C1, C2) instead
+        #[arg(long, requires_all = ["c2x", "c2y"])]
+        sk: Option<String>,
+        /// x coordinate of the second point C2 of the ciphertext
+        #[arg(long, requires = "sk")]
+        c2x: Option<String>,
+        /// y coordinate of the second point C2 of the ciphertext
+        #[arg(long, requires = "sk")]
+        c2y: Option<String>,
+        /// Maximum bitwidth of the embedded plaintext
+        #[arg(long, default_value_t = zkay::MAX_BITWIDTH, value_parser = clap::value_parser!(u64).range(1..=64))]
+        bits: u64,
+        /// Number of threads used by the baby-step giant-step algorithm [default: number of available cores]
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        threads: Option<u64>,
+    },
+    /// Print a deterministic suite of test vectors as JSON, e.g as shared fixtures of the tests of Noir circuits and Solidity contracts
+    Vectors {
+        /// Seed of the suite, from which its private key, randomness and pseudo-random plaintexts are derived
+        #[arg(long)]
+        seed: String,
+        /// Maximum bitwidth of the plaintexts, whose edge cases are included in the suite
+        #[arg(long, default_value_t = 40, value_parser = clap::value_parser!(u64).range(1..=64))]
+        bits: u64,
+        /// Number of pseudo-random plaintexts added after the edge cases
+        #[arg(long, default_value_t = 8)]
+        random: u64,
+        /// Print the suites of every curve and every profile of Baby Jubjub enabled by the features, instead of the one of noir-elgamal
+        #[arg(long)]
+        all: bool,
+    },
+    /// Check this crate against a JSON fixture of reference vectors, e.g exported from the noir-elgamal circuits, and print the failed cases
+    Conformance {
+        /// Fixture file, see the `conformance` module of the library for its format (`-` for the standard input)
+        fixture: PathBuf,
+        /// Maximum bitwidth of the plaintexts of the fixture
+        #[arg(long, default_value_t = 40, value_parser = clap::value_parser!(u64).range(1..=64))]
+        bits: u64,
+        /// Number of threads used by the baby-step giant-step algorithm [default: number of available cores]
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        threads: Option<u64>,
+    },
+}
+
+#[derive(Subcommand)]
+enum TableCommand {
+    /// Build a table of baby steps and save it to a file
+    Build {
+        /// File the table is written to
+        output: PathBuf,
+        /// Bitwidth of the table, see `babygiant bench`
+        #[arg(long, default_value_t = 20, value_parser = clap::value_parser!(u64).range(1..=32))]
+        table_bits: u64,
+        /// Number of threads building the table [default: number of available cores]
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        threads: Option<u64>,
+    },
+    /// Print the bitwidth, curve, generator, number of entries and checksum of a table file, after checking its checksum
+    Inspect {
+        file: PathBuf,
+    },
+    /// Check the checksum and the generator of a table file, and recompute a random sample of its baby steps
+    Verify {
+        file: PathBuf,
+        /// Number of baby steps recomputed, besides the first and the last ones
+        #[arg(long, default_value_t = 1000)]
+        samples: u64,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum KeyFormat {
+    /// Prover.toml entries with 0x-prefixed hexadecimal Fields, as expected by noir-elgamal
+    Noir,
+    /// JSON input with decimal strings, as expected by circom circuits
+    Circom,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum KeyProfile {
+    /// The private key is the scalar itself, like in noir-elgamal
+    Noir,
+    /// The private key is the bytes hashed into the scalar by the prv2pub function of circomlib, and the coordinates can be decimal
+    Circomlib,
+    /// The generator of zkay, and the coordinates of the Edwards form as the hexadecimal digits of their little-endian bytes
+    Zkay,
+}
+
+impl From<KeyProfile> for Profile {
+    fn from(profile: KeyProfile) -> Self {
+        match profile {
+            KeyProfile::Noir => Profile::Noir,
+            KeyProfile::Circomlib => Profile::Circomlib,
+            KeyProfile::Zkay => Profile::Zkay,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum PointForm {
+    /// Twisted Edwards coordinates (x, y), like Noir and circomlib
+    Twisted,
+    /// Montgomery coordinates (u, v) of v^2 = u^3 + 168698u^2 + u, like some tooling and EIP-2494
+    Montgomery,
+    /// Short Weierstrass coordinates (x, y) of y^2 = x^3 + a·x + b, like some implementations friendly to the precompiles of the EVM
+    Weierstrass,
+}
+
+impl PointForm {
+    /// Converts the coordinates `(x, y)` in this form into Twisted Edwards coordinates formatted like Noir.
+    fn to_noir(self, x: &str, y: &str) -> Result<HexPoint, Error> {
+        match self {
+            PointForm::Twisted => Ok((x.to_string(), y.to_string())),
+            PointForm::Montgomery => curve::montgomery_to_noir(x, y),
+            PointForm::Weierstrass => curve::weierstrass_to_noir(x, y),
+        }
+    }
+}
+
+/// Point or ciphertext read from the calldata of a transaction or the data of an event log, instead of its coordinates.
+#[derive(clap::Args)]
+struct AbiArgs {
+    /// ABI-encoded calldata or event log data, as an hexadecimal string, holding the coordinates as consecutive uint256 or bytes32 words
+    #[arg(long, value_name = "HEX", conflicts_with_all = ["x", "y", "c1x", "c1y", "c2x", "c2y"])]
+    abi: Option<String>,
+    /// Index of the word holding the first coordinate, not counting the selector of calldata
+    #[arg(long, value_name = "WORDS", default_value_t = 0, requires = "abi")]
+    abi_offset: usize,
+    /// The words hold little-endian integers, instead of the big-endian uint256 encoding of Solidity
+    #[arg(long, requires = "abi")]
+    little_endian: bool,
+}
+
+impl AbiArgs {
+    fn endianness(&self) -> Endianness {
+        if self.little_endian { Endianness::Little } else { Endianness::Big }
+    }
+}
+
+#[derive(clap::Args)]
+struct SolverArgs {
+    /// Number of threads used by the baby-step giant-step algorithm [default: number of available cores]
+    #[arg(long, env = "BABYGIANT_THREADS", value_parser = clap::value_parser!(u64).range(1..))]
+    threads: Option<u64>,
+    /// Maximum bitwidth of the embedded plaintext [default: 40]
+    #[arg(long, env = "BABYGIANT_BITS", value_parser = clap::value_parser!(u64).range(1..=64))]
+    bits: Option<u64>,
+    /// Bitwidth of the table of baby steps, see `babygiant bench` [default: half of --bits]
+    #[arg(long, env = "BABYGIANT_TABLE_BITS", value_parser = clap::value_parser!(u64).range(0..=32))]
+    table_bits: Option<u64>,
+    /// Load the table of baby steps from a file written by `babygiant table build`, instead of building it
+    #[arg(long, value_name = "FILE", env = "BABYGIANT_TABLE", conflicts_with = "table_bits")]
+    table: Option<PathBuf>,
+    /// Do not display the progress of the search
+    #[arg(long, short)]
+    quiet: bool,
+    /// File where the state of the search is saved when it is interrupted with Ctrl-C
+    #[arg(long, value_name = "FILE", default_value = "checkpoint.bin")]
+    checkpoint: PathBuf,
+    #[arg(skip)]
+    parse_mode: ParseMode,
+    #[arg(skip)]
+    progress_interval: Option<u64>,
+}
+
+impl SolverArgs {
+    /// Takes the options which are not set on the command line from the configuration file.
+    fn with_config(mut self, config: &Config) -> Self {
+        self.threads = self.threads.or(config.threads);
+        self.bits = self.bits.or(config.bits);
+        if self.table_bits.is_none() {
+            self.table = self.table.or_else(|| config.table.clone());
+        }
+        self.table_bits = self.table_bits.or(config.table_bits);
+        self.parse_mode = config.parse_mode.unwrap_or_default();
+        self.progress_interval = config.progress_interval;
+        self
+    }
+
+    fn bits(&self) -> u64 {
+        self.bits.unwrap_or(40)
+    }
+
+    fn num_threads(&self) -> u64 {
+        self.threads.unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get() as u64))
+    }
+}
+
+/// Point whose Discrete Logarithm is searched, or search resumed from a checkpoint.
+enum Target {
+    Point(String, String),
+    Checkpoint(Checkpoint),
+}
+
+/// Set while a search is running, so that Ctrl-C cancels it and saves a checkpoint instead of exiting right away.
+static SEARCHING: AtomicBool = AtomicBool::new(false);
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Builds the table of baby steps, or loads it from the file `table`, displaying a spinner unless `quiet`.
+///
+/// The workers of the solver report their progress every `progress_interval` giant steps, if it is set.
+fn make_solver(max_bitwidth: u64, table_bitwidth: u64, table: Option<&Path>, num_threads: u64, progress_interval: Option<u64>, quiet: bool) -> Result<Solver, Error> {
+    let message = if table.is_some() { "Loading the table of baby steps" } else { "Building the table of baby steps" };
+    let spinner = (!quiet).then(|| {
+        let spinner = ProgressBar::new_spinner().with_message(message);
+        spinner.enable_steady_tick(Duration::from_millis(100));
+        spinner
+    });
+    let solver = match table {
+        Some(path) => File::open(path)
+            .map_err(|e| Error::InvalidTable(format!("{} : {}", path.display(), e)))
+            .and_then(|file| Solver::from_table(BufReader::new(file), max_bitwidth, num_threads)),
+        None => Ok(Solver::with_table_bitwidth(max_bitwidth, table_bitwidth, num_threads)),
+    };
+    if let Some(spinner) = spinner {
+        spinner.finish_and_clear();
+    }
+    match progress_interval {
+        Some(giant_steps) => solver.map(|solver| solver.with_progress_interval(giant_steps)),
+        None => solver,
+    }
+}
+
+fn solve(target: Target, args: &SolverArgs) -> Result<u64, Error> {
+    let (max_bitwidth, table_bitwidth) = match &target {
+        Target::Point(x, y) => {
+            validate_point(x, y)?;
+            let bits = args.bits();
+            (bits, args.table_bits.unwrap_or(bits / 2).clamp(bits.saturating_sub(63), bits))
+        }
+        Target::Checkpoint(checkpoint) => (checkpoint.max_bitwidth(), checkpoint.table_bitwidth()),
+    };
+    let solver = make_solver(max_bitwidth, table_bitwidth, args.table.as_deref(), args.num_threads(), args.progress_interval, args.quiet)?.with_parse_mode(args.parse_mode);
+    let search = match &target {
+        Target::Point(x, y) => solver.search(x, y)?,
+        Target::Checkpoint(checkpoint) => solver.resume(checkpoint)?,
+    };
+
+    let style = ProgressStyle::with_template("worker {prefix:>2} [{bar:40}] {percent:>3}% (ETA {eta})").unwrap().progress_chars("=> ");
+    let bars = MultiProgress::new();
+    let workers: Vec<ProgressBar> = if args.quiet {
+        Vec::new()
+    } else {
+        search.progress()
+            .iter()
+            .enumerate()
+            .map(|(idx, p)| bars.add(ProgressBar::new(p.total).with_position(p.done).with_style(style.clone()).with_prefix(idx.to_string())))
+            .collect()
+    };
+    let done = AtomicBool::new(false);
+    SEARCHING.store(true, Ordering::SeqCst);
+    let result = thread::scope(|s| {
+        s.spawn(|| {
+            while !done.load(Ordering::Relaxed) {
+                if INTERRUPTED.load(Ordering::Relaxed) {
+                    search.cancel();
+                }
+                for (bar, p) in workers.iter().zip(search.progress()) {
+                    bar.set_position(p.done);
+                }
+                thread::sleep(Duration::from_millis(100));
+            }
+            for bar in &workers {
+                bar.finish_and_clear();
+            }
+        });
+        let result = search.run();
+        done.store(true, Ordering::Relaxed);
+        result
+    });
+    SEARCHING.store(false, Ordering::SeqCst);
+
+    if result == Err(Error::Cancelled) {
+        let path = &args.checkpoint;
+        if let Err(e) = File::create(path).and_then(|f| search.checkpoint().write_to(BufWriter::new(f))) {
+            eprintln!("Error: unable to write the checkpoint to {} : {}", path.display(), e);
+            process::exit(1);
+        }
+        eprintln!("Interrupted, the state of the search was saved to {0}. Resume it with: babygiant dlog --resume {0}", path.display());
+        process::exit(130);
+    }
+    result
+}
+
+/// Solves the point `(x, y)` in the encoding and in base the generator of `profile`, whose table of baby steps is always built if it is
+/// not the generator of noir-elgamal.
+fn solve_profile(profile: Profile, x: &str, y: &str, args: &SolverArgs) -> Result<u64, Error> {
+    if profile.generator() == Profile::Noir.generator() {
+        let (x, y) = profile.to_noir(x, y)?;
+        return solve(Target::Point(x, y), args);
+    }
+    if args.table.is_some() {
+        return Err(Error::ProfileMismatch(format!("the tables of baby steps are in base the generator of the noir profile, not {}", profile)));
+    }
+    let bits = args.bits();
+    profile.solve(&profile.solver(bits, args.table_bits.unwrap_or(bits / 2).clamp(bits.saturating_sub(63), bits), args.num_threads()), x, y)
+}
+
+fn read_checkpoint(path: &Path) -> Result<Checkpoint, Error> {
+    let file = File::open(path).map_err(|e| Error::InvalidCheckpoint(format!("{} : {}", path.display(), e)))?;
+    Checkpoint::read_from(BufReader::new(file))
+}
+
+/// Reads the file at `path`, or the standard input if it is `-`.
+fn read_input(path: &Path) -> std::io::Result<String> {
+    if path == Path::new("-") {
+        let mut contents = String::new();
+        std::io::stdin().read_to_string(&mut contents)?;
+        Ok(contents)
+    } else {
+        std::fs::read_to_string(path)
+    }
+}
+
+/// Reads the point `field` of a TOML file written by nargo, or the last point printed in the output of nargo for the other files.
+fn read_nargo_point(path: &Path, field: &str) -> Result<HexPoint, Error> {
+    let contents = read_input(path).map_err(|e| Error::InvalidArtifact(format!("{} : {}", path.display(), e)))?;
+    if path.extension().is_some_and(|e| e == "toml") {
+        nargo::point_from_toml(&contents, field)
+    } else {
+        nargo::point_from_output(&contents)
+    }
+}
+
+fn read_nargo_ciphertext(path: &Path, field: &str) -> Result<(HexPoint, HexPoint), Error> {
+    let contents = read_input(path).map_err(|e| Error::InvalidArtifact(format!("{} : {}", path.display(), e)))?;
+    nargo::ciphertext_from_toml(&contents, field)
+}
+
+fn parse_coordinates(s: &str) -> Result<HexPoint, String> {
+    match s.split_once(',') {
+        Some((x, y)) => Ok((x.trim().to_string(), y.trim().to_string())),
+        None => Err("expected the x and y coordinates separated by a comma".to_string()),
+    }
+}
+
+fn to_decimal(hex: &str) -> String {
+    BigUint::parse_bytes(hex.trim_start_matches("0x").as_bytes(), 16).unwrap().to_string()
+}
+
+fn keygen(from_seed: Option<String>, path: Option<String>, format: KeyFormat) -> Result<String, Error> {
+    let sk = match (from_seed, path) {
+        (Some(seed), Some(path)) => ExtendedPrivateKey::from_seed(seed.as_bytes()).derive_path(&path)?.private_key(),
+        (Some(seed), None) => private_key_from_seed(seed.as_bytes()),
+        (None, _) => generate_private_key(),
+    };
+    let (x, y) = priv_to_pub_key(&sk)?;
+    Ok(match format {
+        KeyFormat::Noir => format!("private_key = \"{}\"\n\n[public_key]\nx = \"{}\"\ny = \"{}\"", sk, x, y),
+        KeyFormat::Circom => format!("{{\"private_key\": \"{}\", \"public_key\": [\"{}\", \"{}\"]}}", to_decimal(&sk), to_decimal(&x), to_decimal(&y)),
+    })
+}
+
+fn encrypt(pk: HexPoint, plaintext: u64, randomness: Option<String>) -> Result<String, Error> {
+    let randomness = randomness.unwrap_or_else(generate_randomness);
+    let (c1, c2) = exp_elgamal_encrypt((&pk.0, &pk.1), plaintext, &randomness)?;
+    Ok(format!("randomness = \"{}\"\n\n[c1]\nx = \"{}\"\ny = \"{}\"\n\n[c2]\nx = \"{}\"\ny = \"{}\"", randomness, c1.0, c1.1, c2.0, c2.1))
+}
+
+/// Reads the embedded plaintexts of the batch `input`, one per line as `X,Y`, or one per row of a CSV file with the `columns` of the
+/// coordinates, the ciphertexts being decrypted with `sk`. The lines which could not be read are kept with the reason, in their order.
+fn read_batch(input: &Path, columns: Option<&[String]>, delimiter: char, sk: Option<&str>) -> Result<Vec<Result<HexPoint, String>>, Error> {
+    let contents = read_input(input).unwrap_or_else(|e| {
+        eprintln!("Error: unable to read {} : {}", input.display(), e);
+        process::exit(1);
+    });
+    let columns = match columns {
+        None => {
+            let lines = contents.lines().map(str::trim).filter(|line| !line.is_empty());
+            return Ok(lines.map(|line| parse_coordinates(line).map_err(|e| format!("{:?} : {}", line, e))).collect());
+        }
+        Some([x, y]) => Columns::point(x, y),
+        Some([c1x, c1y, c2x, c2y]) => Columns::ciphertext(c1x, c1y, c2x, c2y),
+        Some(names) => return Err(Error::InvalidCsv(format!("--columns should name 2 or 4 columns, not {}", names.len()))),
+    }
+    .with_delimiter(delimiter);
+    if columns.is_ciphertext() && sk.is_none() {
+        return Err(Error::InvalidCsv("the ciphertexts of the columns C1X,C1Y,C2X,C2Y are decrypted with --sk".to_string()));
+    }
+    Ok(columns
+        .parse(&contents)?
+        .into_iter()
+        .map(|record| match record {
+            Record::Point(p) => Ok(p),
+            Record::Ciphertext(c1, c2) => exp_elgamal_decrypt(sk.unwrap(), (&c1.0, &c1.1), (&c2.0, &c2.1)).map_err(|e| e.to_string()),
+        })
+        .collect())
+}
+
+/// Streams the JSON-lines file `input` through the solver, printing the result of each line as soon as it is found.
+fn batch_jsonl(input: &Path, bits: u64, table_bits: u64, table: Option<&Path>, num_threads: u64, config: &Config, quiet: bool) -> Result<String, Error> {
+    let reader: Box<dyn BufRead> = match input {
+        path if path == Path::new("-") => Box::new(std::io::stdin().lock()),
+        path => Box::new(BufReader::new(File::open(path).unwrap_or_else(|e| {
+            eprintln!("Error: unable to read {} : {}", path.display(), e);
+            process::exit(1);
+        }))),
+    };
+    let solver = make_solver(bits, table_bits, table, num_threads, config.progress_interval, quiet)?.with_parse_mode(config.parse_mode.unwrap_or_default());
+    let summary = jsonl::solve_lines(reader, std::io::stdout().lock(), &solver).unwrap_or_else(|e| {
+        eprintln!("Error: unable to stream {} : {}", input.display(), e);
+        process::exit(1);
+    });
+    if !quiet {
+        eprintln!("{} solved, {} failed", summary.solved, summary.failed);
+    }
+    Ok(String::new())
+}
+
+/// Solves the embedded plaintexts `points` with `solver`, `jobs` at a time, and returns one line per point with its Discrete Logarithm
+/// or the reason it could not be computed, in the same order as `points`.
+fn batch(points: Vec<Result<HexPoint, String>>, jobs: u64, solver: &Solver, quiet: bool) -> Result<String, Error> {
+
+    let style = ProgressStyle::with_template("[{bar:40}] {pos}/{len} solved (ETA {eta})").unwrap().progress_chars("=> ");
+    let bar = if quiet { ProgressBar::hidden() } else { ProgressBar::new(points.len() as u64).with_style(style) };
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<(usize, String)> = thread::scope(|s| {
+        let handles: Vec<_> = (0..jobs)
+            .map(|_| {
+                s.spawn(|| {
+                    let mut results = Vec::new();
+                    loop {
+                        let idx = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(point) = points.get(idx) else { return results };
+                        let result = point.clone().and_then(|(x, y)| solver.solve(&x, &y).map_err(|e| e.to_string()));
+                        results.push((idx, result.map_or_else(|e| format!("error: {}", e), |dlog| dlog.to_string())));
+                        bar.inc(1);
+                    }
+                })
+            })
+            .collect();
+        handles.into_iter().flat_map(|h| h.join().unwrap()).collect()
+    });
+    bar.finish_and_clear();
+    results.sort_unstable_by_key(|(idx, _)| *idx);
+    Ok(results.into_iter().map(|(_, line)| line).collect::<Vec<_>>().join("\n"))
+}
+
+fn run_table(command: TableCommand) -> Result<String, Error> {
+    let open = |path: &Path| File::open(path).map(BufReader::new).map_err(|e| Error::InvalidTable(format!("{} : {}", path.display(), e)));
+    let describe = |info: TableInfo| {
+        format!("table bits  {}\ncurve       {}\ngenerator   {},{}\nentries     {}\nchecksum    sha256:{}", info.table_bitwidth, info.curve, info.generator.0, info.generator.1, info.entries, info.checksum)
+    };
+    match command {
+        TableCommand::Build { output, table_bits, threads } => {
+            let threads = threads.unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get() as u64));
+            let solver = make_solver(table_bits, table_bits, None, threads, None, false)?;
+            if let Err(e) = File::create(&output).and_then(|f| solver.write_table(BufWriter::new(f))) {
+                eprintln!("Error: unable to write the table to {} : {}", output.display(), e);
+                process::exit(1);
+            }
+            Ok(format!("Saved the table of 2^{} baby steps to {}", table_bits, output.display()))
+        }
+        TableCommand::Inspect { file } => inspect_table(open(&file)?).map(describe),
+        TableCommand::Verify { file, samples } => {
+            let info = verify_table(open(&file)?, samples)?;
+            Ok(format!("{}\n\nOK : the checksum and the generator match, as well as the first, the last and {} random baby steps recomputed", describe(info), samples))
+        }
+    }
+}
+
+fn watch(dir: PathBuf, private_key: Option<String>, interval: Duration, settle_time: Duration, once: bool, args: &SolverArgs) -> Result<String, Error> {
+    let bits = args.bits();
+    let solver = make_solver(bits, args.table_bits.unwrap_or(bits / 2).clamp(bits.saturating_sub(63), bits), args.table.as_deref(), args.num_threads(), args.progress_interval, args.quiet)?.with_parse_mode(args.parse_mode);
+    let mut watcher = Watcher::new(&dir, &solver).with_settle_time(settle_time);
+    if let Some(private_key) = private_key {
+        watcher = watcher.with_private_key(private_key);
+    }
+    let report = |results: &Path| eprintln!("Wrote {}", results.display());
+    // Ctrl-C stops the watcher once the current file is processed
+    SEARCHING.store(true, Ordering::SeqCst);
+    let watched = if once {
+        watcher.poll(&INTERRUPTED).map(|written| written.iter().for_each(|results| report(results)))
+    } else {
+        eprintln!("Watching {}", dir.display());
+        watcher.run(interval, &INTERRUPTED, report)
+    };
+    SEARCHING.store(false, Ordering::SeqCst);
+    if let Err(e) = watched {
+        eprintln!("Error: unable to process the files of {} : {}", dir.display(), e);
+        process::exit(1);
+    }
+    Ok(String::new())
+}
+
+/// Thread counts measured by `babygiant bench` : the powers of two up to `max_threads`, and `max_threads` itself.
+fn thread_counts(max_threads: u64) -> Vec<u64> {
+    let mut counts: Vec<u64> = std::iter::successors(Some(1u64), |t| Some(t * 2)).take_while(|t| *t < max_threads).collect();
+    counts.push(max_threads);
+    counts
+}
+
+fn run_bench(bits: u64, max_threads: u64, sample_bits: u64, solves: u64, max_memory: u64, save: Option<PathBuf>) -> Result<String, Error> {
+    let mut out = String::new();
+    eprintln!("Measuring the cost of the elementary operations...");
+    let costs = bench::measure_op_costs(100_000);
+    writeln!(out, "Cost of the elementary operations on one thread :").unwrap();
+    writeln!(out, "  affine addition         {:>8.0} ns  (each baby step)", costs.affine_addition_ns).unwrap();
+    writeln!(out, "  projective subtraction  {:>8.0} ns  (each giant step)", costs.projective_subtraction_ns).unwrap();
+    writeln!(out, "  affine conversion       {:>8.0} ns  (each giant step)", costs.affine_conversion_ns).unwrap();
+
+    let mut measurements: Vec<ThreadMeasurement> = Vec::new();
+    writeln!(out, "\n threads   baby steps/s  giant steps/s").unwrap();
+    for threads in thread_counts(max_threads) {
+        eprintln!("Measuring the throughput with {} threads...", threads);
+        let m = bench::measure_threads(threads, sample_bits);
+        writeln!(out, "{:>8} {:>14.0} {:>14.0}", m.threads, m.build_rate, m.step_rate).unwrap();
+        measurements.push(m);
+    }
+
+    let max_memory = max_memory.saturating_mul(1 << 20);
+    let Some(r) = bench::recommend(&measurements, bits, max_memory, solves) else {
+        writeln!(out, "\nNo table of baby steps fits in the memory limit").unwrap();
+        return Ok(out.trim_end().to_string());
+    };
+    writeln!(out, "\nOptimal configuration for {} bits and {} search(es) per table :", bits, solves).unwrap();
+    writeln!(out, "  --threads {} --table-bits {}", r.threads, r.table_bitwidth).unwrap();
+    writeln!(out, "  table of 2^{} baby steps, using {} MiB, built in {:.1?}", r.table_bitwidth, r.table_bytes >> 20, r.build_time).unwrap();
+    write!(out, "  searches taking {:.1?} on average, {:.1?} at worst", r.search_time, r.search_time * 2).unwrap();
+    if let Some(path) = save {
+        let config = format!("# Measured by `babygiant bench`\nthreads = {}\nbits = {}\ntable_bits = {}\n", r.threads, bits, r.table_bitwidth);
+        if let Err(e) = std::fs::write(&path, config) {
+            eprintln!("Error: unable to write the configuration to {} : {}", path.display(), e);
+            process::exit(1);
+        }
+        write!(out, "\n\nSaved to {}", path.display()).unwrap();
+    }
+    Ok(out)
+}
+
+fn run(command: Command, config: &Config) -> Result<String, Error> {
+    match command {
+        Command::Dlog { x, y, resume, nargo, field, profile, form, base64, noir_field, abi, solver } => {
+            let target = match (x, y, resume, nargo, &abi.abi) {
+                (_, _, Some(path), _, _) => Target::Checkpoint(read_checkpoint(&path)?),
+                (_, _, None, Some(path), _) => {
+                    let (x, y) = read_nargo_point(&path, &field)?;
+                    Target::Point(x, y)
+                }
+                (_, _, None, None, Some(data)) => {
+                    let (x, y) = abi::decode_point(data, abi.abi_offset, abi.endianness())?;
+                    Target::Point(x, y)
+                }
+                (Some(x), Some(y), None, None, None) if base64 => Target::Point(base64_to_hex(&x)?, base64_to_hex(&y)?),
+                (Some(x), Some(y), None, None, None) if noir_field => Target::Point(noir_field_to_hex(&x)?, noir_field_to_hex(&y)?),
+                (Some(x), Some(y), None, None, None) => Target::Point(x, y),
+                _ => unreachable!("enforced by clap"),
+            };
+            let profile = profile.map(Profile::from).or(config.profile).unwrap_or(Profile::Noir);
+            let dlog = match target {
+                Target::Point(x, y) if form != PointForm::Twisted => {
+                    if profile.generator() != Profile::Noir.generator() {
+                        return Err(Error::ProfileMismatch(format!("the forms of the curve other than the Twisted Edwards one are in base the generator of the noir profile, not {}", profile)));
+                    }
+                    let (x, y) = form.to_noir(&x, &y)?;
+                    solve(Target::Point(x, y), &solver.with_config(config))
+                }
+                Target::Point(x, y) => solve_profile(profile, &x, &y, &solver.with_config(config)),
+                checkpoint => solve(checkpoint, &solver.with_config(config)),
+            };
+            dlog.map(|dlog| dlog.to_string())
+        }
+        Command::Decrypt { sk, profile, c1x, c1y, c2x, c2y, base64, noir_field, nargo, field, abi, point_only, solver } => {
+            let ((c1x, c1y), (c2x, c2y)) = match (c1x, c1y, c2x, c2y, &abi.abi, &nargo) {
+                (_, _, _, _, Some(data), _) => abi::decode_ciphertext(data, abi.abi_offset, abi.endianness())?,
+                (_, _, _, _, None, Some(path)) => read_nargo_ciphertext(path, &field)?,
+                (Some(c1x), Some(c1y), Some(c2x), Some(c2y), None, None) if base64 => {
+                    ((base64_to_hex(&c1x)?, base64_to_hex(&c1y)?), (base64_to_hex(&c2x)?, base64_to_hex(&c2y)?))
+                }
+                (Some(c1x), Some(c1y), Some(c2x), Some(c2y), None, None) if noir_field => {
+                    ((noir_field_to_hex(&c1x)?, noir_field_to_hex(&c1y)?), (noir_field_to_hex(&c2x)?, noir_field_to_hex(&c2y)?))
+                }
+                (Some(c1x), Some(c1y), Some(c2x), Some(c2y), None, None) => ((c1x, c1y), (c2x, c2y)),
+                _ => unreachable!("enforced by clap"),
+            };
+            let sk = if noir_field { noir_field_to_hex(&sk)? } else { sk };
+            let profile = profile.map(Profile::from).or(config.profile).unwrap_or(Profile::Noir);
+            let (c1, c2) = (profile.to_noir(&c1x, &c1y)?, profile.to_noir(&c2x, &c2y)?);
+            let (x, y) = exp_elgamal_decrypt(&profile.scalar(&sk)?, (&c1.0, &c1.1), (&c2.0, &c2.1))?;
+            if point_only {
+                return Ok(format_noir_point(&(x, y)));
+            }
+            let (x, y) = profile.from_noir(&x, &y)?;
+            solve_profile(profile, &x, &y, &solver.with_config(config)).map(|dlog| dlog.to_string())
+        }
+        Command::Keygen { from_seed, path, format } => keygen(from_seed, path, format),
+        Command::Encrypt { pk, plaintext, randomness } => encrypt(pk, plaintext, randomness),
+        Command::Batch { input, jobs, threads_per_job, bits, table_bits, table, columns, delimiter, sk, jsonl, quiet } => {
+            let available = std::thread::available_parallelism().map_or(1, |n| n.get() as u64);
+            let threads_per_job = threads_per_job.unwrap_or((available / jobs).max(1));
+            let bits = bits.or(config.bits).unwrap_or(40);
+            let table = if table_bits.is_none() { table.or_else(|| config.table.clone()) } else { None };
+            let table_bits = table_bits.or(config.table_bits).unwrap_or(bits / 2).clamp(bits.saturating_sub(63), bits);
+            if jsonl {
+                return batch_jsonl(&input, bits, table_bits, table.as_deref(), jobs * threads_per_job, config, quiet);
+            }
+            let points = read_batch(&input, columns.as_deref(), delimiter, sk.as_deref())?;
+            // the table is built with all the threads of the jobs, before they share it
+            let solver = make_solver(bits, table_bits, table.as_deref(), jobs * threads_per_job, config.progress_interval, quiet)?.with_num_threads(threads_per_job);
+            batch(points, jobs, &solver.with_parse_mode(config.parse_mode.unwrap_or_default()), quiet)
+        }
+        Command::Watch { dir, sk, sk_file, interval, settle_time, once, solver } => {
+            let sk = match sk_file {
+                Some(path) => Some(std::fs::read_to_string(&path).unwrap_or_else(|e| {
+                    eprintln!("Error: unable to read the private key from {} : {}", path.display(), e);
+                    process::exit(1);
+                }).trim().to_string()),
+                None => sk,
+            };
+            let seconds = |s: f64| Duration::try_from_secs_f64(s).unwrap_or_default();
+            watch(dir, sk, seconds(interval), seconds(settle_time), once, &solver.with_config(config))
+        }
+        Command::Table { command } => run_table(command),
+        Command::Bench { bits, max_threads, sample_bits, solves, max_memory, save } => {
+            let max_threads = max_threads.unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get() as u64));
+            run_bench(bits, max_threads, sample_bits, solves, max_memory, save)
+        }
+        Command::Zkay { x, y, sk, c2x, c2y, bits, threads } => {
+            let threads = threads.unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get() as u64));
+            let solver = ZkaySolver::new(bits, threads);
+            let dlog = match (sk, c2x, c2y) {
+                (Some(sk), Some(c2x), Some(c2y)) => solver.decrypt(&sk, (&x, &y), (&c2x, &c2y))?,
+                _ => solver.solve(&x, &y)?,
+            };
+            Ok(dlog.to_string())
+        }
+        Command::Vectors { seed, bits, random, all: false } => {
+            let suite = vectors::generate(seed.as_bytes(), bits, random)?;
+            Ok(serde_json::to_string_pretty(&suite).expect("test vectors are serializable"))
+        }
+        Command::Vectors { seed, bits, random, all: true } => {
+            let suites = vectors::generate_all(seed.as_bytes(), bits, random)?;
+            Ok(serde_json::to_string_pretty(&suites).expect("test vectors are serializable"))
+        }
+        Command::Conformance { fixture, bits, threads } => {
+            let contents = read_input(&fixture).unwrap_or_else(|e| {
+                eprintln!("Error: unable to read {} : {}", fixture.display(), e);
+                process::exit(1);
+            });
+            let fixture = Fixture::from_json(&contents)?;
+            let threads = threads.unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get() as u64));
+            let failures = fixture.run(&Solver::new(bits, threads));
+            for failure in &failures {
+                eprintln!("{}", failure);
+            }
+            if !failures.is_empty() {
+                eprintln!("Error: {} of the {} cases failed", failures.len(), fixture.cases.len());
+                process::exit(1);
+            }
+            Ok(format!("{} cases passed", fixture.cases.len()))
+        }
+    }
+}
+
+/// Same as [`run`], recording the searches in the audit log `path`, with the coordinates given on the command line but never the private keys.
+fn run_audited(command: Command, config: &Config, path: &Path) -> Result<String, Error> {
+    let (operation, coordinates): (&str, Vec<String>) = match &command {
+        Command::Dlog { x, y, .. } => ("dlog", [x, y].into_iter().flatten().cloned().collect()),
+        Command::Decrypt { c1x, c1y, c2x, c2y, .. } => ("decrypt", [c1x, c1y, c2x, c2y].into_iter().flatten().cloned().collect()),
+        Command::Batch { .. } => ("batch", Vec::new()),
+        _ => return run(command, config),
+    };
+    let audit_log = AuditLog::open(path).unwrap_or_else(|e| {
+        eprintln!("Error: unable to open the audit log {} : {}", path.display(), e);
+        process::exit(1);
+    });
+    let actor = std::env::var("USER").or_else(|_| std::env::var("USERNAME")).ok();
+    let (timestamp, start) = (SystemTime::now(), Instant::now());
+    let result = run(command, config);
+    let outcome = result.as_ref().map(|output| output.parse::<u64>().ok()).map_err(Error::to_string);
+    let coordinates: Vec<&str> = coordinates.iter().map(String::as_str).collect();
+    if let Err(e) = audit_log.write(&audit::Record::new(actor.as_deref(), operation, &coordinates, timestamp, start.elapsed(), outcome)) {
+        eprintln!("Error: unable to write the audit record to {} : {}", path.display(), e);
+    }
+    result
+}
+
+fn main() {
+    let cli = Cli::parse();
+    ctrlc::set_handler(|| {
+        if !SEARCHING.load(Ordering::SeqCst) {
+            process::exit(130);
+        }
+        INTERRUPTED.store(true, Ordering::SeqCst);
+    }).expect("unable to set the Ctrl-C handler");
+    let result = Config::load(cli.config.as_deref()).and_then(|mut config| {
+        if cli.strict {
+            config.parse_mode = Some(ParseMode::Strict);
+        }
+        config.progress_interval = cli.progress_interval.or(config.progress_interval);
+        if config.progress_interval.is_some() {
+            JsonLines::install().expect("no other subscriber is installed");
+        }
+        match cli.audit_log.or(config.audit_log.clone()) {
+            Some(path) => run_audited(cli.command, &config, &path),
+            None => run(cli.command, &config),
+        }
+    });
+    match result {
+        Ok(output) if output.is_empty() => {}
+        Ok(output) => println!("{}", output),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    }
+}
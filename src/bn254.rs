@@ -0,0 +1,114 @@
+//! Exponential ElGamal on the group G1 of bn254 (alt_bn128), for the schemes which embed their plaintexts in G1 rather than in
+//! Baby Jubjub, e.g to use the precompiles of the EVM, with the same Baby-step Giant-step search as Baby Jubjub.
+//!
+//! G1 is the short Weierstrass curve `y^2 = x^3 + 3` over the base field of bn254, of prime order `r`, the modulus of the `Field` of
+//! Noir, and generated by `G = (1, 2)`, see [`GENERATOR`]. Points are encoded as their affine coordinates `(x, y)` in `0x`-prefixed
+//! hexadecimal strings, the point at infinity being `(0, 0)`, like the inputs and outputs of the `ecAdd` and `ecMul` precompiles, whose
+//! 64 bytes are converted by [`point_to_bytes`] and [`point_from_bytes`]. Scalars, i.e private keys and the encryption randomness, are
+//! `Field`s, i.e integers smaller than `r`.
+
+use ark_bn254::{g1, Fr};
+use ark_ec::models::short_weierstrass_jacobian::GroupAffine;
+use ark_ec::AffineCurve;
+use ark_ff::PrimeField;
+use crate::dlog::{self, DlogCurve};
+use crate::table::ArkworksCurve;
+use crate::{Error, HexPoint};
+
+/// Coordinates of the generator `G = (1, 2)` of G1.
+pub const GENERATOR: (&str, &str) = (
+    "0x0000000000000000000000000000000000000000000000000000000000000001",
+    "0x0000000000000000000000000000000000000000000000000000000000000002",
+);
+
+/// Points of G1, with its concrete parameters instead of the ones of `ark_bn254::G1Affine`, so that the implementations of the traits of
+/// this crate do not overlap with the ones of the other short Weierstrass curves.
+pub(crate) type G1Affine = GroupAffine<g1::Parameters>;
+
+impl ArkworksCurve for G1Affine {
+    fn x_low_bits(&self) -> u64 {
+        self.x.into_repr().0[0]
+    }
+}
+
+impl DlogCurve for G1Affine {
+    type Scalar = Fr;
+
+    fn generator() -> Self {
+        Self::prime_subgroup_generator()
+    }
+
+    fn mul_scalar(&self, s: &Fr) -> <Self as AffineCurve>::Projective {
+        self.mul(*s)
+    }
+
+    fn parse_point(x: &str, y: &str) -> Result<Self, Error> {
+        dlog::weierstrass_point(x, y)
+    }
+
+    fn to_hex(&self) -> HexPoint {
+        dlog::weierstrass_to_hex(self)
+    }
+
+    fn parse_scalar(s: &str) -> Result<Fr, Error> {
+        dlog::parse_scalar::<Fr, Fr>(s)
+    }
+}
+
+/// Encodes the point `(x, y)` in the 64 bytes of the precompiles of the EVM, i.e its two coordinates as 32 bytes big-endian integers.
+pub fn point_to_bytes(x: &str, y: &str) -> Result<[u8; 64], Error> {
+    let (x, y) = G1Affine::parse_point(x, y)?.to_hex();
+    let mut bytes = [0u8; 64];
+    bytes[..32].copy_from_slice(&hex::decode(&x[2..]).unwrap());
+    bytes[32..].copy_from_slice(&hex::decode(&y[2..]).unwrap());
+    Ok(bytes)
+}
+
+/// Decodes a point encoded by [`point_to_bytes`], checking that it is on the curve.
+pub fn point_from_bytes(bytes: &[u8; 64]) -> Result<HexPoint, Error> {
+    let (x, y) = (format!("0x{}", hex::encode(&bytes[..32])), format!("0x{}", hex::encode(&bytes[32..])));
+    Ok(G1Affine::parse_point(&x, &y)?.to_hex())
+}
+
+dlog::curve_module!(G1Affine, weierstrass);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fq;
+    use ark_ff::{BigInteger, FpParameters, One};
+    use crate::point::field_to_hex;
+
+    #[test]
+    fn test_bn254() {
+        assert_eq!(embed_plaintext(1), (GENERATOR.0.to_string(), GENERATOR.1.to_string()));
+        // 2·G, as returned by ecMul
+        assert_eq!(
+            embed_plaintext(2),
+            (
+                "0x030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3".to_string(),
+                "0x15ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4".to_string(),
+            )
+        );
+
+        let private_key = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        let pk = priv_to_pub_key(private_key).unwrap();
+        let (c1, c2) = exp_elgamal_encrypt((&pk.0, &pk.1), 65535, "0x05").unwrap();
+        assert_eq!(exp_elgamal_decrypt(private_key, (&c1.0, &c1.1), (&c2.0, &c2.1)), Ok(embed_plaintext(65535)));
+        let solver = Solver::new(16, 2);
+        assert_eq!(decrypt_to_u64(private_key, (&c1.0, &c1.1), (&c2.0, &c2.1), &solver), Ok(65535));
+        for plaintext in [0, 1, 255, 256] {
+            let p = embed_plaintext(plaintext);
+            assert_eq!(solver.solve(&p.0, &p.1), Ok(plaintext));
+        }
+
+        for p in [&pk, &c2, &embed_plaintext(0)] {
+            assert_eq!(point_from_bytes(&point_to_bytes(&p.0, &p.1).unwrap()).as_ref(), Ok(p));
+        }
+        assert_eq!(G1Affine::parse_point("0x01", "0x01"), Err(Error::NotOnCurve));
+        // the coordinates are elements of the base field, and the scalars of the scalar field
+        assert!(matches!(G1Affine::parse_point(&field_to_hex(&-Fq::one()).replace("0x30", "0x31"), "0x02"), Err(Error::InvalidCoordinate { .. })));
+        let order = format!("0x{}", hex::encode(<Fr as PrimeField>::Params::MODULUS.to_bytes_be()));
+        assert!(matches!(priv_to_pub_key(&order), Err(Error::NotInField { .. })));
+    }
+}
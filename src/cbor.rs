@@ -0,0 +1,228 @@
+//! CBOR encoding (RFC 8949) of the points, the ciphertexts and the results of the solver, for the constrained messaging protocols which
+//! already speak CBOR, e.g the ones of hardware wallets and embedded gateways.
+//!
+//! The items are encoded as :
+//!
+//! - a point `(x, y)` : an array of 2 byte strings, the 32 big-endian bytes of `x` and `y` in the Twisted Edwards form of Noir,
+//! - a ciphertext `(C1, C2)` : an array of 2 points,
+//! - a result : a map `{"dlog": unsigned}` for a Discrete Logarithm, or `{"error": text}` for an error, like the results of the
+//!   [`crate::watch`] module.
+//!
+//! Only the canonical encoding is written and read back : the lengths are definite and encoded on the fewest bytes, and the decoded
+//! items should not be followed by any byte. The points are checked to be in the prime subgroup of Baby Jubjub when they are decoded.
+
+use crate::point::{parse_point, point_bytes, point_to_hex};
+use crate::{Ciphertext, Error, HexPoint};
+
+const UNSIGNED: u8 = 0;
+const BYTES: u8 = 2;
+const TEXT: u8 = 3;
+const ARRAY: u8 = 4;
+const MAP: u8 = 5;
+
+/// Encodes the point `(x, y)` of the prime subgroup of Baby Jubjub.
+pub fn encode_point(x: &str, y: &str) -> Result<Vec<u8>, Error> {
+    let mut out = Vec::with_capacity(69);
+    write_point(&mut out, &point_bytes(&parse_point(x, y)?));
+    Ok(out)
+}
+
+/// Decodes a point encoded by [`encode_point`].
+pub fn decode_point(bytes: &[u8]) -> Result<HexPoint, Error> {
+    let mut reader = Reader { bytes, position: 0 };
+    let point = reader.point()?;
+    reader.end()?;
+    Ok(point)
+}
+
+/// Encodes the ciphertext `(C1, C2)`.
+pub fn encode_ciphertext(ciphertext: &Ciphertext) -> Vec<u8> {
+    let mut out = Vec::with_capacity(139);
+    write_head(&mut out, ARRAY, 2);
+    write_point(&mut out, &point_bytes(&ciphertext.c1));
+    write_point(&mut out, &point_bytes(&ciphertext.c2));
+    out
+}
+
+/// Decodes a ciphertext encoded by [`encode_ciphertext`].
+pub fn decode_ciphertext(bytes: &[u8]) -> Result<Ciphertext, Error> {
+    let mut reader = Reader { bytes, position: 0 };
+    reader.expect_head(ARRAY, 2, "a ciphertext should be an array of 2 points")?;
+    let (c1, c2) = (reader.point()?, reader.point()?);
+    reader.end()?;
+    Ciphertext::new((&c1.0, &c1.1), (&c2.0, &c2.1))
+}
+
+/// Encodes the result of a search, e.g of [`crate::Solver::solve`], the error being encoded as its message.
+pub fn encode_result(result: &Result<u64, Error>) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_head(&mut out, MAP, 1);
+    match result {
+        Ok(dlog) => {
+            write_text(&mut out, "dlog");
+            write_head(&mut out, UNSIGNED, *dlog);
+        }
+        Err(e) => {
+            write_text(&mut out, "error");
+            write_text(&mut out, &e.to_string());
+        }
+    }
+    out
+}
+
+/// Decodes a result encoded by [`encode_result`], into the Discrete Logarithm or the message of the error.
+pub fn decode_result(bytes: &[u8]) -> Result<Result<u64, String>, Error> {
+    let mut reader = Reader { bytes, position: 0 };
+    reader.expect_head(MAP, 1, "a result should be a map of 1 entry")?;
+    let result = match reader.text()?.as_str() {
+        "dlog" => Ok(reader.head(UNSIGNED)?),
+        "error" => Err(reader.text()?),
+        key => return Err(Error::InvalidCbor(format!("the key of a result should be dlog or error, not {:?}", key))),
+    };
+    reader.end()?;
+    Ok(result)
+}
+
+fn write_head(out: &mut Vec<u8>, major: u8, value: u64) {
+    let major = major << 5;
+    match value {
+        0..=23 => out.push(major | value as u8),
+        24..=0xff => out.extend([major | 24, value as u8]),
+        0x100..=0xffff => {
+            out.push(major | 25);
+            out.extend((value as u16).to_be_bytes());
+        }
+        0x10000..=0xffff_ffff => {
+            out.push(major | 26);
+            out.extend((value as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(major | 27);
+            out.extend(value.to_be_bytes());
+        }
+    }
+}
+
+fn write_text(out: &mut Vec<u8>, s: &str) {
+    write_head(out, TEXT, s.len() as u64);
+    out.extend(s.as_bytes());
+}
+
+/// Writes the point of coordinates `x || y`, see [`point_bytes`].
+fn write_point(out: &mut Vec<u8>, xy: &[u8]) {
+    write_head(out, ARRAY, 2);
+    for coordinate in xy.chunks(32) {
+        write_head(out, BYTES, 32);
+        out.extend(coordinate);
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], Error> {
+        let taken = self.bytes[self.position..].get(..n).ok_or_else(|| Error::InvalidCbor("the input is truncated".to_string()))?;
+        self.position += n;
+        Ok(taken)
+    }
+
+    /// Reads the head of an item of type `major`, and returns its argument, i.e its value or its length.
+    fn head(&mut self, major: u8) -> Result<u64, Error> {
+        let position = self.position;
+        let initial = self.take(1)?[0];
+        if initial >> 5 != major {
+            return Err(Error::InvalidCbor(format!("the item at byte {} should be of major type {}, not {}", position, major, initial >> 5)));
+        }
+        let (value, min) = match initial & 0x1f {
+            info @ 0..=23 => (info as u64, 0),
+            24 => (self.take(1)?[0] as u64, 24),
+            25 => (u16::from_be_bytes(self.take(2)?.try_into().unwrap()) as u64, 0x100),
+            26 => (u32::from_be_bytes(self.take(4)?.try_into().unwrap()) as u64, 0x10000),
+            27 => (u64::from_be_bytes(self.take(8)?.try_into().unwrap()), 0x1_0000_0000),
+            _ => return Err(Error::InvalidCbor(format!("the item at byte {} should have a definite length", position))),
+        };
+        if value < min {
+            return Err(Error::InvalidCbor(format!("the argument of the item at byte {} is not encoded on the fewest bytes", position)));
+        }
+        Ok(value)
+    }
+
+    fn expect_head(&mut self, major: u8, value: u64, reason: &str) -> Result<(), Error> {
+        match self.head(major)? {
+            v if v == value => Ok(()),
+            _ => Err(Error::InvalidCbor(reason.to_string())),
+        }
+    }
+
+    fn text(&mut self) -> Result<String, Error> {
+        let len = self.head(TEXT)?;
+        let bytes = self.take(len.try_into().unwrap_or(usize::MAX))?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| Error::InvalidCbor("a text string should be valid UTF-8".to_string()))
+    }
+
+    fn point(&mut self) -> Result<HexPoint, Error> {
+        self.expect_head(ARRAY, 2, "a point should be an array of 2 coordinates")?;
+        let mut coordinate = || -> Result<String, Error> {
+            self.expect_head(BYTES, 32, "a coordinate should be a byte string of 32 bytes")?;
+            Ok(format!("0x{}", hex::encode(self.take(32)?)))
+        };
+        let (x, y) = (coordinate()?, coordinate()?);
+        Ok(point_to_hex(&parse_point(&x, &y)?))
+    }
+
+    fn end(&self) -> Result<(), Error> {
+        match self.bytes.len() - self.position {
+            0 => Ok(()),
+            n => Err(Error::InvalidCbor(format!("{} bytes follow the decoded item", n))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{embed_plaintext, exp_elgamal_encrypt, priv_to_pub_key};
+
+    #[test]
+    fn test_cbor() {
+        let p = embed_plaintext(1);
+        let encoded = encode_point(&p.0, &p.1).unwrap();
+        assert_eq!(hex::encode(&encoded[..3]), "825820");
+        assert_eq!(encoded[3..35], hex::decode(&p.0[2..]).unwrap()[..]);
+        assert_eq!(encoded.len(), 69);
+        assert_eq!(decode_point(&encoded), Ok(p.clone()));
+        assert_eq!(encode_point("0x1", "0x1"), Err(Error::NotOnCurve));
+
+        let pk = priv_to_pub_key("0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c").unwrap();
+        let ciphertext: Ciphertext = exp_elgamal_encrypt((&pk.0, &pk.1), 42, "0x05").unwrap().try_into().unwrap();
+        let encoded = encode_ciphertext(&ciphertext);
+        assert_eq!(encoded.len(), 139);
+        assert_eq!(decode_ciphertext(&encoded), Ok(ciphertext));
+
+        // the diagnostic notation of RFC 8949 : {"dlog": 1000000} and {"error": "..."}
+        assert_eq!(hex::encode(encode_result(&Ok(1000000))), "a164646c6f671a000f4240");
+        assert_eq!(decode_result(&encode_result(&Ok(u64::MAX))), Ok(Ok(u64::MAX)));
+        assert_eq!(decode_result(&encode_result(&Err(Error::Cancelled))), Ok(Err(Error::Cancelled.to_string())));
+
+        for invalid in [
+            &encoded[..138],
+            &[encoded.as_slice(), &[0]].concat(),
+            // truncated items, the argument 16 on 2 bytes instead of 1, an indefinite-length map and an unknown key
+            &hex::decode("a164646c6f6718").unwrap()[..],
+            &hex::decode("a164646c6f67182a").unwrap()[..4],
+            &hex::decode("a164646c6f67190010").unwrap(),
+            &hex::decode("bf64646c6f67182aff").unwrap(),
+            &hex::decode("a1657374617465182a").unwrap(),
+        ] {
+            assert!(matches!(decode_result(invalid), Err(Error::InvalidCbor(_))), "{}", hex::encode(invalid));
+        }
+        assert!(matches!(decode_ciphertext(&encoded[..138]), Err(Error::InvalidCbor(_))));
+        assert!(matches!(decode_point(&encode_result(&Ok(1))), Err(Error::InvalidCbor(_))));
+        let mut not_on_curve = encode_point(&p.0, &p.1).unwrap();
+        not_on_curve[68] ^= 1;
+        assert!(matches!(decode_point(&not_on_curve), Err(Error::NotOnCurve | Error::NotInSubgroup)));
+    }
+}
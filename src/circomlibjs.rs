@@ -0,0 +1,107 @@
+//! Byte layouts of the field elements and points of [ffjavascript](https://github.com/iden3/ffjavascript) and
+//! [circomlibjs](https://github.com/iden3/circomlibjs), so that the fixtures produced in JavaScript can be consumed directly.
+//!
+//! ffjavascript writes a field element as its 32 little-endian bytes, with one of two [`Representation`]s :
+//!
+//! - [`Representation::Standard`] : the integer of the element itself, as written by `F.toRprLE` and read by `F.fromRprLE`.
+//! - [`Representation::Montgomery`] : the integer `a·R mod p` of the element `a`, for `R = 2^256`, which is how the WASM fields of
+//!   ffjavascript store their elements, and thus the content of the `Uint8Array`s returned by `babyJub.F.e`, `babyJub.addPoint` or
+//!   `babyJub.Base8` in circomlibjs, and of `F.toRprLEM`. Dumping these arrays as is gives this representation, not the value of the element.
+//!
+//! A point of circomlibjs is the pair `[x, y]` of its Twisted Edwards coordinates, the same as Noir, written as `x || y` in 64 bytes by
+//! [`point_to_bytes`]. `babyJub.packPoint` and `babyJub.unpackPoint` use the packed encoding of EIP-2494, see [`crate::pack_point`].
+
+use ark_ff::{BigInteger, BigInteger256, Field, PrimeField};
+use ark_ed_on_bn254::Fq;
+use crate::curve::Point;
+use crate::point::{field_to_hex, parse_field};
+use crate::{Error, HexPoint};
+
+/// Representation of the field elements in the bytes of ffjavascript, see the [module documentation](self).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Representation {
+    #[default]
+    Standard,
+    Montgomery,
+}
+
+/// Reads the field element written in `bytes` with `representation`, as a `0x`-prefixed hexadecimal string accepted by the other functions of this crate.
+pub fn field_from_bytes(bytes: &[u8; 32], representation: Representation) -> Result<String, Error> {
+    Ok(field_to_hex(&read_field(bytes, representation)?))
+}
+
+/// Writes the field element `f` as 32 little-endian bytes with `representation`.
+pub fn field_to_bytes(f: &str, representation: Representation) -> Result<[u8; 32], Error> {
+    Ok(write_field(&parse_field(f)?, representation))
+}
+
+/// Reads the point written as `x || y` in `bytes` with `representation`, e.g the concatenation of the two `Uint8Array`s of a point of
+/// circomlibjs, checking that it is on the curve.
+pub fn point_from_bytes(bytes: &[u8; 64], representation: Representation) -> Result<HexPoint, Error> {
+    let mut coordinates = [Fq::from(0u64); 2];
+    for (c, half) in coordinates.iter_mut().zip(bytes.chunks_exact(32)) {
+        *c = read_field(half.try_into().unwrap(), representation)?;
+    }
+    Ok(Point::from_coordinates(coordinates[0], coordinates[1])?.to_hex())
+}
+
+/// Writes the point `(x, y)` of the curve as `x || y` in 64 bytes with `representation`.
+pub fn point_to_bytes(x: &str, y: &str, representation: Representation) -> Result<[u8; 64], Error> {
+    let (x, y) = Point::new(x, y)?.coordinates();
+    let mut bytes = [0u8; 64];
+    bytes[..32].copy_from_slice(&write_field(&x, representation));
+    bytes[32..].copy_from_slice(&write_field(&y, representation));
+    Ok(bytes)
+}
+
+/// `R = 2^256 mod p`, the Montgomery factor of the fields of ffjavascript.
+fn montgomery_factor() -> Fq {
+    Fq::from(2u64).pow([256])
+}
+
+fn read_field(bytes: &[u8; 32], representation: Representation) -> Result<Fq, Error> {
+    let mut repr = BigInteger256::new([0; 4]);
+    repr.read_le(&mut bytes.as_ref()).unwrap();
+    let f = Fq::from_repr(repr).ok_or_else(|| Error::InvalidFormat(format!("0x{}", hex::encode(bytes))))?;
+    Ok(match representation {
+        Representation::Standard => f,
+        Representation::Montgomery => f * montgomery_factor().inverse().unwrap(),
+    })
+}
+
+fn write_field(f: &Fq, representation: Representation) -> [u8; 32] {
+    let f = match representation {
+        Representation::Standard => *f,
+        Representation::Montgomery => *f * montgomery_factor(),
+    };
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&f.into_repr().to_bytes_le());
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::priv_to_pub_key;
+
+    #[test]
+    fn test_circomlibjs_layouts() {
+        // the Montgomery representation of 1 is R mod p, e.g `F.one` in ffjavascript
+        let mut one = hex::decode("0e0a77c19a07df2f666ea36f7879462e36fc76959f60cd29ac96341c4ffffffb").unwrap();
+        one.reverse();
+        assert_eq!(field_to_bytes("0x01", Representation::Montgomery).unwrap().to_vec(), one);
+        assert_eq!(field_from_bytes(&one.try_into().unwrap(), Representation::Montgomery), Ok(field_to_hex(&Fq::from(1u64))));
+        let mut forty_two = [0u8; 32];
+        forty_two[0] = 42;
+        assert_eq!(field_to_bytes("0x2a", Representation::Standard), Ok(forty_two));
+
+        let pk = priv_to_pub_key("0x05").unwrap();
+        for representation in [Representation::Standard, Representation::Montgomery] {
+            let bytes = point_to_bytes(&pk.0, &pk.1, representation).unwrap();
+            assert_eq!(point_from_bytes(&bytes, representation), Ok(pk.clone()));
+        }
+        let standard = point_to_bytes(&pk.0, &pk.1, Representation::Standard).unwrap();
+        assert_eq!(point_from_bytes(&standard, Representation::Montgomery), Err(Error::NotOnCurve));
+        assert!(matches!(field_from_bytes(&[0xff; 32], Representation::Standard), Err(Error::InvalidFormat(_))));
+    }
+}
@@ -0,0 +1,237 @@
+//! Interpretation of the plaintexts of ciphertexts, declared once as a [`PlaintextCodec`] and applied by [`encrypt`], [`decrypt`] and [`solve`],
+//! instead of converting the integers recovered by the Baby-step Giant-step algorithm by hand on each side of an integration.
+//!
+//! A codec maps its values to the unsigned integers of [`PlaintextCodec::max_bitwidth`] bits which are searched for, and the searched
+//! integer `s` of a value is embedded as the point `(s - shift)·G`, which lets e.g the signed amounts of the complement convention be
+//! embedded as `v·G`. The codecs of this crate are :
+//!
+//! - [`Unsigned`] : unsigned integers of at most `bitwidth` bits, embedded as is like in noir-elgamal.
+//! - [`SignedEncoding`](crate::signed::SignedEncoding) : signed integers, see [`crate::signed`].
+//! - [`FixedPoint`] : unsigned decimal amounts with a fixed number of decimals, e.g `"12.50"`, embedded as integers of their smallest unit.
+//! - [`Packing`](crate::packing::Packing) : several small counters packed into a single plaintext, see [`crate::packing`].
+
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ed_on_bn254::{EdwardsAffine as BabyJubJub, Fr};
+use crate::elgamal::encrypt_point;
+use crate::packing::Packing;
+use crate::point::{base_point, parse_point, parse_scalar, point_to_hex};
+use crate::signed::{Convention, SignedEncoding};
+use crate::{Ciphertext, Error, HexPoint, Solver};
+
+/// Mapping between values and the plaintexts embedding them, see the [module documentation](self).
+pub trait PlaintextCodec {
+    type Value;
+
+    /// Bitwidth of the searched integers, i.e the `max_bitwidth` of the [`Solver`] recovering the values.
+    fn max_bitwidth(&self) -> u64;
+
+    /// Searched integer of `value`, or an error if `value` cannot be encoded.
+    fn encode(&self, value: &Self::Value) -> Result<u64, Error>;
+
+    /// Value of the searched integer `plaintext`.
+    fn decode(&self, plaintext: u64) -> Result<Self::Value, Error>;
+
+    /// Offset between the searched integers and the scalars of the embedded points, i.e the searched integer `s` is embedded as `(s - shift)·G`.
+    fn shift(&self) -> u64 {
+        0
+    }
+}
+
+/// Unsigned integers of at most `bitwidth` bits, which are their own plaintexts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unsigned {
+    bitwidth: u64,
+}
+
+impl Unsigned {
+    /// Unsigned integers of at most `bitwidth` bits, which is between `1` and `64`.
+    pub fn new(bitwidth: u64) -> Self {
+        assert!((1..=64).contains(&bitwidth), "bitwidth should be between 1 and 64");
+        Unsigned { bitwidth }
+    }
+}
+
+impl PlaintextCodec for Unsigned {
+    type Value = u64;
+
+    fn max_bitwidth(&self) -> u64 {
+        self.bitwidth
+    }
+
+    fn encode(&self, value: &u64) -> Result<u64, Error> {
+        if self.bitwidth < 64 && *value >> self.bitwidth != 0 {
+            return Err(Error::PlaintextOutOfRange { plaintext: *value, max_bitwidth: self.bitwidth });
+        }
+        Ok(*value)
+    }
+
+    fn decode(&self, plaintext: u64) -> Result<u64, Error> {
+        self.encode(&plaintext)
+    }
+}
+
+/// Unsigned decimal amounts with `decimals` decimals, e.g `"12.50"` for `2` decimals, whose plaintexts are the integers of their smallest
+/// unit (`1250`), of at most `bitwidth` bits. Decoded amounts always have `decimals` decimals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedPoint {
+    decimals: u32,
+    bitwidth: u64,
+}
+
+impl FixedPoint {
+    /// Amounts with `decimals` decimals, at most `19`, whose smallest units are integers of at most `bitwidth` bits, which is between `1` and `64`.
+    pub fn new(decimals: u32, bitwidth: u64) -> Self {
+        assert!(decimals <= 19, "decimals should be at most 19");
+        assert!((1..=64).contains(&bitwidth), "bitwidth should be between 1 and 64");
+        FixedPoint { decimals, bitwidth }
+    }
+
+    pub fn decimals(&self) -> u32 {
+        self.decimals
+    }
+}
+
+impl PlaintextCodec for FixedPoint {
+    type Value = String;
+
+    fn max_bitwidth(&self) -> u64 {
+        self.bitwidth
+    }
+
+    fn encode(&self, value: &String) -> Result<u64, Error> {
+        let invalid = || Error::InvalidAmount(format!("{:?} should be a decimal number with at most {} decimals", value, self.decimals));
+        let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+        let is_digits = |digits: &str| digits.bytes().all(|b| b.is_ascii_digit());
+        if whole.is_empty() || !is_digits(whole) || !is_digits(fraction) || fraction.len() > self.decimals as usize || value.ends_with('.') {
+            return Err(invalid());
+        }
+        let padded = format!("{}{:0<width$}", whole, fraction, width = self.decimals as usize);
+        let plaintext = padded.parse::<u64>().map_err(|_| Error::InvalidAmount(format!("{:?} does not fit in a plaintext of {} bits", value, self.bitwidth)))?;
+        Unsigned::new(self.bitwidth).encode(&plaintext)
+    }
+
+    fn decode(&self, plaintext: u64) -> Result<String, Error> {
+        let plaintext = Unsigned::new(self.bitwidth).decode(plaintext)?;
+        let unit = 10u64.pow(self.decimals);
+        Ok(match self.decimals {
+            0 => plaintext.to_string(),
+            decimals => format!("{}.{:0width$}", plaintext / unit, plaintext % unit, width = decimals as usize),
+        })
+    }
+}
+
+impl PlaintextCodec for SignedEncoding {
+    type Value = i64;
+
+    fn max_bitwidth(&self) -> u64 {
+        self.bitwidth()
+    }
+
+    fn encode(&self, value: &i64) -> Result<u64, Error> {
+        self.offset_plaintext(*value)
+    }
+
+    fn decode(&self, plaintext: u64) -> Result<i64, Error> {
+        if plaintext >> (self.bitwidth() - 1) > 1 {
+            return Err(Error::AmountOutOfRange { bitwidth: self.bitwidth() });
+        }
+        Ok((plaintext as i64).wrapping_add(self.min()))
+    }
+
+    fn shift(&self) -> u64 {
+        match self.convention() {
+            Convention::Offset => 0,
+            Convention::Complement => self.min().unsigned_abs(),
+        }
+    }
+}
+
+impl PlaintextCodec for Packing {
+    type Value = Vec<u64>;
+
+    fn max_bitwidth(&self) -> u64 {
+        Packing::max_bitwidth(self)
+    }
+
+    fn encode(&self, value: &Vec<u64>) -> Result<u64, Error> {
+        Packing::encode(self, value)
+    }
+
+    fn decode(&self, plaintext: u64) -> Result<Vec<u64>, Error> {
+        Packing::decode(self, plaintext)
+    }
+}
+
+/// Embeds `value` as a point, i.e the point recovered by [`crate::exp_elgamal_decrypt`] from its encryptions.
+pub fn embed<C: PlaintextCodec>(codec: &C, value: &C::Value) -> Result<HexPoint, Error> {
+    Ok(point_to_hex(&base_point().mul(scalar(codec, value)?).into_affine()))
+}
+
+/// Encrypts `value` for `public_key` with `randomness`, like [`crate::exp_elgamal_encrypt`] does for unsigned plaintexts.
+pub fn encrypt<C: PlaintextCodec>(codec: &C, public_key: (&str, &str), value: &C::Value, randomness: &str) -> Result<Ciphertext, Error> {
+    let pk = parse_point(public_key.0, public_key.1)?;
+    Ok(encrypt_point(&pk, scalar(codec, value)?, parse_scalar(randomness)?))
+}
+
+/// Decrypts `ciphertext` with `private_key` down to its value, with `solver`, whose maximum bitwidth should be at least
+/// [`PlaintextCodec::max_bitwidth`].
+pub fn decrypt<C: PlaintextCodec>(codec: &C, private_key: &str, ciphertext: &Ciphertext, solver: &Solver) -> Result<C::Value, Error> {
+    recover(codec, ciphertext.decrypt_point(parse_scalar(private_key)?), solver)
+}
+
+/// Recovers the value embedded in the point `(x, y)`, e.g returned by the `exp_elgamal_decrypt` Noir function, like [`crate::do_compute_dlog`].
+pub fn solve<C: PlaintextCodec>(codec: &C, x: &str, y: &str, solver: &Solver) -> Result<C::Value, Error> {
+    recover(codec, parse_point(x, y)?, solver)
+}
+
+fn recover<C: PlaintextCodec>(codec: &C, m: BabyJubJub, solver: &Solver) -> Result<C::Value, Error> {
+    let shifted = m + base_point().mul(Fr::from(codec.shift())).into_affine();
+    codec.decode(solver.search_point(shifted).run()?)
+}
+
+fn scalar<C: PlaintextCodec>(codec: &C, value: &C::Value) -> Result<Fr, Error> {
+    Ok(Fr::from(codec.encode(value)?) - Fr::from(codec.shift()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::priv_to_pub_key;
+
+    #[test]
+    fn test_codecs() {
+        let sk = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        let pk = priv_to_pub_key(sk).unwrap();
+        let pk = (pk.0.as_str(), pk.1.as_str());
+        let solver = Solver::new(16, 1);
+
+        let unsigned = Unsigned::new(16);
+        let ciphertext = encrypt(&unsigned, pk, &42, "0x05").unwrap();
+        assert_eq!(<(HexPoint, HexPoint)>::from(ciphertext), crate::exp_elgamal_encrypt(pk, 42, "0x05").unwrap());
+        assert_eq!(decrypt(&unsigned, sk, &ciphertext, &solver), Ok(42));
+        assert_eq!(encrypt(&unsigned, pk, &(1 << 16), "0x05"), Err(Error::PlaintextOutOfRange { plaintext: 1 << 16, max_bitwidth: 16 }));
+
+        let fixed = FixedPoint::new(2, 16);
+        let ciphertext = encrypt(&fixed, pk, &"12.5".to_string(), "0x05").unwrap();
+        assert_eq!(decrypt(&unsigned, sk, &ciphertext, &solver), Ok(1250));
+        assert_eq!(decrypt(&fixed, sk, &ciphertext, &solver), Ok("12.50".to_string()));
+        assert_eq!(FixedPoint::new(0, 16).decode(7), Ok("7".to_string()));
+        assert_eq!(fixed.encode(&"655.35".to_string()), Ok(65535));
+        assert_eq!(fixed.encode(&"655.36".to_string()), Err(Error::PlaintextOutOfRange { plaintext: 65536, max_bitwidth: 16 }));
+        assert!(matches!(fixed.encode(&"9".repeat(30)), Err(Error::InvalidAmount(_))));
+        for invalid in ["", ".5", "1.", "1.234", "-1", "1.2.3", "1e3", "+1"] {
+            assert!(matches!(fixed.encode(&invalid.to_string()), Err(Error::InvalidAmount(_))), "{}", invalid);
+        }
+
+        let complement = SignedEncoding::new(Convention::Complement, 16);
+        let embedded = embed(&complement, &-42).unwrap();
+        assert_eq!(embedded, complement.embed(-42).unwrap());
+        assert_eq!(solve(&complement, &embedded.0, &embedded.1, &solver), Ok(-42));
+
+        let packing = Packing::new(2, 8).unwrap();
+        let ciphertext = encrypt(&packing, pk, &vec![3, 200], "0x05").unwrap();
+        assert_eq!(decrypt(&packing, sk, &ciphertext, &solver), Ok(vec![3, 200]));
+        // 200 + 200 overflows the last slot
+        assert!(matches!(decrypt(&packing, sk, &(ciphertext + ciphertext), &Solver::new(17, 1)), Err(Error::InvalidPacking(_))));
+    }
+}
@@ -0,0 +1,90 @@
+//! Column-wise solving of nullable coordinates : the results of the rows of a dataset are returned as columns of the same length.
+//!
+//! The input columns are iterators over the optional hexadecimal strings of their rows, and the output [`DlogColumn`] holds the
+//! nullable Discrete Logarithm and error of each row. The rows where one of the coordinates is null are null in both output columns.
+//! There is no support for a specific columnar format : the caller converts its columns from and to these iterators and vectors.
+
+use crate::{decrypt_to_u64, Ciphertext, Error, Solver};
+
+/// Results of the rows of the input columns, in the same order : the Discrete Logarithm of each row, or the message of its error.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DlogColumn {
+    pub dlogs: Vec<Option<u64>>,
+    pub errors: Vec<Option<String>>,
+}
+
+impl DlogColumn {
+    /// Number of rows.
+    pub fn len(&self) -> usize {
+        self.dlogs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dlogs.is_empty()
+    }
+}
+
+/// Solves the embedded plaintexts of the columns `x` and `y`, which should have the same number of rows.
+pub fn solve_columns<'a, I: IntoIterator<Item = Option<&'a str>>>(x: I, y: I, solver: &Solver) -> Result<DlogColumn, Error> {
+    solve_rows([x, y], |[x, y]| solver.solve(x, y))
+}
+
+/// Decrypts the ciphertexts of the columns `c1x`, `c1y`, `c2x` and `c2y` with `private_key`, and solves their embedded plaintexts.
+pub fn decrypt_columns<'a, I: IntoIterator<Item = Option<&'a str>>>(
+    private_key: &str,
+    [c1x, c1y, c2x, c2y]: [I; 4],
+    solver: &Solver,
+) -> Result<DlogColumn, Error> {
+    solve_rows([c1x, c1y, c2x, c2y], |[c1x, c1y, c2x, c2y]| decrypt_to_u64(private_key, &Ciphertext::new((c1x, c1y), (c2x, c2y))?, solver))
+}
+
+fn solve_rows<'a, I: IntoIterator<Item = Option<&'a str>>, const N: usize>(
+    columns: [I; N],
+    solve: impl Fn([&'a str; N]) -> Result<u64, Error>,
+) -> Result<DlogColumn, Error> {
+    let mut columns = columns.map(IntoIterator::into_iter);
+    let mut results = DlogColumn::default();
+    loop {
+        let row: Vec<Option<Option<&str>>> = columns.iter_mut().map(Iterator::next).collect();
+        if row.iter().all(Option::is_none) {
+            return Ok(results);
+        }
+        let Some(row) = row.into_iter().collect::<Option<Vec<Option<&str>>>>() else {
+            return Err(Error::InvalidColumns(format!("the columns should have the same length, some of them end at row {}", results.len())));
+        };
+        let (dlog, error) = match row.into_iter().collect::<Option<Vec<&str>>>() {
+            Some(values) => match solve(values.try_into().unwrap()) {
+                Ok(dlog) => (Some(dlog), None),
+                Err(e) => (None, Some(e.to_string())),
+            },
+            None => (None, None),
+        };
+        results.dlogs.push(dlog);
+        results.errors.push(error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{embed_plaintext, exp_elgamal_encrypt, priv_to_pub_key};
+
+    #[test]
+    fn test_columns() {
+        let solver = Solver::new(20, 2);
+        let (p, q) = (embed_plaintext(42), embed_plaintext(1000));
+        let x = [Some(p.0.as_str()), None, Some("0x01"), Some(q.0.as_str())];
+        let y = [Some(p.1.as_str()), Some("0x01"), Some("0x01"), Some(q.1.as_str())];
+        let column = solve_columns(x, y, &solver).unwrap();
+        assert_eq!(column.dlogs, [Some(42), None, None, Some(1000)]);
+        assert_eq!(column.errors, [None, None, Some(Error::NotOnCurve.to_string()), None]);
+        assert!(matches!(solve_columns(x.to_vec(), vec![None; 3], &solver), Err(Error::InvalidColumns(_))));
+
+        let private_key = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        let pk = priv_to_pub_key(private_key).unwrap();
+        let (c1, c2) = exp_elgamal_encrypt((&pk.0, &pk.1), 65545, "0x05").unwrap();
+        let columns = [vec![Some(c1.0.as_str())], vec![Some(c1.1.as_str())], vec![Some(c2.0.as_str())], vec![Some(c2.1.as_str())]];
+        assert_eq!(decrypt_columns(private_key, columns, &solver), Ok(DlogColumn { dlogs: vec![Some(65545)], errors: vec![None] }));
+        assert!(decrypt_columns(private_key, [Vec::new(), Vec::new(), Vec::new(), Vec::new()], &solver).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,140 @@
+//! Configuration file of the binaries, so that a deployment can be described in one TOML file instead of a wall of command-line flags :
+//!
+//! ```toml
+//! threads = 8
+//! bits = 40
+//! table_bits = 22
+//! profile = "circomlib"
+//! parse_mode = "strict"
+//! audit_log = "/var/log/babygiant/audit.jsonl"
+//! progress_interval = 100000
+//!
+//! [cache]
+//! path = "/var/lib/babygiant/cache"
+//!
+//! [server]
+//! bind = "0.0.0.0:8080"
+//! rate_limit = 10.0
+//! max_concurrent_solves = 2
+//! ```
+//!
+//! Every setting is optional. The binaries take each of them from their command-line flag, then from the corresponding `BABYGIANT_*`
+//! environment variable, then from this file, and finally from their default value.
+
+use serde::Deserialize;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use crate::profile::Profile;
+use crate::{Error, ParseMode};
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Number of threads used by each search.
+    pub threads: Option<u64>,
+    /// Maximum bitwidth of the embedded plaintexts.
+    pub bits: Option<u64>,
+    /// Bitwidth of the table of baby steps, as recommended by `babygiant bench`.
+    pub table_bits: Option<u64>,
+    /// File of the table of baby steps written by `babygiant table build`, loaded instead of building the table.
+    pub table: Option<PathBuf>,
+    /// Generator and encoding of the points and keys, e.g `"circomlib"` or `"zkay"`, see [`crate::profile`].
+    pub profile: Option<Profile>,
+    /// Policy of acceptance of the hexadecimal coordinates, `"strict"` or `"lenient"`, see [`ParseMode`].
+    pub parse_mode: Option<ParseMode>,
+    /// File to which the searches are appended as JSON lines, see [`crate::audit`].
+    pub audit_log: Option<PathBuf>,
+    /// Number of giant steps between two progress events of each worker, written as JSON lines on the standard error, see
+    /// [`crate::Solver::with_progress_interval`].
+    pub progress_interval: Option<u64>,
+    pub cache: CacheConfig,
+    pub server: ServerConfig,
+}
+
+/// Persistent cache of the results of the service.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CacheConfig {
+    /// Directory of the database, created if it does not exist.
+    pub path: Option<PathBuf>,
+}
+
+/// Options of `babygiant-server`, see its `--help` for their meaning.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub bind: Option<SocketAddr>,
+    pub grpc_bind: Option<SocketAddr>,
+    pub rate_limit: Option<f64>,
+    pub burst: Option<u32>,
+    pub max_concurrent_solves: Option<u64>,
+    pub max_queued: Option<usize>,
+}
+
+impl Config {
+    /// Reads the configuration file at `path`, or returns the empty configuration if there is none.
+    pub fn load(path: Option<&Path>) -> Result<Self, Error> {
+        let Some(path) = path else { return Ok(Config::default()) };
+        let contents = std::fs::read_to_string(path).map_err(|e| Error::InvalidConfig(format!("{} : {}", path.display(), e)))?;
+        contents.parse().map_err(|e: Error| match e {
+            Error::InvalidConfig(reason) => Error::InvalidConfig(format!("{} : {}", path.display(), reason)),
+            e => e,
+        })
+    }
+
+    fn validate(self) -> Result<Self, Error> {
+        let invalid = |reason: &str| Err(Error::InvalidConfig(reason.to_string()));
+        if self.threads == Some(0) {
+            return invalid("threads should be positive");
+        }
+        if self.bits.is_some_and(|bits| !(1..=64).contains(&bits)) {
+            return invalid("bits should be between 1 and 64");
+        }
+        if self.table_bits.is_some_and(|table_bits| table_bits > self.bits.unwrap_or(64).min(32)) {
+            return invalid("table_bits should be at most bits and 32");
+        }
+        if self.progress_interval == Some(0) {
+            return invalid("progress_interval should be positive");
+        }
+        if self.server.rate_limit.is_some_and(|rate| !(rate > 0.0 && rate.is_finite())) {
+            return invalid("server.rate_limit should be a positive number");
+        }
+        if self.server.max_concurrent_solves == Some(0) {
+            return invalid("server.max_concurrent_solves should be positive");
+        }
+        Ok(self)
+    }
+}
+
+impl std::str::FromStr for Config {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let config: Config = toml::from_str(s).map_err(|e| Error::InvalidConfig(e.message().to_string()))?;
+        config.validate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config: Config = "threads = 8\nbits = 40\ntable_bits = 22\n\n[cache]\npath = \"cache\"\n\n[server]\nbind = \"0.0.0.0:8080\"\nrate_limit = 10.0\n".parse().unwrap();
+        assert_eq!((config.threads, config.bits, config.table_bits), (Some(8), Some(40), Some(22)));
+        assert_eq!(config.cache.path, Some(PathBuf::from("cache")));
+        assert_eq!(config.server.bind, Some(SocketAddr::from(([0, 0, 0, 0], 8080))));
+        assert_eq!((config.server.rate_limit, config.server.max_queued), (Some(10.0), None));
+        // the output of `babygiant bench --save`
+        assert!("# Measured by `babygiant bench`\nthreads = 1\nbits = 40\ntable_bits = 19\n".parse::<Config>().is_ok());
+        assert_eq!("".parse::<Config>(), Ok(Config::default()));
+        assert_eq!("profile = \"circom\"".parse::<Config>().map(|config| config.profile), Ok(Some(Profile::Circomlib)));
+        assert_eq!("parse_mode = \"strict\"".parse::<Config>().map(|config| config.parse_mode), Ok(Some(ParseMode::Strict)));
+        assert_eq!("audit_log = \"audit.jsonl\"".parse::<Config>().map(|config| config.audit_log), Ok(Some(PathBuf::from("audit.jsonl"))));
+
+        for invalid in ["thread = 8", "bits = 65", "bits = 20\ntable_bits = 21", "[server]\nrate_limit = 0.0", "bits = \"40\"", "profile = \"arkworks\"", "parse_mode = \"relaxed\"", "progress_interval = 0"] {
+            assert!(matches!(invalid.parse::<Config>(), Err(Error::InvalidConfig(_))), "{}", invalid);
+        }
+    }
+}
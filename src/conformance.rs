@@ -0,0 +1,201 @@
+//! Conformance of this crate with the reference vectors exported from the noir-elgamal circuits, so that a format drift between the
+//! Noir package and this crate, e.g a change of its base point, of its coordinates or of its packed encoding, is caught by the tests
+//! of both sides instead of by their users.
+//!
+//! A [`Fixture`] is a JSON document `{"source": "...", "cases": [...]}`, whose cases are objects tagged by their `kind`, each with an
+//! optional `name` reported when it fails. Points are arrays `["0x...", "0x..."]` of their Twisted Edwards coordinates, and scalars and
+//! `Field`s `0x`-prefixed hexadecimal strings, as printed by nargo :
+//!
+//! - `{"kind": "public_key", "private_key", "public_key"}` : the result of `priv_to_pub_key`.
+//! - `{"kind": "encrypt", "public_key", "plaintext", "randomness", "c1", "c2"}` : the result of `exp_elgamal_encrypt`, with the integer `plaintext`.
+//! - `{"kind": "decrypt", "private_key", "c1", "c2", "embedded"}` : the result of `exp_elgamal_decrypt`.
+//! - `{"kind": "dlog", "embedded", "plaintext"}` : the Discrete Logarithm of the embedded plaintext, found by [`crate::do_compute_dlog`].
+//! - `{"kind": "pack", "point", "packed"}` : the packed encoding of a point, see [`crate::pack_point`], as the hexadecimal string of its 32 bytes.
+//! - `{"kind": "vector", ...}` : all the checks of a [`TestVector`], with its fields, e.g as generated by [`crate::vectors::generate`].
+//!
+//! [`Fixture::run`] checks each case and returns the [`Failure`]s, whose points must have exactly the expected coordinates, whatever their
+//! formatting, e.g with or without leading zeros, and whose packed points exactly the expected bytes.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use crate::curve::Point;
+use crate::vectors::TestVector;
+use crate::{exp_elgamal_decrypt, exp_elgamal_encrypt, pack_point, priv_to_pub_key, unpack_point, Error, HexPoint, Solver};
+
+/// Reference vectors, see the [module documentation](self).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fixture {
+    /// Producer of the vectors, e.g the version of noir-elgamal they were exported from.
+    #[serde(default)]
+    pub source: String,
+    pub cases: Vec<Case>,
+}
+
+/// Case of a [`Fixture`], with its optional `name`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Case {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(flatten)]
+    pub check: Check,
+}
+
+/// Expected result of a [`Case`], see the [module documentation](self).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Check {
+    PublicKey { private_key: String, public_key: HexPoint },
+    Encrypt { public_key: HexPoint, plaintext: u64, randomness: String, c1: HexPoint, c2: HexPoint },
+    Decrypt { private_key: String, c1: HexPoint, c2: HexPoint, embedded: HexPoint },
+    Dlog { embedded: HexPoint, plaintext: u64 },
+    Pack { point: HexPoint, packed: String },
+    Vector(TestVector),
+}
+
+/// Case of a [`Fixture`] whose result differs from the expected one, or could not be computed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    /// Index of the case in the fixture.
+    pub case: usize,
+    pub name: Option<String>,
+    pub reason: String,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "case {} ({}) : {}", self.case, name, self.reason),
+            None => write!(f, "case {} : {}", self.case, self.reason),
+        }
+    }
+}
+
+impl Fixture {
+    /// Parses a fixture from its JSON document.
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        serde_json::from_str(json).map_err(|e| Error::InvalidFixture(e.to_string()))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("fixtures are serializable")
+    }
+
+    /// Fixture of the test vectors of this crate, e.g to run the same checks on the Noir side.
+    pub fn from_vectors(source: &str, vectors: Vec<TestVector>) -> Self {
+        let cases = vectors.into_iter().map(|vector| Case { name: None, check: Check::Vector(vector) }).collect();
+        Fixture { source: source.to_string(), cases }
+    }
+
+    /// Checks all the cases, solving the Discrete Logarithms with `solver`, whose `max_bitwidth` should cover the plaintexts of the
+    /// fixture, and returns the failed cases, in order.
+    pub fn run(&self, solver: &Solver) -> Vec<Failure> {
+        self.cases
+            .iter()
+            .enumerate()
+            .filter_map(|(i, case)| {
+                let reason = match check(&case.check, solver) {
+                    Ok(None) => return None,
+                    Ok(Some(mismatch)) => mismatch,
+                    Err(e) => e.to_string(),
+                };
+                Some(Failure { case: i, name: case.name.clone(), reason })
+            })
+            .collect()
+    }
+}
+
+/// Checks a case, returning the first mismatch, or an error if its inputs are invalid.
+fn check(expected: &Check, solver: &Solver) -> Result<Option<String>, Error> {
+    Ok(match expected {
+        Check::PublicKey { private_key, public_key } => compare_point("public key", public_key, &priv_to_pub_key(private_key)?)?,
+        Check::Encrypt { public_key, plaintext, randomness, c1, c2 } => {
+            let (actual_c1, actual_c2) = exp_elgamal_encrypt((&public_key.0, &public_key.1), *plaintext, randomness)?;
+            compare_point("C1", c1, &actual_c1)?.or(compare_point("C2", c2, &actual_c2)?)
+        }
+        Check::Decrypt { private_key, c1, c2, embedded } => {
+            compare_point("embedded plaintext", embedded, &exp_elgamal_decrypt(private_key, (&c1.0, &c1.1), (&c2.0, &c2.1))?)?
+        }
+        Check::Dlog { embedded, plaintext } => {
+            let dlog = solver.solve(&embedded.0, &embedded.1)?;
+            (dlog != *plaintext).then(|| format!("the plaintext is {}, expected {}", dlog, plaintext))
+        }
+        Check::Pack { point, packed } => {
+            let digits = packed.strip_prefix("0x").unwrap_or(packed);
+            let expected: [u8; 32] = hex::decode(digits).ok().and_then(|b| b.try_into().ok()).ok_or_else(|| Error::InvalidFormat(packed.clone()))?;
+            let actual = pack_point(&point.0, &point.1)?;
+            if actual != expected {
+                Some(format!("the packed point is 0x{}, expected {}", hex::encode(actual), packed))
+            } else {
+                compare_point("unpacked point", point, &unpack_point(&expected)?)?
+            }
+        }
+        Check::Vector(v) => {
+            let checks = [
+                Check::PublicKey { private_key: v.private_key.clone(), public_key: v.public_key.clone() },
+                Check::Encrypt { public_key: v.public_key.clone(), plaintext: v.plaintext, randomness: v.randomness.clone(), c1: v.c1.clone(), c2: v.c2.clone() },
+                Check::Decrypt { private_key: v.private_key.clone(), c1: v.c1.clone(), c2: v.c2.clone(), embedded: v.embedded.clone() },
+                Check::Dlog { embedded: v.embedded.clone(), plaintext: v.plaintext },
+            ];
+            for c in &checks {
+                if let Some(mismatch) = check(c, solver)? {
+                    return Ok(Some(mismatch));
+                }
+            }
+            None
+        }
+    })
+}
+
+/// Compares the expected point with the computed one by value, checking that the expected one is on the curve.
+fn compare_point(what: &str, expected: &HexPoint, actual: &HexPoint) -> Result<Option<String>, Error> {
+    let matches = Point::new(&expected.0, &expected.1)? == Point::new(&actual.0, &actual.1)?;
+    Ok((!matches).then(|| format!("the {} is ({}, {}), expected ({}, {})", what, actual.0, actual.1, expected.0, expected.1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{embed_plaintext, vectors};
+
+    #[test]
+    fn test_conformance() {
+        let pk = priv_to_pub_key("0x05").unwrap();
+        let (c1, c2) = exp_elgamal_encrypt((&pk.0, &pk.1), 42, "0x07").unwrap();
+        // coordinates without leading zeros, like nargo may print them
+        let short = |p: &HexPoint| (format!("0x{}", p.0.trim_start_matches("0x").trim_start_matches('0')), p.1.clone());
+        let json = format!(
+            r#"{{"source": "noir-elgamal v0.0.1", "cases": [
+                {{"kind": "public_key", "name": "priv_to_pub_key", "private_key": "0x05", "public_key": ["{}", "{}"]}},
+                {{"kind": "encrypt", "public_key": ["{}", "{}"], "plaintext": 42, "randomness": "0x07", "c1": ["{}", "{}"], "c2": ["{}", "{}"]}},
+                {{"kind": "decrypt", "private_key": "0x05", "c1": ["{}", "{}"], "c2": ["{}", "{}"], "embedded": ["{}", "{}"]}},
+                {{"kind": "dlog", "embedded": ["{}", "{}"], "plaintext": 42}},
+                {{"kind": "pack", "point": ["{}", "{}"], "packed": "0x{}"}}
+            ]}}"#,
+            pk.0, pk.1, pk.0, pk.1, short(&c1).0, c1.1, c2.0, c2.1, c1.0, c1.1, c2.0, c2.1,
+            embed_plaintext(42).0, embed_plaintext(42).1, embed_plaintext(42).0, embed_plaintext(42).1,
+            pk.0, pk.1, hex::encode(pack_point(&pk.0, &pk.1).unwrap()),
+        );
+        let solver = Solver::new(16, 1);
+        let fixture = Fixture::from_json(&json).unwrap();
+        assert_eq!(fixture.source, "noir-elgamal v0.0.1");
+        assert_eq!(fixture.cases[0].name.as_deref(), Some("priv_to_pub_key"));
+        assert_eq!(fixture.run(&solver), vec![]);
+        assert_eq!(Fixture::from_json(&fixture.to_json()), Ok(fixture.clone()));
+
+        // a drift of the plaintext, of the randomness or of the packed encoding is reported
+        let mut drifted = fixture.clone();
+        drifted.cases[3].check = Check::Dlog { embedded: embed_plaintext(42), plaintext: 43 };
+        drifted.cases[1].check = Check::Encrypt { public_key: pk.clone(), plaintext: 42, randomness: "0x08".to_string(), c1: c1.clone(), c2: c2.clone() };
+        drifted.cases[4].check = Check::Pack { point: pk.clone(), packed: format!("0x{}", hex::encode(pack_point(&c1.0, &c1.1).unwrap())) };
+        let failures = drifted.run(&solver);
+        assert_eq!(failures.iter().map(|f| f.case).collect::<Vec<_>>(), vec![1, 3, 4]);
+        assert!(failures[0].reason.starts_with("the C1 is"));
+        assert_eq!(failures[1].to_string(), "case 3 : the plaintext is 42, expected 43");
+        drifted.cases[0].check = Check::PublicKey { private_key: "0x05".to_string(), public_key: ("0x1".to_string(), "0x1".to_string()) };
+        assert_eq!(drifted.run(&solver)[0].to_string(), format!("case 0 (priv_to_pub_key) : {}", Error::NotOnCurve));
+
+        let suite = Fixture::from_vectors("babygiant-alt-bn128", vectors::generate(b"conformance", 16, 2).unwrap());
+        assert_eq!(Fixture::from_json(&suite.to_json()).unwrap().run(&solver), vec![]);
+        assert!(matches!(Fixture::from_json(r#"{"cases": [{"kind": "unknown"}]}"#), Err(Error::InvalidFixture(_))));
+    }
+}
@@ -0,0 +1,219 @@
+//! Compact and versioned byte encoding of the ciphertexts of all the curves of [`Curve`], so that storage layers and message queues
+//! keep one canonical serialization of a ciphertext instead of the four hexadecimal strings of its coordinates.
+//!
+//! A [`Container`] is encoded as :
+//!
+//! - the version of the format, [`VERSION`], on 1 byte,
+//! - the identifier of the curve, see [`Curve::id`], on 1 byte,
+//! - the points `C1` and `C2`, each one compressed in the encoding of its curve : the packed encoding of EIP-2494 for Baby Jubjub, the
+//!   `repr_J` of Zcash for the other Twisted Edwards curves, i.e 32 bytes, and the SEC1 compressed encoding for the short Weierstrass
+//!   curves, i.e 33 bytes, the point at infinity being 33 zero bytes so that all the points of a curve have the same size.
+//!
+//! The points are checked when a container is built or decoded, like the inputs of the crate root, so that a decoded container always
+//! holds a valid ciphertext of its curve.
+
+use crate::dlog::Curve;
+use crate::{Ciphertext, Error, HexPoint};
+
+/// Version of the encoding of the containers written by [`Container::encode`].
+pub const VERSION: u8 = 1;
+
+/// Ciphertext `(C1, C2)` of one of the curves of [`Curve`], with its canonical byte encoding, see the [module documentation](self).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Container {
+    curve: Curve,
+    c1: HexPoint,
+    c2: HexPoint,
+}
+
+impl Container {
+    /// Builds the container of the ciphertext `(C1, C2)` of `curve`, whose coordinates are in the encoding of the module of the curve.
+    pub fn new(curve: Curve, c1: (&str, &str), c2: (&str, &str)) -> Result<Self, Error> {
+        let (c1, c2) = (pack(curve, c1)?, pack(curve, c2)?);
+        Ok(Container { curve, c1: unpack(curve, &c1)?, c2: unpack(curve, &c2)? })
+    }
+
+    pub fn curve(&self) -> Curve {
+        self.curve
+    }
+
+    pub fn c1(&self) -> &HexPoint {
+        &self.c1
+    }
+
+    pub fn c2(&self) -> &HexPoint {
+        &self.c2
+    }
+
+    /// Encodes the container in `2 + 2·s` bytes, for the size `s` of the compressed points of its curve.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = vec![VERSION, self.curve.id()];
+        for p in [&self.c1, &self.c2] {
+            // the points were checked when the container was built
+            bytes.extend(pack(self.curve, (&p.0, &p.1)).unwrap());
+        }
+        bytes
+    }
+
+    /// Decodes a container encoded by [`Container::encode`], checking its version, its curve and its points.
+    pub fn decode(bytes: &[u8]) -> Result<Self, Error> {
+        let invalid = |reason: String| Error::InvalidContainer(reason);
+        let (version, curve, points) = match bytes {
+            [version, curve, points @ ..] => (*version, *curve, points),
+            _ => return Err(invalid(format!("a container has at least 2 bytes, not {}", bytes.len()))),
+        };
+        if version != VERSION {
+            return Err(invalid(format!("the version {} is not supported, only {}", version, VERSION)));
+        }
+        let curve = Curve::from_id(curve)?;
+        let size = point_size(curve);
+        if points.len() != 2 * size {
+            return Err(invalid(format!("the points of {} take {} bytes, not {}", curve, 2 * size, points.len())));
+        }
+        Ok(Container { curve, c1: unpack(curve, &points[..size])?, c2: unpack(curve, &points[size..])? })
+    }
+
+    /// Encodes the container as an hexadecimal string, e.g to store it in a text column.
+    pub fn to_hex(&self) -> String {
+        format!("0x{}", hex::encode(self.encode()))
+    }
+
+    /// Decodes a container encoded by [`Container::to_hex`].
+    pub fn from_hex(s: &str) -> Result<Self, Error> {
+        let bytes = s.strip_prefix("0x").and_then(|s| hex::decode(s).ok()).ok_or_else(|| Error::InvalidFormat(s.to_string()))?;
+        Container::decode(&bytes)
+    }
+}
+
+impl From<&Ciphertext> for Container {
+    /// Container of a ciphertext of Baby Jubjub.
+    fn from(ciphertext: &Ciphertext) -> Self {
+        Container { curve: Curve::BabyJubJub, c1: ciphertext.c1(), c2: ciphertext.c2() }
+    }
+}
+
+impl TryFrom<&Container> for Ciphertext {
+    type Error = Error;
+
+    /// Ciphertext of a container of Baby Jubjub, or [`Error::InvalidContainer`] for the other curves.
+    fn try_from(container: &Container) -> Result<Self, Error> {
+        if container.curve != Curve::BabyJubJub {
+            return Err(Error::InvalidContainer(format!("the ciphertext is on {}, not {}", container.curve, Curve::BabyJubJub)));
+        }
+        Ciphertext::new((&container.c1.0, &container.c1.1), (&container.c2.0, &container.c2.1))
+    }
+}
+
+/// Size of the compressed points of `curve`.
+fn point_size(curve: Curve) -> usize {
+    match curve {
+        Curve::BabyJubJub => 32,
+        #[cfg(feature = "grumpkin")]
+        Curve::Grumpkin => 33,
+        #[cfg(feature = "jubjub")]
+        Curve::Jubjub => 32,
+        #[cfg(feature = "bandersnatch")]
+        Curve::Bandersnatch => 32,
+        #[cfg(feature = "ed-on-bls12-377")]
+        Curve::EdOnBls12_377 => 32,
+        #[cfg(feature = "bn254")]
+        Curve::Bn254 => 33,
+        #[cfg(feature = "pasta")]
+        Curve::Pallas | Curve::Vesta => 33,
+        #[cfg(feature = "secp256k1")]
+        Curve::Secp256k1 => 33,
+    }
+}
+
+fn pack(curve: Curve, (x, y): (&str, &str)) -> Result<Vec<u8>, Error> {
+    Ok(match curve {
+        Curve::BabyJubJub => crate::pack_point(x, y)?.to_vec(),
+        #[cfg(feature = "grumpkin")]
+        Curve::Grumpkin => crate::grumpkin::pack_point(x, y)?.to_vec(),
+        #[cfg(feature = "jubjub")]
+        Curve::Jubjub => crate::jubjub::pack_point(x, y)?.to_vec(),
+        #[cfg(feature = "bandersnatch")]
+        Curve::Bandersnatch => crate::bandersnatch::pack_point(x, y)?.to_vec(),
+        #[cfg(feature = "ed-on-bls12-377")]
+        Curve::EdOnBls12_377 => crate::ed_on_bls12_377::pack_point(x, y)?.to_vec(),
+        #[cfg(feature = "bn254")]
+        Curve::Bn254 => crate::bn254::pack_point(x, y)?.to_vec(),
+        #[cfg(feature = "pasta")]
+        Curve::Pallas => crate::pasta::pallas::pack_point(x, y)?.to_vec(),
+        #[cfg(feature = "pasta")]
+        Curve::Vesta => crate::pasta::vesta::pack_point(x, y)?.to_vec(),
+        #[cfg(feature = "secp256k1")]
+        Curve::Secp256k1 => {
+            // the point at infinity is the single byte 0x00 in SEC1
+            let mut packed = crate::secp256k1::pack_point(x, y)?;
+            packed.resize(33, 0);
+            packed
+        }
+    })
+}
+
+/// Unpacks a point of `curve` packed by [`pack`], of the size of its points.
+fn unpack(curve: Curve, packed: &[u8]) -> Result<HexPoint, Error> {
+    match curve {
+        Curve::BabyJubJub => crate::unpack_point(packed.try_into().unwrap()),
+        #[cfg(feature = "grumpkin")]
+        Curve::Grumpkin => crate::grumpkin::unpack_point(packed.try_into().unwrap()),
+        #[cfg(feature = "jubjub")]
+        Curve::Jubjub => crate::jubjub::unpack_point(packed.try_into().unwrap()),
+        #[cfg(feature = "bandersnatch")]
+        Curve::Bandersnatch => crate::bandersnatch::unpack_point(packed.try_into().unwrap()),
+        #[cfg(feature = "ed-on-bls12-377")]
+        Curve::EdOnBls12_377 => crate::ed_on_bls12_377::unpack_point(packed.try_into().unwrap()),
+        #[cfg(feature = "bn254")]
+        Curve::Bn254 => crate::bn254::unpack_point(packed.try_into().unwrap()),
+        #[cfg(feature = "pasta")]
+        Curve::Pallas => crate::pasta::pallas::unpack_point(packed.try_into().unwrap()),
+        #[cfg(feature = "pasta")]
+        Curve::Vesta => crate::pasta::vesta::unpack_point(packed.try_into().unwrap()),
+        #[cfg(feature = "secp256k1")]
+        Curve::Secp256k1 if packed.iter().all(|&b| b == 0) => crate::secp256k1::unpack_point(&[0]),
+        #[cfg(feature = "secp256k1")]
+        Curve::Secp256k1 => crate::secp256k1::unpack_point(packed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::priv_to_pub_key;
+
+    #[test]
+    fn test_container() {
+        let private_key = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        for curve in Curve::all() {
+            let pk = curve.priv_to_pub_key(private_key).unwrap();
+            let (c1, c2) = curve.encrypt((&pk.0, &pk.1), 65545, "0x05").unwrap();
+            let container = Container::new(curve, (&c1.0, &c1.1), (&c2.0, &c2.1)).unwrap();
+            assert_eq!((container.c1(), container.c2()), (&c1, &c2), "{}", curve);
+            let bytes = container.encode();
+            assert_eq!(bytes[..2], [VERSION, curve.id()]);
+            assert_eq!(bytes.len(), 2 + 2 * point_size(curve));
+            assert_eq!(Container::decode(&bytes), Ok(container.clone()), "{}", curve);
+            assert_eq!(Container::from_hex(&container.to_hex()), Ok(container.clone()));
+            // the identity, e.g the C1 of a null randomness, is a point of every curve
+            let zero = curve.embed_plaintext(0);
+            let identity = Container::new(curve, (&zero.0, &zero.1), (&c2.0, &c2.1)).unwrap();
+            assert_eq!(Container::decode(&identity.encode()), Ok(identity), "{}", curve);
+            assert_eq!(Container::decode(&bytes[..bytes.len() - 1]).map_err(|e| matches!(e, Error::InvalidContainer(_))), Err(true));
+        }
+
+        let pk = priv_to_pub_key(private_key).unwrap();
+        let ciphertext = Ciphertext::new((&pk.0, &pk.1), (&pk.0, &pk.1)).unwrap();
+        let container = Container::from(&ciphertext);
+        assert_eq!(container.encode().len(), 66);
+        assert_eq!(Ciphertext::try_from(&container), Ok(ciphertext));
+        let mut bytes = container.encode();
+        bytes[0] = 2;
+        assert!(matches!(Container::decode(&bytes), Err(Error::InvalidContainer(_))));
+        bytes[0] = VERSION;
+        bytes[1] = 0xff;
+        assert_eq!(Container::decode(&bytes), Err(Error::UnknownCurve("0xff".to_string())));
+        assert!(matches!(Container::decode(&[VERSION]), Err(Error::InvalidContainer(_))));
+        assert_eq!(Container::new(Curve::BabyJubJub, ("0x01", "0x01"), (&pk.0, &pk.1)), Err(Error::NotOnCurve));
+    }
+}
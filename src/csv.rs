@@ -0,0 +1,143 @@
+//! Adapter reading the embedded plaintexts or the ciphertexts of a CSV file, e.g a spreadsheet of exported event data, for the batch
+//! searches such as `babygiant batch --columns`.
+//!
+//! The file starts with a header row naming its columns, and the [`Columns`] map the names of the columns holding the coordinates to
+//! the fields of the [`Record`]s, whatever the other columns and their order. The fields follow RFC 4180 : they are separated by a
+//! delimiter, `,` by default, and fields holding the delimiter, quotes or line breaks are quoted, with their quotes doubled. The empty
+//! lines are skipped, and the names of the columns are compared ignoring the case and the surrounding whitespace.
+
+use crate::{Error, HexPoint};
+
+/// Names of the columns holding the coordinates, see the [module documentation](self).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Columns {
+    names: Vec<String>,
+    delimiter: char,
+}
+
+/// Embedded plaintext or ciphertext of a row of a CSV file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Record {
+    Point(HexPoint),
+    Ciphertext(HexPoint, HexPoint),
+}
+
+impl Columns {
+    /// Columns of the coordinates `x` and `y` of embedded plaintexts.
+    pub fn point(x: &str, y: &str) -> Self {
+        Columns { names: vec![x.to_string(), y.to_string()], delimiter: ',' }
+    }
+
+    /// Columns of the coordinates of the points `C1` and `C2` of ciphertexts.
+    pub fn ciphertext(c1x: &str, c1y: &str, c2x: &str, c2y: &str) -> Self {
+        Columns { names: [c1x, c1y, c2x, c2y].map(str::to_string).to_vec(), delimiter: ',' }
+    }
+
+    /// Sets the delimiter of the fields, e.g `;` for the spreadsheets of some locales, or a tab.
+    pub fn with_delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Whether the records are ciphertexts rather than embedded plaintexts.
+    pub fn is_ciphertext(&self) -> bool {
+        self.names.len() == 4
+    }
+
+    /// Reads the records of the CSV file `contents`, one per row after the header, in the same order.
+    pub fn parse(&self, contents: &str) -> Result<Vec<Record>, Error> {
+        let mut rows = rows(contents, self.delimiter)?.into_iter();
+        let Some((_, header)) = rows.next() else {
+            return Err(Error::InvalidCsv("the file has no header row".to_string()));
+        };
+        let indices = self
+            .names
+            .iter()
+            .map(|name| {
+                header.iter().position(|column| column.trim().eq_ignore_ascii_case(name.trim())).ok_or_else(|| {
+                    Error::InvalidCsv(format!("no column named {:?}, the columns are {:?}", name, header))
+                })
+            })
+            .collect::<Result<Vec<usize>, Error>>()?;
+        rows.map(|(line, fields)| {
+            let field = |i: usize| match fields.get(indices[i]) {
+                Some(field) => Ok(field.trim().to_string()),
+                None => Err(Error::InvalidCsv(format!("line {} has {} fields, no value for the column {:?}", line, fields.len(), self.names[i]))),
+            };
+            Ok(match self.is_ciphertext() {
+                true => Record::Ciphertext((field(0)?, field(1)?), (field(2)?, field(3)?)),
+                false => Record::Point((field(0)?, field(1)?)),
+            })
+        })
+        .collect()
+    }
+}
+
+/// Splits `contents` into its nonempty rows of fields, with the line where each row starts.
+fn rows(contents: &str, delimiter: char) -> Result<Vec<(usize, Vec<String>)>, Error> {
+    let mut rows = Vec::new();
+    let (mut fields, mut field) = (Vec::new(), String::new());
+    let (mut line, mut start, mut quoted) = (1, 1, false);
+    let mut chars = contents.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            '\n' if quoted => {
+                line += 1;
+                field.push(c);
+            }
+            c if quoted => field.push(c),
+            c if c == delimiter => fields.push(std::mem::take(&mut field)),
+            '\n' => {
+                fields.push(std::mem::take(&mut field));
+                if fields.len() > 1 || !fields[0].trim().is_empty() {
+                    rows.push((start, std::mem::take(&mut fields)));
+                }
+                fields.clear();
+                line += 1;
+                start = line;
+            }
+            '\r' if chars.peek() == Some(&'\n') => {}
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(Error::InvalidCsv(format!("the quoted field of line {} is not closed", start)));
+    }
+    fields.push(field);
+    if fields.len() > 1 || !fields[0].trim().is_empty() {
+        rows.push((start, fields));
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv() {
+        let csv = "block,Tx Hash,X,Y\r\n100,0xab,0x01,0x02\r\n\r\n101,\"0xcd, \"\"quoted\"\"\",0x03,0x04\n";
+        let points = Columns::point("x", "y").parse(csv).unwrap();
+        assert_eq!(points, [Record::Point(("0x01".to_string(), "0x02".to_string())), Record::Point(("0x03".to_string(), "0x04".to_string()))]);
+        let csv = "c2y;c2x;note;c1y;c1x\n0x4;0x3;\"multi\nline\";0x2;0x1\n";
+        let ciphertexts = Columns::ciphertext("c1x", "c1y", "c2x", "c2y").with_delimiter(';').parse(csv).unwrap();
+        let (c1, c2) = (("0x1".to_string(), "0x2".to_string()), ("0x3".to_string(), "0x4".to_string()));
+        assert_eq!(ciphertexts, [Record::Ciphertext(c1, c2)]);
+
+        let invalid = |csv: &str| Columns::point("x", "y").parse(csv).unwrap_err().to_string();
+        assert!(invalid("a,b\n1,2").contains("no column named \"x\""));
+        assert!(invalid("x,y\n0x1,0x2\n0x3\n").contains("line 3 has 1 fields"));
+        assert!(invalid("x,y\n\"0x1,0x2\n").contains("line 2 is not closed"));
+        assert!(invalid("").contains("no header row"));
+        assert_eq!(Columns::point("x", "y").parse("x,y\n"), Ok(vec![]));
+    }
+}
@@ -0,0 +1,424 @@
+//! Arithmetic on the points of Baby Jubjub in the Twisted Edwards form used by Noir, without having to handle the transform to the
+//! Edwards form of arkworks described in the [crate documentation](crate) : the coordinates of a [`Point`] are always its Twisted Edwards ones.
+//!
+//! Unlike the functions of the crate root, a [`Point`] is only required to be on the curve, so that protocols handling points outside of
+//! the prime subgroup can use it, and [`Point::is_in_subgroup`] checks whether it is in the prime subgroup.
+//!
+//! The coordinates of the same point in the three forms of Baby Jubjub of EIP-2494, and in its short Weierstrass form, are converted by the
+//! functions of this module :
+//!
+//! - Twisted Edwards, used by Noir and circomlib : `168700x^2 + y^2 = 1 + 168696x^2y^2`.
+//! - Edwards, used by arkworks : `x'^2 + y^2 = 1 + (168696/168700)x'^2y^2`, with `x' = sqrt(168700)·x`, see [`twisted_to_edwards`].
+//! - Montgomery : `v^2 = u^3 + 168698u^2 + u`, with `u = (1 + y) / (1 - y)` and `v = u / x`, see [`twisted_to_montgomery`]. The identity
+//!   `(0, 1)` is the point at infinity of the Montgomery form, and has no coordinates.
+//! - Short Weierstrass, used by some implementations friendly to the precompiles of the EVM : `Y^2 = X^3 + a·X + b`, with
+//!   `a = (3 - 168698^2) / 3`, `b = (2·168698^3 - 9·168698) / 27`, `X = u + 168698/3` and `Y = v`, see [`twisted_to_weierstrass`]. The
+//!   identity is its point at infinity too.
+//!
+//! Points given in another form than the Twisted Edwards one, e.g the Montgomery coordinates of some tooling and of the materials of
+//! EIP-2494, fail as not on the curve when they are passed as is to the solver : they are converted from their strings with
+//! [`montgomery_to_noir`] and [`weierstrass_to_noir`], or solved directly with [`crate::Solver::solve_montgomery`] and
+//! [`crate::Solver::solve_weierstrass`].
+//!
+//! The group of the points of Baby Jubjub has the order `8·l`, for the order `l` of its prime subgroup, see [`SUBGROUP_ORDER`]. Inputs coming
+//! from outside, e.g public keys, should be checked with [`Point::check_subgroup`] or cleared of their small-order component with
+//! [`Point::clear_cofactor`], and their scalars reduced with [`canonical_scalar`].
+
+use std::ops::{Add, Neg, Sub};
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ed_on_bn254::{EdwardsAffine as BabyJubJub, Fq, Fr};
+use ark_ff::{Field, One, PrimeField, Zero};
+#[cfg(feature = "ark-serialize")]
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
+use crate::point::{base_point, coeff_twisted, compress, decompress, field_to_hex, parse_coordinates, parse_field, parse_scalar};
+use crate::{Error, HexPoint};
+
+/// Order `l` of the prime subgroup of Baby Jubjub, generated by [`Point::generator`].
+pub const SUBGROUP_ORDER: &str = "0x060c89ce5c263405370a08b6d0302b0bab3eedb83920ee0a677297dc392126f1";
+/// Order `8·l` of the group of the points of Baby Jubjub, which is larger than the modulus of the base field.
+pub const CURVE_ORDER: &str = "0x30644e72e131a029b85045b68181585d59f76dc1c90770533b94bee1c9093788";
+/// Ratio between the orders of the curve and of its prime subgroup.
+pub const COFACTOR: u64 = 8;
+
+/// Point of Baby Jubjub.
+///
+/// With the `ark-serialize` feature, it implements the traits of arkworks as the compressed point of the Edwards form of arkworks, like
+/// its `EdwardsAffine`, which is checked to be in the prime subgroup when deserialized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Point(BabyJubJub);
+
+impl Point {
+    /// Parses the Twisted Edwards coordinates `(x, y)` of a point of Baby Jubjub, in the same format as the inputs of [`crate::do_compute_dlog`].
+    pub fn new(x: &str, y: &str) -> Result<Self, Error> {
+        let (x, y) = parse_coordinates(x, y, parse_field)?;
+        Point::from_coordinates(x, y)
+    }
+
+    /// Point of Twisted Edwards coordinates `(x, y)`, or [`Error::NotOnCurve`] if it is not on Baby Jubjub.
+    pub fn from_coordinates(x: Fq, y: Fq) -> Result<Self, Error> {
+        let (x, y) = twisted_to_edwards(x, y);
+        let p = BabyJubJub::new(x, y);
+        if !p.is_on_curve() {
+            return Err(Error::NotOnCurve);
+        }
+        Ok(Point(p))
+    }
+
+    /// The base point `G` of noir-elgamal, i.e `Base8` in EIP-2494, which generates the prime subgroup.
+    pub fn generator() -> Self {
+        Point(base_point())
+    }
+
+    /// The neutral element `(0, 1)`.
+    pub fn identity() -> Self {
+        Point(BabyJubJub::zero())
+    }
+
+    /// Point of Montgomery coordinates `(u, v)`, see [`montgomery_to_twisted`].
+    pub fn from_montgomery(u: Fq, v: Fq) -> Result<Self, Error> {
+        let (x, y) = montgomery_to_twisted(u, v).ok_or(Error::NotOnCurve)?;
+        Point::from_coordinates(x, y)
+    }
+
+    /// Parses the Montgomery coordinates `(u, v)` of a point of Baby Jubjub, in the same format as [`Point::new`].
+    pub fn new_montgomery(u: &str, v: &str) -> Result<Self, Error> {
+        Point::from_montgomery(parse_field(u)?, parse_field(v)?)
+    }
+
+    /// Point of short Weierstrass coordinates `(x, y)`, see [`weierstrass_to_twisted`].
+    pub fn from_weierstrass(x: Fq, y: Fq) -> Result<Self, Error> {
+        let (x, y) = weierstrass_to_twisted(x, y).ok_or(Error::NotOnCurve)?;
+        Point::from_coordinates(x, y)
+    }
+
+    /// Parses the short Weierstrass coordinates `(x, y)` of a point of Baby Jubjub, in the same format as [`Point::new`].
+    pub fn new_weierstrass(x: &str, y: &str) -> Result<Self, Error> {
+        let (x, y) = parse_coordinates(x, y, parse_field)?;
+        Point::from_weierstrass(x, y)
+    }
+
+    /// Twisted Edwards coordinates `(x, y)`.
+    pub fn coordinates(&self) -> (Fq, Fq) {
+        edwards_to_twisted(self.0.x, self.0.y)
+    }
+
+    /// Montgomery coordinates `(u, v)`, or `None` for the identity, see [`twisted_to_montgomery`].
+    pub fn montgomery_coordinates(&self) -> Option<(Fq, Fq)> {
+        let (x, y) = self.coordinates();
+        twisted_to_montgomery(x, y)
+    }
+
+    /// Short Weierstrass coordinates `(x, y)`, or `None` for the identity, see [`twisted_to_weierstrass`].
+    pub fn weierstrass_coordinates(&self) -> Option<(Fq, Fq)> {
+        let (x, y) = self.coordinates();
+        twisted_to_weierstrass(x, y)
+    }
+
+    /// Twisted Edwards coordinates `(x, y)` formatted like Noir, see [`crate::format_noir_point`].
+    pub fn to_hex(&self) -> HexPoint {
+        let (x, y) = self.coordinates();
+        (field_to_hex(&x), field_to_hex(&y))
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// Whether the point is in the prime subgroup of Baby Jubjub, which the crate root requires from all the points it parses.
+    pub fn is_in_subgroup(&self) -> bool {
+        self.0.is_in_correct_subgroup_assuming_on_curve()
+    }
+
+    /// Returns [`Error::NotInSubgroup`] if the point is not in the prime subgroup, see [`Point::is_in_subgroup`].
+    pub fn check_subgroup(&self) -> Result<(), Error> {
+        match self.is_in_subgroup() {
+            true => Ok(()),
+            false => Err(Error::NotInSubgroup),
+        }
+    }
+
+    /// Whether the order of the point divides the cofactor, i.e the point is in the small subgroup of order `8`, which includes the identity.
+    pub fn has_small_order(&self) -> bool {
+        self.clear_cofactor().is_identity()
+    }
+
+    /// Multiplies the point by the cofactor, which maps any point of the curve to the prime subgroup.
+    pub fn clear_cofactor(&self) -> Self {
+        Point(self.0.mul_by_cofactor())
+    }
+
+    /// Packed encoding of EIP-2494, see [`crate::pack_point`], which also applies to the points outside of the prime subgroup.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        compress(&self.0)
+    }
+
+    /// Decodes the packed encoding of EIP-2494 of a point of the curve, see [`crate::unpack_point`], which also checks the subgroup.
+    pub fn from_bytes(packed: &[u8; 32]) -> Result<Self, Error> {
+        decompress(packed).map(Point)
+    }
+
+    /// Multiplies the point by the `Field` `scalar`, like `std::ec::tecurve::affine::Point::mul` in Noir, i.e by the integer `scalar`
+    /// and not modulo the order of the prime subgroup, which gives the same result for the points of the prime subgroup only.
+    pub fn mul(&self, scalar: &str) -> Result<Self, Error> {
+        Ok(Point(self.0.mul(parse_field(scalar)?.into_repr()).into_affine()))
+    }
+
+    /// Multiplies the point by a scalar modulo the order of the prime subgroup.
+    pub fn mul_scalar(&self, scalar: Fr) -> Self {
+        Point(self.0.mul(scalar).into_affine())
+    }
+
+    /// Equivalent point of the Edwards form of arkworks.
+    pub fn to_edwards(&self) -> BabyJubJub {
+        self.0
+    }
+}
+
+/// Edwards coordinates `(sqrt(168700)·x, y)` of the point of Twisted Edwards coordinates `(x, y)`.
+pub fn twisted_to_edwards(x: Fq, y: Fq) -> (Fq, Fq) {
+    (x * coeff_twisted(), y)
+}
+
+/// Twisted Edwards coordinates of the point of Edwards coordinates `(x, y)`, i.e the inverse of [`twisted_to_edwards`].
+pub fn edwards_to_twisted(x: Fq, y: Fq) -> (Fq, Fq) {
+    (x * coeff_twisted().inverse().unwrap(), y)
+}
+
+/// Montgomery coordinates `((1 + y) / (1 - y), (1 + y) / ((1 - y)·x))` of the point of Twisted Edwards coordinates `(x, y)`, or `None`
+/// for the identity `(0, 1)`, which is the point at infinity of the Montgomery form. The point `(0, -1)` of order `2` is `(0, 0)`.
+pub fn twisted_to_montgomery(x: Fq, y: Fq) -> Option<(Fq, Fq)> {
+    let u = (Fq::one() + y) * (Fq::one() - y).inverse()?;
+    if x.is_zero() {
+        return u.is_zero().then_some((u, u));
+    }
+    Some((u, u * x.inverse()?))
+}
+
+/// Twisted Edwards coordinates `(u / v, (u - 1) / (u + 1))` of the point of Montgomery coordinates `(u, v)`, i.e the inverse of
+/// [`twisted_to_montgomery`], or `None` if no point of the Twisted Edwards form maps to `(u, v)`.
+pub fn montgomery_to_twisted(u: Fq, v: Fq) -> Option<(Fq, Fq)> {
+    let y = (u - Fq::one()) * (u + Fq::one()).inverse()?;
+    if v.is_zero() {
+        return u.is_zero().then_some((v, y));
+    }
+    Some((u * v.inverse()?, y))
+}
+
+/// Short Weierstrass coordinates `(u + 168698/3, v)` of the point of Twisted Edwards coordinates `(x, y)`, for its Montgomery coordinates
+/// `(u, v)` given by [`twisted_to_montgomery`], or `None` for the identity.
+pub fn twisted_to_weierstrass(x: Fq, y: Fq) -> Option<(Fq, Fq)> {
+    let (u, v) = twisted_to_montgomery(x, y)?;
+    Some((u + montgomery_shift(), v))
+}
+
+/// Twisted Edwards coordinates of the point of short Weierstrass coordinates `(x, y)`, i.e the inverse of [`twisted_to_weierstrass`], or
+/// `None` if no point of the Twisted Edwards form maps to `(x, y)`.
+pub fn weierstrass_to_twisted(x: Fq, y: Fq) -> Option<(Fq, Fq)> {
+    montgomery_to_twisted(x - montgomery_shift(), y)
+}
+
+/// Shift `168698/3` between the `u` coordinate of the Montgomery form and the `x` coordinate of the short Weierstrass form.
+fn montgomery_shift() -> Fq {
+    Fq::from(168698u64) * Fq::from(3u64).inverse().unwrap()
+}
+
+/// Twisted Edwards coordinates formatted like Noir of the point of Montgomery coordinates `(u, v)`, e.g to pass them to the crate root.
+pub fn montgomery_to_noir(u: &str, v: &str) -> Result<HexPoint, Error> {
+    Point::new_montgomery(u, v).map(HexPoint::from)
+}
+
+/// Montgomery coordinates of the point of Twisted Edwards coordinates `(x, y)`, formatted like them, or `None` for the identity, i.e the
+/// inverse of [`montgomery_to_noir`].
+pub fn noir_to_montgomery(x: &str, y: &str) -> Result<Option<HexPoint>, Error> {
+    Ok(Point::new(x, y)?.montgomery_coordinates().map(|(u, v)| (field_to_hex(&u), field_to_hex(&v))))
+}
+
+/// Twisted Edwards coordinates formatted like Noir of the point of short Weierstrass coordinates `(x, y)`, e.g read on-chain.
+pub fn weierstrass_to_noir(x: &str, y: &str) -> Result<HexPoint, Error> {
+    Point::new_weierstrass(x, y).map(HexPoint::from)
+}
+
+/// Short Weierstrass coordinates of the point of Twisted Edwards coordinates `(x, y)`, formatted like them, or `None` for the identity,
+/// i.e the inverse of [`weierstrass_to_noir`].
+pub fn noir_to_weierstrass(x: &str, y: &str) -> Result<Option<HexPoint>, Error> {
+    Ok(Point::new(x, y)?.weierstrass_coordinates().map(|(x, y)| (field_to_hex(&x), field_to_hex(&y))))
+}
+
+/// Reduces the `Field` `scalar` modulo [`SUBGROUP_ORDER`] into the canonical scalar acting like it on the prime subgroup, e.g to store private keys
+/// or compare scalars given by different parties.
+pub fn canonical_scalar(scalar: &str) -> Result<String, Error> {
+    Ok(field_to_hex(&parse_scalar(scalar)?))
+}
+
+/// Whether the Twisted Edwards coordinates `(x, y)`, in the same format as the inputs of [`crate::do_compute_dlog`], are the ones of a
+/// point of Baby Jubjub.
+pub fn is_on_curve(x: &str, y: &str) -> Result<bool, Error> {
+    match Point::new(x, y) {
+        Ok(_) => Ok(true),
+        Err(Error::NotOnCurve) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+impl From<BabyJubJub> for Point {
+    fn from(p: BabyJubJub) -> Self {
+        Point(p)
+    }
+}
+
+impl TryFrom<&HexPoint> for Point {
+    type Error = Error;
+
+    fn try_from(p: &HexPoint) -> Result<Self, Error> {
+        Point::new(&p.0, &p.1)
+    }
+}
+
+impl From<Point> for HexPoint {
+    fn from(p: Point) -> Self {
+        p.to_hex()
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point(self.0 + other.0)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point(self.0 + -other.0)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point(-self.0)
+    }
+}
+
+#[cfg(feature = "ark-serialize")]
+impl CanonicalSerialize for Point {
+    fn serialize<W: Write>(&self, writer: W) -> Result<(), SerializationError> {
+        self.0.serialize(writer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.0.serialized_size()
+    }
+}
+
+#[cfg(feature = "ark-serialize")]
+impl CanonicalDeserialize for Point {
+    fn deserialize<R: Read>(reader: R) -> Result<Self, SerializationError> {
+        Ok(Point(BabyJubJub::deserialize(reader)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dlog::priv_to_pub_key;
+    use crate::embed_plaintext;
+
+    #[test]
+    fn test_point_arithmetic() {
+        let g = Point::generator();
+        assert_eq!(g.to_hex(), ("0x0bb77a6ad63e739b4eacb2e09d6277c12ab8d8010534e0b62893f3f6bb957051".to_string(), "0x25797203f7a0b24925572e1cd16bf9edfce0051fb9e133774b3c257a872d7d8b".to_string()));
+        assert_eq!(Point::try_from(&g.to_hex()), Ok(g));
+        assert_eq!(HexPoint::from(g.mul("0x2a").unwrap()), embed_plaintext(42));
+        assert_eq!(g.mul_scalar(Fr::from(42u64)), g.mul("0x2a").unwrap());
+        assert_eq!(g + g - g, g);
+        assert_eq!(g + -g, Point::identity());
+        assert!(Point::identity().is_identity());
+        assert_eq!(Point::identity().to_hex(), (field_to_hex(&Fq::zero()), field_to_hex(&Fq::from(1u64))));
+        let sk = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        assert_eq!(g.mul(sk).unwrap().to_hex(), priv_to_pub_key::<BabyJubJub>(sk).unwrap());
+        assert_eq!(Point::from(g.to_edwards()), g);
+    }
+
+    #[test]
+    fn test_coordinate_forms() {
+        let on_montgomery = |(u, v): (Fq, Fq)| v.square() == u.square() * u + ark_ff::field_new!(Fq, "168698") * u.square() + u;
+        let points = [Point::generator(), Point::generator().mul("0x2a").unwrap(), -Point::generator(), Point::new("0x0", &field_to_hex(&-Fq::one())).unwrap()];
+        for p in points {
+            let (x, y) = p.coordinates();
+            let (ex, ey) = twisted_to_edwards(x, y);
+            assert!(BabyJubJub::new(ex, ey).is_on_curve());
+            assert_eq!((ex, ey), (p.to_edwards().x, p.to_edwards().y));
+            assert_eq!(edwards_to_twisted(ex, ey), (x, y));
+            let (u, v) = p.montgomery_coordinates().unwrap();
+            assert!(on_montgomery((u, v)));
+            assert_eq!(montgomery_to_twisted(u, v), Some((x, y)));
+            assert_eq!(Point::from_montgomery(u, v), Ok(p));
+        }
+        // the point of order 2 is (0, 0) in the Montgomery form, and the identity is at infinity
+        assert_eq!(points[3].montgomery_coordinates(), Some((Fq::zero(), Fq::zero())));
+        assert_eq!(Point::identity().montgomery_coordinates(), None);
+        assert_eq!(montgomery_to_twisted(-Fq::one(), Fq::one()), None);
+        assert_eq!(Point::from_montgomery(Fq::one(), Fq::one()), Err(Error::NotOnCurve));
+
+        // the Montgomery coordinates of an embedded plaintext are solved like its Twisted Edwards ones
+        let p = embed_plaintext(65545);
+        let (u, v) = noir_to_montgomery(&p.0, &p.1).unwrap().unwrap();
+        assert_eq!(montgomery_to_noir(&u, &v), Ok(p.clone()));
+        assert_eq!(crate::Solver::new(20, 2).solve_montgomery(&u, &v), Ok(65545));
+        assert_eq!(crate::Solver::new(20, 2).solve(&u, &v), Err(Error::NotOnCurve));
+        assert_eq!(noir_to_montgomery("0x0", "0x1"), Ok(None));
+        assert_eq!(montgomery_to_noir("0x1", "0x1"), Err(Error::NotOnCurve));
+
+        // the short Weierstrass coordinates are on y^2 = x^3 + a·x + b
+        let (a, b) = (ark_ff::field_new!(Fq, "7296080957279758407415468581752425029516121466805344781232734728849116493472"),
+                      ark_ff::field_new!(Fq, "16213513238399463127589930181672055621146936592900766180517188641980520820846"));
+        for p in points {
+            let (x, y) = p.weierstrass_coordinates().unwrap();
+            assert_eq!(y.square(), x.square() * x + a * x + b);
+            assert_eq!(weierstrass_to_twisted(x, y), Some(p.coordinates()));
+            assert_eq!(Point::from_weierstrass(x, y), Ok(p));
+        }
+        assert_eq!(Point::identity().weierstrass_coordinates(), None);
+        let (x, y) = noir_to_weierstrass(&p.0, &p.1).unwrap().unwrap();
+        assert_eq!(weierstrass_to_noir(&x, &y), Ok(p.clone()));
+        assert_eq!(crate::Solver::new(20, 2).solve_weierstrass(&x, &y), Ok(65545));
+        assert_eq!(weierstrass_to_noir("0x1", "0x1"), Err(Error::NotOnCurve));
+    }
+
+    #[test]
+    fn test_subgroup() {
+        // (0, -1) is on the curve, of order 2
+        let minus_one = field_to_hex(&-Fq::from(1u64));
+        let p = Point::new("0x0", &minus_one).unwrap();
+        assert!(is_on_curve("0x0", &minus_one).unwrap());
+        assert!(!p.is_in_subgroup());
+        assert!(Point::generator().is_in_subgroup());
+        assert_eq!(p + p, Point::identity());
+        // the order of the subgroup is odd, so multiplying p by it gives p back, and not the identity as when reducing the scalar modulo the order
+        assert_eq!(p.mul(SUBGROUP_ORDER).unwrap(), p);
+        assert_eq!(Point::generator().mul(SUBGROUP_ORDER).unwrap(), Point::identity());
+        assert_eq!(p.mul(&format!("{:#x}", COFACTOR)).unwrap(), Point::identity());
+        assert_eq!(p.check_subgroup(), Err(Error::NotInSubgroup));
+        assert_eq!(Point::generator().check_subgroup(), Ok(()));
+        assert!(p.has_small_order() && Point::identity().has_small_order() && !Point::generator().has_small_order());
+
+        // clearing the cofactor maps any point to the prime subgroup
+        let mixed = Point::generator() + p;
+        assert!(!mixed.is_in_subgroup() && !mixed.has_small_order());
+        assert_eq!(mixed.clear_cofactor(), Point::generator().mul_scalar(Fr::from(COFACTOR)));
+        assert_eq!(Point::from_bytes(&mixed.to_bytes()), Ok(mixed));
+        assert_eq!(Point::from_bytes(&Point::generator().to_bytes()).unwrap().to_hex(), crate::unpack_point(&Point::generator().to_bytes()).unwrap());
+        assert_eq!(crate::unpack_point(&mixed.to_bytes()), Err(Error::NotInSubgroup));
+        assert_eq!(canonical_scalar(SUBGROUP_ORDER), Ok(field_to_hex(&Fq::zero())));
+        assert!(canonical_scalar(CURVE_ORDER).is_err());
+        assert_eq!(canonical_scalar("0x2a"), Ok(field_to_hex(&Fq::from(42u64))));
+        assert_eq!(is_on_curve("0x1", "0x1"), Ok(false));
+        assert_eq!(Point::new("0x1", "0x1"), Err(Error::NotOnCurve));
+        assert!(matches!(is_on_curve("0xzz", "0x1"), Err(Error::InvalidCoordinate { .. })));
+    }
+}
@@ -0,0 +1,349 @@
+//! Baby-step Giant-step search on a Twisted Edwards curve given by its parameters at runtime, e.g a research curve or the curve of a
+//! testnet with nonstandard parameters, without patching the crate.
+//!
+//! The curve `a·x^2 + y^2 = 1 + d·x^2y^2` is defined over the base field of Baby Jubjub, i.e the `Field` of Noir, by the [`CurveParams`]
+//! `a`, `d`, the generator `G` of its prime subgroup and the order `n` of this subgroup. They are validated when they are built : the
+//! curve should be complete, i.e `a` a square and `d` a non-square, so that the addition formula holds for all the points, `n` should be
+//! prime, which is checked with a Miller-Rabin test, and `G` should be a point of the curve other than the identity with `n·G = 0`, so
+//! that its order is exactly `n`.
+//!
+//! The points solved by a [`Solver`] are checked to satisfy `n·P = 0` in the same way, i.e to be in the `n`-torsion of the curve. It is
+//! the subgroup generated by `G` as long as `n^2` does not divide the number of points of the curve, which is at most `p + 1 + 2·sqrt(p)`
+//! for the modulus `p` of the field, e.g for every `n` larger than `2^128`. For the smaller subgroups, a point of the `n`-torsion
+//! outside of `<G>` is not rejected, and its Discrete Logarithm is then not found.
+//!
+//! The Twisted Edwards parameters of Baby Jubjub, `a = 168700` and `d = 168696`, give the same Discrete Logarithms as the [`crate::Solver`].
+
+use std::ops::{Add, Sub, SubAssign};
+use ark_ed_on_bn254::Fq;
+use ark_ff::{BigInteger, BigInteger256, Field, One, PrimeField, SquareRootField, Zero};
+use crate::point::{field_to_hex, parse_coordinates, parse_field};
+use crate::table::BabyStepCurve;
+use crate::{Error, HexPoint};
+
+/// Parameters of a Twisted Edwards curve over the base field of Baby Jubjub, see the [module documentation](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CurveParams {
+    a: Fq,
+    d: Fq,
+    generator: Point,
+    order: BigInteger256,
+}
+
+impl CurveParams {
+    /// Parses and validates the coefficients `a` and `d`, the generator `G` and the order `n` of the prime subgroup of the curve.
+    pub fn new(a: &str, d: &str, generator: (&str, &str), order: &str) -> Result<Self, Error> {
+        let invalid = |reason: &str| Error::InvalidCurve(reason.to_string());
+        let (a, d) = (parse_field(a)?, parse_field(d)?);
+        if a.is_zero() || d.is_zero() || a == d {
+            return Err(invalid("a and d should be distinct and nonzero"));
+        }
+        if !a.legendre().is_qr() || !d.legendre().is_qnr() {
+            return Err(invalid("the curve is not complete, a should be a square and d a non-square"));
+        }
+        let order = parse_field(order)?.into_repr();
+        if order <= BigInteger256::from(1) {
+            return Err(invalid("the order of the subgroup should be larger than 1"));
+        }
+        if !is_probable_prime(&order) {
+            return Err(invalid("the order of the subgroup should be prime"));
+        }
+        let mut params = CurveParams { a, d, generator: Point::identity(a, d), order };
+        let generator = params.point(parse_field(generator.0)?, parse_field(generator.1)?).map_err(|_| invalid("the generator is not on the curve"))?;
+        if generator == Point::identity(a, d) {
+            return Err(invalid("the generator should not be the identity"));
+        }
+        if !generator.is_multiple_of_order(&order) {
+            return Err(invalid("the order of the generator is not the order of the subgroup"));
+        }
+        params.generator = generator;
+        Ok(params)
+    }
+
+    pub fn generator(&self) -> HexPoint {
+        self.generator.to_hex()
+    }
+
+    /// Embeds `plaintext` as the point `plaintext·G`.
+    pub fn embed_plaintext(&self, plaintext: u64) -> HexPoint {
+        Point::to_affine(&self.generator.mul_u64(plaintext)).to_hex()
+    }
+
+    fn point(&self, x: Fq, y: Fq) -> Result<Point, Error> {
+        let (x2, y2) = (x.square(), y.square());
+        if self.a * x2 + y2 != Fq::one() + self.d * x2 * y2 {
+            return Err(Error::NotOnCurve);
+        }
+        Ok(Point { x, y, a: self.a, d: self.d })
+    }
+
+    /// Parses the coordinates `(x, y)` of a point of the curve, checking that it is in the `n`-torsion, see the [module documentation](self).
+    fn parse_point(&self, x: &str, y: &str) -> Result<Point, Error> {
+        let (x, y) = parse_coordinates(x, y, parse_field)?;
+        let p = self.point(x, y)?;
+        if !p.is_multiple_of_order(&self.order) {
+            return Err(Error::NotInSubgroup);
+        }
+        Ok(p)
+    }
+}
+
+/// Number of rounds of the Miller-Rabin test of the order of the subgroup, each of them accepting a composite with probability at most `1/4`.
+const MILLER_RABIN_ROUNDS: usize = 32;
+
+/// Miller-Rabin test of `n`, which is smaller than the modulus of the field, with random bases.
+fn is_probable_prime(n: &BigInteger256) -> bool {
+    let (one, two) = (BigInteger256::from(1), BigInteger256::from(2));
+    if *n == two || *n == BigInteger256::from(3) {
+        return true;
+    }
+    if *n < two || n.is_even() {
+        return false;
+    }
+    let mut n_minus_one = *n;
+    n_minus_one.sub_noborrow(&one);
+    // n - 1 = 2^s·d with d odd
+    let (mut d, mut s) = (n_minus_one, 0);
+    while d.is_even() {
+        d.div2();
+        s += 1;
+    }
+    let mut rng = rand::thread_rng();
+    let bits = n.num_bits();
+    'rounds: for _ in 0..MILLER_RABIN_ROUNDS {
+        let base = loop {
+            // a random integer of the bitwidth of n, drawn again until it is in [2, n - 1)
+            let mut base = BigInteger256::new(rand::Rng::gen(&mut rng));
+            base.divn(256 - bits);
+            if base >= two && base < n_minus_one {
+                break base;
+            }
+        };
+        let mut x = pow_mod(&base, &d, n);
+        if x == one || x == n_minus_one {
+            continue;
+        }
+        for _ in 1..s {
+            x = mul_mod(&x, &x, n);
+            if x == n_minus_one {
+                continue 'rounds;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// `(a + b) mod n`, for `a` and `b` smaller than `n`, which is smaller than `2^255`.
+fn add_mod(a: &BigInteger256, b: &BigInteger256, n: &BigInteger256) -> BigInteger256 {
+    let mut sum = *a;
+    sum.add_nocarry(b);
+    if sum >= *n {
+        sum.sub_noborrow(n);
+    }
+    sum
+}
+
+/// `a·b mod n`, by doubling and adding `a` over the bits of `b`.
+fn mul_mod(a: &BigInteger256, b: &BigInteger256, n: &BigInteger256) -> BigInteger256 {
+    let mut product = BigInteger256::from(0);
+    for bit in b.to_bits_be() {
+        product = add_mod(&product, &product, n);
+        if bit {
+            product = add_mod(&product, a, n);
+        }
+    }
+    product
+}
+
+/// `base^exp mod n`, by squaring and multiplying over the bits of `exp`.
+fn pow_mod(base: &BigInteger256, exp: &BigInteger256, n: &BigInteger256) -> BigInteger256 {
+    let mut power = BigInteger256::from(1);
+    for bit in exp.to_bits_be() {
+        power = mul_mod(&power, &power, n);
+        if bit {
+            power = mul_mod(&power, base, n);
+        }
+    }
+    power
+}
+
+/// Affine point of the curve, which carries its coefficients since they are only known at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Point {
+    x: Fq,
+    y: Fq,
+    a: Fq,
+    d: Fq,
+}
+
+/// Point in extended coordinates `(X, Y, T, Z)`, with `x = X/Z`, `y = Y/Z` and `x·y = T/Z`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Extended {
+    x: Fq,
+    y: Fq,
+    t: Fq,
+    z: Fq,
+    a: Fq,
+    d: Fq,
+}
+
+impl Point {
+    fn identity(a: Fq, d: Fq) -> Self {
+        Point { x: Fq::zero(), y: Fq::one(), a, d }
+    }
+
+    fn is_multiple_of_order(&self, order: &BigInteger256) -> bool {
+        let mut acc = Point::identity(self.a, self.d).to_projective();
+        for bit in order.to_bits_be() {
+            acc = acc + acc;
+            if bit {
+                acc = acc + self.to_projective();
+            }
+        }
+        Point::to_affine(&acc) == Point::identity(self.a, self.d)
+    }
+
+    fn to_hex(self) -> HexPoint {
+        (field_to_hex(&self.x), field_to_hex(&self.y))
+    }
+}
+
+impl Add for Extended {
+    type Output = Extended;
+
+    /// Unified addition of `add-2008-hwcd`, which is complete on a complete curve.
+    fn add(self, other: Extended) -> Extended {
+        let a = self.x * other.x;
+        let b = self.y * other.y;
+        let c = self.d * self.t * other.t;
+        let d = self.z * other.z;
+        let e = (self.x + self.y) * (other.x + other.y) - a - b;
+        let f = d - c;
+        let g = d + c;
+        let h = b - self.a * a;
+        Extended { x: e * f, y: g * h, t: e * h, z: f * g, a: self.a, d: self.d }
+    }
+}
+
+impl Sub for Extended {
+    type Output = Extended;
+
+    fn sub(self, other: Extended) -> Extended {
+        self + Extended { x: -other.x, t: -other.t, ..other }
+    }
+}
+
+impl<'a> SubAssign<&'a Extended> for Extended {
+    fn sub_assign(&mut self, other: &'a Extended) {
+        *self = *self - *other;
+    }
+}
+
+impl BabyStepCurve for Point {
+    type Projective = Extended;
+
+    fn key(&self) -> u64 {
+        self.x.into_repr().0[0]
+    }
+
+    fn mul_u64(&self, k: u64) -> Extended {
+        let mut acc = Point::identity(self.a, self.d).to_projective();
+        for i in (0..64).rev() {
+            acc = acc + acc;
+            if (k >> i) & 1 == 1 {
+                acc = acc + self.to_projective();
+            }
+        }
+        acc
+    }
+
+    fn add_affine(&mut self, other: &Self) {
+        *self = Point::to_affine(&(self.to_projective() + other.to_projective()));
+    }
+
+    fn to_projective(&self) -> Extended {
+        Extended { x: self.x, y: self.y, t: self.x * self.y, z: Fq::one(), a: self.a, d: self.d }
+    }
+
+    fn to_affine(p: &Extended) -> Self {
+        let z_inv = p.z.inverse().unwrap();
+        Point { x: p.x * z_inv, y: p.y * z_inv, a: p.a, d: p.d }
+    }
+}
+
+/// Solver for the Discrete Logarithm of the points of a curve given by its [`CurveParams`], like [`crate::Solver`] on Baby Jubjub.
+pub struct Solver {
+    params: CurveParams,
+    inner: crate::Solver<Point>,
+}
+
+impl Solver {
+    /// Builds the table of baby steps for plaintexts of at most `max_bitwidth` bits (between `1` and `64`), using `num_threads` threads.
+    pub fn new(params: CurveParams, max_bitwidth: u64, num_threads: u64) -> Self {
+        Self::with_table_bitwidth(params, max_bitwidth, max_bitwidth / 2, num_threads)
+    }
+
+    /// Same as [`Solver::new`], with a table of `2^table_bitwidth` baby steps, see [`crate::Solver::with_table_bitwidth`].
+    pub fn with_table_bitwidth(params: CurveParams, max_bitwidth: u64, table_bitwidth: u64, num_threads: u64) -> Self {
+        Solver { params, inner: crate::Solver::with_generator(params.generator, max_bitwidth, table_bitwidth, num_threads) }
+    }
+
+    pub fn params(&self) -> &CurveParams {
+        &self.params
+    }
+
+    pub fn max_bitwidth(&self) -> u64 {
+        self.inner.max_bitwidth()
+    }
+
+    /// Computes the Discrete Logarithm of the point `(x, y)` in base the generator of the curve.
+    pub fn solve(&self, x: &str, y: &str) -> Result<u64, Error> {
+        self.inner.search_point(self.params.parse_point(x, y)?).run()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::SUBGROUP_ORDER;
+    use crate::embed_plaintext;
+
+    #[test]
+    fn test_custom_curve() {
+        // the Twisted Edwards parameters of Baby Jubjub give the same points and Discrete Logarithms as the rest of the crate
+        let g = embed_plaintext(1);
+        let params = CurveParams::new("0x292fc", "0x292f8", (&g.0, &g.1), SUBGROUP_ORDER).unwrap();
+        assert_eq!(params.generator(), g);
+        assert_eq!(params.embed_plaintext(65545), embed_plaintext(65545));
+        let solver = Solver::new(params, 20, 2);
+        for plaintext in [0, 1, 65545, 1 << 19] {
+            let p = embed_plaintext(plaintext);
+            assert_eq!(solver.solve(&p.0, &p.1), Ok(plaintext));
+        }
+        let too_large = embed_plaintext(1 << 20);
+        assert_eq!(solver.solve(&too_large.0, &too_large.1), Err(Error::DlogNotFound { max_bitwidth: 20 }));
+        assert_eq!(solver.solve("0x01", "0x01"), Err(Error::NotOnCurve));
+        // (0, -1) is on the curve, of order 2
+        assert_eq!(solver.solve("0x00", &field_to_hex(&-Fq::one())), Err(Error::NotInSubgroup));
+
+        // the generator of a curve with other parameters
+        let pk = embed_plaintext(5);
+        assert!(CurveParams::new("0x292fc", "0x292f8", (&pk.0, &pk.1), SUBGROUP_ORDER).is_ok());
+        for (n, prime) in [(2u64, true), (3, true), (4, false), (561, false), (65537, true), (65535, false), ((1 << 61) - 1, true)] {
+            assert_eq!(is_probable_prime(&BigInteger256::from(n)), prime, "{}", n);
+        }
+        let g = (g.0.as_str(), g.1.as_str());
+        for (a, d, generator, order) in [
+            ("0x292fc", "0x292fc", g, SUBGROUP_ORDER),
+            ("0x292f8", "0x292fc", g, SUBGROUP_ORDER),
+            ("0x292fc", "0x292f8", ("0x01", "0x01"), SUBGROUP_ORDER),
+            ("0x292fc", "0x292f8", ("0x00", "0x01"), SUBGROUP_ORDER),
+            ("0x292fc", "0x292f8", g, "0x05"),
+            // G is of order l, and thus 2·l·G = 0, but 2·l is not prime
+            ("0x292fc", "0x292f8", g, "0xc19139cb84c680a6e14116da0605617567ddb707241dc14cee52fb872424de2"),
+        ] {
+            assert!(matches!(CurveParams::new(a, d, generator, order), Err(Error::InvalidCurve(_))), "{} {} {:?} {}", a, d, generator, order);
+        }
+    }
+}
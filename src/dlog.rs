@@ -0,0 +1,569 @@
+//! Curves on which the [`crate::Solver`] computes Discrete Logarithms, and the exponential ElGamal built on them.
+//!
+//! A curve implements [`DlogCurve`] with its generator, the parsing of its points and scalars and the transform of their coordinates
+//! to its internal representation, on top of the group operations and the key of the baby steps of [`BabyStepCurve`]. Baby Jubjub, in
+//! the Twisted Edwards form of Noir, is the default curve of the crate, and each of the other curves, e.g the `grumpkin`, `jubjub` and
+//! `secp256k1` modules, is a module implementing the same trait, so that they all share the same encryption, decryption and
+//! Baby-step Giant-step search instead of a copy of them. A curve implemented outside of this crate gets them the same way, with
+//! [`crate::Solver::for_curve`] and the functions of this module.
+//!
+//! Every curve module provides the same `Solver`, `priv_to_pub_key`, `embed_plaintext`, `exp_elgamal_encrypt`, `exp_elgamal_decrypt`
+//! and `decrypt_to_u64` : a plaintext `m` is encrypted for the public key `pk = sk·G` with the randomness `r` as
+//! `(C1, C2) = (r·G, m·G + r·pk)`, like [`crate::exp_elgamal_encrypt`] on Baby Jubjub, and decrypted to `m·G`, whose Discrete Logarithm
+//! is searched by the `Solver` of the module.
+
+use ark_ec::AffineCurve;
+use ark_ed_on_bn254::{EdwardsAffine as BabyJubJub, Fr};
+#[cfg(any(feature = "grumpkin", feature = "bn254", feature = "pasta"))]
+use ark_ec::models::{short_weierstrass_jacobian, SWModelParameters};
+#[cfg(any(feature = "jubjub", feature = "bandersnatch", feature = "ed-on-bls12-377"))]
+use ark_ec::models::{twisted_edwards_extended::GroupAffine, TEModelParameters};
+#[cfg(any(feature = "grumpkin", feature = "jubjub", feature = "bandersnatch", feature = "ed-on-bls12-377", feature = "bn254", feature = "pasta"))]
+use ark_ff::{BigInteger, Field, SquareRootField};
+#[cfg(any(feature = "grumpkin", feature = "jubjub", feature = "bandersnatch", feature = "ed-on-bls12-377", feature = "bn254", feature = "pasta"))]
+use ark_ff::{BigInteger256, PrimeField};
+#[cfg(any(feature = "grumpkin", feature = "jubjub", feature = "bandersnatch", feature = "ed-on-bls12-377", feature = "bn254", feature = "pasta"))]
+use ark_ff::Zero;
+#[cfg(any(feature = "jubjub", feature = "bandersnatch", feature = "ed-on-bls12-377"))]
+use ark_ff::One;
+use crate::point::{self, base_point, point_to_hex};
+#[cfg(any(feature = "grumpkin", feature = "bn254", feature = "pasta"))]
+use crate::point::field_to_hex;
+#[cfg(any(feature = "grumpkin", feature = "jubjub", feature = "bandersnatch", feature = "ed-on-bls12-377", feature = "bn254", feature = "pasta"))]
+use crate::point::{parse_coordinates, parse_prime_field};
+use crate::table::BabyStepCurve;
+use crate::{Error, HexPoint};
+
+/// Curve of the Discrete Logarithms, with its generator and the encodings of its points and scalars.
+pub trait DlogCurve: BabyStepCurve {
+    type Scalar;
+
+    /// Generator `G` of the prime subgroup, in base which the plaintexts are embedded.
+    fn generator() -> Self;
+
+    fn mul_scalar(&self, s: &Self::Scalar) -> Self::Projective;
+
+    /// Parses the coordinates `(x, y)` of a point, in the encoding of the curve, into its internal representation, checking that it is
+    /// in the prime subgroup.
+    fn parse_point(x: &str, y: &str) -> Result<Self, Error>;
+
+    /// Coordinates of the point, in the encoding of the curve.
+    fn to_hex(&self) -> HexPoint;
+
+    /// Parses a scalar, i.e a private key or an encryption randomness.
+    fn parse_scalar(s: &str) -> Result<Self::Scalar, Error>;
+}
+
+/// Baby Jubjub, whose points are encoded in the Twisted Edwards form of Noir and transformed to the Edwards form of arkworks.
+impl DlogCurve for BabyJubJub {
+    type Scalar = Fr;
+
+    fn generator() -> Self {
+        base_point()
+    }
+
+    fn mul_scalar(&self, s: &Fr) -> <Self as AffineCurve>::Projective {
+        self.mul(*s)
+    }
+
+    fn parse_point(x: &str, y: &str) -> Result<Self, Error> {
+        point::parse_point(x, y)
+    }
+
+    fn to_hex(&self) -> HexPoint {
+        point_to_hex(self)
+    }
+
+    fn parse_scalar(s: &str) -> Result<Fr, Error> {
+        point::parse_scalar(s)
+    }
+}
+
+/// Evaluates `$body` with `$c` the [`DlogCurve`] of the [`Curve`] `$curve`.
+macro_rules! on_curve {
+    ($curve:expr, $c:ident => $body:expr) => {
+        match $curve {
+            Curve::BabyJubJub => { type $c = BabyJubJub; $body }
+            #[cfg(feature = "grumpkin")]
+            Curve::Grumpkin => { type $c = crate::grumpkin::Grumpkin; $body }
+            #[cfg(feature = "jubjub")]
+            Curve::Jubjub => { type $c = ark_ed_on_bls12_381::EdwardsAffine; $body }
+            #[cfg(feature = "bandersnatch")]
+            Curve::Bandersnatch => { type $c = crate::bandersnatch::Bandersnatch; $body }
+            #[cfg(feature = "ed-on-bls12-377")]
+            Curve::EdOnBls12_377 => { type $c = crate::ed_on_bls12_377::EdOnBls12_377; $body }
+            #[cfg(feature = "bn254")]
+            Curve::Bn254 => { type $c = crate::bn254::G1Affine; $body }
+            #[cfg(feature = "pasta")]
+            Curve::Pallas => { type $c = crate::pasta::Pallas; $body }
+            #[cfg(feature = "pasta")]
+            Curve::Vesta => { type $c = crate::pasta::Vesta; $body }
+            #[cfg(feature = "secp256k1")]
+            Curve::Secp256k1 => { type $c = crate::secp256k1::Secp256k1; $body }
+        }
+    };
+}
+
+/// Curve selected at runtime by [`crate::compute_dlog_on`], e.g by a service handling ciphertexts from several ecosystems in one binary.
+///
+/// The curves other than Baby Jubjub are only available with their feature. Ristretto255, whose points have no coordinates, is solved
+/// by [`crate::ristretto::Solver`] instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Curve {
+    BabyJubJub,
+    #[cfg(feature = "grumpkin")]
+    Grumpkin,
+    #[cfg(feature = "jubjub")]
+    Jubjub,
+    #[cfg(feature = "bandersnatch")]
+    Bandersnatch,
+    #[cfg(feature = "ed-on-bls12-377")]
+    EdOnBls12_377,
+    #[cfg(feature = "bn254")]
+    Bn254,
+    #[cfg(feature = "pasta")]
+    Pallas,
+    #[cfg(feature = "pasta")]
+    Vesta,
+    #[cfg(feature = "secp256k1")]
+    Secp256k1,
+}
+
+impl Curve {
+    /// Name of the curve, e.g `babyjubjub` like in table files, which is parsed back by [`Curve::from_str`](std::str::FromStr).
+    pub fn name(&self) -> &'static str {
+        match self {
+            Curve::BabyJubJub => crate::table::CURVE,
+            #[cfg(feature = "grumpkin")]
+            Curve::Grumpkin => "grumpkin",
+            #[cfg(feature = "jubjub")]
+            Curve::Jubjub => "jubjub",
+            #[cfg(feature = "bandersnatch")]
+            Curve::Bandersnatch => "bandersnatch",
+            #[cfg(feature = "ed-on-bls12-377")]
+            Curve::EdOnBls12_377 => "ed-on-bls12-377",
+            #[cfg(feature = "bn254")]
+            Curve::Bn254 => "bn254",
+            #[cfg(feature = "pasta")]
+            Curve::Pallas => "pallas",
+            #[cfg(feature = "pasta")]
+            Curve::Vesta => "vesta",
+            #[cfg(feature = "secp256k1")]
+            Curve::Secp256k1 => "secp256k1",
+        }
+    }
+
+    /// Identifier of the curve in the byte encodings, e.g the [`crate::container::Container`]s, which is stable across versions.
+    pub fn id(&self) -> u8 {
+        match self {
+            Curve::BabyJubJub => 0x01,
+            #[cfg(feature = "grumpkin")]
+            Curve::Grumpkin => 0x02,
+            #[cfg(feature = "jubjub")]
+            Curve::Jubjub => 0x03,
+            #[cfg(feature = "bandersnatch")]
+            Curve::Bandersnatch => 0x04,
+            #[cfg(feature = "ed-on-bls12-377")]
+            Curve::EdOnBls12_377 => 0x05,
+            #[cfg(feature = "bn254")]
+            Curve::Bn254 => 0x06,
+            #[cfg(feature = "pasta")]
+            Curve::Pallas => 0x07,
+            #[cfg(feature = "pasta")]
+            Curve::Vesta => 0x08,
+            #[cfg(feature = "secp256k1")]
+            Curve::Secp256k1 => 0x09,
+        }
+    }
+
+    /// Curve of the identifier `id`, see [`Curve::id`], or [`Error::UnknownCurve`] if it is not available with the enabled features.
+    pub fn from_id(id: u8) -> Result<Curve, Error> {
+        Curve::all().into_iter().find(|curve| curve.id() == id).ok_or_else(|| Error::UnknownCurve(format!("{:#04x}", id)))
+    }
+
+    /// Curves available with the enabled features.
+    pub fn all() -> Vec<Curve> {
+        vec![
+            Curve::BabyJubJub,
+            #[cfg(feature = "grumpkin")]
+            Curve::Grumpkin,
+            #[cfg(feature = "jubjub")]
+            Curve::Jubjub,
+            #[cfg(feature = "bandersnatch")]
+            Curve::Bandersnatch,
+            #[cfg(feature = "ed-on-bls12-377")]
+            Curve::EdOnBls12_377,
+            #[cfg(feature = "bn254")]
+            Curve::Bn254,
+            #[cfg(feature = "pasta")]
+            Curve::Pallas,
+            #[cfg(feature = "pasta")]
+            Curve::Vesta,
+            #[cfg(feature = "secp256k1")]
+            Curve::Secp256k1,
+        ]
+    }
+
+    /// Computes the Discrete Logarithm of the point `(x, y)` of the curve, in the encoding of its module, see [`crate::compute_dlog_on`].
+    pub(crate) fn solve(&self, x: &str, y: &str, config: &SolveConfig) -> Result<u64, Error> {
+        on_curve!(self, C => solve_on::<C>(x, y, config))
+    }
+
+    /// Computes the public key `sk·G` of the private key `sk` on the curve, see [`priv_to_pub_key`].
+    pub fn priv_to_pub_key(&self, private_key: &str) -> Result<HexPoint, Error> {
+        on_curve!(self, C => priv_to_pub_key::<C>(private_key))
+    }
+
+    /// Embeds `plaintext` as the point `plaintext·G` of the curve, see [`embed_plaintext`].
+    pub fn embed_plaintext(&self, plaintext: u64) -> HexPoint {
+        on_curve!(self, C => embed_plaintext::<C>(plaintext))
+    }
+
+    /// Encrypts `plaintext` for `public_key` with `randomness` on the curve, see [`encrypt`].
+    pub fn encrypt(&self, public_key: (&str, &str), plaintext: u64, randomness: &str) -> Result<(HexPoint, HexPoint), Error> {
+        on_curve!(self, C => encrypt::<C>(public_key, plaintext, randomness))
+    }
+
+    /// Decrypts the ciphertext `(C1, C2)` with `private_key` into its embedded plaintext on the curve, see [`decrypt`].
+    pub fn decrypt(&self, private_key: &str, c1: (&str, &str), c2: (&str, &str)) -> Result<HexPoint, Error> {
+        on_curve!(self, C => decrypt::<C>(private_key, c1, c2))
+    }
+}
+
+impl std::fmt::Display for Curve {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for Curve {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Curve::all().into_iter().find(|curve| curve.name() == s).ok_or_else(|| Error::UnknownCurve(s.to_string()))
+    }
+}
+
+/// Parameters of the search of [`crate::compute_dlog_on`], like the arguments of [`crate::Solver::with_table_bitwidth`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolveConfig {
+    pub max_bitwidth: u64,
+    pub table_bitwidth: u64,
+    pub num_threads: u64,
+}
+
+impl SolveConfig {
+    /// Search for plaintexts of at most `max_bitwidth` bits, with the default table of `2^(max_bitwidth / 2)` baby steps.
+    pub fn new(max_bitwidth: u64, num_threads: u64) -> Self {
+        SolveConfig { max_bitwidth, table_bitwidth: max_bitwidth / 2, num_threads }
+    }
+}
+
+impl Default for SolveConfig {
+    /// Search for `u40` plaintexts on one thread, like [`crate::compute_dlog`].
+    fn default() -> Self {
+        SolveConfig::new(40, 1)
+    }
+}
+
+fn solve_on<C: DlogCurve>(x: &str, y: &str, config: &SolveConfig) -> Result<u64, Error> {
+    let point = C::parse_point(x, y)?;
+    crate::Solver::for_curve(config.max_bitwidth, config.table_bitwidth, config.num_threads).search_point(point).run()
+}
+
+/// Computes the public key `sk·G` of the private key `sk`.
+pub fn priv_to_pub_key<C: DlogCurve>(private_key: &str) -> Result<HexPoint, Error> {
+    Ok(C::to_affine(&C::generator().mul_scalar(&C::parse_scalar(private_key)?)).to_hex())
+}
+
+/// Embeds `plaintext` as the point `plaintext·G`.
+pub fn embed_plaintext<C: DlogCurve>(plaintext: u64) -> HexPoint {
+    C::to_affine(&C::generator().mul_u64(plaintext)).to_hex()
+}
+
+/// Encrypts `plaintext` for `public_key` with `randomness`, i.e returns `(r·G, m·G + r·pk)`.
+pub fn encrypt<C: DlogCurve>(public_key: (&str, &str), plaintext: u64, randomness: &str) -> Result<(HexPoint, HexPoint), Error> {
+    let pk = C::parse_point(public_key.0, public_key.1)?;
+    let r = C::parse_scalar(randomness)?;
+    let g = C::generator();
+    let c2 = g.mul_u64(plaintext) + pk.mul_scalar(&r);
+    Ok((C::to_affine(&g.mul_scalar(&r)).to_hex(), C::to_affine(&c2).to_hex()))
+}
+
+/// Decrypts the ciphertext `(C1, C2)` with `private_key` into its embedded plaintext `C2 - sk·C1`.
+pub fn decrypt<C: DlogCurve>(private_key: &str, c1: (&str, &str), c2: (&str, &str)) -> Result<HexPoint, Error> {
+    let sk = C::parse_scalar(private_key)?;
+    let (c1, c2) = (C::parse_point(c1.0, c1.1)?, C::parse_point(c2.0, c2.1)?);
+    Ok(C::to_affine(&(c2.to_projective() - c1.mul_scalar(&sk))).to_hex())
+}
+
+#[cfg(any(feature = "grumpkin", feature = "jubjub", feature = "bandersnatch", feature = "ed-on-bls12-377", feature = "bn254", feature = "pasta", feature = "secp256k1", feature = "ristretto"))]
+/// Defines, in the module of the curve `$curve`, its `Solver` and its exponential ElGamal on top of the functions of this module, and
+/// with `weierstrass` or `edwards` the `pack_point` and `unpack_point` of the SEC1 compressed encoding or of the `repr_J` of Zcash, so
+/// that every curve module provides the same functions. The `solver` form only defines the `Solver` of a curve which is not a
+/// [`DlogCurve`], with its generator `$generator`, leaving its `solve` to the module.
+macro_rules! curve_module {
+    (solver $curve:ty, $generator:expr) => {
+        /// Solver for the Discrete Logarithm of the embedded plaintexts of the curve, like [`crate::Solver`] on Baby Jubjub.
+        pub struct Solver {
+            inner: $crate::Solver<$curve>,
+        }
+
+        impl Solver {
+            /// Builds the table of baby steps for plaintexts of at most `max_bitwidth` bits (between `1` and `64`), using `num_threads` threads.
+            pub fn new(max_bitwidth: u64, num_threads: u64) -> Self {
+                Self::with_table_bitwidth(max_bitwidth, max_bitwidth / 2, num_threads)
+            }
+
+            /// Same as [`Solver::new`], with a table of `2^table_bitwidth` baby steps, see [`crate::Solver::with_table_bitwidth`].
+            pub fn with_table_bitwidth(max_bitwidth: u64, table_bitwidth: u64, num_threads: u64) -> Self {
+                Solver { inner: $crate::Solver::with_generator($generator, max_bitwidth, table_bitwidth, num_threads) }
+            }
+
+            pub fn max_bitwidth(&self) -> u64 {
+                self.inner.max_bitwidth()
+            }
+        }
+    };
+    ($curve:ty) => {
+        $crate::dlog::curve_module!(solver $curve, <$curve as $crate::dlog::DlogCurve>::generator());
+
+        impl Solver {
+            /// Computes the Discrete Logarithm of the embedded plaintext `(x, y)` in base [`GENERATOR`].
+            pub fn solve(&self, x: &str, y: &str) -> Result<u64, $crate::Error> {
+                self.inner.solve(x, y)
+            }
+        }
+
+        /// Computes the public key `sk·G` of the private key `sk`.
+        pub fn priv_to_pub_key(private_key: &str) -> Result<$crate::HexPoint, $crate::Error> {
+            $crate::dlog::priv_to_pub_key::<$curve>(private_key)
+        }
+
+        /// Embeds `plaintext` as the point `plaintext·G`.
+        pub fn embed_plaintext(plaintext: u64) -> $crate::HexPoint {
+            $crate::dlog::embed_plaintext::<$curve>(plaintext)
+        }
+
+        /// Encrypts `plaintext` for `public_key` with `randomness`, i.e returns `(r·G, m·G + r·pk)`.
+        pub fn exp_elgamal_encrypt(public_key: (&str, &str), plaintext: u64, randomness: &str) -> Result<($crate::HexPoint, $crate::HexPoint), $crate::Error> {
+            $crate::dlog::encrypt::<$curve>(public_key, plaintext, randomness)
+        }
+
+        /// Decrypts the ciphertext `(C1, C2)` with `private_key` into its embedded plaintext `C2 - sk·C1`.
+        pub fn exp_elgamal_decrypt(private_key: &str, c1: (&str, &str), c2: (&str, &str)) -> Result<$crate::HexPoint, $crate::Error> {
+            $crate::dlog::decrypt::<$curve>(private_key, c1, c2)
+        }
+
+        /// Decrypts the ciphertext `(C1, C2)` with `private_key` down to its plaintext, searching its Discrete Logarithm with `solver`.
+        pub fn decrypt_to_u64(private_key: &str, c1: (&str, &str), c2: (&str, &str), solver: &Solver) -> Result<u64, $crate::Error> {
+            let embedded = exp_elgamal_decrypt(private_key, c1, c2)?;
+            solver.solve(&embedded.0, &embedded.1)
+        }
+    };
+    ($curve:ty, weierstrass) => {
+        $crate::dlog::curve_module!($curve);
+
+        /// Packs the point `(x, y)` in the 33 bytes of its SEC1 compressed encoding, the point at infinity being 33 zero bytes.
+        pub fn pack_point(x: &str, y: &str) -> Result<[u8; 33], $crate::Error> {
+            Ok($crate::dlog::pack_weierstrass(&<$curve as $crate::dlog::DlogCurve>::parse_point(x, y)?))
+        }
+
+        /// Unpacks a point packed by [`pack_point`], rejecting the non-canonical encodings and the points outside of the curve.
+        pub fn unpack_point(packed: &[u8; 33]) -> Result<$crate::HexPoint, $crate::Error> {
+            let p: $curve = $crate::dlog::unpack_weierstrass(packed)?;
+            Ok($crate::dlog::DlogCurve::to_hex(&p))
+        }
+    };
+    ($curve:ty, edwards) => {
+        $crate::dlog::curve_module!($curve);
+
+        /// Packs the point `(x, y)` in 32 bytes like the `repr_J` of Zcash : `y` in little-endian, with the parity of `x` as its highest bit.
+        pub fn pack_point(x: &str, y: &str) -> Result<[u8; 32], $crate::Error> {
+            Ok($crate::dlog::pack_edwards(&<$curve as $crate::dlog::DlogCurve>::parse_point(x, y)?))
+        }
+
+        /// Unpacks a point packed by [`pack_point`], rejecting the non-canonical encodings and the points outside of the prime subgroup.
+        pub fn unpack_point(packed: &[u8; 32]) -> Result<$crate::HexPoint, $crate::Error> {
+            let p: $curve = $crate::dlog::unpack_edwards(packed)?;
+            Ok($crate::dlog::DlogCurve::to_hex(&p))
+        }
+    };
+}
+
+#[cfg(any(feature = "grumpkin", feature = "jubjub", feature = "bandersnatch", feature = "ed-on-bls12-377", feature = "bn254", feature = "pasta", feature = "secp256k1", feature = "ristretto"))]
+pub(crate) use curve_module;
+
+#[cfg(any(feature = "jubjub", feature = "bandersnatch", feature = "ed-on-bls12-377"))]
+/// Parses the coordinates `(x, y)` of a point of a Twisted Edwards curve, checking that it is in its prime subgroup.
+pub(crate) fn edwards_point<P: TEModelParameters>(x: &str, y: &str) -> Result<GroupAffine<P>, Error>
+where
+    P::BaseField: PrimeField<BigInt = BigInteger256>,
+{
+    let (x, y) = parse_coordinates(x, y, parse_prime_field)?;
+    check_subgroup(GroupAffine::new(x, y))
+}
+
+#[cfg(any(feature = "jubjub", feature = "bandersnatch", feature = "ed-on-bls12-377"))]
+pub(crate) fn check_subgroup<P: TEModelParameters>(p: GroupAffine<P>) -> Result<GroupAffine<P>, Error> {
+    if !p.is_on_curve() {
+        return Err(Error::NotOnCurve);
+    }
+    if !p.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(Error::NotInSubgroup);
+    }
+    Ok(p)
+}
+
+/// Packs a point of a Twisted Edwards curve in 32 bytes like the `repr_J` of Zcash : `y` in little-endian, with the parity of `x` as its
+/// highest bit, which is free in the base fields of less than 255 bits.
+#[cfg(any(feature = "jubjub", feature = "bandersnatch", feature = "ed-on-bls12-377"))]
+pub(crate) fn pack_edwards<P: TEModelParameters>(p: &GroupAffine<P>) -> [u8; 32]
+where
+    P::BaseField: PrimeField<BigInt = BigInteger256>,
+{
+    let mut packed = [0u8; 32];
+    packed.copy_from_slice(&p.y.into_repr().to_bytes_le());
+    if p.x.into_repr().is_odd() {
+        packed[31] |= 0x80;
+    }
+    packed
+}
+
+/// Unpacks a point packed by [`pack_edwards`], rejecting the non-canonical encodings and the points outside of the prime subgroup.
+#[cfg(any(feature = "jubjub", feature = "bandersnatch", feature = "ed-on-bls12-377"))]
+pub(crate) fn unpack_edwards<P: TEModelParameters>(packed: &[u8; 32]) -> Result<GroupAffine<P>, Error>
+where
+    P::BaseField: PrimeField<BigInt = BigInteger256> + SquareRootField,
+{
+    let invalid = || Error::InvalidFormat(format!("0x{}", hex::encode(packed)));
+    let odd = packed[31] & 0x80 != 0;
+    let mut y_bytes = *packed;
+    y_bytes[31] &= 0x7f;
+    let mut repr = BigInteger256::new([0; 4]);
+    repr.read_le(&mut y_bytes.as_ref()).unwrap();
+    let y = P::BaseField::from_repr(repr).ok_or_else(invalid)?;
+    // x^2 = (1 - y^2) / (a - d·y^2)
+    let y2 = y.square();
+    let denominator = (P::COEFF_A - P::COEFF_D * y2).inverse().ok_or(Error::NotOnCurve)?;
+    let mut x = ((P::BaseField::one() - y2) * denominator).sqrt().ok_or(Error::NotOnCurve)?;
+    if x.is_zero() && odd {
+        return Err(invalid());
+    }
+    if x.into_repr().is_odd() != odd {
+        x = -x;
+    }
+    check_subgroup(GroupAffine::new(x, y))
+}
+
+#[cfg(any(feature = "jubjub", feature = "bandersnatch", feature = "ed-on-bls12-377"))]
+/// Parses a scalar as an element of the base field `F` of a curve, taken modulo the order of its prime subgroup, like the scalars of
+/// Baby Jubjub.
+pub(crate) fn reduced_scalar<F: PrimeField<BigInt = BigInteger256>, S: PrimeField>(s: &str) -> Result<S, Error> {
+    let f: F = parse_prime_field(s)?;
+    Ok(S::from_le_bytes_mod_order(&f.into_repr().to_bytes_le()))
+}
+
+/// Parses the coordinates `(x, y)` of a point of a short Weierstrass curve of prime order, `(0, 0)` being the point at infinity.
+#[cfg(any(feature = "grumpkin", feature = "bn254", feature = "pasta"))]
+pub(crate) fn weierstrass_point<P: SWModelParameters>(x: &str, y: &str) -> Result<short_weierstrass_jacobian::GroupAffine<P>, Error>
+where
+    P::BaseField: PrimeField<BigInt = BigInteger256>,
+{
+    let (x, y): (P::BaseField, P::BaseField) = parse_coordinates(x, y, parse_prime_field)?;
+    if x.is_zero() && y.is_zero() {
+        return Ok(short_weierstrass_jacobian::GroupAffine::zero());
+    }
+    let p = short_weierstrass_jacobian::GroupAffine::new(x, y, false);
+    if !p.is_on_curve() {
+        return Err(Error::NotOnCurve);
+    }
+    Ok(p)
+}
+
+/// Coordinates of a point of a short Weierstrass curve, `(0, 0)` for the point at infinity.
+#[cfg(any(feature = "grumpkin", feature = "bn254", feature = "pasta"))]
+pub(crate) fn weierstrass_to_hex<P: SWModelParameters>(p: &short_weierstrass_jacobian::GroupAffine<P>) -> HexPoint
+where
+    P::BaseField: PrimeField,
+{
+    if p.is_zero() {
+        return (field_to_hex(&P::BaseField::zero()), field_to_hex(&P::BaseField::zero()));
+    }
+    (field_to_hex(&p.x), field_to_hex(&p.y))
+}
+
+/// Packs a point of a short Weierstrass curve in the 33 bytes of its SEC1 compressed encoding, `0x02` or `0x03` for the parity of `y`
+/// followed by `x` in big-endian, the point at infinity being 33 zero bytes so that all the points have the same size.
+#[cfg(any(feature = "grumpkin", feature = "bn254", feature = "pasta"))]
+pub(crate) fn pack_weierstrass<P: SWModelParameters>(p: &short_weierstrass_jacobian::GroupAffine<P>) -> [u8; 33]
+where
+    P::BaseField: PrimeField<BigInt = BigInteger256>,
+{
+    let mut packed = [0u8; 33];
+    if !p.is_zero() {
+        packed[0] = if p.y.into_repr().is_odd() { 0x03 } else { 0x02 };
+        packed[1..].copy_from_slice(&p.x.into_repr().to_bytes_be());
+    }
+    packed
+}
+
+/// Unpacks a point packed by [`pack_weierstrass`], rejecting the non-canonical encodings and the points outside of the curve.
+#[cfg(any(feature = "grumpkin", feature = "bn254", feature = "pasta"))]
+pub(crate) fn unpack_weierstrass<P: SWModelParameters>(packed: &[u8; 33]) -> Result<short_weierstrass_jacobian::GroupAffine<P>, Error>
+where
+    P::BaseField: PrimeField<BigInt = BigInteger256> + SquareRootField,
+{
+    let invalid = || Error::InvalidFormat(format!("0x{}", hex::encode(packed)));
+    if packed == &[0u8; 33] {
+        return Ok(short_weierstrass_jacobian::GroupAffine::zero());
+    }
+    let odd = match packed[0] {
+        0x02 => false,
+        0x03 => true,
+        _ => return Err(invalid()),
+    };
+    let mut x_bytes = [0u8; 32];
+    x_bytes.copy_from_slice(&packed[1..]);
+    x_bytes.reverse();
+    let mut repr = BigInteger256::new([0; 4]);
+    repr.read_le(&mut x_bytes.as_ref()).unwrap();
+    let x = P::BaseField::from_repr(repr).ok_or_else(invalid)?;
+    // y^2 = x^3 + a·x + b
+    let mut y = (x.square() * x + P::COEFF_A * x + P::COEFF_B).sqrt().ok_or(Error::NotOnCurve)?;
+    if y.into_repr().is_odd() != odd {
+        y = -y;
+    }
+    Ok(short_weierstrass_jacobian::GroupAffine::new(x, y, false))
+}
+
+#[cfg(any(feature = "grumpkin", feature = "bn254", feature = "pasta"))]
+/// Parses a scalar as an element of the prime field `F`, which should be at most the order of the prime subgroup of the curve.
+pub(crate) fn parse_scalar<F: PrimeField<BigInt = BigInteger256>, S: PrimeField<BigInt = BigInteger256>>(s: &str) -> Result<S, Error> {
+    let f: F = parse_prime_field(s)?;
+    S::from_repr(f.into_repr()).ok_or_else(|| Error::InvalidFormat(s.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Solver;
+
+    #[test]
+    fn test_baby_jubjub_curve() {
+        // the generic functions on Baby Jubjub are the ones of the crate root
+        let private_key = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        let pk = priv_to_pub_key::<BabyJubJub>(private_key).unwrap();
+        #[cfg(feature = "elgamal")]
+        assert_eq!(Ok(pk.clone()), crate::priv_to_pub_key(private_key));
+        assert_eq!(embed_plaintext::<BabyJubJub>(1234), crate::embed_plaintext(1234));
+        let (c1, c2) = encrypt::<BabyJubJub>((&pk.0, &pk.1), 1234, "0x05").unwrap();
+        let embedded = decrypt::<BabyJubJub>(private_key, (&c1.0, &c1.1), (&c2.0, &c2.1)).unwrap();
+        #[cfg(feature = "elgamal")]
+        assert_eq!(crate::exp_elgamal_decrypt(private_key, (&c1.0, &c1.1), (&c2.0, &c2.1)), Ok(embedded.clone()));
+        let solver = Solver::<BabyJubJub>::for_curve(16, 8, 2);
+        assert_eq!(solver.solve(&embedded.0, &embedded.1), Ok(1234));
+        assert_eq!(Solver::new(16, 2).solve(&embedded.0, &embedded.1), Ok(1234));
+    }
+}
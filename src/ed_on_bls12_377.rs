@@ -0,0 +1,203 @@
+//! Exponential ElGamal on ed-on-bls12-377, the Twisted Edwards curve embedded in BLS12-377 used by Aleo-style stacks, e.g snarkVM,
+//! with the same Baby-step Giant-step search as Baby Jubjub.
+//!
+//! ed-on-bls12-377 is the curve `-x^2 + y^2 = 1 + 3021·x^2y^2` over the scalar field of BLS12-377, with the parameters and the generator
+//! of `ed_on_bls12_377` of arkworks, see [`GENERATOR`]. Its group has the order `4·r`, and the inputs are checked to be in its prime
+//! subgroup of order `r`, see [`SUBGROUP_ORDER`]. Points are encoded as their affine coordinates `(x, y)` in `0x`-prefixed hexadecimal
+//! strings, and scalars, i.e private keys and the encryption randomness, are elements of the base field taken modulo `r`, like the
+//! scalars of Baby Jubjub.
+
+use ark_ec::models::twisted_edwards_extended::GroupAffine;
+use ark_ec::models::{ModelParameters, MontgomeryModelParameters, TEModelParameters};
+use ark_ec::AffineCurve;
+use ark_ff::biginteger::BigInteger256 as BigInteger;
+use ark_ff::fields::{FftParameters, Fp256, Fp256Parameters, FpParameters};
+use ark_ff::{field_new, PrimeField};
+use crate::dlog::{self, DlogCurve};
+use crate::point::field_to_hex;
+use crate::table::ArkworksCurve;
+use crate::{Error, HexPoint};
+
+/// Coordinates of the generator `G` of the prime subgroup of ed-on-bls12-377 used by arkworks.
+pub const GENERATOR: (&str, &str) = (
+    "0x09f1b5a5baf6acf06fed91c9ae9ebfa06068dd2835790980894e2328f3ebca05",
+    "0x09a20df36571ac3cd906b256080ba8454453c177aaf3131bb50a67bf1a806781",
+);
+
+/// Order `r` of the prime subgroup of ed-on-bls12-377.
+pub const SUBGROUP_ORDER: &str = "0x04aad957a68b2955982d1347970dec005293a3afc43c8afeb95aee9ac33fd9ff";
+
+/// Base field of ed-on-bls12-377, i.e the scalar field of BLS12-377.
+pub(crate) type Fq = Fp256<FqParameters>;
+/// Scalar field of ed-on-bls12-377, i.e the integers modulo [`SUBGROUP_ORDER`].
+pub(crate) type Fr = Fp256<FrParameters>;
+
+pub(crate) struct FqParameters;
+
+impl Fp256Parameters for FqParameters {}
+
+impl FftParameters for FqParameters {
+    type BigInt = BigInteger;
+
+    const TWO_ADICITY: u32 = 47;
+
+    const TWO_ADIC_ROOT_OF_UNITY: BigInteger = BigInteger([0xaf80da4dda3ad648, 0x5e223adbfc381dac, 0x03ba0666b2f92525, 0x0f906c5b3befb0ce]);
+}
+
+impl FpParameters for FqParameters {
+    const MODULUS: BigInteger = BigInteger([0x0a11800000000001, 0x59aa76fed0000001, 0x60b44d1e5c37b001, 0x12ab655e9a2ca556]);
+
+    const MODULUS_BITS: u32 = 253;
+
+    const CAPACITY: u32 = Self::MODULUS_BITS - 1;
+
+    const REPR_SHAVE_BITS: u32 = 3;
+
+    const R: BigInteger = BigInteger([0x7d1c7ffffffffff3, 0x7257f50f6ffffff2, 0x16d81575512c0fee, 0x0d4bda322bbb9a9d]);
+
+    const R2: BigInteger = BigInteger([0x25d577bab861857b, 0xcc2c27b58860591f, 0xa7cc008fe5dc8593, 0x011fdae7eff1c939]);
+
+    const INV: u64 = 0x0a117fffffffffff;
+
+    /// `22`, in Montgomery form.
+    const GENERATOR: BigInteger = BigInteger([0x296c7ffffffffed3, 0x929216656ffffec7, 0x4c01534d92860e69, 0x0c79cfc4b9819970]);
+
+    const MODULUS_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([0x8508c00000000000, 0xacd53b7f68000000, 0x305a268f2e1bd800, 0x0955b2af4d1652ab]);
+
+    const T: BigInteger = BigInteger([0xedfda00000021423, 0x9a3cb86f6002b354, 0xcabd34594aacc168, 0x0000000000002556]);
+
+    const T_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([0x76fed00000010a11, 0x4d1e5c37b00159aa, 0x655e9a2ca55660b4, 0x00000000000012ab]);
+}
+
+pub(crate) struct FrParameters;
+
+impl Fp256Parameters for FrParameters {}
+
+impl FftParameters for FrParameters {
+    type BigInt = BigInteger;
+
+    const TWO_ADICITY: u32 = 1;
+
+    const TWO_ADIC_ROOT_OF_UNITY: BigInteger = BigInteger([0xd289433ff2b7d5c9, 0xbdb82ac32901dcb9, 0xb1af246173fdb411, 0x00b4b1d4c7e5e163]);
+}
+
+impl FpParameters for FrParameters {
+    const MODULUS: BigInteger = BigInteger([0xb95aee9ac33fd9ff, 0x5293a3afc43c8afe, 0x982d1347970dec00, 0x04aad957a68b2955]);
+
+    const MODULUS_BITS: u32 = 251;
+
+    const CAPACITY: u32 = Self::MODULUS_BITS - 1;
+
+    const REPR_SHAVE_BITS: u32 = 5;
+
+    const R: BigInteger = BigInteger([0xe6d1ab5ad0880436, 0x94db78ec9b3aae44, 0xe67deee6231037ee, 0x03f62782dea547f1]);
+
+    const R2: BigInteger = BigInteger([0x375699cd6a55d45e, 0xf639c3f57a73da73, 0xca06049ccd027a21, 0x047ada1eef02d841]);
+
+    const INV: u64 = 0x860efbdd70e3da01;
+
+    /// `5`, in Montgomery form.
+    const GENERATOR: BigInteger = BigInteger([0x9cac9e5b05a8ad12, 0x9dfacddff7333b5d, 0x1fc15d60531967a7, 0x0123602fbf0dc263]);
+
+    const MODULUS_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([0x5cad774d619fecff, 0x2949d1d7e21e457f, 0xcc1689a3cb86f600, 0x02556cabd34594aa]);
+
+    const T: BigInteger = BigInteger([0x5cad774d619fecff, 0x2949d1d7e21e457f, 0xcc1689a3cb86f600, 0x02556cabd34594aa]);
+
+    const T_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([0xae56bba6b0cff67f, 0x14a4e8ebf10f22bf, 0x660b44d1e5c37b00, 0x012ab655e9a2ca55]);
+}
+
+#[derive(Clone, Default, PartialEq, Eq)]
+pub(crate) struct Parameters;
+
+impl ModelParameters for Parameters {
+    type BaseField = Fq;
+    type ScalarField = Fr;
+}
+
+impl TEModelParameters for Parameters {
+    const COEFF_A: Fq = field_new!(Fq, "-1");
+    const COEFF_D: Fq = field_new!(Fq, "3021");
+    const COFACTOR: &'static [u64] = &[4];
+    const COFACTOR_INV: Fr = field_new!(Fr, "527778859339273151515551558673846658209717731602102048798421311598680340096");
+    const AFFINE_GENERATOR_COEFFS: (Fq, Fq) = (
+        field_new!(Fq, "4497879464030519973909970603271755437257548612157028181994697785683032656389"),
+        field_new!(Fq, "4357141146396347889246900916607623952598927460421559113092863576544024487809"),
+    );
+
+    type MontgomeryModelParameters = Parameters;
+}
+
+impl MontgomeryModelParameters for Parameters {
+    /// `2·(a + d) / (a - d)`
+    const COEFF_A: Fq = field_new!(Fq, "3990301581132929505568273333084066329187552697088022219156688740916631500114");
+    /// `4 / (a - d)`
+    const COEFF_B: Fq = field_new!(Fq, "4454160168295440918680551605697480202188346638066041608778544715000777738925");
+
+    type TEModelParameters = Parameters;
+}
+
+pub(crate) type EdOnBls12_377 = GroupAffine<Parameters>;
+
+impl ArkworksCurve for EdOnBls12_377 {
+    fn x_low_bits(&self) -> u64 {
+        self.x.into_repr().0[0]
+    }
+}
+
+impl DlogCurve for EdOnBls12_377 {
+    type Scalar = Fr;
+
+    fn generator() -> Self {
+        Self::prime_subgroup_generator()
+    }
+
+    fn mul_scalar(&self, s: &Fr) -> <Self as AffineCurve>::Projective {
+        self.mul(*s)
+    }
+
+    fn parse_point(x: &str, y: &str) -> Result<Self, Error> {
+        dlog::edwards_point(x, y)
+    }
+
+    fn to_hex(&self) -> HexPoint {
+        (field_to_hex(&self.x), field_to_hex(&self.y))
+    }
+
+    fn parse_scalar(s: &str) -> Result<Fr, Error> {
+        dlog::reduced_scalar::<Fq, Fr>(s)
+    }
+}
+
+dlog::curve_module!(EdOnBls12_377, edwards);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ec::AffineCurve;
+    use ark_ff::{One, Zero};
+
+    #[test]
+    fn test_ed_on_bls12_377() {
+        let g = EdOnBls12_377::prime_subgroup_generator();
+        assert!(g.is_on_curve() && g.is_in_correct_subgroup_assuming_on_curve());
+        assert!(g.mul(FrParameters::MODULUS).is_zero() && !g.is_zero());
+        assert_eq!(g.to_hex(), (GENERATOR.0.to_string(), GENERATOR.1.to_string()));
+        assert_eq!(field_to_hex(&-Fr::one()), format!("{}e", &SUBGROUP_ORDER[..65]));
+
+        let private_key = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        let pk = priv_to_pub_key(private_key).unwrap();
+        let (c1, c2) = exp_elgamal_encrypt((&pk.0, &pk.1), 65535, "0x05").unwrap();
+        assert_eq!(exp_elgamal_decrypt(private_key, (&c1.0, &c1.1), (&c2.0, &c2.1)), Ok(embed_plaintext(65535)));
+        let solver = Solver::new(16, 2);
+        assert_eq!(decrypt_to_u64(private_key, (&c1.0, &c1.1), (&c2.0, &c2.1), &solver), Ok(65535));
+        for plaintext in [0, 1, 255, 256] {
+            let p = embed_plaintext(plaintext);
+            assert_eq!(solver.solve(&p.0, &p.1), Ok(plaintext));
+        }
+        // the scalars are taken modulo the order of the prime subgroup
+        assert_eq!(priv_to_pub_key(SUBGROUP_ORDER), Ok(embed_plaintext(0)));
+        // (0, -1) is on the curve, of order 2
+        assert_eq!(EdOnBls12_377::parse_point("0x00", &field_to_hex(&-Fq::one())), Err(Error::NotInSubgroup));
+        assert_eq!(EdOnBls12_377::parse_point("0x01", "0x01"), Err(Error::NotOnCurve));
+    }
+}
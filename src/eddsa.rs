@@ -0,0 +1,137 @@
+//! EdDSA signatures over Baby Jubjub with the Poseidon hash, compatible with the `eddsa_poseidon_verify` function of the Noir standard
+//! library and the `EdDSAPoseidonVerifier` of circomlib, so that the keys used with noir-elgamal can also authorize decryption requests
+//! or sign published tallies.
+//!
+//! A signature of the `Field` message `m` by the private key `sk`, whose public key is `A = sk·G`, is `(R8, S)` with `R8 = r·G` and
+//! `S = r + 8·h·sk` modulo the order of the prime subgroup, for `h = Poseidon(R8.x, R8.y, A.x, A.y, m)`. It is valid if `S·G = R8 + 8·h·A`.
+//!
+//! Unlike circomlib, which derives its scalar from the private key with BLAKE-512 and pruning, the private key is used as is, like the
+//! private keys of noir-elgamal. The nonce `r` is derived deterministically from the private key and the message with SHA-512, so signing
+//! does not need any randomness.
+
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ed_on_bn254::{EdwardsAffine as BabyJubJub, Fq, Fr};
+use ark_ff::{BigInteger, PrimeField};
+use sha2::{Digest, Sha512};
+#[cfg(feature = "ark-serialize")]
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
+use crate::point::{base_point, field_to_hex, parse_field, parse_point, parse_scalar, point_coordinates, point_to_hex, HexPoint};
+use crate::{poseidon, Error};
+
+const NONCE_DOMAIN: &[u8] = b"babygiant-alt-bn128/eddsa-nonce/v1";
+
+/// EdDSA signature `(R8, S)`, see the [module documentation](self).
+///
+/// With the `ark-serialize` feature, it implements the traits of arkworks as the compressed point `R8` followed by `S`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signature {
+    r8: BabyJubJub,
+    s: Fr,
+}
+
+impl Signature {
+    /// Parses the signature `(R8, S)`, e.g the inputs `signature_r8_x`, `signature_r8_y` and `signature_s` of `eddsa_poseidon_verify`.
+    ///
+    /// `S` should be smaller than the order of the prime subgroup, so that signatures cannot be malleated.
+    pub fn new(r8: (&str, &str), s: &str) -> Result<Self, Error> {
+        let s = Fr::from_repr(parse_field(s)?.into_repr()).ok_or_else(|| Error::InvalidFormat(s.to_string()))?;
+        Ok(Signature { r8: parse_point(r8.0, r8.1)?, s })
+    }
+
+    pub fn r8(&self) -> HexPoint {
+        point_to_hex(&self.r8)
+    }
+
+    pub fn s(&self) -> String {
+        field_to_hex(&self.s)
+    }
+}
+
+/// Signs the `Field` `message` with `private_key`.
+pub fn sign(private_key: &str, message: &str) -> Result<Signature, Error> {
+    let sk = parse_scalar(private_key)?;
+    let m = parse_field(message)?;
+    let g = base_point();
+    let a = g.mul(sk).into_affine();
+    let nonce = Sha512::new()
+        .chain_update(NONCE_DOMAIN)
+        .chain_update(sk.into_repr().to_bytes_be())
+        .chain_update(m.into_repr().to_bytes_be())
+        .finalize();
+    let r = Fr::from_le_bytes_mod_order(&nonce);
+    let r8 = g.mul(r).into_affine();
+    let h = challenge(&r8, &a, m);
+    Ok(Signature { r8, s: r + Fr::from(8u64) * h * sk })
+}
+
+/// Checks that `signature` is a signature of the `Field` `message` by the private key of `public_key`.
+///
+/// Returns an error if `public_key` or `message` are invalid, and `false` if the signature is invalid.
+pub fn verify(public_key: (&str, &str), message: &str, signature: &Signature) -> Result<bool, Error> {
+    let a = parse_point(public_key.0, public_key.1)?;
+    let m = parse_field(message)?;
+    let h = challenge(&signature.r8, &a, m);
+    let left = base_point().mul(signature.s);
+    let right = signature.r8.into_projective() + a.mul(Fr::from(8u64) * h);
+    Ok(left == right)
+}
+
+/// `Poseidon(R8.x, R8.y, A.x, A.y, m)`, as a scalar.
+fn challenge(r8: &BabyJubJub, a: &BabyJubJub, m: Fq) -> Fr {
+    let [r8x, r8y] = point_coordinates(r8);
+    let [ax, ay] = point_coordinates(a);
+    let h = poseidon::hash(&[r8x, r8y, ax, ay, m]);
+    Fr::from_le_bytes_mod_order(&h.into_repr().to_bytes_le())
+}
+
+#[cfg(feature = "ark-serialize")]
+impl CanonicalSerialize for Signature {
+    fn serialize<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        self.r8.serialize(&mut writer)?;
+        self.s.serialize(&mut writer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.r8.serialized_size() + self.s.serialized_size()
+    }
+}
+
+#[cfg(feature = "ark-serialize")]
+impl CanonicalDeserialize for Signature {
+    fn deserialize<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let r8 = BabyJubJub::deserialize(&mut reader)?;
+        let s = Fr::deserialize(&mut reader)?;
+        Ok(Signature { r8, s })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::priv_to_pub_key;
+
+    #[test]
+    fn test_sign_verify() {
+        let sk = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        let pk = priv_to_pub_key(sk).unwrap();
+        let pk = (pk.0.as_str(), pk.1.as_str());
+        let signature = sign(sk, "0x2a").unwrap();
+        assert_eq!(sign(sk, "0x2a"), Ok(signature));
+        assert_eq!(verify(pk, "0x2a", &signature), Ok(true));
+        let r8 = signature.r8();
+        assert_eq!(Signature::new((&r8.0, &r8.1), &signature.s()), Ok(signature));
+
+        // another message, another key, or a tampered signature
+        assert_eq!(verify(pk, "0x2b", &signature), Ok(false));
+        let other = priv_to_pub_key("0x05").unwrap();
+        assert_eq!(verify((&other.0, &other.1), "0x2a", &signature), Ok(false));
+        let tampered = Signature { s: signature.s + Fr::from(1u64), ..signature };
+        assert_eq!(verify(pk, "0x2a", &tampered), Ok(false));
+
+        // S + l, which is also a solution of the verification equation, is rejected
+        let order = parse_field("0x060c89ce5c263405370a08b6d0302b0bab3eedb83920ee0a677297dc392126f1").unwrap();
+        let malleated = field_to_hex(&(parse_field(&signature.s()).unwrap() + order));
+        assert!(matches!(Signature::new((&r8.0, &r8.1), &malleated), Err(Error::InvalidFormat(_))));
+        assert!(verify(("0x1", "0x1"), "0x2a", &signature).is_err());
+    }
+}
@@ -0,0 +1,406 @@
+//! Off-circuit counterpart of the exponential ElGamal functions of [noir-elgamal](https://github.com/jat9292/noir-elgamal).
+
+use std::iter::Sum;
+use std::ops::{Add, Sub};
+use ark_ec::msm::VariableBaseMSM;
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ed_on_bn254::{EdwardsAffine, Fr};
+use ark_ff::{BigInteger256, PrimeField, Zero};
+use rand::{CryptoRng, RngCore};
+#[cfg(feature = "ark-serialize")]
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
+use crate::keys::random_scalar;
+use crate::point::{base_point, parse_point, parse_scalar, point_to_hex, HexPoint};
+use crate::{Error, Solver};
+
+/// Encrypts `plaintext` for `public_key`, exactly like the
+/// [`exp_elgamal_encrypt`](https://github.com/jat9292/noir-elgamal/blob/v0.0.1/src/lib.nr) Noir function,
+/// i.e returns the ciphertext `(C1, C2) = (randomness·G, plaintext·G + randomness·public_key)`.
+///
+/// `randomness` is a `Field` like in Noir, i.e it is smaller than the modulus of the base field, and acts modulo the order of the prime subgroup.
+/// It should be a random scalar which is never reused, see [`crate::generate_randomness`]. Keep in mind that the plaintext can only be recovered with [`crate::do_compute_dlog`] if it is a `u40`.
+pub fn exp_elgamal_encrypt(public_key: (&str, &str), plaintext: u64, randomness: &str) -> Result<(HexPoint, HexPoint), Error> {
+    let pk = parse_point(public_key.0, public_key.1)?;
+    Ok(encrypt_point(&pk, Fr::from(plaintext), parse_scalar(randomness)?).into())
+}
+
+/// Encryption `(r·G, m·G + r·pk)` of the point embedding the scalar `m`.
+pub(crate) fn encrypt_point(pk: &EdwardsAffine, m: Fr, r: Fr) -> Ciphertext {
+    let g = base_point();
+    Ciphertext { c1: g.mul(r).into_affine(), c2: (g.mul(m) + pk.mul(r)).into_affine() }
+}
+
+/// Checks that `plaintext` can be recovered by a [`Solver`] of `max_bitwidth` bits once encrypted, i.e that it is an unsigned integer
+/// of at most `max_bitwidth` bits, so that an out of range input is reported with its value when it is encrypted, instead of as an
+/// [`Error::DlogNotFound`] when its ciphertext is decrypted.
+///
+/// A `max_bitwidth` which is not between `1` and `64` is reported as an [`Error::InvalidBitwidth`].
+pub fn assert_encryptable(plaintext: u64, max_bitwidth: u64) -> Result<(), Error> {
+    if !(1..=64).contains(&max_bitwidth) {
+        return Err(Error::InvalidBitwidth(max_bitwidth));
+    }
+    if plaintext > u64::MAX >> (64 - max_bitwidth) {
+        return Err(Error::PlaintextOutOfRange { plaintext, max_bitwidth });
+    }
+    Ok(())
+}
+
+/// Plaintext checked by [`assert_encryptable`] when it is created, whose encryptions can thus be decrypted by a [`Solver`] of its `max_bitwidth`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Plaintext {
+    value: u64,
+    max_bitwidth: u64,
+}
+
+impl Plaintext {
+    /// Checks that `value` is an unsigned integer of at most `max_bitwidth` bits, see [`assert_encryptable`].
+    pub fn new(value: u64, max_bitwidth: u64) -> Result<Self, Error> {
+        assert_encryptable(value, max_bitwidth)?;
+        Ok(Plaintext { value, max_bitwidth })
+    }
+
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    pub fn max_bitwidth(&self) -> u64 {
+        self.max_bitwidth
+    }
+
+    /// Encrypts the plaintext for `public_key` with `randomness`, like [`exp_elgamal_encrypt`].
+    pub fn encrypt(&self, public_key: (&str, &str), randomness: &str) -> Result<(HexPoint, HexPoint), Error> {
+        exp_elgamal_encrypt(public_key, self.value, randomness)
+    }
+
+    /// Encrypts the plaintext for `public_key` with a randomness drawn from `rng`, like [`Ciphertext::encrypt`].
+    pub fn encrypt_with_rng<R: RngCore + CryptoRng>(&self, public_key: (&str, &str), rng: &mut R) -> Result<Ciphertext, Error> {
+        Ciphertext::encrypt(public_key, self.value, rng)
+    }
+}
+
+/// Recovers the embedded plaintext point `C2 - private_key·C1` from a ciphertext `(C1, C2)`, exactly like the
+/// [`exp_elgamal_decrypt`](https://github.com/jat9292/noir-elgamal/blob/v0.0.1/src/lib.nr#L50) Noir function.
+///
+/// `private_key` and the coordinates of `c1` and `c2` are hexadecimal strings in the same format as the inputs of [`crate::do_compute_dlog`].
+/// The coordinates of the returned point are formatted like the ones printed by Noir, see [`crate::format_noir_point`], and can be passed
+/// directly to [`crate::do_compute_dlog`] to recover the plaintext.
+pub fn exp_elgamal_decrypt(private_key: &str, c1: (&str, &str), c2: (&str, &str)) -> Result<HexPoint, Error> {
+    decrypt_point(private_key, c1, c2).map(|p| point_to_hex(&p))
+}
+
+/// Decrypts `ciphertext` with `private_key` down to its plaintext, i.e recovers the embedded plaintext with
+/// [`exp_elgamal_decrypt`] and then computes its Discrete Logarithm with `solver`, whose maximum bitwidth bounds the plaintexts which can be found.
+///
+/// All the inputs are validated before the search starts, and every failure is reported as an [`Error`].
+pub fn decrypt_to_u64(private_key: &str, ciphertext: &Ciphertext, solver: &Solver) -> Result<u64, Error> {
+    let sk = parse_scalar(private_key)?;
+    solver.search_point(ciphertext.decrypt_point(sk)).run()
+}
+
+fn decrypt_point(private_key: &str, c1: (&str, &str), c2: (&str, &str)) -> Result<EdwardsAffine, Error> {
+    let sk = parse_scalar(private_key)?;
+    Ok(Ciphertext::new(c1, c2)?.decrypt_point(sk))
+}
+
+/// Exponential ElGamal ciphertext `(C1, C2)`, whose points are validated when it is created.
+///
+/// Ciphertexts are additively homomorphic : the sum of the encryptions of `a` and `b` for the same public key decrypts to `a + b`,
+/// and their difference to `a - b`, so encrypted votes or balances can be aggregated before recovering a single plaintext with [`decrypt_to_u64`].
+/// The aggregate should still be a small enough unsigned integer for its Discrete Logarithm to be found, e.g `a - b` should not be negative.
+///
+/// With the `serde` feature, a ciphertext is (de)serialized as `{"c1": {"x": "0x...", "y": "0x..."}, "c2": {"x": "0x...", "y": "0x..."}}`.
+/// With the `ark-serialize` feature, it implements the traits of arkworks as the compressed points `C1 || C2`, which are checked to be
+/// in the prime subgroup when deserialized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "SerializedCiphertext", into = "SerializedCiphertext"))]
+pub struct Ciphertext {
+    pub(crate) c1: EdwardsAffine,
+    pub(crate) c2: EdwardsAffine,
+}
+
+impl Ciphertext {
+    /// Parses the ciphertext `(C1, C2)`, whose coordinates have the same format as the inputs of [`crate::do_compute_dlog`].
+    pub fn new(c1: (&str, &str), c2: (&str, &str)) -> Result<Self, Error> {
+        Ok(Ciphertext { c1: parse_point(c1.0, c1.1)?, c2: parse_point(c2.0, c2.1)? })
+    }
+
+    /// Encrypts `plaintext` for `public_key` like [`exp_elgamal_encrypt`], with a randomness drawn from `rng`, e.g `rand::rngs::OsRng`,
+    /// or a seeded generator for reproducible tests and fixtures.
+    pub fn encrypt<R: RngCore + CryptoRng>(public_key: (&str, &str), plaintext: u64, rng: &mut R) -> Result<Self, Error> {
+        let pk = parse_point(public_key.0, public_key.1)?;
+        Ok(encrypt_point(&pk, Fr::from(plaintext), random_scalar(rng)))
+    }
+
+    /// Encryption of `0` with a null randomness, i.e the neutral element of the sum of ciphertexts.
+    pub fn zero() -> Self {
+        Ciphertext { c1: EdwardsAffine::zero(), c2: EdwardsAffine::zero() }
+    }
+
+    /// Multiplies the encrypted plaintext by `k`, i.e returns `(k·C1, k·C2)`, e.g to weight encrypted votes or convert encrypted amounts
+    /// between units before aggregating them. The product should still be small enough for its Discrete Logarithm to be found.
+    pub fn mul_plaintext(&self, k: u64) -> Self {
+        let k = Fr::from(k);
+        Ciphertext { c1: self.c1.mul(k).into_affine(), c2: self.c2.mul(k).into_affine() }
+    }
+
+    /// Returns an unlinkable encryption of the same plaintext for `public_key`, i.e adds an encryption of `0` drawn from `rng`.
+    pub fn rerandomize<R: RngCore + CryptoRng>(&self, public_key: (&str, &str), rng: &mut R) -> Result<Self, Error> {
+        let pk = parse_point(public_key.0, public_key.1)?;
+        Ok(*self + encrypt_point(&pk, Fr::zero(), random_scalar(rng)))
+    }
+
+    /// Returns `sum(w_i·CT_i)` for the pairs `(CT_i, w_i)` of `terms`, i.e an encryption of the weighted sum of their plaintexts.
+    ///
+    /// This is the same as summing the [`Ciphertext::mul_plaintext`] of each term, but computed with a multi-scalar multiplication,
+    /// which is much faster when aggregating many ciphertexts. Like [`Ciphertext::mul_plaintext`], the weighted sum should still be small
+    /// enough for its Discrete Logarithm to be found.
+    pub fn weighted_sum<I: IntoIterator<Item = (Ciphertext, u64)>>(terms: I) -> Self {
+        let (mut c1, mut c2, mut weights) = (Vec::new(), Vec::new(), Vec::<BigInteger256>::new());
+        for (ciphertext, weight) in terms {
+            c1.push(ciphertext.c1);
+            c2.push(ciphertext.c2);
+            weights.push(Fr::from(weight).into_repr());
+        }
+        Ciphertext {
+            c1: VariableBaseMSM::multi_scalar_mul(&c1, &weights).into_affine(),
+            c2: VariableBaseMSM::multi_scalar_mul(&c2, &weights).into_affine(),
+        }
+    }
+
+    pub fn c1(&self) -> HexPoint {
+        point_to_hex(&self.c1)
+    }
+
+    pub fn c2(&self) -> HexPoint {
+        point_to_hex(&self.c2)
+    }
+
+    pub(crate) fn decrypt_point(&self, sk: Fr) -> EdwardsAffine {
+        (self.c2.into_projective() - self.c1.mul(sk)).into_affine()
+    }
+}
+
+impl TryFrom<(HexPoint, HexPoint)> for Ciphertext {
+    type Error = Error;
+
+    /// Parses a ciphertext returned by [`exp_elgamal_encrypt`].
+    fn try_from((c1, c2): (HexPoint, HexPoint)) -> Result<Self, Error> {
+        Ciphertext::new((&c1.0, &c1.1), (&c2.0, &c2.1))
+    }
+}
+
+impl From<Ciphertext> for (HexPoint, HexPoint) {
+    fn from(ciphertext: Ciphertext) -> Self {
+        (ciphertext.c1(), ciphertext.c2())
+    }
+}
+
+impl Add for Ciphertext {
+    type Output = Ciphertext;
+
+    fn add(self, other: Ciphertext) -> Ciphertext {
+        Ciphertext { c1: self.c1 + other.c1, c2: self.c2 + other.c2 }
+    }
+}
+
+impl Sub for Ciphertext {
+    type Output = Ciphertext;
+
+    fn sub(self, other: Ciphertext) -> Ciphertext {
+        Ciphertext { c1: self.c1 + -other.c1, c2: self.c2 + -other.c2 }
+    }
+}
+
+impl Sum for Ciphertext {
+    fn sum<I: Iterator<Item = Ciphertext>>(iter: I) -> Ciphertext {
+        iter.fold(Ciphertext::zero(), Add::add)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+pub(crate) struct SerializedPoint {
+    pub(crate) x: String,
+    pub(crate) y: String,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SerializedCiphertext {
+    c1: SerializedPoint,
+    c2: SerializedPoint,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SerializedCiphertext> for Ciphertext {
+    type Error = Error;
+
+    fn try_from(c: SerializedCiphertext) -> Result<Self, Error> {
+        Ciphertext::new((&c.c1.x, &c.c1.y), (&c.c2.x, &c.c2.y))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Ciphertext> for SerializedCiphertext {
+    fn from(ciphertext: Ciphertext) -> Self {
+        let point = |(x, y): HexPoint| SerializedPoint { x, y };
+        SerializedCiphertext { c1: point(ciphertext.c1()), c2: point(ciphertext.c2()) }
+    }
+}
+
+#[cfg(feature = "ark-serialize")]
+impl CanonicalSerialize for Ciphertext {
+    fn serialize<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        self.c1.serialize(&mut writer)?;
+        self.c2.serialize(&mut writer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.c1.serialized_size() + self.c2.serialized_size()
+    }
+}
+
+#[cfg(feature = "ark-serialize")]
+impl CanonicalDeserialize for Ciphertext {
+    fn deserialize<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let c1 = EdwardsAffine::deserialize(&mut reader)?;
+        let c2 = EdwardsAffine::deserialize(&mut reader)?;
+        Ok(Ciphertext { c1, c2 })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{embed_plaintext, priv_to_pub_key};
+
+    #[test]
+    fn test_exp_elgamal_encrypt_decrypt() {
+        // Same values as the noir-elgamal example used in the documentation of `do_compute_dlog`.
+        let private_key = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        let randomness = "0x03f90f366f9fd55bb1335eac3b11f2190f2ce9ff1769db241edaa7774136099b";
+        let pub_key = priv_to_pub_key(private_key).unwrap();
+        let (c1, c2) = exp_elgamal_encrypt((&pub_key.0, &pub_key.1), 42, randomness).unwrap();
+
+        // C1 = randomness·G, like the public key of the randomness
+        assert_eq!(c1, priv_to_pub_key(randomness).unwrap());
+        // the randomness is a Field acting modulo the order of the subgroup, here it is increased by the order
+        let shifted = "0x0a059904cbc60960e83d67630b421d24ba6bd7b7508ac92e864d3f537a57308c";
+        assert_eq!(exp_elgamal_encrypt((&pub_key.0, &pub_key.1), 42, shifted).unwrap(), (c1.clone(), c2.clone()));
+
+        let decrypted = exp_elgamal_decrypt(private_key, (&c1.0, &c1.1), (&c2.0, &c2.1)).unwrap();
+        assert_eq!(decrypted, ("0x06184da392a17823e9c1d38cb50980b17150ffa411965b03f0b0200d9557daa9".to_string(),
+                               "0x244a710118db92636e46e3f97bd80093ba7026ff97ca32d387145337e250549c".to_string()));
+        assert_eq!(decrypted, embed_plaintext(42));
+    }
+
+    #[test]
+    fn test_assert_encryptable() {
+        assert_eq!(assert_encryptable((1 << 40) - 1, 40), Ok(()));
+        assert_eq!(assert_encryptable(1 << 40, 40), Err(Error::PlaintextOutOfRange { plaintext: 1 << 40, max_bitwidth: 40 }));
+        assert_eq!(assert_encryptable(u64::MAX, 64), Ok(()));
+        assert_eq!(assert_encryptable(0, 0), Err(Error::InvalidBitwidth(0)));
+        assert_eq!(assert_encryptable(0, 65), Err(Error::InvalidBitwidth(65)));
+        let message = Error::PlaintextOutOfRange { plaintext: 256, max_bitwidth: 8 }.to_string();
+        assert!(message.contains("256") && message.ends_with("between 0 and 255"), "{}", message);
+
+        let pk = priv_to_pub_key("0x05").unwrap();
+        let plaintext = Plaintext::new(200, 8).unwrap();
+        assert_eq!((plaintext.value(), plaintext.max_bitwidth()), (200, 8));
+        assert_eq!(plaintext.encrypt((&pk.0, &pk.1), "0x03"), exp_elgamal_encrypt((&pk.0, &pk.1), 200, "0x03"));
+        let ciphertext = plaintext.encrypt_with_rng((&pk.0, &pk.1), &mut rand::rngs::OsRng).unwrap();
+        assert_eq!(decrypt_to_u64("0x05", &ciphertext, &Solver::new(plaintext.max_bitwidth(), 1)), Ok(200));
+        assert_eq!(Plaintext::new(300, 8), Err(Error::PlaintextOutOfRange { plaintext: 300, max_bitwidth: 8 }));
+    }
+
+    #[test]
+    fn test_decrypt_to_u64() {
+        let private_key = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        let pub_key = priv_to_pub_key(private_key).unwrap();
+        let ciphertext = exp_elgamal_encrypt((&pub_key.0, &pub_key.1), 4242, "0x05").unwrap().try_into().unwrap();
+        let solver = Solver::new(16, 1);
+        assert_eq!(decrypt_to_u64(private_key, &ciphertext, &solver), Ok(4242));
+
+        // the plaintext is out of the range of the solver
+        let ciphertext: Ciphertext = exp_elgamal_encrypt((&pub_key.0, &pub_key.1), 1 << 16, "0x05").unwrap().try_into().unwrap();
+        assert_eq!(decrypt_to_u64(private_key, &ciphertext, &solver), Err(Error::DlogNotFound { max_bitwidth: 16 }));
+        assert!(matches!(decrypt_to_u64("0xzz", &ciphertext, &solver), Err(Error::InvalidHex { .. })));
+        let c1 = ciphertext.c1();
+        assert_eq!(Ciphertext::new((&c1.0, &c1.1), ("0x1", "0x1")), Err(Error::NotOnCurve));
+    }
+
+    #[test]
+    fn test_ciphertext_homomorphism() {
+        let private_key = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        let pub_key = priv_to_pub_key(private_key).unwrap();
+        let encrypt = |plaintext: u64, randomness: &str| -> Ciphertext {
+            exp_elgamal_encrypt((&pub_key.0, &pub_key.1), plaintext, randomness).unwrap().try_into().unwrap()
+        };
+        let votes = [encrypt(3, "0x05"), encrypt(10, "0x07"), encrypt(29, "0x0b")];
+        let solver = Solver::new(16, 1);
+        assert_eq!(decrypt_to_u64(private_key, &votes.iter().copied().sum(), &solver), Ok(42));
+        assert_eq!(decrypt_to_u64(private_key, &(votes[1] - votes[0]), &solver), Ok(7));
+        let weighted: Ciphertext = votes.iter().zip([100, 10, 1]).map(|(vote, weight)| vote.mul_plaintext(weight)).sum();
+        assert_eq!(decrypt_to_u64(private_key, &weighted, &solver), Ok(429));
+        assert_eq!(Ciphertext::weighted_sum(votes.iter().copied().zip([100, 10, 1])), weighted);
+        assert_eq!(Ciphertext::weighted_sum([]), Ciphertext::zero());
+        let many: Vec<(Ciphertext, u64)> = (0..300u64).map(|i| (votes[(i % 3) as usize].mul_plaintext(i), i * i + 1)).collect();
+        assert_eq!(Ciphertext::weighted_sum(many.iter().copied()), many.iter().map(|(vote, weight)| vote.mul_plaintext(*weight)).sum());
+        assert_eq!(votes[0].mul_plaintext(2), votes[0] + votes[0]);
+        assert_eq!(votes[0].mul_plaintext(0), Ciphertext::zero());
+        // the randomness adds up as well
+        assert_eq!(votes[0] + votes[1], encrypt(13, "0x0c"));
+        assert_eq!(votes[0] - votes[0], Ciphertext::zero());
+        assert_eq!(Ciphertext::try_from(<(HexPoint, HexPoint)>::from(votes[2])), Ok(votes[2]));
+    }
+
+    #[test]
+    fn test_encrypt_with_rng() {
+        use rand::SeedableRng;
+        let private_key = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        let pub_key = priv_to_pub_key(private_key).unwrap();
+        let pk = (pub_key.0.as_str(), pub_key.1.as_str());
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let ciphertext = Ciphertext::encrypt(pk, 42, &mut rng).unwrap();
+        let rerandomized = ciphertext.rerandomize(pk, &mut rng).unwrap();
+        let mut replay = rand::rngs::StdRng::seed_from_u64(42);
+        assert_eq!(Ciphertext::encrypt(pk, 42, &mut replay), Ok(ciphertext));
+        assert_eq!(ciphertext.rerandomize(pk, &mut replay), Ok(rerandomized));
+        assert_ne!(rerandomized, ciphertext);
+        let solver = Solver::new(16, 1);
+        assert_eq!(decrypt_to_u64(private_key, &rerandomized, &solver), Ok(42));
+        assert!(Ciphertext::encrypt(("0x1", "0x1"), 42, &mut rng).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_ciphertext_serde() {
+        let pk = point_to_hex(&base_point());
+        let ciphertext: Ciphertext = exp_elgamal_encrypt((&pk.0, &pk.1), 5, "0x03").unwrap().try_into().unwrap();
+        let json = serde_json::to_string(&ciphertext).unwrap();
+        assert_eq!(json, format!("{{\"c1\":{{\"x\":\"{}\",\"y\":\"{}\"}},\"c2\":{{\"x\":\"{}\",\"y\":\"{}\"}}}}", ciphertext.c1().0, ciphertext.c1().1, ciphertext.c2().0, ciphertext.c2().1));
+        assert_eq!(serde_json::from_str::<Ciphertext>(&json).unwrap(), ciphertext);
+        assert!(serde_json::from_str::<Ciphertext>("{\"c1\":{\"x\":\"0x1\",\"y\":\"0x1\"},\"c2\":{\"x\":\"0x0\",\"y\":\"0x1\"}}").is_err());
+    }
+
+    #[cfg(feature = "ark-serialize")]
+    #[test]
+    fn test_ciphertext_ark_serialize() {
+        let pk = point_to_hex(&base_point());
+        let ciphertext: Ciphertext = exp_elgamal_encrypt((&pk.0, &pk.1), 5, "0x03").unwrap().try_into().unwrap();
+        let mut bytes = Vec::new();
+        ciphertext.serialize(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), ciphertext.serialized_size());
+        let mut points = Vec::new();
+        (ciphertext.c1, ciphertext.c2).serialize(&mut points).unwrap();
+        assert_eq!(bytes, points);
+        assert_eq!(Ciphertext::deserialize(bytes.as_slice()).unwrap(), ciphertext);
+        assert!(Ciphertext::deserialize(&bytes[..32]).is_err());
+
+        let point = crate::curve::Point::generator();
+        let mut bytes = Vec::new();
+        point.serialize(&mut bytes).unwrap();
+        assert_eq!(crate::curve::Point::deserialize(bytes.as_slice()).unwrap(), point);
+    }
+}
@@ -0,0 +1,123 @@
+use std::fmt;
+
+/// Errors returned by the fallible functions of this crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The input is not a hexadecimal string representing a byte array of size `32` at most.
+    InvalidFormat(String),
+    /// The input is not an hexadecimal string of at most 64 digits, for the given reason.
+    InvalidHex { input: String, reason: String },
+    /// The input is not the base64 encoding of at most `32` bytes, for the given reason.
+    InvalidBase64 { input: String, reason: String },
+    /// The input is a valid hexadecimal string, but not smaller than the `modulus` of its field.
+    NotInField { input: String, modulus: String },
+    /// The `coordinate` of a point, i.e `x` or `y`, could not be parsed.
+    InvalidCoordinate { coordinate: String, error: Box<Error> },
+    /// The point is not on the curve, e.g not on Baby Jubjub in Twisted Edwards form for the default curve.
+    NotOnCurve,
+    /// The point is on the curve but not in its prime subgroup.
+    NotInSubgroup,
+    /// The Baby-step Giant-step algorithm did not find the Discrete Logarithm among the integers of `max_bitwidth` bits.
+    DlogNotFound { max_bitwidth: u64 },
+    /// The search was cancelled before finding the Discrete Logarithm.
+    Cancelled,
+    /// A checkpoint could not be read, or does not match the solver it is resumed with.
+    InvalidCheckpoint(String),
+    /// The range `[start, end)` is not a part of the `giant_steps` giant steps of the search.
+    InvalidRange { start: u64, end: u64, giant_steps: u64 },
+    /// A remote worker of a distributed search failed.
+    Remote(String),
+    /// No point could be read from a file written by nargo.
+    InvalidArtifact(String),
+    /// The calldata or event log data does not contain ABI-encoded points.
+    InvalidAbi(String),
+    /// The configuration file could not be read, or has invalid settings.
+    InvalidConfig(String),
+    /// A table file could not be read, is corrupted, or does not match the solver it is loaded in.
+    InvalidTable(String),
+    /// The derivation path of a hierarchical deterministic key is malformed, or needs a private key.
+    InvalidDerivationPath(String),
+    /// The plaintext of a range proof, or to encrypt, is not an integer of `max_bitwidth` bits.
+    PlaintextOutOfRange { plaintext: u64, max_bitwidth: u64 },
+    /// The bitwidth of the plaintexts is not between `1` and `64`.
+    InvalidBitwidth(u64),
+    /// The shares of a threshold key or their partial decryptions are invalid, or too few to decrypt.
+    InvalidShare(String),
+    /// The public key is the identity of Baby Jubjub, whose shared secret with any private key is the identity.
+    IdentityPublicKey,
+    /// A ballot or a tally report is invalid, or does not match the election it is added to.
+    InvalidTally(String),
+    /// The layout of a packed plaintext is invalid, or one of its slots overflowed.
+    InvalidPacking(String),
+    /// The signed amount is not an integer of `bitwidth` bits in two's complement.
+    AmountOutOfRange { bitwidth: u64 },
+    /// The decimal amount is malformed, or has more decimals than its fixed-point encoding.
+    InvalidAmount(String),
+    /// The external holder of a private key failed to perform an operation with it.
+    KeyProvider(String),
+    /// A fixture of reference vectors could not be parsed.
+    InvalidFixture(String),
+    /// The name is not one of the curves available with the enabled features.
+    UnknownCurve(String),
+    /// The parameters of a curve given at runtime are invalid.
+    InvalidCurve(String),
+    /// The inputs are not in the encoding or not for the generator of the selected profile, see [`crate::profile`].
+    ProfileMismatch(String),
+    /// A ciphertext container is truncated, or of an unsupported version.
+    InvalidContainer(String),
+    /// The JSON input is malformed, or is neither an embedded plaintext nor a ciphertext.
+    InvalidJson(String),
+    /// The CBOR input is truncated, not in the canonical encoding, or not of the expected item.
+    InvalidCbor(String),
+    /// The CSV input is malformed, or has no column of the given name.
+    InvalidCsv(String),
+    /// The protobuf message is malformed, or lacks one of its fields.
+    InvalidProtobuf(String),
+    /// The columns of a columnar dataset do not have the same number of rows.
+    InvalidColumns(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidFormat(input) => write!(f, "Invalid input format : {:?} should be a hexadecimal string representing a byte array of size 32 at most", input),
+            Error::InvalidHex { input, reason } => write!(f, "Invalid hexadecimal string : {:?}, {}", input, reason),
+            Error::InvalidBase64 { input, reason } => write!(f, "Invalid base64 string : {:?}, {}", input, reason),
+            Error::NotInField { input, modulus } => write!(f, "Invalid field element : {:?} is not smaller than the modulus {} of the field", input, modulus),
+            Error::InvalidCoordinate { coordinate, error } => write!(f, "Invalid {} coordinate : {}", coordinate, error),
+            Error::NotOnCurve => write!(f, "(x,y) is not a valid point on the curve (the points of Baby Jubjub are in Twisted Edwards form)"),
+            Error::NotInSubgroup => write!(f, "(x,y) is on the curve but not in its prime subgroup"),
+            Error::DlogNotFound { max_bitwidth } => write!(f, "The Baby-step Giant-step algorithm was unable to solve the Discrete Logarithm. Make sure that the embedded plaintext is an unsigned integer between 0 and {}.", u64::MAX >> (64 - max_bitwidth)),
+            Error::Cancelled => write!(f, "The search was cancelled before finding the Discrete Logarithm"),
+            Error::InvalidCheckpoint(reason) => write!(f, "Invalid checkpoint : {}", reason),
+            Error::InvalidRange { start, end, giant_steps } => write!(f, "Invalid range of giant steps : [{}, {}) is not included in [0, {})", start, end, giant_steps),
+            Error::Remote(reason) => write!(f, "Remote worker failed : {}", reason),
+            Error::InvalidArtifact(reason) => write!(f, "Invalid nargo artifact : {}", reason),
+            Error::InvalidAbi(reason) => write!(f, "Invalid ABI encoding : {}", reason),
+            Error::InvalidConfig(reason) => write!(f, "Invalid configuration : {}", reason),
+            Error::InvalidTable(reason) => write!(f, "Invalid table of baby steps : {}", reason),
+            Error::InvalidDerivationPath(reason) => write!(f, "Invalid derivation path : {}", reason),
+            Error::PlaintextOutOfRange { plaintext, max_bitwidth } => write!(f, "The plaintext {} is out of range : it should be an unsigned integer between 0 and {}", plaintext, u64::MAX >> (64 - max_bitwidth)),
+            Error::InvalidBitwidth(max_bitwidth) => write!(f, "Invalid bitwidth : the plaintexts should have between 1 and 64 bits, not {}", max_bitwidth),
+            Error::InvalidShare(reason) => write!(f, "Invalid threshold decryption : {}", reason),
+            Error::IdentityPublicKey => write!(f, "The public key is the identity point (0, 1) of Baby Jubjub, which is not a valid public key"),
+            Error::InvalidTally(reason) => write!(f, "Invalid tally : {}", reason),
+            Error::InvalidPacking(reason) => write!(f, "Invalid packed plaintext : {}", reason),
+            Error::AmountOutOfRange { bitwidth } => write!(f, "The amount should be a signed integer between {} and {}", i64::MIN >> (64 - bitwidth), i64::MAX >> (64 - bitwidth)),
+            Error::InvalidAmount(reason) => write!(f, "Invalid amount : {}", reason),
+            Error::KeyProvider(reason) => write!(f, "Key provider failed : {}", reason),
+            Error::InvalidFixture(reason) => write!(f, "Invalid fixture : {}", reason),
+            Error::InvalidCurve(reason) => write!(f, "Invalid curve parameters : {}", reason),
+            Error::ProfileMismatch(reason) => write!(f, "Profile mismatch : {}", reason),
+            Error::InvalidContainer(reason) => write!(f, "Invalid ciphertext container : {}", reason),
+            Error::InvalidJson(reason) => write!(f, "Invalid JSON input : {}", reason),
+            Error::InvalidCbor(reason) => write!(f, "Invalid CBOR input : {}", reason),
+            Error::InvalidCsv(reason) => write!(f, "Invalid CSV input : {}", reason),
+            Error::InvalidProtobuf(reason) => write!(f, "Invalid protobuf message : {}", reason),
+            Error::InvalidColumns(reason) => write!(f, "Invalid columns : {}", reason),
+            Error::UnknownCurve(name) => write!(f, "Unknown curve : {:?} is not one of the curves enabled by the features of the crate", name),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
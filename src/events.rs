@@ -0,0 +1,102 @@
+//! Subscriber writing the `tracing` events of the crate as JSON lines, for the binaries which, unlike the applications embedding the
+//! library, have no subscriber of their own : `babygiant --progress-interval`, `babygiant-server` and `babygiant-worker`.
+//!
+//! Each event is written as `{"level": "INFO", "message": "search progress", "worker": 0, "start": 0, "end": 524288, ...}`, with the
+//! fields described in [`crate::Solver::with_progress_interval`].
+
+use serde_json::{Map, Value};
+use std::fmt;
+use std::io::Write;
+use std::sync::Mutex;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+/// Subscriber writing the events of the crate to `out`, one JSON object per line, see the [module documentation](self).
+pub struct JsonLines {
+    out: Mutex<Box<dyn Write + Send>>,
+}
+
+impl JsonLines {
+    pub fn new<W: Write + Send + 'static>(out: W) -> Self {
+        JsonLines { out: Mutex::new(Box::new(out)) }
+    }
+
+    /// Installs a subscriber writing to the standard error as the subscriber of the whole process.
+    pub fn install() -> Result<(), tracing::subscriber::SetGlobalDefaultError> {
+        tracing::subscriber::set_global_default(JsonLines::new(std::io::stderr()))
+    }
+}
+
+struct Fields(Map<String, Value>);
+
+impl Visit for Fields {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(field.name().to_string(), Value::from(format!("{:?}", value)));
+    }
+}
+
+impl Subscriber for JsonLines {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.target().starts_with(env!("CARGO_CRATE_NAME"))
+    }
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields(Map::new());
+        fields.0.insert("level".to_string(), Value::from(event.metadata().level().as_str()));
+        event.record(&mut fields);
+        let line = Value::Object(fields.0).to_string();
+        let mut out = self.out.lock().unwrap();
+        // like the audit log, a failed write must not stop the searches
+        if writeln!(out, "{}", line).and_then(|_| out.flush()).is_err() {
+            eprintln!("Error: unable to write the event {}", line);
+        }
+    }
+    fn enter(&self, _: &Id) {}
+    fn exit(&self, _: &Id) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::sync::Arc;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_json_lines() {
+        let buffer = Buffer::default();
+        tracing::subscriber::with_default(JsonLines::new(buffer.clone()), || {
+            tracing::info!(worker = 1u64, found = true, "search progress");
+        });
+        let line: Value = serde_json::from_slice(&buffer.0.lock().unwrap()).unwrap();
+        assert_eq!(line, serde_json::json!({"level": "INFO", "message": "search progress", "worker": 1, "found": true}));
+    }
+}
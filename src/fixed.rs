@@ -0,0 +1,99 @@
+//! Solvers whose bitwidth is a constant of their type, for the common configurations of `u32` and `u40` plaintexts, e.g [`Solver32`] and
+//! [`Solver40`].
+//!
+//! The bitwidth of the search and of its table of `2^(BITS / 2)` baby steps are then part of the type, so that an invalid bitwidth fails
+//! when the solver is instantiated, at compile time, rather than when it is built, and a solver cannot be passed where another bitwidth
+//! is expected. The search itself is the one of a [`crate::Solver`], whose table and loops are sized at runtime from `BITS`.
+//!
+//! Up to 40 bits, the table has at most `2^20` baby steps, which are keyed by the lowest 32 bits of their `x` coordinate instead of 64,
+//! so that it takes half the memory of the table of a [`crate::Solver`] : a giant step matches the key of a baby step by chance with a
+//! probability of at most `2^-12`, and the false matches are discarded when they are confirmed. The searches are otherwise the same, and
+//! give the same Discrete Logarithms.
+
+use ark_ed_on_bn254::EdwardsAffine as BabyJubJub;
+use crate::point::{base_point, parse_coordinates, parse_point, ParseMode};
+use crate::{Error, Search};
+
+/// Solver for the Discrete Logarithm of embedded plaintexts of at most `BITS` bits, between `1` and `40`, see the [module documentation](self).
+pub struct FixedSolver<const BITS: u64> {
+    inner: crate::Solver<BabyJubJub, u32>,
+}
+
+/// Solver of `u32` plaintexts, e.g the ones of zkay.
+pub type Solver32 = FixedSolver<32>;
+
+/// Solver of `u40` plaintexts, like [`crate::compute_dlog`].
+pub type Solver40 = FixedSolver<40>;
+
+impl<const BITS: u64> FixedSolver<BITS> {
+    /// Bitwidth of the table of baby steps.
+    pub const TABLE_BITWIDTH: u64 = BITS / 2;
+
+    const VALID: () = assert!(BITS >= 1 && BITS <= 40, "the bitwidth of a FixedSolver should be between 1 and 40");
+
+    /// Builds the table of `2^(BITS / 2)` baby steps, using `num_threads` threads.
+    pub fn new(num_threads: u64) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID;
+        FixedSolver { inner: crate::Solver::with_generator(base_point(), BITS, Self::TABLE_BITWIDTH, num_threads) }
+    }
+
+    pub const fn max_bitwidth(&self) -> u64 {
+        BITS
+    }
+
+    /// Sets the parsing mode of the hexadecimal coordinates, like [`crate::Solver::with_parse_mode`].
+    pub fn with_parse_mode(self, parse_mode: ParseMode) -> Self {
+        FixedSolver { inner: self.inner.with_parse_mode(parse_mode) }
+    }
+
+    pub fn parse_mode(&self) -> ParseMode {
+        self.inner.parse_mode()
+    }
+
+    /// Approximate memory used by the table of baby steps, in bytes.
+    pub fn table_size_bytes(&self) -> u64 {
+        self.inner.table_size_bytes()
+    }
+
+    /// Memory allocated by the solver, like [`crate::Solver::memory_usage`].
+    pub fn memory_usage(&self) -> crate::MemoryUsage {
+        self.inner.memory_usage()
+    }
+
+    /// Computes the Discrete Logarithm of the point `(x, y)`, like [`crate::Solver::solve`].
+    pub fn solve(&self, x: &str, y: &str) -> Result<u64, Error> {
+        self.search(x, y)?.run()
+    }
+
+    /// Prepares the search for the Discrete Logarithm of the point `(x, y)`, without running it, like [`crate::Solver::search`].
+    pub fn search(&self, x: &str, y: &str) -> Result<Search<'_, BabyJubJub, u32>, Error> {
+        parse_coordinates(x, y, |s| self.inner.parse_mode().check(s))?;
+        Ok(self.inner.search_point(parse_point(x, y)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{embed_plaintext, Solver};
+
+    #[test]
+    fn test_fixed_solver() {
+        let solver = FixedSolver::<20>::new(2);
+        assert_eq!((solver.max_bitwidth(), FixedSolver::<20>::TABLE_BITWIDTH), (20, 10));
+        for plaintext in [0, 1, 1023, 1024, 65545, (1 << 20) - 1] {
+            let p = embed_plaintext(plaintext);
+            assert_eq!(solver.solve(&p.0, &p.1), Ok(plaintext));
+        }
+        let too_large = embed_plaintext(1 << 20);
+        assert_eq!(solver.solve(&too_large.0, &too_large.1), Err(Error::DlogNotFound { max_bitwidth: 20 }));
+        assert_eq!(solver.solve("0x01", "0x01"), Err(Error::NotOnCurve));
+        let strict = solver.with_parse_mode(ParseMode::Strict);
+        assert!(matches!(strict.solve("0x0", "0x1"), Err(Error::InvalidCoordinate { .. })));
+        let (x, y) = embed_plaintext(1024);
+        assert_eq!(strict.solve(&x, &y), Ok(1024));
+        // the keys of 32 bits halve the memory of the table
+        assert!(strict.table_size_bytes() * 3 / 2 < Solver::new(20, 2).table_size_bytes());
+    }
+}
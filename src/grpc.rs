@@ -0,0 +1,196 @@
+//! gRPC decryption service, the counterpart of the HTTP [`crate::server`] for infrastructures standardized on gRPC.
+//!
+//! The messages and the `Babygiant` service are defined in `proto/babygiant.proto`, shipped with the crate.
+
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status, Streaming};
+use crate::server::limits::Rejection;
+use crate::server::Backend;
+use crate::{Error, Search};
+
+/// Types generated from `proto/babygiant.proto`, with the messages of the [`crate::protobuf`] module.
+pub use crate::protobuf as proto;
+
+use proto::babygiant_server::{Babygiant, BabygiantServer};
+use proto::{DlogResult, Point, SolveBatchResponse, SolveProgress, SolveResponse, WorkerProgress};
+
+/// Implementation of the `Babygiant` gRPC service, sharing one [`Backend`] (or a [`crate::Solver`] without cache) between all the calls.
+pub struct BabygiantService {
+    backend: Backend,
+    progress_interval: Duration,
+}
+
+impl BabygiantService {
+    pub fn new(backend: impl Into<Backend>) -> Self {
+        BabygiantService { backend: backend.into(), progress_interval: Duration::from_millis(500) }
+    }
+
+    /// Sets the interval between two messages of the `SolveWithProgress` stream (`500ms` by default).
+    pub fn with_progress_interval(mut self, progress_interval: Duration) -> Self {
+        self.progress_interval = progress_interval;
+        self
+    }
+
+    pub fn into_server(self) -> BabygiantServer<Self> {
+        BabygiantServer::new(self)
+    }
+}
+
+fn status(e: Error) -> Status {
+    match e {
+        Error::InvalidFormat(_) | Error::InvalidHex { .. } | Error::InvalidBase64 { .. } | Error::NotInField { .. } | Error::InvalidCoordinate { .. } | Error::NotOnCurve | Error::NotInSubgroup => Status::invalid_argument(e.to_string()),
+        Error::DlogNotFound { .. } => Status::not_found(e.to_string()),
+        Error::Cancelled => Status::cancelled(e.to_string()),
+        _ => Status::internal(e.to_string()),
+    }
+}
+
+fn rejected(rejection: Rejection) -> Status {
+    match rejection {
+        Rejection::RateLimited { .. } | Rejection::QueueFull => Status::resource_exhausted(rejection.to_string()),
+        Rejection::TooManyPoints { .. } => Status::invalid_argument(rejection.to_string()),
+    }
+}
+
+fn progress(search: &Search<'_>, result: Option<DlogResult>) -> SolveProgress {
+    let workers = search.progress().iter().map(|p| WorkerProgress { done: p.done, total: p.total }).collect();
+    SolveProgress { workers, result }
+}
+
+#[tonic::async_trait]
+impl Babygiant for BabygiantService {
+    async fn solve(&self, request: Request<Point>) -> Result<Response<SolveResponse>, Status> {
+        let client = request.remote_addr();
+        let point = request.into_inner();
+        let dlog = self.backend.run_blocking(client, 1, move |backend| backend.solve_for(client, &point.x, &point.y))
+            .await
+            .map_err(rejected)?
+            .map_err(status)?;
+        Ok(Response::new(SolveResponse { dlog }))
+    }
+
+    async fn solve_batch(&self, request: Request<Streaming<Point>>) -> Result<Response<SolveBatchResponse>, Status> {
+        let client = request.remote_addr();
+        let mut points = request.into_inner();
+        let mut results = Vec::new();
+        while let Some(point) = points.message().await? {
+            let result = self.backend.run_blocking(client, 1, move |backend| backend.solve_for(client, &point.x, &point.y))
+                .await
+                .map_err(rejected)?;
+            results.push(DlogResult::from(result));
+        }
+        Ok(Response::new(SolveBatchResponse { results }))
+    }
+
+    type SolveWithProgressStream = ReceiverStream<Result<SolveProgress, Status>>;
+
+    async fn solve_with_progress(&self, request: Request<Point>) -> Result<Response<Self::SolveWithProgressStream>, Status> {
+        let client = request.remote_addr();
+        let point = request.into_inner();
+        let backend = self.backend.clone();
+        let interval = self.progress_interval;
+        let (tx, rx) = mpsc::channel(16);
+        // the slot is held by the search, which is cancelled once the client goes away
+        let slot = backend.admit(client, 1).await.map_err(rejected)?;
+        tokio::task::spawn_blocking(move || {
+            let _slot = slot;
+            // progress of the workers once the search started, the errors before it are the ones of the call itself
+            let mut workers = None;
+            let result = backend.audit(client, "dlog", &[&point.x, &point.y], || {
+                if let Some(cache) = backend.cache() {
+                    if let Some(dlog) = cache.get(&point.x, &point.y, backend.solver().parse_mode())? {
+                        return Ok(dlog);
+                    }
+                }
+                let search = backend.solver().search(&point.x, &point.y)?;
+                let (done_tx, done_rx) = channel::<()>();
+                let result = thread::scope(|s| {
+                    let (search, tx) = (&search, &tx);
+                    s.spawn(move || {
+                        while done_rx.recv_timeout(interval) == Err(RecvTimeoutError::Timeout) {
+                            // the client went away, so there is no point in finishing the search
+                            if tx.blocking_send(Ok(progress(search, None))).is_err() {
+                                search.cancel();
+                            }
+                        }
+                    });
+                    let result = search.run();
+                    drop(done_tx);
+                    result
+                });
+                if let (Some(cache), Ok(dlog)) = (backend.cache(), &result) {
+                    let _ = cache.insert(&point.x, &point.y, *dlog);
+                }
+                workers = Some(progress(&search, None).workers);
+                result
+            });
+            let _ = match (workers, result) {
+                (None, Err(e)) => tx.blocking_send(Err(status(e))),
+                (workers, result) => tx.blocking_send(Ok(SolveProgress { workers: workers.unwrap_or_default(), result: Some(result.into()) })),
+            };
+        });
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proto::dlog_result;
+    use crate::Solver;
+    use std::sync::Arc;
+    use tokio_stream::StreamExt;
+
+    fn generator() -> Point {
+        Point {
+            x: "0x0bb77a6ad63e739b4eacb2e09d6277c12ab8d8010534e0b62893f3f6bb957051".to_string(),
+            y: "0x25797203f7a0b24925572e1cd16bf9edfce0051fb9e133774b3c257a872d7d8b".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_solve() {
+        let service = BabygiantService::new(Arc::new(Solver::new(16, 2)));
+        let response = service.solve(Request::new(generator())).await.unwrap();
+        assert_eq!(response.into_inner().dlog, 1);
+        let invalid = Point { x: "0x1".to_string(), y: "0x1".to_string() };
+        assert_eq!(service.solve(Request::new(invalid)).await.unwrap_err().code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_limits() {
+        let limits = crate::server::limits::Limits { rate: Some(crate::server::limits::RateLimit { per_second: 0.001, burst: 1.0 }), ..Default::default() };
+        let service = BabygiantService::new(Backend::new(Arc::new(Solver::new(16, 2))).with_limits(limits));
+        assert!(service.solve(Request::new(generator())).await.is_ok());
+        assert_eq!(service.solve(Request::new(generator())).await.unwrap_err().code(), tonic::Code::ResourceExhausted);
+        assert_eq!(service.solve_with_progress(Request::new(generator())).await.unwrap_err().code(), tonic::Code::ResourceExhausted);
+    }
+
+    #[tokio::test]
+    async fn test_solve_with_progress() {
+        let service = BabygiantService::new(Arc::new(Solver::new(16, 2))).with_progress_interval(Duration::from_millis(1));
+        let stream = service.solve_with_progress(Request::new(generator())).await.unwrap().into_inner();
+        let messages: Vec<SolveProgress> = stream.map(|m| m.unwrap()).collect().await;
+        let last = messages.last().unwrap();
+        assert_eq!(last.result, Some(DlogResult { outcome: Some(dlog_result::Outcome::Dlog(1)) }));
+        assert_eq!(last.workers.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_solve_with_progress_audit_log() {
+        let path = std::env::temp_dir().join(format!("babygiant-grpc-audit-{}.jsonl", std::process::id()));
+        let backend = Backend::new(Arc::new(Solver::new(16, 2))).with_audit_log(Arc::new(crate::audit::AuditLog::open(&path).unwrap()));
+        let service = BabygiantService::new(backend);
+        let _: Vec<_> = service.solve_with_progress(Request::new(generator())).await.unwrap().into_inner().collect().await;
+        let invalid = Point { x: "0x1".to_string(), y: "0x1".to_string() };
+        let messages: Vec<_> = service.solve_with_progress(Request::new(invalid)).await.unwrap().into_inner().collect().await;
+        assert_eq!(messages[0].as_ref().unwrap_err().code(), tonic::Code::InvalidArgument);
+        let records: Vec<crate::audit::Record> = std::fs::read_to_string(&path).unwrap().lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(records.iter().map(|r| (r.ok, r.dlog)).collect::<Vec<_>>(), [(true, Some(1)), (false, None)]);
+    }
+}
@@ -0,0 +1,125 @@
+//! Exponential ElGamal on Grumpkin, the other curve of the bn254 cycle, whose operations are the embedded curve operations of Noir
+//! (`std::embedded_curve_ops`) and are used across the Aztec ecosystem, with the same Baby-step Giant-step search as Baby Jubjub.
+//!
+//! Grumpkin is the short Weierstrass curve `y^2 = x^3 - 17` over the scalar field of bn254, i.e the `Field` of Noir, and its scalar
+//! field is the base field of bn254. Its order is prime, so every point of the curve is in the prime subgroup. Points are encoded like
+//! the `EmbeddedCurvePoint`s of Noir, as their affine coordinates `(x, y)` in `0x`-prefixed hexadecimal strings, the point at infinity
+//! being `(0, 0)`. Scalars, i.e private keys and the encryption randomness, are `Field`s, like the argument of
+//! `EmbeddedCurveScalar::from_field`, and [`scalar_limbs`] splits them into the `lo` and `hi` limbs of an `EmbeddedCurveScalar`.
+
+use ark_bn254::Fq as Scalar;
+use ark_ec::models::short_weierstrass_jacobian::GroupAffine;
+use ark_ec::models::{ModelParameters, SWModelParameters};
+use ark_ec::AffineCurve;
+use ark_ed_on_bn254::Fq;
+use ark_ff::{field_new, BigInteger, PrimeField, Zero};
+use crate::dlog::{self, DlogCurve};
+use crate::table::ArkworksCurve;
+use crate::{Error, HexPoint};
+
+/// Coordinates of the generator `G = (1, sqrt(-16))` of Grumpkin used by Noir.
+pub const GENERATOR: (&str, &str) = (
+    "0x0000000000000000000000000000000000000000000000000000000000000001",
+    "0x0000000000000002cf135e7506a45d632d270d45f1181294833fc48d823f272c",
+);
+
+#[derive(Clone, Default, PartialEq, Eq)]
+pub(crate) struct Parameters;
+
+impl ModelParameters for Parameters {
+    type BaseField = Fq;
+    type ScalarField = Scalar;
+}
+
+impl SWModelParameters for Parameters {
+    const COEFF_A: Fq = field_new!(Fq, "0");
+    const COEFF_B: Fq = field_new!(Fq, "-17");
+    const COFACTOR: &'static [u64] = &[1];
+    const COFACTOR_INV: Scalar = field_new!(Scalar, "1");
+    const AFFINE_GENERATOR_COEFFS: (Fq, Fq) =
+        (field_new!(Fq, "1"), field_new!(Fq, "17631683881184975370165255887551781615748388533673675138860"));
+
+    #[inline(always)]
+    fn mul_by_a(_: &Fq) -> Fq {
+        Fq::zero()
+    }
+}
+
+pub(crate) type Grumpkin = GroupAffine<Parameters>;
+
+impl ArkworksCurve for Grumpkin {
+    fn x_low_bits(&self) -> u64 {
+        self.x.into_repr().0[0]
+    }
+}
+
+impl DlogCurve for Grumpkin {
+    type Scalar = Scalar;
+
+    fn generator() -> Self {
+        Self::prime_subgroup_generator()
+    }
+
+    fn mul_scalar(&self, s: &Scalar) -> <Self as AffineCurve>::Projective {
+        self.mul(*s)
+    }
+
+    fn parse_point(x: &str, y: &str) -> Result<Self, Error> {
+        dlog::weierstrass_point(x, y)
+    }
+
+    fn to_hex(&self) -> HexPoint {
+        dlog::weierstrass_to_hex(self)
+    }
+
+    /// Parses a scalar as a `Field`, which is always smaller than the order of Grumpkin.
+    fn parse_scalar(s: &str) -> Result<Scalar, Error> {
+        dlog::parse_scalar::<Fq, Scalar>(s)
+    }
+}
+
+/// Splits the scalar `s` into the limbs `(lo, hi)` of its lowest and highest 128 bits, the fields of an `EmbeddedCurveScalar` of Noir.
+pub fn scalar_limbs(s: &str) -> Result<(String, String), Error> {
+    let bytes = Grumpkin::parse_scalar(s)?.into_repr().to_bytes_be();
+    let limb = |b: &[u8]| format!("0x{}{}", "0".repeat(32), hex::encode(b));
+    Ok((limb(&bytes[16..]), limb(&bytes[..16])))
+}
+
+dlog::curve_module!(Grumpkin, weierstrass);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ec::ProjectiveCurve;
+    use crate::table::BabyStepCurve;
+    use ark_ff::FpParameters;
+
+    #[test]
+    fn test_grumpkin() {
+        let g = Grumpkin::prime_subgroup_generator();
+        assert!(g.is_on_curve());
+        assert_eq!(g.to_hex(), (GENERATOR.0.to_string(), GENERATOR.1.to_string()));
+        // the order of Grumpkin is the modulus of the base field of bn254
+        assert!(g.mul(<Scalar as PrimeField>::Params::MODULUS).is_zero());
+        assert_eq!(g.mul_u64(2).into_affine(), g + g);
+
+        let private_key = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        let pk = priv_to_pub_key(private_key).unwrap();
+        let (c1, c2) = exp_elgamal_encrypt((&pk.0, &pk.1), 65535, "0x05").unwrap();
+        assert_eq!(exp_elgamal_decrypt(private_key, (&c1.0, &c1.1), (&c2.0, &c2.1)), Ok(embed_plaintext(65535)));
+        let solver = Solver::new(16, 2);
+        assert_eq!(decrypt_to_u64(private_key, (&c1.0, &c1.1), (&c2.0, &c2.1), &solver), Ok(65535));
+        for plaintext in [0, 1, 255, 256] {
+            let p = embed_plaintext(plaintext);
+            assert_eq!(solver.solve(&p.0, &p.1), Ok(plaintext));
+        }
+        let too_large = embed_plaintext(65536);
+        assert_eq!(solver.solve(&too_large.0, &too_large.1), Err(Error::DlogNotFound { max_bitwidth: 16 }));
+        assert_eq!(embed_plaintext(0), ("0x".to_string() + &"0".repeat(64), "0x".to_string() + &"0".repeat(64)));
+        assert_eq!(Grumpkin::parse_point("0x1", "0x1"), Err(Error::NotOnCurve));
+
+        let (lo, hi) = scalar_limbs("0x0000000000000000000000000000000700000000000000000000000000000005").unwrap();
+        assert_eq!((lo.trim_start_matches("0x").trim_start_matches('0'), hi.trim_start_matches("0x").trim_start_matches('0')), ("5", "7"));
+        assert_eq!(lo.len(), 66);
+    }
+}
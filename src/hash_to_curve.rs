@@ -0,0 +1,140 @@
+//! Hashing to the curve and to the scalars of Baby Jubjub following RFC 9380, so that applications derive auxiliary generators, e.g
+//! for Pedersen commitments and proofs, whose Discrete Logarithms nobody knows, in the same way as their circuits.
+//!
+//! [`hash_to_curve`] is the random oracle encoding of the suite `BabyJubJub_XMD:SHA-256_ELL2_RO_` : the message is expanded by
+//! [`expand_message_xmd`] with SHA-256 into two elements of the base field of `48` bytes each, i.e `L = ceil((254 + 128) / 8)`, which are
+//! mapped to the Montgomery form `t^2 = s^3 + 168698·s^2 + s` by Elligator 2 with `Z = 5`, the first non-square of RFC 9380, then to the
+//! Twisted Edwards form with the rational map of the RFC, added and multiplied by the cofactor `8`. [`encode_to_curve`] is the
+//! nonuniform encoding `BabyJubJub_XMD:SHA-256_ELL2_NU_`, which maps a single element, and [`hash_to_scalar`] reduces `48` bytes of
+//! [`expand_message_xmd`] modulo the order of the prime subgroup.
+//!
+//! The domain separation tag `dst` should be unique to the application and to the use of the hash, e.g
+//! `"MYAPP-V01-CS01-with-BabyJubJub_XMD:SHA-256_ELL2_RO_"`, as recommended by the RFC.
+
+use ark_ed_on_bn254::{Fq, Fr};
+use ark_ff::{BigInteger, Field, One, PrimeField, SquareRootField, Zero};
+use sha2::{Digest, Sha256};
+use crate::curve::{montgomery_to_twisted, Point};
+use crate::point::field_to_hex;
+
+/// Identifier of the suite of [`hash_to_curve`].
+pub const SUITE: &str = "BabyJubJub_XMD:SHA-256_ELL2_RO_";
+
+/// Identifier of the suite of [`encode_to_curve`].
+pub const NU_SUITE: &str = "BabyJubJub_XMD:SHA-256_ELL2_NU_";
+
+/// Number of bytes expanded for each element of a field, for a security level of 128 bits.
+const L: usize = 48;
+
+/// Coefficient `J` of the Montgomery form, whose coefficient `K` is `1`.
+const J: u64 = 168698;
+
+/// Non-square `Z` of Elligator 2.
+const Z: u64 = 5;
+
+/// Expands `msg` into `len_in_bytes` uniform bytes with SHA-256, like `expand_message_xmd` of RFC 9380, for the domain separation tag
+/// `dst`, which is hashed first if it is longer than 255 bytes. `len_in_bytes` should be at most `8160`, i.e `255` blocks of SHA-256.
+pub fn expand_message_xmd(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Vec<u8> {
+    assert!(len_in_bytes <= 255 * 32, "len_in_bytes should be at most 8160");
+    let dst = match dst.len() {
+        0..=255 => dst.to_vec(),
+        _ => Sha256::new().chain_update(b"H2C-OVERSIZE-DST-").chain_update(dst).finalize().to_vec(),
+    };
+    let dst_prime = [dst.as_slice(), &[dst.len() as u8]].concat();
+    let b_0 = Sha256::new()
+        .chain_update([0u8; 64])
+        .chain_update(msg)
+        .chain_update((len_in_bytes as u16).to_be_bytes())
+        .chain_update([0u8])
+        .chain_update(&dst_prime)
+        .finalize();
+    let mut b_i = Sha256::new().chain_update(b_0).chain_update([1u8]).chain_update(&dst_prime).finalize();
+    let mut uniform_bytes = b_i.to_vec();
+    for i in 2..=len_in_bytes.div_ceil(32) {
+        let xored: Vec<u8> = b_0.iter().zip(b_i).map(|(a, b)| a ^ b).collect();
+        b_i = Sha256::new().chain_update(xored).chain_update([i as u8]).chain_update(&dst_prime).finalize();
+        uniform_bytes.extend(b_i);
+    }
+    uniform_bytes.truncate(len_in_bytes);
+    uniform_bytes
+}
+
+/// Hashes `msg` into `count` elements of the prime field `F`, like `hash_to_field` of RFC 9380.
+fn hash_to_field<F: PrimeField>(msg: &[u8], dst: &[u8], count: usize) -> Vec<F> {
+    expand_message_xmd(msg, dst, count * L).chunks(L).map(F::from_be_bytes_mod_order).collect()
+}
+
+/// Maps a field element to the Montgomery form with Elligator 2, then to the Twisted Edwards form with the rational map of RFC 9380,
+/// whose exceptional points are mapped to the identity.
+fn map_to_curve(u: Fq) -> Point {
+    let j = Fq::from(J);
+    let g = |x: Fq| x.square() * x + j * x.square() + x;
+    let mut x1 = -j * (Fq::one() + Fq::from(Z) * u.square()).inverse().unwrap_or_else(Fq::zero);
+    if x1.is_zero() {
+        x1 = -j;
+    }
+    let x2 = -x1 - j;
+    let (s, mut t, odd) = match g(x1).sqrt() {
+        Some(y) => (x1, y, true),
+        None => (x2, g(x2).sqrt().unwrap(), false),
+    };
+    if t.into_repr().is_odd() != odd {
+        t = -t;
+    }
+    if t.is_zero() || s == -Fq::one() {
+        return Point::identity();
+    }
+    let (x, y) = montgomery_to_twisted(s, t).unwrap();
+    // the points of the Montgomery form are mapped to points of the Twisted Edwards form
+    Point::from_coordinates(x, y).unwrap()
+}
+
+/// Hashes `msg` to a point of the prime subgroup of Baby Jubjub, indistinguishable from a random oracle, see the
+/// [module documentation](self).
+pub fn hash_to_curve(msg: &[u8], dst: &[u8]) -> Point {
+    let u: Vec<Fq> = hash_to_field(msg, dst, 2);
+    (map_to_curve(u[0]) + map_to_curve(u[1])).clear_cofactor()
+}
+
+/// Encodes `msg` to a point of the prime subgroup of Baby Jubjub, which is not uniformly distributed but twice as fast as
+/// [`hash_to_curve`].
+pub fn encode_to_curve(msg: &[u8], dst: &[u8]) -> Point {
+    let u: Vec<Fq> = hash_to_field(msg, dst, 1);
+    map_to_curve(u[0]).clear_cofactor()
+}
+
+/// Hashes `msg` to a scalar modulo the order of the prime subgroup, in the same format as [`crate::generate_randomness`].
+pub fn hash_to_scalar(msg: &[u8], dst: &[u8]) -> String {
+    let s: Vec<Fr> = hash_to_field(msg, dst, 1);
+    field_to_hex(&s[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::SUBGROUP_ORDER;
+
+    #[test]
+    fn test_hash_to_curve() {
+        // the vectors of expand_message_xmd with SHA-256 of RFC 9380
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        assert_eq!(hex::encode(expand_message_xmd(b"", dst, 0x20)), "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235");
+        assert_eq!(hex::encode(expand_message_xmd(b"abc", dst, 0x20)), "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615");
+        assert_eq!(expand_message_xmd(b"abc", dst, 0x80).len(), 0x80);
+
+        let dst = b"babygiant-alt-bn128-V01-CS02-with-BabyJubJub_XMD:SHA-256_ELL2_RO_";
+        assert_eq!(hash_to_curve(b"", dst).to_hex(), ("0x057fbf9c2725622e68631756854292aac8753095269cec576552dddec5c77ee6".to_string(),
+                                                     "0x1622ce2b83b6bc6d17ccaf1ee630ec64b8599ce6c6fcac87ab8d84fe8a7eb277".to_string()));
+        assert_eq!(hash_to_curve(b"abc", dst).to_hex(), ("0x170904c7c697196441fba574d3e0eb42afeacf6720fa4b514c68e33e37d0f628".to_string(),
+                                                        "0x2f15dca03d67e9c8cc101994a73155bd8dcd9a2289b744192bed6d5f79272f66".to_string()));
+        for msg in [b"".as_slice(), b"abc", b"H_1"] {
+            for p in [hash_to_curve(msg, dst), encode_to_curve(msg, dst)] {
+                assert!(p.is_in_subgroup() && !p.is_identity());
+            }
+        }
+        assert_ne!(hash_to_curve(b"abc", dst), hash_to_curve(b"abc", b"other"));
+
+        assert_eq!(hash_to_scalar(b"abc", b"babygiant-alt-bn128-V01-scalar"), "0x018b03a8db85a24e876817e4c9ed0959a9a505c88145899619b419ee971dd22f");
+        assert!(hash_to_scalar(b"abc", dst).as_str() < SUBGROUP_ORDER);
+    }
+}
@@ -0,0 +1,268 @@
+//! Hierarchical deterministic derivation of Baby Jubjub keys, so that a wallet can derive a decryption key per asset or per counterparty
+//! from a single seed instead of storing many independent secrets.
+//!
+//! The derivation follows [BIP32](https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki), with the order of the prime subgroup
+//! of Baby Jubjub in place of the one of secp256k1 :
+//!
+//! - The master key is `I = HMAC-SHA512("Baby Jubjub seed", seed)` : the left half of `I` modulo the order is its private key,
+//!   and the right half its chain code.
+//! - The child `i` of a key is derived from `I = HMAC-SHA512(chain_code, data || i)`, where `data` is `0x00 || private_key` for hardened
+//!   children (`i >= 2^31`, written `i'` or `ih` in paths), and the Twisted Edwards coordinates `x || y` of the public key otherwise.
+//!   The private key of the child is the private key of its parent plus the left half of `I` modulo the order, and its chain code the right half.
+//! - Non-hardened children can thus also be derived from an [`ExtendedPublicKey`], e.g by a server assigning a public key to each
+//!   counterparty without holding any private key.
+//!
+//! Scalars and coordinates are serialized as 32 bytes big-endian integers, and child indices as 4 bytes big-endian integers.
+
+use std::fmt;
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ed_on_bn254::{EdwardsAffine as BabyJubJub, Fr};
+use ark_ff::{BigInteger, PrimeField};
+use sha2::{Digest, Sha512};
+#[cfg(feature = "ark-serialize")]
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
+use crate::point::{base_point, field_to_hex, point_bytes, point_to_hex, HexPoint};
+use crate::Error;
+
+/// Offset of the indices of hardened children.
+pub const HARDENED: u32 = 1 << 31;
+
+const MASTER_HMAC_KEY: &[u8] = b"Baby Jubjub seed";
+const SHA512_BLOCK_BYTES: usize = 128;
+
+/// Private key with its chain code, from which the keys of its children are derived.
+///
+/// With the `ark-serialize` feature, it implements the traits of arkworks as the private key followed by the 32 bytes of the chain code.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ExtendedPrivateKey {
+    private_key: Fr,
+    chain_code: [u8; 32],
+}
+
+/// Public key with its chain code, from which the public keys of its non-hardened children are derived.
+///
+/// With the `ark-serialize` feature, it implements the traits of arkworks as the compressed public key followed by the 32 bytes of the chain code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtendedPublicKey {
+    public_key: BabyJubJub,
+    chain_code: [u8; 32],
+}
+
+impl ExtendedPrivateKey {
+    /// Derives the master key of `seed`, which should contain at least 16 bytes of entropy.
+    pub fn from_seed(seed: &[u8]) -> Self {
+        let (left, chain_code) = split(hmac_sha512(MASTER_HMAC_KEY, seed));
+        ExtendedPrivateKey { private_key: Fr::from_be_bytes_mod_order(&left), chain_code }
+    }
+
+    /// Derives the child `index`, which is hardened if `index >= HARDENED`.
+    pub fn derive_child(&self, index: u32) -> Self {
+        let mut data = Vec::with_capacity(69);
+        if index >= HARDENED {
+            data.push(0);
+            data.extend(self.private_key.into_repr().to_bytes_be());
+        } else {
+            data.extend(point_bytes(&self.point()));
+        }
+        data.extend(index.to_be_bytes());
+        let (left, chain_code) = split(hmac_sha512(&self.chain_code, &data));
+        ExtendedPrivateKey { private_key: self.private_key + Fr::from_be_bytes_mod_order(&left), chain_code }
+    }
+
+    /// Derives the descendant at `path`, such as `m/44'/1'/0`, relative to this key.
+    pub fn derive_path(&self, path: &str) -> Result<Self, Error> {
+        Ok(parse_path(path)?.into_iter().fold(self.clone(), |key, index| key.derive_child(index)))
+    }
+
+    /// Private key, in the same format as [`crate::generate_private_key`].
+    pub fn private_key(&self) -> String {
+        field_to_hex(&self.private_key)
+    }
+
+    /// Public key, as computed by [`crate::priv_to_pub_key`] from [`ExtendedPrivateKey::private_key`].
+    pub fn public_key(&self) -> HexPoint {
+        point_to_hex(&self.point())
+    }
+
+    pub fn chain_code(&self) -> [u8; 32] {
+        self.chain_code
+    }
+
+    /// Extended public key deriving the public keys of the non-hardened children of this key.
+    pub fn to_public(&self) -> ExtendedPublicKey {
+        ExtendedPublicKey { public_key: self.point(), chain_code: self.chain_code }
+    }
+
+    fn point(&self) -> BabyJubJub {
+        base_point().mul(self.private_key).into_affine()
+    }
+}
+
+/// The private key is not printed, so that it does not leak into logs.
+impl fmt::Debug for ExtendedPrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtendedPrivateKey").field("public_key", &self.public_key()).finish_non_exhaustive()
+    }
+}
+
+impl ExtendedPublicKey {
+    /// Derives the public key of the non-hardened child `index`, which is the public key of
+    /// [`ExtendedPrivateKey::derive_child`] for the extended private key of this one.
+    pub fn derive_child(&self, index: u32) -> Result<Self, Error> {
+        if index >= HARDENED {
+            return Err(Error::InvalidDerivationPath(format!("the hardened child {}' cannot be derived from a public key", index - HARDENED)));
+        }
+        let mut data = point_bytes(&self.public_key);
+        data.extend(index.to_be_bytes());
+        let (left, chain_code) = split(hmac_sha512(&self.chain_code, &data));
+        let public_key = (self.public_key.into_projective() + base_point().mul(Fr::from_be_bytes_mod_order(&left))).into_affine();
+        Ok(ExtendedPublicKey { public_key, chain_code })
+    }
+
+    /// Derives the descendant at `path`, which should only contain non-hardened indices, relative to this key.
+    pub fn derive_path(&self, path: &str) -> Result<Self, Error> {
+        parse_path(path)?.into_iter().try_fold(self.clone(), |key, index| key.derive_child(index))
+    }
+
+    pub fn public_key(&self) -> HexPoint {
+        point_to_hex(&self.public_key)
+    }
+
+    pub fn chain_code(&self) -> [u8; 32] {
+        self.chain_code
+    }
+}
+
+/// Parses a derivation path such as `m/44'/1'/0` into its child indices, with hardened indices offset by [`HARDENED`].
+pub fn parse_path(path: &str) -> Result<Vec<u32>, Error> {
+    let invalid = |reason: String| Error::InvalidDerivationPath(format!("{:?} : {}", path, reason));
+    let mut components = path.trim().split('/');
+    if components.next() != Some("m") {
+        return Err(invalid("a path should start with m".to_string()));
+    }
+    components
+        .map(|component| {
+            let (digits, offset) = match component.strip_suffix('\'').or_else(|| component.strip_suffix('h')) {
+                Some(digits) => (digits, HARDENED),
+                None => (component, 0),
+            };
+            match digits.parse::<u32>() {
+                Ok(index) if index < HARDENED && digits.bytes().all(|b| b.is_ascii_digit()) => Ok(index + offset),
+                _ => Err(invalid(format!("{:?} is not an index smaller than 2^31", component))),
+            }
+        })
+        .collect()
+}
+
+fn split(i: [u8; 64]) -> ([u8; 32], [u8; 32]) {
+    (i[..32].try_into().unwrap(), i[32..].try_into().unwrap())
+}
+
+fn hmac_sha512(key: &[u8], data: &[u8]) -> [u8; 64] {
+    let mut padded = [0u8; SHA512_BLOCK_BYTES];
+    if key.len() > SHA512_BLOCK_BYTES {
+        padded[..64].copy_from_slice(&Sha512::digest(key));
+    } else {
+        padded[..key.len()].copy_from_slice(key);
+    }
+    let pad = |byte: u8| padded.iter().map(|k| k ^ byte).collect::<Vec<u8>>();
+    let inner = Sha512::new().chain_update(pad(0x36)).chain_update(data).finalize();
+    Sha512::new().chain_update(pad(0x5c)).chain_update(inner).finalize().into()
+}
+
+#[cfg(feature = "ark-serialize")]
+impl CanonicalSerialize for ExtendedPrivateKey {
+    fn serialize<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        self.private_key.serialize(&mut writer)?;
+        Ok(writer.write_all(&self.chain_code)?)
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.private_key.serialized_size() + self.chain_code.len()
+    }
+}
+
+#[cfg(feature = "ark-serialize")]
+impl CanonicalDeserialize for ExtendedPrivateKey {
+    fn deserialize<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let private_key = Fr::deserialize(&mut reader)?;
+        let mut chain_code = [0u8; 32];
+        reader.read_exact(&mut chain_code)?;
+        Ok(ExtendedPrivateKey { private_key, chain_code })
+    }
+}
+
+#[cfg(feature = "ark-serialize")]
+impl CanonicalSerialize for ExtendedPublicKey {
+    fn serialize<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        self.public_key.serialize(&mut writer)?;
+        Ok(writer.write_all(&self.chain_code)?)
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.public_key.serialized_size() + self.chain_code.len()
+    }
+}
+
+#[cfg(feature = "ark-serialize")]
+impl CanonicalDeserialize for ExtendedPublicKey {
+    fn deserialize<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let public_key = BabyJubJub::deserialize(&mut reader)?;
+        let mut chain_code = [0u8; 32];
+        reader.read_exact(&mut chain_code)?;
+        Ok(ExtendedPublicKey { public_key, chain_code })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::priv_to_pub_key;
+
+    #[test]
+    fn test_hmac_sha512() {
+        // test case 2 of RFC 4231
+        let mac = hmac_sha512(b"Jefe", b"what do ya want for nothing?");
+        assert_eq!(hex::encode(mac), "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737");
+    }
+
+    #[test]
+    fn test_derive_path() {
+        let master = ExtendedPrivateKey::from_seed(b"babygiant test seed");
+        assert_eq!(master, ExtendedPrivateKey::from_seed(b"babygiant test seed"));
+        assert_eq!(master.derive_path("m").unwrap(), master);
+        let child = master.derive_path("m/44'/1h/0").unwrap();
+        assert_eq!(child, master.derive_child(44 + HARDENED).derive_child(1 + HARDENED).derive_child(0));
+        assert_eq!(priv_to_pub_key(&child.private_key()).unwrap(), child.public_key());
+        assert_ne!(master.derive_child(0), master.derive_child(HARDENED));
+        assert_ne!(master.derive_child(0).private_key(), master.derive_child(1).private_key());
+
+        // non-hardened children can be derived from the public key of their parent only
+        let account = master.derive_path("m/44'/1'").unwrap();
+        let public = account.to_public().derive_path("m/3/7").unwrap();
+        assert_eq!(public.public_key(), account.derive_path("m/3/7").unwrap().public_key());
+        assert_eq!(public.chain_code(), account.derive_path("m/3/7").unwrap().chain_code());
+        assert!(matches!(account.to_public().derive_child(HARDENED), Err(Error::InvalidDerivationPath(_))));
+
+        assert!(!format!("{:?}", master).contains(&master.private_key()[2..]));
+        for invalid in ["", "44'/0", "m/", "m/-1", "m/+1", "m/2147483648", "m/1''", "m/x"] {
+            assert!(matches!(parse_path(invalid), Err(Error::InvalidDerivationPath(_))), "{}", invalid);
+        }
+    }
+
+    #[cfg(feature = "ark-serialize")]
+    #[test]
+    fn test_extended_keys_ark_serialize() {
+        let master = ExtendedPrivateKey::from_seed(b"an example of seed of 32 bytes !");
+        let mut bytes = Vec::new();
+        master.serialize(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), 64);
+        assert_eq!(ExtendedPrivateKey::deserialize(bytes.as_slice()).unwrap(), master);
+
+        let public = master.to_public();
+        let mut bytes = Vec::new();
+        public.serialize(&mut bytes).unwrap();
+        assert_eq!(ExtendedPublicKey::deserialize(bytes.as_slice()).unwrap(), public);
+        assert!(ExtendedPublicKey::deserialize(&bytes[..40]).is_err());
+    }
+}
@@ -0,0 +1,73 @@
+//! Embedded plaintexts and ciphertexts read from JSON objects, so that webhook payloads and queue messages are passed to the solver as
+//! they are received, instead of plucking their fields in every caller.
+//!
+//! An [`Input`] is either :
+//!
+//! - an embedded plaintext `{"x": "0x...", "y": "0x..."}`, as returned by `exp_elgamal_decrypt`,
+//! - a ciphertext `{"private_key": "0x...", "c1x": "0x...", "c1y": "0x...", "c2x": "0x...", "c2y": "0x..."}`, decrypted with its private
+//!   key before its embedded plaintext is solved.
+//!
+//! The coordinates are in the same format as the ones of [`crate::compute_dlog`], and the other fields of the objects are ignored, e.g
+//! the metadata of a webhook.
+
+use serde::Deserialize;
+use crate::{decrypt_to_u64, Ciphertext, Error, Solver};
+
+/// Embedded plaintext or ciphertext of a JSON object, see the [module documentation](self).
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum Input {
+    Ciphertext { private_key: String, c1x: String, c1y: String, c2x: String, c2y: String },
+    Point { x: String, y: String },
+}
+
+impl Input {
+    /// Parses the JSON object `json`.
+    pub fn parse(json: &str) -> Result<Self, Error> {
+        let value: serde_json::Value = serde_json::from_str(json).map_err(|e| Error::InvalidJson(e.to_string()))?;
+        if !value.is_object() {
+            return Err(Error::InvalidJson("the input should be an object".to_string()));
+        }
+        serde_json::from_value(value).map_err(|_| {
+            Error::InvalidJson("the object should have the string fields x and y, or private_key, c1x, c1y, c2x and c2y".to_string())
+        })
+    }
+
+    /// Computes the Discrete Logarithm of the embedded plaintext, decrypting it first for a ciphertext.
+    pub fn solve(&self, solver: &Solver) -> Result<u64, Error> {
+        match self {
+            Input::Ciphertext { private_key, c1x, c1y, c2x, c2y } => {
+                decrypt_to_u64(private_key, &Ciphertext::new((c1x, c1y), (c2x, c2y))?, solver)
+            }
+            Input::Point { x, y } => solver.solve(x, y),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{exp_elgamal_encrypt, priv_to_pub_key};
+
+    #[test]
+    fn test_json_input() {
+        let solver = Solver::new(20, 2);
+        let private_key = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        let pk = priv_to_pub_key(private_key).unwrap();
+        let (c1, c2) = exp_elgamal_encrypt((&pk.0, &pk.1), 65545, "0x05").unwrap();
+        let json = format!(
+            r#"{{"event": "transfer", "private_key": "{}", "c1x": "{}", "c1y": "{}", "c2x": "{}", "c2y": "{}"}}"#,
+            private_key, c1.0, c1.1, c2.0, c2.1
+        );
+        assert_eq!(Input::parse(&json).and_then(|input| input.solve(&solver)), Ok(65545));
+        let p = crate::embed_plaintext(42);
+        let json = format!(r#"{{"x": "{}", "y": "{}", "id": 7}}"#, p.0, p.1);
+        assert_eq!(Input::parse(&json), Ok(Input::Point { x: p.0, y: p.1 }));
+        assert_eq!(Input::parse(&json).and_then(|input| input.solve(&solver)), Ok(42));
+
+        for json in [r#"{"x": "0x01"}"#, r#"{"x": "0x01", "y": 1}"#, r#"["0x01", "0x01"]"#, r#"{"x": "0x01", "#] {
+            assert!(matches!(Input::parse(json), Err(Error::InvalidJson(_))), "{}", json);
+        }
+        assert_eq!(Input::parse(r#"{"x": "0x01", "y": "0x01"}"#).and_then(|input| input.solve(&solver)), Err(Error::NotOnCurve));
+    }
+}
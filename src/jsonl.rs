@@ -0,0 +1,146 @@
+//! Streaming reader of JSON-lines batch files of any size, one [`Input`] of the [`crate::json`] module per line, which are solved as
+//! they are read so that the memory used does not grow with the size of the file.
+//!
+//! [`solve_lines`] writes one result per input line, in the same order, with the number of the line in the file :
+//! `{"line": 3, "dlog": 42}`, or `{"line": 4, "error": "..."}` for the malformed lines and the inputs which could not be solved. The empty
+//! lines are skipped, and the lines longer than [`MAX_LINE_LEN`] are reported as malformed without being buffered.
+
+use std::io::{self, BufRead, Read, Write};
+use serde::Serialize;
+use crate::json::Input;
+use crate::{Error, Solver};
+
+/// Maximum length of a line, in bytes, which is much larger than the inputs of the [`crate::json`] module.
+pub const MAX_LINE_LEN: usize = 64 * 1024;
+
+/// Iterator over the inputs of the lines of a JSON-lines file, with the number of their line starting at `1`.
+pub struct Lines<R> {
+    reader: R,
+    line: usize,
+    buffer: Vec<u8>,
+}
+
+#[derive(Serialize)]
+struct LineResult<'a> {
+    line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dlog: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+}
+
+/// Number of the inputs solved, and of the lines which were malformed or could not be solved, by [`solve_lines`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub solved: u64,
+    pub failed: u64,
+}
+
+impl<R: BufRead> Lines<R> {
+    pub fn new(reader: R) -> Self {
+        Lines { reader, line: 0, buffer: Vec::new() }
+    }
+
+    /// Reads the next line of at most [`MAX_LINE_LEN`] bytes into the buffer, and discards the rest of the longer lines. Returns
+    /// whether the line was complete, or `None` at the end of the file.
+    fn read_line(&mut self) -> io::Result<Option<bool>> {
+        self.buffer.clear();
+        let read = Read::take(&mut self.reader, MAX_LINE_LEN as u64 + 1).read_until(b'\n', &mut self.buffer)?;
+        if read == 0 {
+            return Ok(None);
+        }
+        if self.buffer.len() <= MAX_LINE_LEN || self.buffer.last() == Some(&b'\n') {
+            return Ok(Some(true));
+        }
+        loop {
+            let available = self.reader.fill_buf()?;
+            match available.iter().position(|&b| b == b'\n') {
+                Some(end) => {
+                    self.reader.consume(end + 1);
+                    return Ok(Some(false));
+                }
+                None if available.is_empty() => return Ok(Some(false)),
+                None => {
+                    let len = available.len();
+                    self.reader.consume(len);
+                }
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Lines<R> {
+    type Item = (usize, Result<Input, Error>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line += 1;
+            let number = self.line;
+            let invalid = |reason: String| Error::InvalidJson(format!("line {} : {}", number, reason));
+            let complete = match self.read_line() {
+                Ok(None) => return None,
+                Ok(Some(complete)) => complete,
+                Err(e) => return Some((number, Err(invalid(e.to_string())))),
+            };
+            if !complete {
+                return Some((number, Err(invalid(format!("the line is longer than {} bytes", MAX_LINE_LEN)))));
+            }
+            let line = match std::str::from_utf8(&self.buffer) {
+                Ok(line) => line.trim(),
+                Err(_) => return Some((number, Err(invalid("the line is not valid UTF-8".to_string())))),
+            };
+            if !line.is_empty() {
+                let input = Input::parse(line).map_err(|e| match e {
+                    Error::InvalidJson(reason) => invalid(reason),
+                    e => e,
+                });
+                return Some((number, input));
+            }
+        }
+    }
+}
+
+/// Solves the inputs of the JSON-lines file `reader` one after the other with `solver`, and writes their results to `out` as they are
+/// found, see the [module documentation](self).
+pub fn solve_lines<R: BufRead, W: Write>(reader: R, mut out: W, solver: &Solver) -> io::Result<Summary> {
+    let mut summary = Summary::default();
+    for (line, input) in Lines::new(reader) {
+        let result = match input.and_then(|input| input.solve(solver)) {
+            Ok(dlog) => {
+                summary.solved += 1;
+                serde_json::to_string(&LineResult { line, dlog: Some(dlog), error: None })
+            }
+            Err(e) => {
+                summary.failed += 1;
+                serde_json::to_string(&LineResult { line, dlog: None, error: Some(&e.to_string()) })
+            }
+        };
+        writeln!(out, "{}", result.unwrap())?;
+    }
+    out.flush()?;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embed_plaintext;
+
+    #[test]
+    fn test_solve_lines() {
+        let (p, q) = (embed_plaintext(42), embed_plaintext(65545));
+        let input = format!(
+            "{{\"x\": \"{}\", \"y\": \"{}\"}}\n\n{{\"x\": \"0x01\"\n{}\n{{\"x\": \"{}\", \"y\": \"{}\"}}",
+            p.0, p.1, "a".repeat(MAX_LINE_LEN + 10), q.0, q.1
+        );
+        let mut out = Vec::new();
+        let summary = solve_lines(input.as_bytes(), &mut out, &Solver::new(20, 2)).unwrap();
+        assert_eq!(summary, Summary { solved: 2, failed: 2 });
+        let lines: Vec<serde_json::Value> = String::from_utf8(out).unwrap().lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(lines[0], serde_json::json!({"line": 1, "dlog": 42}));
+        assert_eq!(lines[1]["line"], 3);
+        assert!(lines[1]["error"].as_str().unwrap().starts_with("Invalid JSON input : line 3 : "));
+        assert!(lines[2]["error"].as_str().unwrap().contains("line 4 : the line is longer than 65536 bytes"));
+        assert_eq!(lines[3], serde_json::json!({"line": 5, "dlog": 65545}));
+    }
+}
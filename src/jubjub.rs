@@ -0,0 +1,90 @@
+//! Exponential ElGamal on Jubjub, the Twisted Edwards curve embedded in BLS12-381 used by Zcash Sapling, so that ElGamal schemes on
+//! BLS12-381 reuse the same Baby-step Giant-step search as Baby Jubjub instead of maintaining a fork with swapped curve types.
+//!
+//! Jubjub is the curve `-x^2 + y^2 = 1 + d·x^2y^2` over the scalar field of BLS12-381, with `d = -(10240/10241)`, like `ed_on_bls12_381`
+//! of arkworks. Its group has the order `8·r_J`, and the inputs are checked to be in its prime subgroup of order `r_J`. Points are encoded
+//! as their affine coordinates `(x, y)` in `0x`-prefixed hexadecimal strings, or packed in 32 bytes like the `repr_J` of the Zcash protocol
+//! with [`pack_point`], and scalars, i.e private keys and the encryption randomness, are elements of the base field taken modulo `r_J`,
+//! like the scalars of Baby Jubjub.
+
+use ark_ec::AffineCurve;
+use ark_ed_on_bls12_381::{EdwardsAffine as Jubjub, Fq, Fr};
+use ark_ff::PrimeField;
+use crate::dlog::{self, DlogCurve};
+use crate::point::field_to_hex;
+use crate::table::ArkworksCurve;
+use crate::{Error, HexPoint};
+
+/// Coordinates of the generator `G` of the prime subgroup of Jubjub used by arkworks.
+pub const GENERATOR: (&str, &str) = (
+    "0x11dafe5d23e1218086a365b99fbf3d3be72f6afd7d1f72623e6b071492d1122b",
+    "0x1d523cf1ddab1a1793132e78c866c0c33e26ba5cc220fed7cc3f870e59d292aa",
+);
+
+impl ArkworksCurve for Jubjub {
+    fn x_low_bits(&self) -> u64 {
+        self.x.into_repr().0[0]
+    }
+}
+
+impl DlogCurve for Jubjub {
+    type Scalar = Fr;
+
+    fn generator() -> Self {
+        Self::prime_subgroup_generator()
+    }
+
+    fn mul_scalar(&self, s: &Fr) -> <Self as AffineCurve>::Projective {
+        self.mul(*s)
+    }
+
+    fn parse_point(x: &str, y: &str) -> Result<Self, Error> {
+        dlog::edwards_point(x, y)
+    }
+
+    fn to_hex(&self) -> HexPoint {
+        (field_to_hex(&self.x), field_to_hex(&self.y))
+    }
+
+    fn parse_scalar(s: &str) -> Result<Fr, Error> {
+        dlog::reduced_scalar::<Fq, Fr>(s)
+    }
+}
+
+dlog::curve_module!(Jubjub, edwards);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ff::One;
+
+    #[test]
+    fn test_jubjub() {
+        let g = Jubjub::prime_subgroup_generator();
+        assert_eq!(g.to_hex(), (GENERATOR.0.to_string(), GENERATOR.1.to_string()));
+        assert_eq!(embed_plaintext(1).0, GENERATOR.0);
+
+        let private_key = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        let pk = priv_to_pub_key(private_key).unwrap();
+        let (c1, c2) = exp_elgamal_encrypt((&pk.0, &pk.1), 65535, "0x05").unwrap();
+        assert_eq!(exp_elgamal_decrypt(private_key, (&c1.0, &c1.1), (&c2.0, &c2.1)), Ok(embed_plaintext(65535)));
+        let solver = Solver::new(16, 2);
+        assert_eq!(decrypt_to_u64(private_key, (&c1.0, &c1.1), (&c2.0, &c2.1), &solver), Ok(65535));
+        for plaintext in [0, 1, 255, 256] {
+            let p = embed_plaintext(plaintext);
+            assert_eq!(solver.solve(&p.0, &p.1), Ok(plaintext));
+        }
+
+        for p in [&pk, &c1, &embed_plaintext(0)] {
+            assert_eq!(unpack_point(&pack_point(&p.0, &p.1).unwrap()).as_ref(), Ok(p));
+        }
+        // the identity with an odd x is not canonical
+        let mut identity = pack_point(&embed_plaintext(0).0, &embed_plaintext(0).1).unwrap();
+        identity[31] |= 0x80;
+        assert!(matches!(unpack_point(&identity), Err(Error::InvalidFormat(_))));
+        // (0, -1) is on the curve, of order 2
+        let minus_one = field_to_hex(&-Fq::one());
+        assert_eq!(Jubjub::parse_point("0x00", &minus_one), Err(Error::NotInSubgroup));
+        assert_eq!(Jubjub::parse_point("0x01", "0x01"), Err(Error::NotOnCurve));
+    }
+}
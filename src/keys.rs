@@ -0,0 +1,145 @@
+//! Generation of Baby Jubjub key pairs compatible with [noir-elgamal](https://github.com/jat9292/noir-elgamal).
+
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ed_on_bn254::Fr;
+use ark_ff::{PrimeField, UniformRand, Zero};
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+use sha2::{Digest, Sha512};
+use crate::point::{base_point, parse_point, parse_scalar, point_to_hex, field_to_hex, HexPoint};
+use crate::Error;
+
+/// Generates a new random private key, i.e a non-zero scalar smaller than the order of the prime subgroup of Baby Jubjub,
+/// as a `0x`-prefixed 32 bytes hexadecimal string.
+pub fn generate_private_key() -> String {
+    generate_private_key_with_rng(&mut OsRng)
+}
+
+/// Generates fresh randomness for [`crate::exp_elgamal_encrypt`], in the same format as [`generate_private_key`].
+pub fn generate_randomness() -> String {
+    generate_randomness_with_rng(&mut OsRng)
+}
+
+/// Same as [`generate_private_key`] with the random number generator `rng` instead of the one of the operating system,
+/// e.g a seeded generator to write reproducible tests and fixtures.
+pub fn generate_private_key_with_rng<R: RngCore + CryptoRng>(rng: &mut R) -> String {
+    field_to_hex(&random_scalar(rng))
+}
+
+/// Same as [`generate_randomness`] with the random number generator `rng`, see [`generate_private_key_with_rng`].
+pub fn generate_randomness_with_rng<R: RngCore + CryptoRng>(rng: &mut R) -> String {
+    field_to_hex(&random_scalar(rng))
+}
+
+pub(crate) fn random_scalar<R: RngCore + CryptoRng>(rng: &mut R) -> Fr {
+    loop {
+        let s = Fr::rand(rng);
+        if !s.is_zero() {
+            return s;
+        }
+    }
+}
+
+/// Deterministically derives a private key from a seed, as `SHA-512(seed)` reduced modulo the order of the prime subgroup of Baby Jubjub.
+pub fn private_key_from_seed(seed: &[u8]) -> String {
+    field_to_hex(&Fr::from_le_bytes_mod_order(&Sha512::digest(seed)))
+}
+
+/// Computes the public key `private_key·G` corresponding to `private_key`, exactly like the
+/// [`priv_to_pub_key`](https://github.com/jat9292/noir-elgamal/blob/v0.0.1/src/lib.nr) Noir function.
+///
+/// Like in Noir, the private key is a `Field` used as is, without any pruning or clamping of its bits.
+/// The coordinates of the returned point are in Twisted Edwards form, with the same format as the inputs of [`crate::do_compute_dlog`].
+pub fn priv_to_pub_key(private_key: &str) -> Result<(String, String), Error> {
+    let sk = parse_scalar(private_key)?;
+    Ok(point_to_hex(&base_point().mul(sk).into_affine()))
+}
+
+/// Derives the public key of `private_key`, i.e the same as [`priv_to_pub_key`], under the name used by the key management tools.
+pub fn derive_public_key(private_key: &str) -> Result<HexPoint, Error> {
+    priv_to_pub_key(private_key)
+}
+
+/// Checks that `public_key` is the public key of `private_key`, see [`priv_to_pub_key`], e.g to check that a public key provided
+/// by a client corresponds to a private key held by a backend.
+///
+/// The points are compared rather than their coordinates, so that differently formatted coordinates, such as `0x0bb7...` and `0xbb7...`, match.
+pub fn verify_key_pair(private_key: &str, public_key: (&str, &str)) -> Result<bool, Error> {
+    let sk = parse_scalar(private_key)?;
+    let pk = parse_point(public_key.0, public_key.1)?;
+    Ok(base_point().mul(sk).into_affine() == pk)
+}
+
+/// Computes the Diffie-Hellman shared secret `private_key·public_key` of a key pair and the public key of a peer, which is also the
+/// shared secret of the private key of the peer and the public key of the pair, so that keys used with noir-elgamal can also set up a channel.
+///
+/// The public key should be in the prime subgroup, which rules out the small subgroup attacks otherwise allowed by the cofactor of `8`
+/// of Baby Jubjub, and should not be the identity. The returned point should be hashed into a symmetric key, e.g its `x` coordinate with a KDF.
+pub fn shared_secret(private_key: &str, public_key: (&str, &str)) -> Result<HexPoint, Error> {
+    let sk = parse_scalar(private_key)?;
+    let pk = parse_point(public_key.0, public_key.1)?;
+    if pk.is_zero() {
+        return Err(Error::IdentityPublicKey);
+    }
+    Ok(point_to_hex(&pk.mul(sk).into_affine()))
+}
+
+/// Derives a symmetric key from the [`shared_secret`] of a key pair and the public key of a peer, e.g for hybrid encryption, as the Poseidon
+/// hash of the coordinates of the shared point, see [`crate::poseidon`], so that circuits can derive the same key from the same keys.
+pub fn derive_shared_key(private_key: &str, public_key: (&str, &str)) -> Result<String, Error> {
+    let (x, y) = shared_secret(private_key, public_key)?;
+    crate::poseidon::poseidon_hash(&[&x, &y])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_private_key_from_seed() {
+        let sk = private_key_from_seed(b"babygiant");
+        assert_eq!(sk, private_key_from_seed(b"babygiant"));
+        assert_ne!(sk, private_key_from_seed(b"babygiant2"));
+        assert_eq!(field_to_hex(&parse_scalar(&sk).unwrap()), sk);
+    }
+
+    #[test]
+    fn test_generate_with_rng() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let sk = generate_private_key_with_rng(&mut rng);
+        let randomness = generate_randomness_with_rng(&mut rng);
+        let mut replay = rand::rngs::StdRng::seed_from_u64(42);
+        assert_eq!((generate_private_key_with_rng(&mut replay), generate_randomness_with_rng(&mut replay)), (sk.clone(), randomness.clone()));
+        assert_ne!(sk, randomness);
+    }
+
+    #[test]
+    fn test_priv_to_pub_key() {
+        let sk = generate_private_key();
+        let (x, y) = priv_to_pub_key(&sk).unwrap();
+        assert!(crate::point::parse_point(&x, &y).is_ok());
+        assert_eq!(priv_to_pub_key("0x1").unwrap(), point_to_hex(&base_point()));
+
+        assert_eq!(verify_key_pair(&sk, (&x, &y)), Ok(true));
+        assert_eq!(derive_public_key(&sk), Ok((x.clone(), y.clone())));
+        assert_eq!(verify_key_pair("0x1", ("0xbb77a6ad63e739b4eacb2e09d6277c12ab8d8010534e0b62893f3f6bb957051", "0x25797203f7a0b24925572e1cd16bf9edfce0051fb9e133774b3c257a872d7d8b")), Ok(true));
+        assert_eq!(verify_key_pair("0x2", (&x, &y)), Ok(false));
+        assert!(verify_key_pair(&sk, ("0x1", "0x1")).is_err());
+    }
+
+    #[test]
+    fn test_shared_secret() {
+        let (alice, bob) = (generate_private_key(), generate_private_key());
+        let (alice_pk, bob_pk) = (priv_to_pub_key(&alice).unwrap(), priv_to_pub_key(&bob).unwrap());
+        let secret = shared_secret(&alice, (&bob_pk.0, &bob_pk.1)).unwrap();
+        assert_eq!(shared_secret(&bob, (&alice_pk.0, &alice_pk.1)), Ok(secret.clone()));
+        assert_eq!(shared_secret(&alice, ("0x0", "0x1")), Err(Error::IdentityPublicKey));
+        let key = derive_shared_key(&alice, (&bob_pk.0, &bob_pk.1)).unwrap();
+        assert_eq!(derive_shared_key(&bob, (&alice_pk.0, &alice_pk.1)), Ok(key.clone()));
+        assert_eq!(key, crate::poseidon::poseidon_hash(&[&secret.0, &secret.1]).unwrap());
+        // a point of order 2, outside of the prime subgroup
+        let p = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000";
+        assert_eq!(shared_secret(&alice, ("0x0", p)), Err(Error::NotInSubgroup));
+    }
+}
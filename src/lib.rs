@@ -14,108 +14,124 @@
 //! 2/ Another big difference is that the imported arkworks library uses the Edwards form instead of the Twisted Edwards form which is used in Noir for the Baby Jubjub curve, 
 //! so we did a coordinate transform to encode points in the Twisted Edwards form instead of the Edwards form, for using the same format as the Noir implementation. 
 
-use ark_ed_on_bn254::{EdwardsAffine as BabyJubJub, Fr, Fq, EdwardsParameters};
-use ark_ff::{BigInteger256, field_new, PrimeField, BigInteger, SquareRootField};
-use ark_ec::{AffineCurve, ProjectiveCurve};
-use ark_ec::twisted_edwards_extended::{GroupProjective, GroupAffine};
-use hex;
-use std::collections::HashMap;
-use std::sync::mpsc;
-use std::{thread, process};
-use regex::Regex;
+use std::process;
 
-fn baby_giant(max_bitwidth: u64, a: &GroupAffine<EdwardsParameters>, b: &GroupProjective<EdwardsParameters>, num_threads: u64) -> Option<u64> {
-    let m = 1u64 << (max_bitwidth / 2);
-    let chunk_size = m / num_threads;
-    let (tx, rx) = mpsc::channel();
+#[cfg(feature = "bindings")]
+pub mod abi;
+#[cfg(feature = "alloy")]
+pub mod alloy;
+#[cfg(feature = "audit")]
+pub mod audit;
+#[cfg(feature = "bandersnatch")]
+pub mod bandersnatch;
+#[cfg(feature = "bench")]
+pub mod bench;
+#[cfg(feature = "bn254")]
+pub mod bn254;
+#[cfg(feature = "cbor")]
+pub mod cbor;
+#[cfg(feature = "bindings")]
+pub mod circomlibjs;
+#[cfg(feature = "elgamal")]
+pub mod codec;
+#[cfg(feature = "elgamal")]
+pub mod columnar;
+#[cfg(feature = "config")]
+pub mod config;
+#[cfg(feature = "conformance")]
+pub mod conformance;
+#[cfg(feature = "elgamal")]
+pub mod container;
+#[cfg(feature = "csv")]
+pub mod csv;
+pub mod curve;
+#[cfg(feature = "custom")]
+pub mod custom;
+pub mod dlog;
+#[cfg(feature = "ed-on-bls12-377")]
+pub mod ed_on_bls12_377;
+#[cfg(feature = "proofs")]
+pub mod eddsa;
+#[cfg(feature = "tracing")]
+pub mod events;
+#[cfg(feature = "fixed")]
+pub mod fixed;
+#[cfg(feature = "elgamal")]
+mod elgamal;
+mod error;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "grumpkin")]
+pub mod grumpkin;
+#[cfg(feature = "elgamal")]
+pub mod hd;
+#[cfg(feature = "proofs")]
+pub mod hash_to_curve;
+#[cfg(feature = "json")]
+pub mod json;
+#[cfg(feature = "json")]
+pub mod jsonl;
+#[cfg(feature = "jubjub")]
+pub mod jubjub;
+#[cfg(feature = "elgamal")]
+mod keys;
+#[cfg(feature = "nargo")]
+pub mod nargo;
+pub mod output;
+#[cfg(feature = "elgamal")]
+pub mod packing;
+#[cfg(feature = "pasta")]
+pub mod pasta;
+#[cfg(feature = "proofs")]
+pub mod pedersen;
+mod point;
+#[cfg(feature = "elgamal")]
+pub mod poseidon;
+#[cfg(feature = "elgamal")]
+pub mod profile;
+#[cfg(feature = "proofs")]
+pub mod proof;
+#[cfg(feature = "protobuf")]
+pub mod protobuf;
+#[cfg(feature = "elgamal")]
+pub mod provider;
+#[cfg(feature = "ristretto")]
+pub mod ristretto;
+#[cfg(feature = "elgamal")]
+pub mod rotation;
+#[cfg(feature = "secp256k1")]
+pub mod secp256k1;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(feature = "elgamal")]
+pub mod signed;
+mod solver;
+pub mod table;
+#[cfg(feature = "proofs")]
+pub mod tally;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+#[cfg(feature = "proofs")]
+pub mod threshold;
+pub mod validation;
+#[cfg(feature = "elgamal")]
+pub mod vectors;
+#[cfg(feature = "watch")]
+pub mod watch;
+#[cfg(feature = "worker")]
+pub mod worker;
+#[cfg(feature = "zkay")]
+pub mod zkay;
 
-    for idx in 0..num_threads {
-        let a = a.clone();
-        let b = b.clone();
-        let tx = tx.clone();
-        thread::spawn(move || {
-            let start = idx * chunk_size;
-            let end = if idx == num_threads - 1 { m } else { start + chunk_size };
-            let mut table = HashMap::new();
-
-            // NOTE: equality and hashing (used for HashMap) does not perform as expected
-            // for projective representation (because coordinates are ambiguous), so switching
-            // to affine coordinates here
-            let mut v =  a.mul(Fr::new(BigInteger256::from(start))).into_affine();
-            let a1 = a.mul(Fr::new(BigInteger256::from(1))).into_affine();
-
-            for j in start..end { // baby_steps
-                table.insert(v, j);
-                v =  v + a1; // original zkay version was doing scalar multiplication inside the loop, we replaced it by constant increment, because addition is faster than scalar multiplication on the elliptic curve
-            }
-            let am = a.mul(Fr::new(BigInteger256::from(m)));
-            let mut gamma = b.clone();
-
-            for i in 0..m { // giant_steps
-                if let Some(j) = table.get(&gamma.into_affine()) {
-                    tx.send(Some(i * m + j)).unwrap();
-                    return;
-                }
-                gamma = gamma - &am;
-                
-            }
-            let _ = tx.send(None);
-            
-        });
-    }
-
-    let mut result = None;
-    for _ in 0..num_threads {
-        if let Some(res) = rx.recv().unwrap() {
-            result = Some(res);
-            break;
-        }
-    }
-    result
-}
-
-fn parse_be_bytes_str(s: &str) -> BigInteger256 {
-    let s = s.trim_start_matches("0x");
-    let le_str = reverse_byte_order(s);
-    parse_le_bytes_str(&le_str)
-}
-
-fn reverse_byte_order(s: &str) -> String {
-    s.as_bytes()
-        .chunks_exact(2)
-        .rev()
-        .map(|chunk| std::str::from_utf8(chunk).unwrap())
-        .collect()
-}
-
-fn parse_le_bytes_str(s: &str) -> BigInteger256 {
-    let mut buffer = [0u8; 32];     // 32 bytes for 256 bits
-
-    let v = hex::decode(s).unwrap();
-    assert_eq!(v.len(), 32);
-    let v = v.as_slice();
-    for i in 0..32 {
-        buffer[i] = v[i];
-    }
-
-    let mut bi = BigInteger256::new([0; 4]);
-    bi.read_le(&mut buffer.as_ref()).unwrap();
-    return bi;
-}
-
-fn pad_with_zeros(input: &str) -> String {
-    if input.len() < 66 && input.starts_with("0x") {
-        let padding_needed = 66 - input.len();
-        format!("0x{}{}", "0".repeat(padding_needed), &input[2..])
-    } else {
-        input.to_string()
-    }
-}
-
-fn is_valid_format(input: &str) -> bool {
-    let re = Regex::new(r"^0x[a-fA-F0-9]{64}$").unwrap();
-    re.is_match(input)
-}
+pub use dlog::{Curve, SolveConfig};
+#[cfg(feature = "elgamal")]
+pub use elgamal::{assert_encryptable, decrypt_to_u64, exp_elgamal_decrypt, exp_elgamal_encrypt, Ciphertext, Plaintext};
+pub use error::Error;
+#[cfg(feature = "elgamal")]
+pub use keys::{derive_public_key, derive_shared_key, generate_private_key, generate_private_key_with_rng, generate_randomness, generate_randomness_with_rng, private_key_from_seed, priv_to_pub_key, shared_secret, verify_key_pair};
+pub use point::{base64_to_hex, embed_plaintext, format_noir_point, noir_field_to_hex, pack_point, unpack_point, HexPoint, ParseMode};
+pub use solver::{Checkpoint, MemoryUsage, Search, SolveStats, Solver, WorkerProgress};
+pub use validation::{validate_embedding, ValidationReport};
 
 /// This function will compute the Discrete Logarithm of a point on the Baby Jubjub curve, in Twisted Edwards form.
 /// 
@@ -136,7 +152,7 @@ fn is_valid_format(input: &str) -> bool {
 /// 
 /// For example, this circuit should output the plaintext value `42` embedded as a point on the Baby Jubjub curve:
 /// 
-/// ```rust
+/// ```noir
 /// use dep::elgamal::{priv_to_pub_key,exp_elgamal_encrypt,exp_elgamal_decrypt};
 /// use dep::std;
 /// fn main(){
@@ -152,13 +168,13 @@ fn is_valid_format(input: &str) -> bool {
 /// 
 /// Indeed, running `nargo execute` should return the following point in a terminal: 
 /// 
-/// ```
+/// ```text
 /// Point { x: 0x06184da392a17823e9c1d38cb50980b17150ffa411965b03f0b0200d9557daa9, y: 0x244a710118db92636e46e3f97bd80093ba7026ff97ca32d387145337e250549c }
 /// ```
 /// 
 /// For the last step of decryption, i.e to recover the original plaintext (as an unsigned integer of size 40 bits) from the previous embedded form, you can import this crate in a Rust project by adding the following dependency in `Cargo.toml` : 
 /// 
-/// ```
+/// ```toml
 /// [dependencies]
 /// babygiant-alt-bn128 = "0.1.0"
 /// ```
@@ -176,43 +192,74 @@ fn is_valid_format(input: &str) -> bool {
 /// ```
 /// 
 /// You can check that the baby-step giant-step algorithm is indeed able to recover the original plaintext value `42` by running : 
-/// ```
+/// ```sh
 /// cargo run --release
 /// ```
 /// 
 /// The Rust program should run successfully in less than 2 seconds on a modern computer.
 pub fn do_compute_dlog(x: &str, y: &str, num_threads: u64) -> u64 {
-    let padded_x = pad_with_zeros(&x);
-    let padded_y = pad_with_zeros(&y);
-    
-    if !is_valid_format(&padded_x) || !is_valid_format(&padded_y)  {
-        eprintln!(r#"Invalid input format : x and y should be hexadecimal strings representing two bytes of size 32 at most. 
-Also make sure the coordinates x and y are points on the Baby Jubjub curve (Twisted Edwards form) and follow the same format as returned by the exp_elgamal_decrypt function in the noir-elgamal package).
-Eg of valid inputs: x="0xbb77a6ad63e739b4eacb2e09d6277c12ab8d8010534e0b62893f3f6bb957051" and y="0x25797203f7a0b24925572e1cd16bf9edfce0051fb9e133774b3c257a872d7d8b".
-Also please keep in mind that the embedded plaintext corresponding to the (x,y) point should not exceed type(uint40).max, i.e 1099511627775 or else the program will not find a valid discrete logarithm and panic."#);
-        process::exit(1);
+    match compute_dlog(x, y, num_threads) {
+        Ok(dlog) => dlog,
+        Err(e @ (Error::InvalidFormat(_) | Error::InvalidHex { .. } | Error::NotInField { .. } | Error::InvalidCoordinate { .. })) => {
+            eprintln!(r#"{}
+x and y should be hexadecimal strings of 32 bytes at most, the coordinates of a point of Baby Jubjub (Twisted Edwards form) in the same format as returned by the exp_elgamal_decrypt function of the noir-elgamal package.
+Eg of valid inputs: x="0xbb77a6ad63e739b4eacb2e09d6277c12ab8d8010534e0b62893f3f6bb957051" and y="0x25797203f7a0b24925572e1cd16bf9edfce0051fb9e133774b3c257a872d7d8b"."#, e);
+            process::exit(1);
+        }
+        Err(e) => panic!("{}", e),
     }
+}
 
-    let coeff_twisted = field_new!(Fq,"168700").sqrt().unwrap(); // this coeff_twisted was introduced to transform the coordinates of baby Jubjub points from the Twisted Edwards form coming from Noir, to the Edwards form compatible with arkworks
-    let gx = field_new!(Fq, "5299619240641551281634865583518297030282874472190772894086521144482721001553")*coeff_twisted;
-    let gy = field_new!(Fq, "16950150798460657717958625567821834550301663161624707787222815936182638968203");
-    let a = BabyJubJub::new(gx, gy); // the base point of the twisted Edwards form of Baby Jubjub : https://eips.ethereum.org/EIPS/eip-2494#forms-of-the-curve
-    assert!(BabyJubJub::is_on_curve(&a), "(x,y) is not a valid point on Baby Jubjub curve in Twisted Edwards form");
-    assert!(BabyJubJub::is_in_correct_subgroup_assuming_on_curve(&a), "(x,y) is not a valid point in the prime subgroup of Baby Jubjub curve in Twisted Edwards form");
-    let bx = Fq::from_repr(parse_be_bytes_str(&padded_x)).unwrap()*coeff_twisted;
-    let by = Fq::from_repr(parse_be_bytes_str(&padded_y)).unwrap();
-    let b = BabyJubJub::new(bx, by);
-    assert!(BabyJubJub::is_on_curve(&b), "(x,y) is not a valid point on Baby Jubjub curve in Twisted Edwards form");
-    assert!(BabyJubJub::is_in_correct_subgroup_assuming_on_curve(&b), "(x,y) is not a valid point in the prime subgroup of Baby Jubjub curve in Twisted Edwards form");
-    let b = b.mul(Fr::new(BigInteger256::from(1)));
+/// Fallible version of [`do_compute_dlog`], returning an [`Error`] instead of exiting the process or panicking on invalid inputs,
+/// or when the embedded plaintext is not a `u40`.
+pub fn compute_dlog(x: &str, y: &str, num_threads: u64) -> Result<u64, Error> {
+    let b = point::parse_point(x, y)?;
+    Solver::new(40, num_threads).search_point(b).run()
+}
 
-    baby_giant(40, &a, &b, num_threads).expect("The Baby-step Giant-step algorithm was unable to solve the Discrete Logarithm. Make sure that the embedded plaintext is an unsigned integer between 0 and 1099511627775.")
+/// Same as [`compute_dlog`] for the embedded plaintext `{"x", "y"}` or the ciphertext `{"private_key", "c1x", "c1y", "c2x", "c2y"}` of the
+/// JSON object `json`, e.g the payload of a webhook, see the [`json`] module.
+#[cfg(feature = "json")]
+pub fn compute_dlog_from_json(json: &str, num_threads: u64) -> Result<u64, Error> {
+    let input = json::Input::parse(json)?;
+    input.solve(&Solver::new(40, num_threads))
+}
+
+/// Same as [`compute_dlog`] on the curve `curve` chosen at runtime, with the point `(x, y)` in the encoding of the module of the curve,
+/// e.g [`grumpkin`], and the parameters of the search in `config`.
+pub fn compute_dlog_on(curve: Curve, x: &str, y: &str, config: &SolveConfig) -> Result<u64, Error> {
+    curve.solve(x, y, config)
+}
+
+/// Checks that `(x, y)` is a valid point of the prime subgroup of Baby Jubjub in Twisted Edwards form, i.e a valid input for
+/// [`compute_dlog`], without solving its Discrete Logarithm. [`validate_embedding`] reports the outcome of each of its checks.
+pub fn validate_point(x: &str, y: &str) -> Result<(), Error> {
+    point::parse_point(x, y).map(|_| ())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_compute_dlog_on() {
+        let config = SolveConfig::new(20, 2);
+        let p = embed_plaintext(65545);
+        assert_eq!(compute_dlog_on(Curve::BabyJubJub, &p.0, &p.1, &config), Ok(65545));
+        assert_eq!(compute_dlog_on(Curve::BabyJubJub, &p.0, &p.1, &SolveConfig::new(16, 2)), Err(Error::DlogNotFound { max_bitwidth: 16 }));
+        #[cfg(feature = "grumpkin")]
+        {
+            let q = grumpkin::embed_plaintext(65545);
+            assert_eq!(compute_dlog_on(Curve::Grumpkin, &q.0, &q.1, &config), Ok(65545));
+            // the points of a curve are not points of the other ones
+            assert_eq!(compute_dlog_on(Curve::BabyJubJub, &q.0, &q.1, &config), Err(Error::NotOnCurve));
+        }
+        for curve in Curve::all() {
+            assert_eq!(curve.to_string().parse(), Ok(curve));
+        }
+        assert_eq!("ed25519".parse::<Curve>(), Err(Error::UnknownCurve("ed25519".to_string())));
+    }
+
     #[test]
     fn test_compute_dlog1() {
         let dlog = do_compute_dlog("0x05e712cbd0bee349ab612d42b81672d48546ab29a90798ad2b88f64585f0c805",
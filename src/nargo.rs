@@ -0,0 +1,153 @@
+//! Extraction of the points written by nargo runs of noir-elgamal programs, so that their Discrete Logarithm can be computed
+//! without copying their coordinates by hand between the two tools.
+//!
+//! Points are read either from a `Prover.toml` or `Verifier.toml` file, where a Noir struct such as `Point { x, y }` is a table
+//! with `x` and `y` keys, or from the output of `nargo execute`, which prints the value returned by the circuit and the arguments of `std::println`.
+//!
+//! Ciphertexts are tables with the points `c1` and `c2`, and [`point_to_toml`] and [`ciphertext_to_toml`] write points and ciphertexts
+//! in the same format, so that they are round-tripped between the Noir tooling and this crate without manual conversion.
+
+use toml::{Table, Value};
+use crate::point::HexPoint;
+use crate::{noir_field_to_hex, Error};
+
+/// Name of the value returned by the circuit in the `Verifier.toml` files written by nargo.
+pub const RETURN_FIELD: &str = "return";
+
+/// Reads the point `field` of the TOML file `contents`, e.g `decrypted_point` for the following `Prover.toml` :
+///
+/// ```toml
+/// [decrypted_point]
+/// x = "0x06184da392a17823e9c1d38cb50980b17150ffa411965b03f0b0200d9557daa9"
+/// y = "0x244a710118db92636e46e3f97bd80093ba7026ff97ca32d387145337e250549c"
+/// ```
+///
+/// Points nested in other structs are named by their path, such as `ciphertext.c2`, and the empty name is the top level of the file,
+/// e.g for a fragment `x = "0x..."` and `y = "0x..."`.
+pub fn point_from_toml(contents: &str, field: &str) -> Result<HexPoint, Error> {
+    point_at(&parse_toml(contents)?, field)
+}
+
+/// Reads the ciphertext `field` of the TOML file `contents`, i.e a table with the points `c1` and `c2`, such as the ciphertexts written
+/// by `babygiant encrypt` at the top level of the file, or the following `ciphertext` :
+///
+/// ```toml
+/// [ciphertext.c1]
+/// x = "0x..."
+/// y = "0x..."
+///
+/// [ciphertext.c2]
+/// x = "0x..."
+/// y = "0x..."
+/// ```
+pub fn ciphertext_from_toml(contents: &str, field: &str) -> Result<(HexPoint, HexPoint), Error> {
+    let root = parse_toml(contents)?;
+    let path = |name: &str| if field.is_empty() { name.to_string() } else { format!("{}.{}", field, name) };
+    Ok((point_at(&root, &path("c1"))?, point_at(&root, &path("c2"))?))
+}
+
+/// Writes the point `(x, y)` as the table `field` of a TOML file, which is read back by [`point_from_toml`], e.g to write the inputs
+/// of a `Prover.toml`.
+pub fn point_to_toml(field: &str, point: &HexPoint) -> String {
+    crate::output::toml_point(field, point)
+}
+
+/// Writes the ciphertext `(C1, C2)` as the table `field` of a TOML file, which is read back by [`ciphertext_from_toml`].
+pub fn ciphertext_to_toml(field: &str, c1: &HexPoint, c2: &HexPoint) -> String {
+    let path = |name: &str| if field.is_empty() { name.to_string() } else { format!("{}.{}", field, name) };
+    format!("{}\n{}", point_to_toml(&path("c1"), c1), point_to_toml(&path("c2"), c2))
+}
+
+fn parse_toml(contents: &str) -> Result<Value, Error> {
+    let table: Table = contents.parse().map_err(|e: toml::de::Error| Error::InvalidArtifact(e.message().to_string()))?;
+    Ok(Value::Table(table))
+}
+
+/// Reads the point at the path `field` of `root`, whose coordinates are nonnegative integers or strings read like Noir does, see
+/// [`noir_field_to_hex`].
+fn point_at(root: &Value, field: &str) -> Result<HexPoint, Error> {
+    let mut point = Some(root);
+    for key in field.split('.').filter(|key| !key.is_empty()) {
+        point = point.and_then(|value| value.get(key));
+    }
+    let point = point.ok_or_else(|| Error::InvalidArtifact(format!("no field named {:?}", field)))?;
+    let coordinate = |name: &str| match point.get(name) {
+        Some(Value::String(s)) => noir_field_to_hex(s),
+        Some(Value::Integer(i)) if *i >= 0 => Ok(format!("{:#x}", i)),
+        Some(_) => Err(Error::InvalidArtifact(format!("{}.{} should be an hexadecimal string", field, name))),
+        None => Err(Error::InvalidArtifact(format!("{} should be a point with x and y fields", field))),
+    };
+    Ok((coordinate("x")?, coordinate("y")?))
+}
+
+/// Reads the last point printed in the output of `nargo execute`, such as `Circuit output: Point { x: 0x06..., y: 0x24... }`.
+pub fn point_from_output(output: &str) -> Result<HexPoint, Error> {
+    output
+        .match_indices("x: ")
+        .filter(|(i, _)| !output[..*i].ends_with(|c: char| c.is_alphanumeric() || c == '_'))
+        .filter_map(|(i, _)| {
+            let (x, rest) = hex_prefix(&output[i + 3..])?;
+            let (y, _) = hex_prefix(rest.strip_prefix(", y: ")?)?;
+            Some((x.to_string(), y.to_string()))
+        })
+        .last()
+        .ok_or_else(|| Error::InvalidArtifact("no point was printed by nargo".to_string()))
+}
+
+/// Splits the `0x`-prefixed hexadecimal integer at the start of `s` from the rest of `s`.
+fn hex_prefix(s: &str) -> Option<(&str, &str)> {
+    let digits = s.strip_prefix("0x")?;
+    let len = digits.find(|c: char| !c.is_ascii_hexdigit()).unwrap_or(digits.len());
+    (len > 0).then(|| s.split_at(len + 2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const X: &str = "0x06184da392a17823e9c1d38cb50980b17150ffa411965b03f0b0200d9557daa9";
+    const Y: &str = "0x244a710118db92636e46e3f97bd80093ba7026ff97ca32d387145337e250549c";
+
+    #[test]
+    fn test_point_from_toml() {
+        let expected = (X.to_string(), Y.to_string());
+        let prover = format!("private_key = \"0x1\"\n\n[decrypted_point]\nx = \"{}\"\ny = \"{}\"\n", X, Y);
+        assert_eq!(point_from_toml(&prover, "decrypted_point"), Ok(expected.clone()));
+        let verifier = format!("return = {{ x = \"{}\", y = \"{}\" }}\n", X, Y);
+        assert_eq!(point_from_toml(&verifier, RETURN_FIELD), Ok(expected.clone()));
+        let nested = format!("[ciphertext.c2]\nx = \"{}\"\ny = \"{}\"\n", X, Y);
+        assert_eq!(point_from_toml(&nested, "ciphertext.c2"), Ok(expected));
+        assert_eq!(point_from_toml("[p]\nx = 0\ny = 1\n", "p"), Ok(("0x0".to_string(), "0x1".to_string())));
+        // Noir reads the strings without the 0x prefix as decimal integers
+        let one = format!("0x{:0>64}", 1);
+        assert_eq!(point_from_toml("[p]\nx = \"0\"\ny = \"1\"\n", "p"), Ok((format!("0x{:0>64}", 0), one.clone())));
+        assert_eq!(point_from_toml("[p]\nx = \"0x0\"\ny = \"10\"\n", "p").map(|p| p.1), Ok(format!("0x{:0>64}", "a")));
+
+        assert!(matches!(point_from_toml(&prover, "return"), Err(Error::InvalidArtifact(_))));
+        assert!(matches!(point_from_toml(&prover, "private_key"), Err(Error::InvalidArtifact(_))));
+        assert!(matches!(point_from_toml("[p]\nx = 1.5\ny = 1\n", "p"), Err(Error::InvalidArtifact(_))));
+        assert!(matches!(point_from_toml("not toml", "p"), Err(Error::InvalidArtifact(_))));
+    }
+
+    #[test]
+    fn test_toml_roundtrip() {
+        let p = (X.to_string(), Y.to_string());
+        let q = (Y.to_string(), X.to_string());
+        assert_eq!(point_from_toml(&format!("x = \"{}\"\ny = \"{}\"\n", X, Y), ""), Ok(p.clone()));
+        for field in ["", "decrypted_point", "inputs.point"] {
+            assert_eq!(point_from_toml(&point_to_toml(field, &p), field), Ok(p.clone()), "{:?}", field);
+            assert_eq!(ciphertext_from_toml(&ciphertext_to_toml(field, &p, &q), field), Ok((p.clone(), q.clone())), "{:?}", field);
+        }
+        // the ciphertexts written by babygiant encrypt
+        let encrypted = format!("randomness = \"0x05\"\n\n[c1]\nx = \"{}\"\ny = \"{}\"\n\n[c2]\nx = \"{}\"\ny = \"{}\"", X, Y, Y, X);
+        assert_eq!(ciphertext_from_toml(&encrypted, ""), Ok((p.clone(), q)));
+        assert!(matches!(ciphertext_from_toml(&point_to_toml("c1", &p), ""), Err(Error::InvalidArtifact(_))));
+    }
+
+    #[test]
+    fn test_point_from_output() {
+        let output = format!("Point {{ x: 0x01, y: 0x02 }}\n[elgamal] Circuit witness successfully solved\n[elgamal] Circuit output: Point {{ x: {}, y: {} }}\n", X, Y);
+        assert_eq!(point_from_output(&output), Ok((X.to_string(), Y.to_string())));
+        assert!(matches!(point_from_output("[elgamal] Circuit witness successfully solved"), Err(Error::InvalidArtifact(_))));
+    }
+}
@@ -0,0 +1,77 @@
+//! Formatting of the Discrete Logarithms and the points recovered by this crate into the formats expected by the next tools of a
+//! pipeline, so that they are passed along without custom formatting code :
+//!
+//! - [`to_hex`] : the `0x`-prefixed hexadecimal string of the 32 bytes of a `Field`, like Noir prints it and Solidity encodes it,
+//! - [`to_decimal`] : the decimal integer of a `Field`, like circomlib and snarkjs print it,
+//! - [`toml_value`] and [`toml_point`] : the lines of a `Prover.toml` file giving a value or a `Point { x, y }` to an argument of a Noir
+//!   circuit.
+//!
+//! The coordinates are read in any format displayed by Noir, see [`crate::noir_field_to_hex`], and a Discrete Logarithm is formatted
+//! like the `Field` of the same value, e.g with [`dlog_to_hex`].
+
+use crate::point::{field_to_decimal, parse_field};
+use crate::{noir_field_to_hex, Error, HexPoint};
+
+/// Formats the `Field` `field` as the `0x`-prefixed hexadecimal string of its 32 bytes, e.g `"42"` as `"0x00...2a"`.
+pub fn to_hex(field: &str) -> Result<String, Error> {
+    noir_field_to_hex(field)
+}
+
+/// Formats the `Field` `field` as a decimal integer, e.g `"0x2a"` as `"42"`.
+pub fn to_decimal(field: &str) -> Result<String, Error> {
+    Ok(field_to_decimal(&parse_field(&noir_field_to_hex(field)?)?))
+}
+
+/// Formats the coordinates of `point` with [`to_hex`].
+pub fn point_to_hex(point: &HexPoint) -> Result<HexPoint, Error> {
+    Ok((to_hex(&point.0)?, to_hex(&point.1)?))
+}
+
+/// Formats the coordinates of `point` with [`to_decimal`].
+pub fn point_to_decimal(point: &HexPoint) -> Result<HexPoint, Error> {
+    Ok((to_decimal(&point.0)?, to_decimal(&point.1)?))
+}
+
+/// Formats the Discrete Logarithm `dlog` as the `0x`-prefixed hexadecimal string of the 32 bytes of the `Field` of the same value.
+pub fn dlog_to_hex(dlog: u64) -> String {
+    format!("0x{:064x}", dlog)
+}
+
+/// Writes the line of a TOML file giving `value`, e.g a Discrete Logarithm or a coordinate, to the argument `name` of a Noir circuit,
+/// such as `amount = "42"`.
+pub fn toml_value(name: &str, value: &str) -> String {
+    format!("{} = \"{}\"\n", name, value)
+}
+
+/// Writes the point `(x, y)` as the table `field` of a TOML file, e.g the argument `decrypted_point` of a Noir circuit, or as the `x`
+/// and `y` keys at the top level of the file when `field` is empty.
+pub fn toml_point(field: &str, point: &HexPoint) -> String {
+    match field {
+        "" => format!("{}{}", toml_value("x", &point.0), toml_value("y", &point.1)),
+        _ => format!("[{}]\n{}{}", field, toml_value("x", &point.0), toml_value("y", &point.1)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embed_plaintext;
+
+    #[test]
+    fn test_output() {
+        assert_eq!(to_hex("0x2a"), Ok(format!("0x{:0>64}", "2a")));
+        assert_eq!(to_decimal("0x2a"), Ok("42".to_string()));
+        assert_eq!(to_decimal("0"), Ok("0".to_string()));
+        assert_eq!(dlog_to_hex(42), to_hex("42").unwrap());
+        let max = "21888242871839275222246405745257275088548364400416034343698204186575808495616";
+        assert_eq!(to_decimal(&to_hex(max).unwrap()), Ok(max.to_string()));
+
+        let p = embed_plaintext(1);
+        let decimal = point_to_decimal(&p).unwrap();
+        assert_eq!(decimal.0, "5299619240641551281634865583518297030282874472190772894086521144482721001553");
+        assert_eq!(point_to_hex(&decimal), Ok(p.clone()));
+        assert_eq!(toml_value("amount", "42"), "amount = \"42\"\n");
+        assert_eq!(toml_point("decrypted_point", &p), format!("[decrypted_point]\nx = \"{}\"\ny = \"{}\"\n", p.0, p.1));
+        assert!(matches!(to_decimal("0xzz"), Err(Error::InvalidHex { .. })));
+    }
+}
@@ -0,0 +1,119 @@
+//! Packing of several small counters into a single plaintext, so that one ciphertext carries e.g 4 vote counters and only one
+//! Discrete Logarithm has to be found to recover them all.
+//!
+//! The counters are the limbs of the plaintext in base `2^b` : the slot `i` of a [`Packing`] of `b` bits per slot holds the bits
+//! `[i·b, (i+1)·b)` of the plaintext, so that adding packed plaintexts, e.g homomorphically, adds their counters slot by slot.
+//! A slot which reaches `2^b` carries into the next one and corrupts it : [`Packing::capacity`] bounds the number of packed plaintexts
+//! that can be added without overflow, and [`Packing::decode`] detects the overflow of the last slot.
+
+use crate::Error;
+
+/// Layout of `slots` counters of `slot_bitwidth` bits each in a plaintext of `slots · slot_bitwidth` bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Packing {
+    slots: u64,
+    slot_bitwidth: u64,
+}
+
+impl Packing {
+    /// Layout of `slots` counters of `slot_bitwidth` bits, which should fit together in a `u64`.
+    pub fn new(slots: u64, slot_bitwidth: u64) -> Result<Self, Error> {
+        if slots == 0 || slot_bitwidth == 0 || slots.checked_mul(slot_bitwidth).map_or(true, |bits| bits > 64) {
+            return Err(Error::InvalidPacking(format!("{} slots of {} bits do not fit in 64 bits", slots, slot_bitwidth)));
+        }
+        Ok(Packing { slots, slot_bitwidth })
+    }
+
+    pub fn slots(&self) -> u64 {
+        self.slots
+    }
+
+    pub fn slot_bitwidth(&self) -> u64 {
+        self.slot_bitwidth
+    }
+
+    /// Bitwidth of the packed plaintexts, i.e the `max_bitwidth` of the [`crate::Solver`] recovering them.
+    pub fn max_bitwidth(&self) -> u64 {
+        self.slots * self.slot_bitwidth
+    }
+
+    /// Number of packed plaintexts whose counters are at most `max_counter` that can be added without any slot overflowing.
+    pub fn capacity(&self, max_counter: u64) -> u64 {
+        self.max_counter().checked_div(max_counter).unwrap_or(u64::MAX)
+    }
+
+    /// Packs `counters`, one per slot, each of them being an integer of at most [`Packing::slot_bitwidth`] bits.
+    pub fn encode(&self, counters: &[u64]) -> Result<u64, Error> {
+        if counters.len() as u64 != self.slots {
+            return Err(Error::InvalidPacking(format!("{} counters for {} slots", counters.len(), self.slots)));
+        }
+        counters.iter().enumerate().try_fold(0, |plaintext, (slot, counter)| {
+            if *counter > self.max_counter() {
+                return Err(Error::PlaintextOutOfRange { plaintext: *counter, max_bitwidth: self.slot_bitwidth });
+            }
+            Ok(plaintext | counter << (slot as u64 * self.slot_bitwidth))
+        })
+    }
+
+    /// Plaintext with the counter `1` in `slot` and `0` in the others, e.g to encrypt a vote for the option `slot`.
+    pub fn unit(&self, slot: u64) -> Result<u64, Error> {
+        if slot >= self.slots {
+            return Err(Error::InvalidPacking(format!("there is no slot {} among {} slots", slot, self.slots)));
+        }
+        Ok(1 << (slot * self.slot_bitwidth))
+    }
+
+    /// Unpacks the counters of `plaintext`, e.g recovered by [`crate::decrypt_to_u64`] from a sum of packed plaintexts.
+    ///
+    /// Returns an error if the last slot overflowed, i.e `plaintext` has more than [`Packing::max_bitwidth`] bits. The overflow of the other
+    /// slots cannot be detected as it carries into the next slot, and should be ruled out with [`Packing::capacity`].
+    pub fn decode(&self, plaintext: u64) -> Result<Vec<u64>, Error> {
+        if self.max_bitwidth() < 64 && plaintext >> self.max_bitwidth() != 0 {
+            return Err(Error::InvalidPacking(format!("{} overflows {} slots of {} bits", plaintext, self.slots, self.slot_bitwidth)));
+        }
+        Ok((0..self.slots).map(|slot| plaintext >> (slot * self.slot_bitwidth) & self.max_counter()).collect())
+    }
+
+    fn max_counter(&self) -> u64 {
+        u64::MAX >> (64 - self.slot_bitwidth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decrypt_to_u64, exp_elgamal_encrypt, priv_to_pub_key, Ciphertext, Solver};
+
+    #[test]
+    fn test_packing() {
+        let packing = Packing::new(4, 10).unwrap();
+        assert_eq!(packing.max_bitwidth(), 40);
+        assert_eq!(packing.encode(&[1, 2, 3, 1023]), Ok(1 | 2 << 10 | 3 << 20 | 1023 << 30));
+        assert_eq!(packing.decode(packing.encode(&[1, 2, 3, 1023]).unwrap()), Ok(vec![1, 2, 3, 1023]));
+        assert_eq!(packing.unit(2), packing.encode(&[0, 0, 1, 0]));
+        assert_eq!(packing.capacity(1), 1023);
+        assert_eq!(packing.capacity(0), u64::MAX);
+        assert_eq!(packing.encode(&[0, 1024, 0, 0]), Err(Error::PlaintextOutOfRange { plaintext: 1024, max_bitwidth: 10 }));
+        assert!(matches!(packing.encode(&[0; 3]), Err(Error::InvalidPacking(_))));
+        assert!(matches!(packing.unit(4), Err(Error::InvalidPacking(_))));
+        assert!(matches!(packing.decode(1 << 40), Err(Error::InvalidPacking(_))));
+        assert_eq!(Packing::new(1, 64).unwrap().decode(u64::MAX), Ok(vec![u64::MAX]));
+        for (slots, bits) in [(0, 8), (8, 0), (5, 13), (u64::MAX, 2)] {
+            assert!(matches!(Packing::new(slots, bits), Err(Error::InvalidPacking(_))));
+        }
+    }
+
+    #[test]
+    fn test_packed_votes() {
+        let sk = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        let pk = priv_to_pub_key(sk).unwrap();
+        let packing = Packing::new(4, 4).unwrap();
+        let ballots: Ciphertext = [0, 3, 3, 1, 3, 2]
+            .iter()
+            .zip(2u64..)
+            .map(|(choice, r)| exp_elgamal_encrypt((&pk.0, &pk.1), packing.unit(*choice).unwrap(), &format!("{:#x}", r)).unwrap().try_into().unwrap())
+            .sum();
+        let plaintext = decrypt_to_u64(sk, &ballots, &Solver::new(packing.max_bitwidth(), 1)).unwrap();
+        assert_eq!(packing.decode(plaintext), Ok(vec![1, 1, 1, 3]));
+    }
+}
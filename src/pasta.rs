@@ -0,0 +1,246 @@
+//! Exponential ElGamal on the Pasta curves Pallas and Vesta, the cycle of curves of Mina and Halo 2, so that the projects of these
+//! ecosystems encrypting with exponential ElGamal decrypt with the same Baby-step Giant-step search as Baby Jubjub instead of porting it.
+//!
+//! Pallas and Vesta are the short Weierstrass curves `y^2 = x^3 + 5` over the fields `F_p` and `F_q` respectively, and the order of each
+//! of them is the modulus of the field of the other one, so that every point of a curve is in its prime subgroup. The [`pallas`] and
+//! [`vesta`] modules provide the same functions for each curve, with the generator `G = (-1, 2)` of `pasta_curves`. Points are encoded
+//! as their affine coordinates `(x, y)` in `0x`-prefixed hexadecimal strings, the point at infinity being `(0, 0)`, and scalars, i.e
+//! private keys and the encryption randomness, are integers smaller than the order of the curve.
+
+use ark_ec::models::short_weierstrass_jacobian::GroupAffine;
+use ark_ec::models::{ModelParameters, SWModelParameters};
+use ark_ff::biginteger::BigInteger256 as BigInteger;
+use ark_ff::fields::{FftParameters, Fp256, Fp256Parameters, FpParameters};
+use ark_ff::{field_new, Zero};
+
+/// Modulus `p` of the base field of Pallas, which is the order of Vesta.
+pub const P: &str = "0x40000000000000000000000000000000224698fc094cf91b992d30ed00000001";
+/// Modulus `q` of the base field of Vesta, which is the order of Pallas.
+pub const Q: &str = "0x40000000000000000000000000000000224698fc0994a8dd8c46eb2100000001";
+
+/// Base field of Pallas and scalar field of Vesta.
+pub(crate) type Fp = Fp256<FpParams>;
+/// Base field of Vesta and scalar field of Pallas.
+pub(crate) type Fq = Fp256<FqParams>;
+
+pub(crate) struct FpParams;
+
+impl Fp256Parameters for FpParams {}
+
+impl FftParameters for FpParams {
+    type BigInt = BigInteger;
+
+    const TWO_ADICITY: u32 = 32;
+
+    const TWO_ADIC_ROOT_OF_UNITY: BigInteger = BigInteger([0xa28db849bad6dbf0, 0x9083cd03d3b539df, 0xfba6b9ca9dc8448e, 0x3ec928747b89c6da]);
+}
+
+impl FpParameters for FpParams {
+    const MODULUS: BigInteger = BigInteger([0x992d30ed00000001, 0x224698fc094cf91b, 0x0000000000000000, 0x4000000000000000]);
+
+    const MODULUS_BITS: u32 = 255;
+
+    const CAPACITY: u32 = Self::MODULUS_BITS - 1;
+
+    const REPR_SHAVE_BITS: u32 = 1;
+
+    const R: BigInteger = BigInteger([0x34786d38fffffffd, 0x992c350be41914ad, 0xffffffffffffffff, 0x3fffffffffffffff]);
+
+    const R2: BigInteger = BigInteger([0x8c78ecb30000000f, 0xd7d30dbd8b0de0e7, 0x7797a99bc3c95d18, 0x096d41af7b9cb714]);
+
+    const INV: u64 = 0x992d30ecffffffff;
+
+    /// `5`, in Montgomery form.
+    const GENERATOR: BigInteger = BigInteger([0xa1a55e68ffffffed, 0x74c2a54b4f4982f3, 0xfffffffffffffffd, 0x3fffffffffffffff]);
+
+    const MODULUS_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([0xcc96987680000000, 0x11234c7e04a67c8d, 0x0000000000000000, 0x2000000000000000]);
+
+    const T: BigInteger = BigInteger([0x094cf91b992d30ed, 0x00000000224698fc, 0x0000000000000000, 0x0000000040000000]);
+
+    const T_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([0x04a67c8dcc969876, 0x0000000011234c7e, 0x0000000000000000, 0x0000000020000000]);
+}
+
+pub(crate) struct FqParams;
+
+impl Fp256Parameters for FqParams {}
+
+impl FftParameters for FqParams {
+    type BigInt = BigInteger;
+
+    const TWO_ADICITY: u32 = 32;
+
+    const TWO_ADIC_ROOT_OF_UNITY: BigInteger = BigInteger([0x218077428c9942de, 0xcc49578921b60494, 0xac2e5d27b2efbee2, 0x0b79fa897f2db056]);
+}
+
+impl FpParameters for FqParams {
+    const MODULUS: BigInteger = BigInteger([0x8c46eb2100000001, 0x224698fc0994a8dd, 0x0000000000000000, 0x4000000000000000]);
+
+    const MODULUS_BITS: u32 = 255;
+
+    const CAPACITY: u32 = Self::MODULUS_BITS - 1;
+
+    const REPR_SHAVE_BITS: u32 = 1;
+
+    const R: BigInteger = BigInteger([0x5b2b3e9cfffffffd, 0x992c350be3420567, 0xffffffffffffffff, 0x3fffffffffffffff]);
+
+    const R2: BigInteger = BigInteger([0xfc9678ff0000000f, 0x67bb433d891a16e3, 0x7fae231004ccf590, 0x096d41af7ccfdaa9]);
+
+    const INV: u64 = 0x8c46eb20ffffffff;
+
+    /// `5`, in Montgomery form.
+    const GENERATOR: BigInteger = BigInteger([0x96bc8c8cffffffed, 0x74c2a54b49f7778e, 0xfffffffffffffffd, 0x3fffffffffffffff]);
+
+    const MODULUS_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([0xc623759080000000, 0x11234c7e04ca546e, 0x0000000000000000, 0x2000000000000000]);
+
+    const T: BigInteger = BigInteger([0x0994a8dd8c46eb21, 0x00000000224698fc, 0x0000000000000000, 0x0000000040000000]);
+
+    const T_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([0x04ca546ec6237590, 0x0000000011234c7e, 0x0000000000000000, 0x0000000020000000]);
+}
+
+#[derive(Clone, Default, PartialEq, Eq)]
+pub(crate) struct PallasParameters;
+
+impl ModelParameters for PallasParameters {
+    type BaseField = Fp;
+    type ScalarField = Fq;
+}
+
+impl SWModelParameters for PallasParameters {
+    const COEFF_A: Fp = field_new!(Fp, "0");
+    const COEFF_B: Fp = field_new!(Fp, "5");
+    const COFACTOR: &'static [u64] = &[1];
+    const COFACTOR_INV: Fq = field_new!(Fq, "1");
+    const AFFINE_GENERATOR_COEFFS: (Fp, Fp) = (field_new!(Fp, "-1"), field_new!(Fp, "2"));
+
+    #[inline(always)]
+    fn mul_by_a(_: &Fp) -> Fp {
+        Fp::zero()
+    }
+}
+
+#[derive(Clone, Default, PartialEq, Eq)]
+pub(crate) struct VestaParameters;
+
+impl ModelParameters for VestaParameters {
+    type BaseField = Fq;
+    type ScalarField = Fp;
+}
+
+impl SWModelParameters for VestaParameters {
+    const COEFF_A: Fq = field_new!(Fq, "0");
+    const COEFF_B: Fq = field_new!(Fq, "5");
+    const COFACTOR: &'static [u64] = &[1];
+    const COFACTOR_INV: Fp = field_new!(Fp, "1");
+    const AFFINE_GENERATOR_COEFFS: (Fq, Fq) = (field_new!(Fq, "-1"), field_new!(Fq, "2"));
+
+    #[inline(always)]
+    fn mul_by_a(_: &Fq) -> Fq {
+        Fq::zero()
+    }
+}
+
+pub(crate) type Pallas = GroupAffine<PallasParameters>;
+pub(crate) type Vesta = GroupAffine<VestaParameters>;
+
+/// Defines the module of the functions on one of the Pasta curves, which only differ by their types and generators.
+macro_rules! pasta_module {
+    ($(#[$doc:meta])* $name:ident, $curve:ident, $scalar:ident, $generator:literal) => {
+        $(#[$doc])*
+        pub mod $name {
+            use ark_ec::AffineCurve;
+            use ark_ff::PrimeField;
+            use super::{$curve, $scalar};
+            use crate::dlog::{self, DlogCurve};
+            use crate::table::ArkworksCurve;
+            use crate::{Error, HexPoint};
+
+            /// Coordinates of the generator `G = (-1, 2)`.
+            pub const GENERATOR: (&str, &str) = ($generator, "0x0000000000000000000000000000000000000000000000000000000000000002");
+
+            impl ArkworksCurve for $curve {
+                fn x_low_bits(&self) -> u64 {
+                    self.x.into_repr().0[0]
+                }
+            }
+
+            impl DlogCurve for $curve {
+                type Scalar = $scalar;
+
+                fn generator() -> Self {
+                    Self::prime_subgroup_generator()
+                }
+
+                fn mul_scalar(&self, s: &$scalar) -> <Self as AffineCurve>::Projective {
+                    self.mul(*s)
+                }
+
+                fn parse_point(x: &str, y: &str) -> Result<Self, Error> {
+                    dlog::weierstrass_point(x, y)
+                }
+
+                fn to_hex(&self) -> HexPoint {
+                    dlog::weierstrass_to_hex(self)
+                }
+
+                fn parse_scalar(s: &str) -> Result<$scalar, Error> {
+                    dlog::parse_scalar::<$scalar, $scalar>(s)
+                }
+            }
+
+            dlog::curve_module!($curve, weierstrass);
+        }
+    };
+}
+
+pasta_module!(
+    /// Exponential ElGamal on Pallas, over `F_p` and of order `q`.
+    pallas, Pallas, Fq, "0x40000000000000000000000000000000224698fc094cf91b992d30ed00000000"
+);
+
+pasta_module!(
+    /// Exponential ElGamal on Vesta, over `F_q` and of order `p`.
+    vesta, Vesta, Fp, "0x40000000000000000000000000000000224698fc0994a8dd8c46eb2100000000"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ec::{AffineCurve, ProjectiveCurve};
+    use ark_ff::{Field, PrimeField, SquareRootField};
+    use crate::dlog::DlogCurve;
+    use crate::Error;
+
+    #[test]
+    fn test_pasta() {
+        let (g, h) = (Pallas::prime_subgroup_generator(), Vesta::prime_subgroup_generator());
+        assert!(g.is_on_curve() && h.is_on_curve());
+        assert_eq!(g.to_hex(), (pallas::GENERATOR.0.to_string(), pallas::GENERATOR.1.to_string()));
+        assert_eq!(h.to_hex(), (vesta::GENERATOR.0.to_string(), vesta::GENERATOR.1.to_string()));
+        // the order of each curve is the modulus of the base field of the other one
+        assert!(g.mul(FqParams::MODULUS).is_zero() && h.mul(FpParams::MODULUS).is_zero());
+        assert_eq!(g.mul(Fq::from(2u64)).into_affine(), g + g);
+        assert_eq!(Fp::from(4u64).sqrt().map(|r| r.square()), Some(Fp::from(4u64)));
+        assert_eq!(pallas::priv_to_pub_key(Q), Err(Error::NotInField { input: Q.to_string(), modulus: Q.to_string() }));
+        assert_eq!(vesta::priv_to_pub_key(P), Err(Error::NotInField { input: P.to_string(), modulus: P.to_string() }));
+        assert_eq!(Fp::from_repr(FpParams::MODULUS), None);
+
+        let private_key = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        let pk = pallas::priv_to_pub_key(private_key).unwrap();
+        let (c1, c2) = pallas::exp_elgamal_encrypt((&pk.0, &pk.1), 65535, "0x05").unwrap();
+        assert_eq!(pallas::exp_elgamal_decrypt(private_key, (&c1.0, &c1.1), (&c2.0, &c2.1)), Ok(pallas::embed_plaintext(65535)));
+        let solver = pallas::Solver::new(16, 2);
+        assert_eq!(pallas::decrypt_to_u64(private_key, (&c1.0, &c1.1), (&c2.0, &c2.1), &solver), Ok(65535));
+
+        let pk = vesta::priv_to_pub_key(private_key).unwrap();
+        let (c1, c2) = vesta::exp_elgamal_encrypt((&pk.0, &pk.1), 65535, "0x05").unwrap();
+        let solver = vesta::Solver::new(16, 2);
+        assert_eq!(vesta::decrypt_to_u64(private_key, (&c1.0, &c1.1), (&c2.0, &c2.1), &solver), Ok(65535));
+        for plaintext in [0, 1, 255, 256] {
+            let p = vesta::embed_plaintext(plaintext);
+            assert_eq!(solver.solve(&p.0, &p.1), Ok(plaintext));
+        }
+        assert_eq!(Pallas::parse_point("0x01", "0x01"), Err(Error::NotOnCurve));
+        // the coordinates of Vesta, which are smaller than q > p, are not all elements of the base field of Pallas
+        assert!(matches!(Pallas::parse_point(vesta::GENERATOR.0, vesta::GENERATOR.1), Err(Error::InvalidCoordinate { .. })));
+    }
+}
@@ -0,0 +1,163 @@
+//! Pedersen commitments on Baby Jubjub, with the same encodings as the rest of this crate, so that applications combining noir-elgamal
+//! with commitments do not need a second curve library.
+//!
+//! The commitment to the value `v` with the blinding factor `r` is `C = v·G + r·H`, for the base point `G` of noir-elgamal, which embeds
+//! values like the plaintexts of ciphertexts, and the generator `H = H_0` of [`generator`]. Commitments are additively homomorphic : the sum
+//! of the commitments to `a` and `b` is the commitment to `a + b` with the sum of their blinding factors.
+//!
+//! The generators `H_i` of [`generator`] are derived independently of `G` by hashing to the curve, so that nobody knows their Discrete
+//! Logarithms in base `G`, which would allow opening a commitment to another value : `H_i` is the first point `8·P` such that `P` is the
+//! point of the packed encoding of EIP-2494 `SHA-256(DOMAIN || i || counter)`, for `i` and the counter as 8 and 4 big-endian bytes, with
+//! its three highest bits cleared, and `8·P` is not the identity. [`commit_vector`] commits to several values at once with `H_1, H_2, ...`.
+
+use std::iter::Sum;
+use std::ops::{Add, Sub};
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ed_on_bn254::{EdwardsAffine as BabyJubJub, Fr};
+use ark_ff::Zero;
+use rand::{CryptoRng, RngCore};
+use sha2::{Digest, Sha256};
+use crate::keys::random_scalar;
+use crate::point::{base_point, decompress, field_to_hex, parse_point, parse_scalar, point_to_hex};
+use crate::{Error, HexPoint};
+
+/// Domain separator of the derivation of the generators, see the [module documentation](self).
+pub const DOMAIN: &[u8] = b"babygiant-alt-bn128/pedersen/v1";
+
+/// Pedersen commitment, whose point is validated when it is parsed, see the [module documentation](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Commitment(BabyJubJub);
+
+/// Opening of a [`Commitment`], i.e its value and its blinding factor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Opening {
+    value: u64,
+    blinding: Fr,
+}
+
+impl Commitment {
+    /// Parses the point `(x, y)` of a commitment, which should be in the prime subgroup.
+    pub fn new(x: &str, y: &str) -> Result<Self, Error> {
+        Ok(Commitment(parse_point(x, y)?))
+    }
+
+    pub fn point(&self) -> HexPoint {
+        point_to_hex(&self.0)
+    }
+}
+
+impl Opening {
+    /// Parses the opening of `value` with the blinding factor `blinding`, in the same format as [`crate::generate_randomness`].
+    pub fn new(value: u64, blinding: &str) -> Result<Self, Error> {
+        Ok(Opening { value, blinding: parse_scalar(blinding)? })
+    }
+
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    pub fn blinding(&self) -> String {
+        field_to_hex(&self.blinding)
+    }
+}
+
+impl Add for Commitment {
+    type Output = Commitment;
+
+    fn add(self, other: Commitment) -> Commitment {
+        Commitment(self.0 + other.0)
+    }
+}
+
+impl Sub for Commitment {
+    type Output = Commitment;
+
+    fn sub(self, other: Commitment) -> Commitment {
+        Commitment(self.0 + -other.0)
+    }
+}
+
+impl Sum for Commitment {
+    fn sum<I: Iterator<Item = Commitment>>(iter: I) -> Commitment {
+        iter.fold(Commitment(BabyJubJub::zero()), Add::add)
+    }
+}
+
+/// Generator `H_index` of the prime subgroup, derived from [`DOMAIN`] and `index` as described in the [module documentation](self).
+pub fn generator(index: u64) -> HexPoint {
+    point_to_hex(&derive_generator(index))
+}
+
+fn derive_generator(index: u64) -> BabyJubJub {
+    (0u32..)
+        .find_map(|counter| {
+            let mut packed: [u8; 32] = Sha256::digest([DOMAIN, &index.to_be_bytes(), &counter.to_be_bytes()].concat()).into();
+            packed[31] &= 0x1f;
+            let h = decompress(&packed).ok()?.mul_by_cofactor();
+            (!h.is_zero()).then_some(h)
+        })
+        .unwrap()
+}
+
+/// Commits to the value `v` of `opening` with its blinding factor `r`, i.e returns `v·G + r·H`.
+pub fn commit(opening: &Opening) -> Commitment {
+    let c = base_point().mul(Fr::from(opening.value)) + derive_generator(0).mul(opening.blinding);
+    Commitment(c.into_affine())
+}
+
+/// Commits to `value` with a blinding factor drawn from `rng`, e.g `rand::rngs::OsRng`, and returns the commitment with its opening.
+pub fn commit_with_rng<R: RngCore + CryptoRng>(value: u64, rng: &mut R) -> (Commitment, Opening) {
+    let opening = Opening { value, blinding: random_scalar(rng) };
+    (commit(&opening), opening)
+}
+
+/// Checks that `opening` opens `commitment`.
+pub fn verify(commitment: &Commitment, opening: &Opening) -> bool {
+    commit(opening) == *commitment
+}
+
+/// Commits to the vector `values` with the blinding factor `blinding`, i.e returns `sum(v_i·H_(i+1)) + r·H_0`, for the generators of [`generator`].
+pub fn commit_vector(values: &[u64], blinding: &str) -> Result<Commitment, Error> {
+    let r = parse_scalar(blinding)?;
+    let c = values
+        .iter()
+        .enumerate()
+        .fold(derive_generator(0).mul(r), |c, (i, v)| c + derive_generator(i as u64 + 1).mul(Fr::from(*v)));
+    Ok(Commitment(c.into_affine()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn test_pedersen_commitments() {
+        let h = derive_generator(0);
+        assert!(h.is_on_curve() && h.is_in_correct_subgroup_assuming_on_curve());
+        assert_ne!(h, base_point());
+        assert_ne!(generator(0), generator(1));
+        assert_eq!(generator(0), point_to_hex(&h));
+
+        let opening = Opening::new(42, "0x05").unwrap();
+        let commitment = commit(&opening);
+        assert!(verify(&commitment, &opening));
+        assert!(!verify(&commitment, &Opening::new(43, "0x05").unwrap()));
+        assert!(!verify(&commitment, &Opening::new(42, "0x06").unwrap()));
+        let (x, y) = commitment.point();
+        assert_eq!(Commitment::new(&x, &y), Ok(commitment));
+        assert_eq!(opening.blinding(), field_to_hex(&Fr::from(5u64)));
+
+        // the sum of commitments opens to the sum of the values and of the blinding factors
+        let (other, other_opening) = commit_with_rng(100, &mut OsRng);
+        let sum = Opening { value: 142, blinding: opening.blinding + other_opening.blinding };
+        assert!(verify(&(commitment + other), &sum));
+        assert!(verify(&(commitment + other - other), &opening));
+        assert_eq!([commitment, other].into_iter().sum::<Commitment>(), commitment + other);
+
+        let vector = commit_vector(&[1, 2], "0x05").unwrap();
+        assert_ne!(vector, commit_vector(&[2, 1], "0x05").unwrap());
+        assert_eq!(commit_vector(&[], "0x05"), Ok(Commitment(h.mul(Fr::from(5u64)).into_affine())));
+        assert_eq!(Commitment::new("0x1", "0x1"), Err(Error::NotOnCurve));
+    }
+}
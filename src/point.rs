@@ -0,0 +1,604 @@
+//! Conversions between the hexadecimal strings used by Noir and the arkworks representation of Baby Jubjub points.
+//!
+//! Noir encodes Baby Jubjub points in Twisted Edwards form (`168700x^2 + y^2 = 1 + 168696x^2y^2`), while arkworks
+//! uses the equivalent Edwards form (`x^2 + y^2 = 1 + (168696/168700)x^2y^2`), so the `x` coordinate is scaled by
+//! `sqrt(168700)` when crossing the boundary between the two.
+
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ed_on_bn254::{EdwardsAffine as BabyJubJub, Fr, Fq};
+use ark_ff::{BigInteger256, field_new, Field, One, PrimeField, BigInteger, SquareRootField, Zero};
+use crate::Error;
+
+/// Twisted Edwards coordinates `(x, y)` of a point, as `0x`-prefixed hexadecimal strings.
+pub type HexPoint = (String, String);
+
+/// Coefficient used to transform the coordinates of Baby Jubjub points from the Twisted Edwards form coming from Noir,
+/// to the Edwards form compatible with arkworks.
+pub(crate) fn coeff_twisted() -> Fq {
+    field_new!(Fq, "168700").sqrt().unwrap()
+}
+
+/// The base point of the Twisted Edwards form of Baby Jubjub used by noir-elgamal : https://eips.ethereum.org/EIPS/eip-2494#forms-of-the-curve
+pub(crate) fn base_point() -> BabyJubJub {
+    let gx = field_new!(Fq, "5299619240641551281634865583518297030282874472190772894086521144482721001553") * coeff_twisted();
+    let gy = field_new!(Fq, "16950150798460657717958625567821834550301663161624707787222815936182638968203");
+    BabyJubJub::new(gx, gy)
+}
+
+/// Parses the Twisted Edwards coordinates `(x, y)` of a point in the prime subgroup of Baby Jubjub.
+pub(crate) fn parse_point(x: &str, y: &str) -> Result<BabyJubJub, Error> {
+    let (x, y) = parse_coordinates(x, y, parse_field)?;
+    point_from_coordinates(x, y)
+}
+
+/// Parses the coordinates `x` and `y` with `parse`, naming the coordinate which could not be parsed in [`Error::InvalidCoordinate`].
+pub(crate) fn parse_coordinates<I: ?Sized, T>(x: &I, y: &I, parse: impl Fn(&I) -> Result<T, Error>) -> Result<(T, T), Error> {
+    let named = |coordinate: &str, s: &I| {
+        parse(s).map_err(|e| match e {
+            Error::InvalidHex { .. } | Error::NotInField { .. } => Error::InvalidCoordinate { coordinate: coordinate.to_string(), error: Box::new(e) },
+            e => e,
+        })
+    };
+    Ok((named("x", x)?, named("y", y)?))
+}
+
+/// Point of Twisted Edwards coordinates `(x, y)`, checking that it is in the prime subgroup of Baby Jubjub.
+pub(crate) fn point_from_coordinates(x: Fq, y: Fq) -> Result<BabyJubJub, Error> {
+    let p = BabyJubJub::new(x * coeff_twisted(), y);
+    if !p.is_on_curve() {
+        return Err(Error::NotOnCurve);
+    }
+    if !p.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(Error::NotInSubgroup);
+    }
+    Ok(p)
+}
+
+/// Formats a point as its Twisted Edwards coordinates, using the same `0x`-prefixed 32 bytes hexadecimal strings as Noir.
+pub(crate) fn point_to_hex(p: &BabyJubJub) -> HexPoint {
+    let x = p.x * coeff_twisted().inverse().unwrap();
+    (field_to_hex(&x), field_to_hex(&p.y))
+}
+
+/// Twisted Edwards coordinates `(x, y)` of a point, as field elements.
+pub(crate) fn point_coordinates(p: &BabyJubJub) -> [Fq; 2] {
+    [p.x * coeff_twisted().inverse().unwrap(), p.y]
+}
+
+/// Serializes a point as its Twisted Edwards coordinates `x || y`, each of them a 32 bytes big-endian integer.
+#[cfg(feature = "elgamal")]
+pub(crate) fn point_bytes(p: &BabyJubJub) -> Vec<u8> {
+    let (x, y) = point_to_hex(p);
+    [hex::decode(&x[2..]).unwrap(), hex::decode(&y[2..]).unwrap()].concat()
+}
+
+/// Formats a point exactly like Noir prints the `Point` returned by `exp_elgamal_decrypt`, e.g with `std::println` or `nargo execute` :
+/// `Point { x: 0x06184da392a17823e9c1d38cb50980b17150ffa411965b03f0b0200d9557daa9, y: 0x244a710118db92636e46e3f97bd80093ba7026ff97ca32d387145337e250549c }`.
+pub fn format_noir_point(point: &HexPoint) -> String {
+    format!("Point {{ x: {}, y: {} }}", point.0, point.1)
+}
+
+/// Embeds `plaintext` as the point `plaintext·G`, i.e the point whose Discrete Logarithm is searched by a [`crate::Solver`], and the one
+/// recovered by `exp_elgamal_decrypt` from its encryptions.
+pub fn embed_plaintext(plaintext: u64) -> HexPoint {
+    point_to_hex(&base_point().mul(Fr::from(plaintext)).into_affine())
+}
+
+/// Packs the point `(x, y)` into 32 bytes like noir-elgamal and its Solidity consumers, following EIP-2494 : the `y` coordinate as
+/// a little-endian integer, whose most significant bit (the top bit of the last byte) is set when `x` is larger than `(p-1)/2`.
+///
+/// The returned bytes are in the order they are stored in a `bytes32`.
+pub fn pack_point(x: &str, y: &str) -> Result<[u8; 32], Error> {
+    Ok(compress(&parse_point(x, y)?))
+}
+
+/// Unpacks a point packed by [`pack_point`], checking that it is in the prime subgroup of Baby Jubjub.
+pub fn unpack_point(packed: &[u8; 32]) -> Result<HexPoint, Error> {
+    let p = decompress(packed)?;
+    if !p.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(Error::NotInSubgroup);
+    }
+    Ok(point_to_hex(&p))
+}
+
+/// Packed encoding of EIP-2494 of any point of the curve, see [`pack_point`].
+pub(crate) fn compress(p: &BabyJubJub) -> [u8; 32] {
+    let [x, y] = point_coordinates(p);
+    let mut packed = [0u8; 32];
+    packed.copy_from_slice(&y.into_repr().to_bytes_le());
+    if is_negative(&x) {
+        packed[31] |= 0x80;
+    }
+    packed
+}
+
+/// Decodes the packed encoding of EIP-2494 of a point of the curve, which is not required to be in the prime subgroup, rejecting
+/// the non-canonical encodings.
+pub(crate) fn decompress(packed: &[u8; 32]) -> Result<BabyJubJub, Error> {
+    let invalid = || Error::InvalidFormat(format!("0x{}", hex::encode(packed)));
+    let negative = packed[31] & 0x80 != 0;
+    let mut y_bytes = *packed;
+    y_bytes[31] &= 0x7f;
+    let mut repr = BigInteger256::new([0; 4]);
+    repr.read_le(&mut y_bytes.as_ref()).unwrap();
+    let y = Fq::from_repr(repr).ok_or_else(invalid)?;
+    // x^2 = (1 - y^2) / (a - d·y^2) on the Twisted Edwards form
+    let y2 = y.square();
+    let denominator = (field_new!(Fq, "168700") - field_new!(Fq, "168696") * y2).inverse().ok_or(Error::NotOnCurve)?;
+    let mut x = ((Fq::one() - y2) * denominator).sqrt().ok_or(Error::NotOnCurve)?;
+    if x.is_zero() && negative {
+        return Err(invalid());
+    }
+    if is_negative(&x) != negative {
+        x = -x;
+    }
+    Ok(BabyJubJub::new(x * coeff_twisted(), y))
+}
+
+/// Whether `f` is larger than `(p-1)/2`, i.e is the larger one of `f` and `-f`.
+fn is_negative(f: &Fq) -> bool {
+    f.into_repr() > (-*f).into_repr()
+}
+
+/// Canonical encoding of the point `(x, y)`, shared by all the encodings of its coordinates, used as key by the stores of results.
+#[cfg(feature = "server")]
+pub(crate) fn canonical_key(x: &str, y: &str) -> Result<String, Error> {
+    let (x, y) = point_to_hex(&parse_point(x, y)?);
+    Ok(format!("{}_{}", x, y))
+}
+
+/// Parses a scalar, such as a private key or the encryption randomness, exactly like noir-elgamal : as a `Field` of the circuit,
+/// i.e an element of the base field of Baby Jubjub whose values larger than the modulus are rejected, which acts on the points of
+/// the prime subgroup modulo its order.
+pub(crate) fn parse_scalar(s: &str) -> Result<Fr, Error> {
+    let f = parse_field(s)?;
+    Ok(Fr::from_le_bytes_mod_order(&f.into_repr().to_bytes_le()))
+}
+
+/// Formats a field element or a scalar as a `0x`-prefixed 32 bytes hexadecimal string.
+pub(crate) fn field_to_hex<F: PrimeField>(f: &F) -> String {
+    format!("0x{}", hex::encode(f.into_repr().to_bytes_be()))
+}
+
+pub(crate) fn parse_field(s: &str) -> Result<Fq, Error> {
+    parse_prime_field(s)
+}
+
+/// Parses a field element written as a decimal integer, like the coordinates printed by circomlib, rejecting the values larger than the modulus.
+pub(crate) fn parse_decimal_field(s: &str) -> Result<Fq, Error> {
+    let invalid = || Error::InvalidFormat(s.to_string());
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let mut limbs = [0u64; 4];
+    for digit in s.bytes() {
+        let mut carry = (digit - b'0') as u128;
+        for limb in limbs.iter_mut() {
+            let v = *limb as u128 * 10 + carry;
+            *limb = v as u64;
+            carry = v >> 64;
+        }
+        if carry != 0 {
+            return Err(invalid());
+        }
+    }
+    Fq::from_repr(BigInteger256::new(limbs)).ok_or_else(invalid)
+}
+
+/// Formats a field element as a decimal integer, which is read back by [`parse_decimal_field`].
+pub(crate) fn field_to_decimal(f: &Fq) -> String {
+    let mut limbs = f.into_repr().0;
+    let mut digits = Vec::new();
+    loop {
+        let mut remainder = 0u128;
+        for limb in limbs.iter_mut().rev() {
+            let v = remainder << 64 | *limb as u128;
+            *limb = (v / 10) as u64;
+            remainder = v % 10;
+        }
+        digits.push(b'0' + remainder as u8);
+        if limbs.iter().all(|limb| *limb == 0) {
+            break;
+        }
+    }
+    digits.iter().rev().map(|digit| *digit as char).collect()
+}
+
+/// Same as [`parse_field`], for another prime field of 256 bits at most, e.g the base field of the curve of another backend.
+pub(crate) fn parse_prime_field<F: PrimeField<BigInt = BigInteger256>>(s: &str) -> Result<F, Error> {
+    field_from_be_bytes(&parse_hex(s)?).ok_or_else(|| not_in_field::<F>(s.to_string()))
+}
+
+/// Error of the `input` which is not smaller than the modulus of the field `F`.
+fn not_in_field<F: PrimeField<BigInt = BigInteger256>>(input: String) -> Error {
+    let m = F::characteristic();
+    Error::NotInField { input, modulus: format!("0x{}", hex::encode(BigInteger256::new([m[0], m[1], m[2], m[3]]).to_bytes_be())) }
+}
+
+/// Field element of the 32 big-endian bytes `bytes`, or `None` if they are larger than the modulus.
+pub(crate) fn field_from_be_bytes<F: PrimeField<BigInt = BigInteger256>>(bytes: &[u8; 32]) -> Option<F> {
+    let mut le = *bytes;
+    le.reverse();
+    let mut repr = BigInteger256::new([0; 4]);
+    repr.read_le(&mut le.as_ref()).unwrap();
+    F::from_repr(repr)
+}
+
+/// Same as [`parse_field`] for the 32 big-endian bytes of the field element, without allocating unless they are rejected.
+pub(crate) fn parse_field_be_bytes(bytes: &[u8; 32]) -> Result<Fq, Error> {
+    field_from_be_bytes(bytes).ok_or_else(|| not_in_field::<Fq>(format!("0x{}", hex::encode(bytes))))
+}
+
+/// Parses a `0x`-prefixed hexadecimal string of at most 32 bytes into its 32 big-endian bytes, e.g the coordinates of a curve which
+/// is not implemented with arkworks.
+#[cfg(any(feature = "secp256k1", feature = "ristretto"))]
+pub(crate) fn parse_be_bytes(s: &str) -> Result<[u8; 32], Error> {
+    parse_hex(s)
+}
+
+/// Policy of acceptance of the hexadecimal coordinates, e.g of the [`crate::Solver`]s with [`crate::Solver::with_parse_mode`].
+///
+/// In both modes, the values which are not canonical field elements, i.e not smaller than the modulus, are rejected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum ParseMode {
+    /// Exactly `0x` followed by 64 hexadecimal digits, so that each value has one encoding, e.g for the consensus-critical paths.
+    Strict,
+    /// The surrounding whitespace is ignored, the `0x` or `0X` prefix is optional, and there can be fewer digits, e.g `" 0x1 "`, for
+    /// the interactive tools.
+    #[default]
+    Lenient,
+}
+
+impl ParseMode {
+    /// Checks that the hexadecimal string `s` is accepted in this mode, before it is parsed.
+    pub fn check(self, s: &str) -> Result<(), Error> {
+        self.check_ascii(s.as_bytes())
+    }
+
+    /// Same as [`ParseMode::check`], for the bytes of the hexadecimal string.
+    pub(crate) fn check_ascii(self, s: &[u8]) -> Result<(), Error> {
+        if self == ParseMode::Lenient {
+            return Ok(());
+        }
+        let invalid = |reason: String| Err(Error::InvalidHex { input: String::from_utf8_lossy(s).into_owned(), reason });
+        let Some(digits) = s.strip_prefix(b"0x") else {
+            return invalid("the strict mode expects the 0x prefix".to_string());
+        };
+        if let Some(position) = digits.iter().position(|b| !b.is_ascii_hexdigit()) {
+            let c = String::from_utf8_lossy(&digits[position..]).chars().next().unwrap();
+            return invalid(format!("{:?} at position {} is not an hexadecimal digit", c, position + 2));
+        }
+        match digits.len() {
+            64 => Ok(()),
+            len => invalid(format!("the strict mode expects 64 hexadecimal digits, not {}", len)),
+        }
+    }
+}
+
+/// Parses an hexadecimal string of at most 64 digits, i.e 32 bytes, into its 32 big-endian bytes, ignoring the surrounding whitespace.
+///
+/// The `0x` or `0X` prefix is optional, the digits may be uppercase and their number odd, so that `"0x1"`, `"0X01"` and `" 1 "` are
+/// the same value. The prefix alone is zero, like in noir-elgamal, but an empty string is rejected.
+pub(crate) fn parse_hex(s: &str) -> Result<[u8; 32], Error> {
+    parse_hex_ascii(s.as_bytes())
+}
+
+/// Same as [`parse_hex`] on the bytes of the string, e.g a slice of a network buffer, without allocating unless they are rejected. Only
+/// the ASCII whitespace is ignored.
+pub(crate) fn parse_hex_ascii(s: &[u8]) -> Result<[u8; 32], Error> {
+    let invalid = |reason: String| Error::InvalidHex { input: String::from_utf8_lossy(s).into_owned(), reason };
+    let start = s.iter().position(|b| !b.is_ascii_whitespace()).unwrap_or(s.len());
+    let end = s.iter().rposition(|b| !b.is_ascii_whitespace()).map_or(start, |i| i + 1);
+    let trimmed = &s[start..end];
+    let digits = match trimmed {
+        [b'0', b'x' | b'X', digits @ ..] => digits,
+        [] => return Err(invalid("the string is empty".to_string())),
+        _ => trimmed,
+    };
+    let offset = start + trimmed.len() - digits.len();
+    if let Some(position) = digits.iter().position(|b| !b.is_ascii_hexdigit()) {
+        let c = String::from_utf8_lossy(&digits[position..]).chars().next().unwrap();
+        return Err(invalid(format!("{:?} at position {} is not an hexadecimal digit", c, offset + position)));
+    }
+    if digits.len() > 64 {
+        return Err(invalid(format!("{} digits are more than the 64 digits of 32 bytes", digits.len())));
+    }
+    let mut bytes = [0u8; 32];
+    // the digits are read from the least significant one, so that an odd number of digits is padded with a leading zero
+    for (i, digit) in digits.iter().rev().enumerate() {
+        let digit = (*digit as char).to_digit(16).unwrap() as u8;
+        bytes[31 - i / 2] |= digit << (4 * (i % 2));
+    }
+    Ok(bytes)
+}
+
+/// Decodes the base64 encoding of the big-endian bytes of a coordinate, of at most 32 bytes, into the `0x`-prefixed hexadecimal string
+/// of 32 bytes of Noir, e.g for the storage layers and the APIs which emit base64 rather than hexadecimal strings.
+///
+/// Both the standard and the URL-safe alphabets of RFC 4648 are accepted, with or without padding, ignoring the surrounding whitespace.
+pub fn base64_to_hex(s: &str) -> Result<String, Error> {
+    let invalid = |reason: String| Error::InvalidBase64 { input: s.to_string(), reason };
+    let trimmed = s.trim();
+    let unpadded = trimmed.trim_end_matches('=');
+    if unpadded.is_empty() {
+        return Err(invalid("the string is empty".to_string()));
+    }
+    if trimmed.len() - unpadded.len() > 2 || (trimmed.len() != unpadded.len() && trimmed.len() % 4 != 0) || unpadded.len() % 4 == 1 {
+        return Err(invalid(format!("{} characters are not a valid length of base64", trimmed.len())));
+    }
+    let offset = s.len() - s.trim_start().len();
+    let (mut bytes, mut acc, mut bits) = (Vec::with_capacity(32), 0u32, 0);
+    for (position, c) in unpadded.char_indices() {
+        let value = match c {
+            'A'..='Z' => c as u32 - 'A' as u32,
+            'a'..='z' => c as u32 - 'a' as u32 + 26,
+            '0'..='9' => c as u32 - '0' as u32 + 52,
+            '+' | '-' => 62,
+            '/' | '_' => 63,
+            _ => return Err(invalid(format!("{:?} at position {} is not a base64 character", c, offset + position))),
+        };
+        acc = acc << 6 | value;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+    if acc != 0 {
+        return Err(invalid("the unused bits of the last character are not zero".to_string()));
+    }
+    if bytes.len() > 32 {
+        return Err(invalid(format!("{} bytes are more than the 32 bytes of a coordinate", bytes.len())));
+    }
+    Ok(format!("0x{:0>64}", hex::encode(bytes)))
+}
+
+/// Reads a `Field` in any format displayed by Noir and nargo into the `0x`-prefixed hexadecimal string of 32 bytes of Noir, so that the
+/// values copied from their outputs are accepted as they are.
+///
+/// Like Noir reads the literals of its `Prover.toml` files, the integers without a `0x` prefix are decimal when they only have decimal
+/// digits, e.g `"42"`, and hexadecimal otherwise, e.g `"bb77a6ad..."`, while the `0x`-prefixed ones are hexadecimal, however short.
+pub fn noir_field_to_hex(s: &str) -> Result<String, Error> {
+    let trimmed = s.trim();
+    let field = match trimmed.is_empty() || trimmed.starts_with("0x") || trimmed.starts_with("0X") || !trimmed.bytes().all(|b| b.is_ascii_digit()) {
+        true => parse_field(s)?,
+        false => parse_decimal_field(trimmed).map_err(|_| not_in_field::<Fq>(s.to_string()))?,
+    };
+    Ok(field_to_hex(&field))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_hex_roundtrip() {
+        let x = "0x06184da392a17823e9c1d38cb50980b17150ffa411965b03f0b0200d9557daa9";
+        let y = "0x244a710118db92636e46e3f97bd80093ba7026ff97ca32d387145337e250549c";
+        let p = parse_point(x, y).unwrap();
+        assert_eq!(point_to_hex(&p), (x.to_string(), y.to_string()));
+    }
+
+    #[test]
+    fn test_format_noir_point() {
+        let p = point_to_hex(&base_point());
+        assert_eq!(format_noir_point(&p), "Point { x: 0x0bb77a6ad63e739b4eacb2e09d6277c12ab8d8010534e0b62893f3f6bb957051, y: 0x25797203f7a0b24925572e1cd16bf9edfce0051fb9e133774b3c257a872d7d8b }");
+    }
+
+    #[test]
+    fn test_pack_point() {
+        let (x, y) = point_to_hex(&base_point());
+        // x is smaller than (p-1)/2, so the packed point is y in little-endian
+        let mut expected = hex::decode(&y[2..]).unwrap();
+        expected.reverse();
+        assert_eq!(pack_point(&x, &y).unwrap().to_vec(), expected);
+        assert_eq!(unpack_point(&pack_point(&x, &y).unwrap()), Ok((x.clone(), y.clone())));
+
+        let (x, y) = point_to_hex(&-base_point());
+        let packed = pack_point(&x, &y).unwrap();
+        assert_eq!(packed[31] & 0x80, 0x80);
+        assert_eq!(unpack_point(&packed), Ok((x, y)));
+
+        let (x, y) = ("0x06184da392a17823e9c1d38cb50980b17150ffa411965b03f0b0200d9557daa9", "0x244a710118db92636e46e3f97bd80093ba7026ff97ca32d387145337e250549c");
+        let packed = pack_point(x, y).unwrap();
+        assert_eq!(unpack_point(&packed), Ok((x.to_string(), y.to_string())));
+
+        // the identity (0, 1) has no negative counterpart
+        let identity = pack_point("0x0", "0x1").unwrap();
+        assert_eq!(unpack_point(&identity).unwrap().1, field_to_hex(&Fq::one()));
+        let mut negative_zero = identity;
+        negative_zero[31] |= 0x80;
+        assert!(matches!(unpack_point(&negative_zero), Err(Error::InvalidFormat(_))));
+        assert!(matches!(unpack_point(&[0xff; 32]), Err(Error::InvalidFormat(_))));
+    }
+
+    #[test]
+    fn test_parse_scalar() {
+        // the order of the prime subgroup, and the modulus of the base field
+        let order = "0x060c89ce5c263405370a08b6d0302b0bab3eedb83920ee0a677297dc392126f1";
+        let modulus = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+        assert_eq!(parse_scalar(order), Ok(Fr::from(0u64)));
+        assert_eq!(parse_scalar("0x060c89ce5c263405370a08b6d0302b0bab3eedb83920ee0a677297dc392126f6"), Ok(Fr::from(5u64)));
+        assert_eq!(parse_scalar(modulus), Err(Error::NotInField { input: modulus.to_string(), modulus: modulus.to_string() }));
+    }
+
+    #[test]
+    fn test_parse_point_errors() {
+        let invalid = |input: &str, reason: &str| Error::InvalidHex { input: input.to_string(), reason: reason.to_string() };
+        let coordinate = |coordinate: &str, error: Error| Err(Error::InvalidCoordinate { coordinate: coordinate.to_string(), error: Box::new(error) });
+        let error = parse_point("0x1g", "0x1");
+        assert_eq!(error, coordinate("x", invalid("0x1g", "'g' at position 3 is not an hexadecimal digit")));
+        assert_eq!(error.unwrap_err().to_string(), "Invalid x coordinate : Invalid hexadecimal string : \"0x1g\", 'g' at position 3 is not an hexadecimal digit");
+        let modulus = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+        let not_in_field = Error::NotInField { input: modulus.to_string(), modulus: modulus.to_string() };
+        assert_eq!(parse_point("0x0", modulus), coordinate("y", not_in_field));
+        assert_eq!(parse_hex(" 0x1 g"), Err(invalid(" 0x1 g", "' ' at position 4 is not an hexadecimal digit")));
+        assert_eq!(parse_hex(" \t"), Err(invalid(" \t", "the string is empty")));
+        assert_eq!(parse_hex(&format!("0x{}", "0".repeat(65))), Err(invalid(&format!("0x{}", "0".repeat(65)), "65 digits are more than the 64 digits of 32 bytes")));
+        assert_eq!(parse_point("0x1", "0x1"), Err(Error::NotOnCurve));
+    }
+
+    #[test]
+    fn test_parse_mode() {
+        let (x, y) = point_to_hex(&base_point());
+        let strict = crate::Solver::new(8, 1).with_parse_mode(ParseMode::Strict);
+        assert_eq!(strict.solve(&x, &y), Ok(1));
+        assert_eq!(strict.solve_hex_bytes(x.as_bytes(), y.as_bytes()), Ok(1));
+        let invalid = |input: &str, reason: &str| Err(Error::InvalidHex { input: input.to_string(), reason: reason.to_string() });
+        assert_eq!(ParseMode::Strict.check("0x1"), invalid("0x1", "the strict mode expects 64 hexadecimal digits, not 1"));
+        assert_eq!(ParseMode::Strict.check(&x[2..]), invalid(&x[2..], "the strict mode expects the 0x prefix"));
+        let padded = format!(" {}", x);
+        assert_eq!(ParseMode::Strict.check(&padded), invalid(&padded, "the strict mode expects the 0x prefix"));
+        let spaced = format!("{} ", x);
+        assert_eq!(ParseMode::Strict.check(&spaced), invalid(&spaced, "' ' at position 66 is not an hexadecimal digit"));
+        assert!(matches!(strict.solve(&x, &y.to_uppercase().replace("0X", "0x")), Ok(1)));
+        assert!(matches!(strict.solve(&spaced, &y), Err(Error::InvalidCoordinate { coordinate, .. }) if coordinate == "x"));
+        assert_eq!(ParseMode::Lenient.check(&spaced), Ok(()));
+        assert_eq!(crate::Solver::new(8, 1).solve(&spaced, &y), Ok(1));
+    }
+
+    #[test]
+    fn test_noir_field_to_hex() {
+        let (x, _) = point_to_hex(&base_point());
+        assert_eq!(noir_field_to_hex(&x), Ok(x.clone()));
+        assert_eq!(noir_field_to_hex(&x[2..]), Ok(x.clone()));
+        assert_eq!(noir_field_to_hex("0xbb77a6ad63e739b4eacb2e09d6277c12ab8d8010534e0b62893f3f6bb957051"), Ok(x.clone()));
+        assert_eq!(noir_field_to_hex("0x10"), Ok(field_to_hex(&Fq::from(16u64))));
+        assert_eq!(noir_field_to_hex(" 10\n"), Ok(field_to_hex(&Fq::from(10u64))));
+        assert_eq!(noir_field_to_hex("1a"), Ok(field_to_hex(&Fq::from(26u64))));
+        // the modulus minus one is the largest decimal Field
+        let max = "21888242871839275222246405745257275088548364400416034343698204186575808495616";
+        assert_eq!(noir_field_to_hex(max), Ok(field_to_hex(&-Fq::one())));
+        let modulus = "21888242871839275222246405745257275088548364400416034343698204186575808495617";
+        assert!(matches!(noir_field_to_hex(modulus), Err(Error::NotInField { .. })));
+        assert!(matches!(noir_field_to_hex(""), Err(Error::InvalidHex { .. })));
+        assert!(matches!(noir_field_to_hex("-1"), Err(Error::InvalidHex { .. })));
+    }
+
+    #[test]
+    fn test_base64_to_hex() {
+        let (x, y) = point_to_hex(&base_point());
+        for (bx, by) in [
+            ("C7d6atY+c5tOrLLgnWJ3wSq42AEFNOC2KJPz9ruVcFE=", "JXlyA/egskklVy4c0Wv57fzgBR+54TN3SzwleoctfYs="),
+            ("C7d6atY-c5tOrLLgnWJ3wSq42AEFNOC2KJPz9ruVcFE", " JXlyA_egskklVy4c0Wv57fzgBR-54TN3SzwleoctfYs=\n"),
+        ] {
+            assert_eq!((base64_to_hex(bx), base64_to_hex(by)), (Ok(x.clone()), Ok(y.clone())));
+            assert_eq!(crate::Solver::new(8, 1).solve_base64(bx, by), Ok(1));
+        }
+        assert_eq!(base64_to_hex("AQ=="), Ok(field_to_hex(&Fq::one())));
+        assert_eq!(base64_to_hex("AQA"), Ok(field_to_hex(&Fq::from(256u64))));
+
+        let invalid = |input: &str, reason: &str| Err(Error::InvalidBase64 { input: input.to_string(), reason: reason.to_string() });
+        assert_eq!(base64_to_hex(" AQ.="), invalid(" AQ.=", "'.' at position 3 is not a base64 character"));
+        assert_eq!(base64_to_hex("AR=="), invalid("AR==", "the unused bits of the last character are not zero"));
+        assert_eq!(base64_to_hex("AQ="), invalid("AQ=", "3 characters are not a valid length of base64"));
+        assert_eq!(base64_to_hex("A"), invalid("A", "1 characters are not a valid length of base64"));
+        assert_eq!(base64_to_hex("=="), invalid("==", "the string is empty"));
+        let too_long = "A".repeat(44);
+        assert_eq!(base64_to_hex(&too_long), invalid(&too_long, "33 bytes are more than the 32 bytes of a coordinate"));
+    }
+
+    #[test]
+    fn test_parse_bytes() {
+        let (x, y) = point_to_hex(&base_point());
+        assert_eq!(parse_hex_ascii(x.as_bytes()), parse_hex(&x));
+        assert_eq!(parse_hex_ascii(b"\t0X0bB7 "), parse_hex("0xbb7"));
+        assert_eq!(parse_hex_ascii(b"0x\xff"), Err(Error::InvalidHex { input: "0x\u{fffd}".to_string(), reason: "'\u{fffd}' at position 2 is not an hexadecimal digit".to_string() }));
+        let be = parse_hex(&y).unwrap();
+        assert_eq!(parse_field_be_bytes(&be), parse_field(&y));
+        let solver = crate::Solver::new(8, 1);
+        assert_eq!(solver.solve_hex_bytes(x.as_bytes(), y.as_bytes()), Ok(1));
+        assert_eq!(solver.solve_be_bytes(&parse_hex(&x).unwrap(), &be), Ok(1));
+        let modulus = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+        assert_eq!(parse_field_be_bytes(&parse_hex(modulus).unwrap()), Err(Error::NotInField { input: modulus.to_string(), modulus: modulus.to_string() }));
+    }
+
+    #[test]
+    fn test_parse_hex() {
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        for s in ["0x1", "0X01", "1", " 0x001\n", "0x0000000000000000000000000000000000000000000000000000000000000001"] {
+            assert_eq!(parse_hex(s), Ok(one), "{:?}", s);
+        }
+        assert_eq!(parse_hex("0x"), Ok([0u8; 32]));
+        assert_eq!(parse_hex("0xABC"), parse_hex("abc"));
+        assert_eq!(parse_hex("0xabc").unwrap()[30..], [0x0a, 0xbc]);
+        let (x, y) = point_to_hex(&base_point());
+        assert_eq!(parse_point(&x.to_uppercase().replace("0X", "  0x"), &y[2..]), Ok(base_point()));
+    }
+}
+
+/// Proof harnesses of the helpers parsing the untrusted strings and bytes into field elements, checked by `cargo kani` for all their
+/// inputs of at most 32 bytes, or of the 32 bytes of a value.
+#[cfg(kani)]
+mod verification {
+    use super::*;
+
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    /// Any prefix of `bytes`.
+    fn any_prefix<const N: usize>(bytes: &[u8; N]) -> &[u8] {
+        let len: usize = kani::any();
+        kani::assume(len <= N);
+        &bytes[..len]
+    }
+
+    /// `0x` followed by the 64 hexadecimal digits of `bytes`.
+    fn to_hex_ascii(bytes: &[u8; 32]) -> [u8; 66] {
+        let mut s = [b'0'; 66];
+        s[1] = b'x';
+        for (i, b) in bytes.iter().enumerate() {
+            s[2 + 2 * i] = DIGITS[usize::from(b >> 4)];
+            s[3 + 2 * i] = DIGITS[usize::from(b & 0xf)];
+        }
+        s
+    }
+
+    #[kani::proof]
+    #[kani::unwind(34)]
+    fn parse_hex_ascii_never_panics() {
+        let bytes: [u8; 32] = kani::any();
+        let _ = parse_hex_ascii(any_prefix(&bytes));
+    }
+
+    #[kani::proof]
+    #[kani::unwind(34)]
+    fn check_ascii_never_panics() {
+        let bytes: [u8; 32] = kani::any();
+        let s = any_prefix(&bytes);
+        let _ = ParseMode::Strict.check_ascii(s);
+        assert!(ParseMode::Lenient.check_ascii(s).is_ok());
+    }
+
+    /// The digits of any value of 32 bytes are parsed back into the value, whatever the number of their leading zeros which are
+    /// removed, i.e the shorter strings are padded with zeros.
+    #[kani::proof]
+    #[kani::unwind(67)]
+    fn parse_hex_ascii_round_trips() {
+        let bytes: [u8; 32] = kani::any();
+        let mut s = to_hex_ascii(&bytes);
+        assert!(ParseMode::Strict.check_ascii(&s).is_ok());
+        let removed: usize = kani::any();
+        kani::assume(removed <= 64 && s[2..2 + removed].iter().all(|&d| d == b'0'));
+        s.copy_within(2 + removed.., 2);
+        assert_eq!(parse_hex_ascii(&s[..66 - removed]), Ok(bytes));
+    }
+
+    /// The big-endian bytes of a field element are reversed into its little-endian representation and back, and the other values are
+    /// rejected.
+    #[kani::proof]
+    #[kani::unwind(33)]
+    fn field_from_be_bytes_round_trips() {
+        let bytes: [u8; 32] = kani::any();
+        match field_from_be_bytes::<Fq>(&bytes) {
+            Some(f) => {
+                assert!(f.into_repr().to_bytes_be() == bytes);
+                assert_eq!(parse_field_be_bytes(&bytes), Ok(f));
+            }
+            None => assert!(matches!(parse_field_be_bytes(&bytes), Err(Error::NotInField { .. }))),
+        }
+    }
+}
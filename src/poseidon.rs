@@ -0,0 +1,183 @@
+//! Poseidon hash over the scalar field of BN254, i.e the base field of Baby Jubjub, with the parameters of circomlib, which are also
+//! the ones of `std::hash::poseidon::bn254` in Noir, so that hashes computed natively match the ones computed in circuits.
+//!
+//! The permutation has a width of `t = inputs + 1` field elements, with the S-box `x^5`, `8` full rounds and the number of partial rounds of circomlib.
+//! Its round constants and MDS matrix are generated with the Grain LFSR of the reference implementation of Poseidon, like the ones of circomlib.
+
+use std::sync::OnceLock;
+use ark_ed_on_bn254::Fq;
+use ark_ff::{BigInteger, BigInteger256, Field, PrimeField, Zero};
+use crate::point::{field_to_hex, parse_field};
+use crate::Error;
+
+/// Maximum number of inputs of [`poseidon_hash`].
+pub const MAX_INPUTS: usize = 16;
+
+const FULL_ROUNDS: usize = 8;
+/// Number of partial rounds for the widths `t = 2..=17`.
+const PARTIAL_ROUNDS: [usize; MAX_INPUTS] = [56, 57, 56, 60, 60, 63, 64, 63, 60, 66, 60, 65, 70, 60, 64, 68];
+
+struct Parameters {
+    round_constants: Vec<Fq>,
+    mds: Vec<Vec<Fq>>,
+}
+
+/// Hashes `inputs`, which are between `1` and [`MAX_INPUTS`] `Field`s in the same format as the other inputs of this crate,
+/// like `std::hash::poseidon::bn254::hash_N` in Noir and `poseidon` in circomlib.
+pub fn poseidon_hash(inputs: &[&str]) -> Result<String, Error> {
+    let inputs = inputs.iter().map(|input| parse_field(input)).collect::<Result<Vec<Fq>, Error>>()?;
+    if inputs.is_empty() || inputs.len() > MAX_INPUTS {
+        return Err(Error::InvalidFormat(format!("Poseidon hashes between 1 and {} inputs, not {}", MAX_INPUTS, inputs.len())));
+    }
+    Ok(field_to_hex(&hash(&inputs)))
+}
+
+/// Hashes the domain separator `domain` followed by any number of `elements`, by chaining the hashes of chunks of [`MAX_INPUTS`] elements :
+/// `h_0 = domain` and `h_{i+1} = hash(h_i, chunk_i)`, where `domain` is the big-endian integer of its bytes.
+#[cfg(feature = "proofs")]
+pub(crate) fn hash_with_domain(domain: &[u8], elements: &[Fq]) -> Fq {
+    let domain = Fq::from_be_bytes_mod_order(domain);
+    if elements.is_empty() {
+        return hash(&[domain]);
+    }
+    elements.chunks(MAX_INPUTS - 1).fold(domain, |state, chunk| {
+        let inputs: Vec<Fq> = std::iter::once(state).chain(chunk.iter().copied()).collect();
+        hash(&inputs)
+    })
+}
+
+/// Hashes between `1` and [`MAX_INPUTS`] field elements, see [`poseidon_hash`].
+pub(crate) fn hash(inputs: &[Fq]) -> Fq {
+    assert!(!inputs.is_empty() && inputs.len() <= MAX_INPUTS, "Poseidon hashes between 1 and {} inputs", MAX_INPUTS);
+    let t = inputs.len() + 1;
+    let parameters = parameters(t);
+    let partial_rounds = PARTIAL_ROUNDS[t - 2];
+    let mut state: Vec<Fq> = std::iter::once(Fq::zero()).chain(inputs.iter().copied()).collect();
+    for round in 0..FULL_ROUNDS + partial_rounds {
+        for (s, c) in state.iter_mut().zip(&parameters.round_constants[round * t..]) {
+            *s += c;
+        }
+        if round < FULL_ROUNDS / 2 || round >= FULL_ROUNDS / 2 + partial_rounds {
+            state.iter_mut().for_each(sbox);
+        } else {
+            sbox(&mut state[0]);
+        }
+        state = parameters.mds.iter().map(|row| row.iter().zip(&state).map(|(m, s)| *m * s).sum()).collect();
+    }
+    state[0]
+}
+
+fn sbox(x: &mut Fq) {
+    *x *= x.square().square();
+}
+
+fn parameters(t: usize) -> &'static Parameters {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const UNINITIALIZED: OnceLock<Parameters> = OnceLock::new();
+    static PARAMETERS: [OnceLock<Parameters>; MAX_INPUTS] = [UNINITIALIZED; MAX_INPUTS];
+    PARAMETERS[t - 2].get_or_init(|| generate_parameters(t))
+}
+
+/// Generates the parameters of the width `t` like `generate_parameters_grain.sage` in the reference implementation of Poseidon.
+fn generate_parameters(t: usize) -> Parameters {
+    let partial_rounds = PARTIAL_ROUNDS[t - 2];
+    let mut grain = Grain::new(t, partial_rounds);
+    let round_constants = (0..(FULL_ROUNDS + partial_rounds) * t)
+        .map(|_| loop {
+            // rejection sampling of the elements of the field
+            if let Some(c) = Fq::from_repr(grain.next_integer()) {
+                break c;
+            }
+        })
+        .collect();
+    // Cauchy matrix 1 / (x_i + y_j) for distinct x_i and y_j
+    let elements: Vec<Fq> = loop {
+        let elements: Vec<Fq> = (0..2 * t).map(|_| Fq::from_le_bytes_mod_order(&grain.next_integer().to_bytes_le())).collect();
+        if (0..2 * t).all(|i| (0..i).all(|j| elements[i] != elements[j])) {
+            break elements;
+        }
+    };
+    let (xs, ys) = elements.split_at(t);
+    let mds = xs.iter().map(|x| ys.iter().map(|y| (*x + y).inverse().unwrap()).collect()).collect();
+    Parameters { round_constants, mds }
+}
+
+/// Grain LFSR generating the pseudo-random bits of the parameters of Poseidon.
+struct Grain {
+    state: [bool; 80],
+}
+
+impl Grain {
+    fn new(t: usize, partial_rounds: usize) -> Self {
+        let field_bits = Fq::size_in_bits();
+        let mut bits = Vec::with_capacity(80);
+        let mut push = |value: usize, width: usize| bits.extend((0..width).rev().map(|i| (value >> i) & 1 == 1));
+        // prime field, S-box x^alpha
+        push(1, 2);
+        push(0, 4);
+        push(field_bits, 12);
+        push(t, 12);
+        push(FULL_ROUNDS, 10);
+        push(partial_rounds, 10);
+        bits.extend([true; 30]);
+        let mut grain = Grain { state: bits.try_into().unwrap() };
+        for _ in 0..160 {
+            grain.update();
+        }
+        grain
+    }
+
+    fn update(&mut self) -> bool {
+        let s = &self.state;
+        let bit = s[62] ^ s[51] ^ s[38] ^ s[23] ^ s[13] ^ s[0];
+        self.state.rotate_left(1);
+        self.state[79] = bit;
+        bit
+    }
+
+    /// Self-shrinking output : a bit is output only if the bit before it is set.
+    fn next_bit(&mut self) -> bool {
+        loop {
+            if self.update() {
+                return self.update();
+            }
+            self.update();
+        }
+    }
+
+    /// Integer made of as many bits as the modulus of the field, most significant first.
+    fn next_integer(&mut self) -> BigInteger256 {
+        let bits = Fq::size_in_bits();
+        let mut limbs = [0u64; 4];
+        for i in (0..bits).rev() {
+            if self.next_bit() {
+                limbs[i / 64] |= 1 << (i % 64);
+            }
+        }
+        BigInteger256::new(limbs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_poseidon_hash() {
+        // test vectors of circomlib
+        assert_eq!(poseidon_hash(&["0x1"]).unwrap(), field_to_hex(&ark_ff::field_new!(Fq, "18586133768512220936620570745912940619677854269274689475585506675881198879027")));
+        assert_eq!(poseidon_hash(&["0x1", "0x2"]).unwrap(), "0x115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a");
+        assert_eq!(poseidon_hash(&["0x1", "0x2", "0x3", "0x4"]).unwrap(), "0x299c867db6c1fdd79dcefa40e4510b9837e60ebb1ce0663dbaa525df65250465");
+        assert!(matches!(poseidon_hash(&[]), Err(Error::InvalidFormat(_))));
+        assert!(matches!(poseidon_hash(&["0x1"; 17]), Err(Error::InvalidFormat(_))));
+    }
+
+    #[test]
+    #[cfg(feature = "proofs")]
+    fn test_hash_with_domain() {
+        let elements: Vec<Fq> = (1..=20u64).map(Fq::from).collect();
+        let first = hash(&[[Fq::from_be_bytes_mod_order(b"domain")].as_slice(), &elements[..15]].concat());
+        assert_eq!(hash_with_domain(b"domain", &elements), hash(&[[first].as_slice(), &elements[15..]].concat()));
+        assert_ne!(hash_with_domain(b"domain", &elements), hash_with_domain(b"other", &elements));
+    }
+}
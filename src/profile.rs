@@ -0,0 +1,375 @@
+//! Profiles of the systems producing Baby Jubjub keys, points and ciphertexts, so that the keys and points of circom-based systems, such as
+//! the ones built on circomlib or MACI-style tooling, or of zkay, can be used with this crate without converting them by hand.
+//!
+//! The three ecosystems use exponential ElGamal on the very same curve, but not with the same conventions :
+//!
+//! - [`Profile::Noir`] : the generator is the base point `Base8` of circomlib and EIP-2494, see [`crate::curve::Point::generator`], the
+//!   coordinates are the `0x`-prefixed hexadecimal strings of the Twisted Edwards form, and the private key is the scalar itself, like in
+//!   noir-elgamal and in the rest of this crate.
+//! - [`Profile::Circomlib`] : the generator and the points are the same as the ones of Noir, the coordinates can also be the decimal
+//!   integers printed by circomlib, and the private key is a byte string hashed into the scalar like `prv2pub` in circomlib, i.e the scalar
+//!   is `s >> 3`, where `s` is the little-endian integer of the first 32 bytes of the BLAKE-512 hash of the private key, pruned by clearing
+//!   its 3 lowest bits and its highest bit, and setting its second highest bit.
+//! - [`Profile::Zkay`] : the generator is the one of zkay, see [`crate::zkay::GENERATOR`], the coordinates are the ones of the Edwards form
+//!   written as 64 hexadecimal digits of their little-endian bytes, and the private key is the scalar itself.
+//!
+//! A point encoded for a profile and solved with another one, or with a [`Solver`] in base the generator of another profile, used to fail
+//! with [`Error::DlogNotFound`] after the full search : [`Profile::solve`] and [`Profile::decrypt_to_u64`] check the encoding of the points
+//! and the generator of the solver first, and fail with [`Error::ProfileMismatch`] naming the profile of the inputs, see [`Profile::detect`].
+//! A solver for a profile is built with [`Profile::solver`], and the profile of the CLI is selected with `--profile` or in its configuration.
+//!
+//! [`Profile::scalar`] returns the scalar of a private key in the format of [`crate::generate_private_key`], which all the other functions
+//! of this crate accept. EdDSA signatures made with the scalar of a circomlib private key are verified like the ones of circomlib,
+//! see [`crate::eddsa`].
+
+use std::fmt;
+use std::str::FromStr;
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ed_on_bn254::{EdwardsAffine as BabyJubJub, Fr};
+use ark_ff::PrimeField;
+use crate::point::{base_point, field_to_hex, parse_decimal_field, parse_point, parse_scalar, point_to_hex};
+use crate::{decrypt_to_u64, zkay, Ciphertext, Error, HexPoint, Solver};
+
+/// Conventions of a system, see the [module documentation](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Profile {
+    Noir,
+    #[cfg_attr(feature = "serde", serde(alias = "circom"))]
+    Circomlib,
+    Zkay,
+}
+
+impl Profile {
+    pub const ALL: [Profile; 3] = [Profile::Noir, Profile::Circomlib, Profile::Zkay];
+
+    /// Name of the profile, as parsed by [`Profile::from_str`] and in the configuration files.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Profile::Noir => "noir",
+            Profile::Circomlib => "circomlib",
+            Profile::Zkay => "zkay",
+        }
+    }
+
+    /// Scalar of `private_key`, in the same format as [`crate::generate_private_key`].
+    ///
+    /// With [`Profile::Noir`] and [`Profile::Zkay`], `private_key` is a `Field` in the same format as the other inputs of this crate, and
+    /// with [`Profile::Circomlib`], the hexadecimal string of the bytes of the private key, e.g `0x0001...` for the `Buffer` given to `prv2pub`.
+    pub fn scalar(&self, private_key: &str) -> Result<String, Error> {
+        match self {
+            Profile::Noir | Profile::Zkay => crate::curve::canonical_scalar(private_key),
+            Profile::Circomlib => {
+                let digits = private_key.strip_prefix("0x").unwrap_or(private_key);
+                let bytes = hex::decode(digits).map_err(|_| Error::InvalidFormat(private_key.to_string()))?;
+                let mut s = [0u8; 32];
+                s.copy_from_slice(&blake512(&bytes)[..32]);
+                s[0] &= 0xf8;
+                s[31] &= 0x7f;
+                s[31] |= 0x40;
+                // s >> 3, as a little-endian integer
+                let shifted: Vec<u8> = (0..32).map(|i| s[i] >> 3 | s.get(i + 1).map_or(0, |next| next << 5)).collect();
+                Ok(field_to_hex(&Fr::from_le_bytes_mod_order(&shifted)))
+            }
+        }
+    }
+
+    /// Generator of the profile, in its encoding.
+    pub fn generator(&self) -> HexPoint {
+        self.encode(&self.generator_point())
+    }
+
+    /// Public key `sk·G` of `private_key` for the generator `G` of the profile, in its encoding, see [`Profile::scalar`].
+    pub fn public_key(&self, private_key: &str) -> Result<HexPoint, Error> {
+        let sk = parse_scalar(&self.scalar(private_key)?)?;
+        Ok(self.encode(&self.generator_point().mul(sk).into_affine()))
+    }
+
+    /// Encrypts `plaintext` for `public_key` with `randomness`, i.e returns `(r·G, m·G + r·pk)` for the generator `G` of the profile, the
+    /// points being in its encoding, see [`crate::exp_elgamal_encrypt`].
+    pub fn encrypt(&self, public_key: (&str, &str), plaintext: u64, randomness: &str) -> Result<(HexPoint, HexPoint), Error> {
+        let pk = self.point(public_key.0, public_key.1)?;
+        let r = parse_scalar(randomness)?;
+        let g = self.generator_point();
+        let c2 = g.mul(Fr::from(plaintext)) + pk.mul(r);
+        Ok((self.encode(&g.mul(r).into_affine()), self.encode(&c2.into_affine())))
+    }
+
+    /// Decrypts the ciphertext `(C1, C2)` in the encoding of the profile with `private_key` into its embedded plaintext `C2 - sk·C1`,
+    /// see [`Profile::scalar`] and [`crate::exp_elgamal_decrypt`].
+    pub fn decrypt(&self, private_key: &str, c1: (&str, &str), c2: (&str, &str)) -> Result<HexPoint, Error> {
+        let sk = parse_scalar(&self.scalar(private_key)?)?;
+        let (c1, c2) = (self.point(c1.0, c1.1)?, self.point(c2.0, c2.1)?);
+        Ok(self.encode(&(c2.into_projective() - c1.mul(sk)).into_affine()))
+    }
+
+    /// Converts the point `(x, y)` in the encoding of the profile to the format of noir-elgamal and of the rest of this crate, failing with
+    /// [`Error::ProfileMismatch`] if it is in the encoding of another profile only.
+    ///
+    /// The point is the same, but its Discrete Logarithm is still in base the generator of the profile.
+    pub fn to_noir(&self, x: &str, y: &str) -> Result<HexPoint, Error> {
+        Ok(point_to_hex(&self.point(x, y)?))
+    }
+
+    /// Converts the point `(x, y)` in the format of noir-elgamal to the encoding of the profile, the inverse of [`Profile::to_noir`].
+    pub fn from_noir(&self, x: &str, y: &str) -> Result<HexPoint, Error> {
+        Ok(self.encode(&parse_point(x, y)?))
+    }
+
+    /// Profiles whose encoding the point `(x, y)` is in, e.g the only one of zkay for coordinates without `0x` prefix.
+    pub fn detect(x: &str, y: &str) -> Vec<Profile> {
+        Profile::ALL.into_iter().filter(|profile| profile.parse_point(x, y).is_ok()).collect()
+    }
+
+    /// Builds a [`Solver`] in base the generator of the profile, see [`Solver::with_table_bitwidth`].
+    pub fn solver(&self, max_bitwidth: u64, table_bitwidth: u64, num_threads: u64) -> Solver {
+        Solver::with_generator(self.generator_point(), max_bitwidth, table_bitwidth, num_threads)
+    }
+
+    /// Computes the Discrete Logarithm of the point `(x, y)` in the encoding of the profile, in base its generator.
+    ///
+    /// Fails with [`Error::ProfileMismatch`] if `solver` is not in base the generator of the profile, or if the point is in the encoding of
+    /// another profile only.
+    pub fn solve(&self, solver: &Solver, x: &str, y: &str) -> Result<u64, Error> {
+        self.check_solver(solver)?;
+        solver.search_point(self.point(x, y)?).run()
+    }
+
+    /// Decrypts `ciphertext` with `private_key` down to its plaintext, see [`crate::decrypt_to_u64`].
+    ///
+    /// The points of `ciphertext` are in the format of noir-elgamal, e.g converted with [`Profile::to_noir`], and `solver` should be in
+    /// base the generator of the profile.
+    pub fn decrypt_to_u64(&self, private_key: &str, ciphertext: &Ciphertext, solver: &Solver) -> Result<u64, Error> {
+        self.check_solver(solver)?;
+        decrypt_to_u64(&self.scalar(private_key)?, ciphertext, solver)
+    }
+
+    fn generator_point(&self) -> BabyJubJub {
+        match self {
+            Profile::Noir | Profile::Circomlib => base_point(),
+            Profile::Zkay => zkay::generator(),
+        }
+    }
+
+    fn encode(&self, p: &BabyJubJub) -> HexPoint {
+        let (x, y) = point_to_hex(p);
+        match self {
+            Profile::Noir | Profile::Circomlib => (x, y),
+            Profile::Zkay => zkay::from_noir(&x, &y).expect("the point is on the curve"),
+        }
+    }
+
+    fn parse_point(&self, x: &str, y: &str) -> Result<BabyJubJub, Error> {
+        match self {
+            Profile::Noir => parse_point(x, y),
+            Profile::Circomlib => {
+                let hex = |s: &str| if s.starts_with("0x") { Ok(s.to_string()) } else { parse_decimal_field(s).map(|f| field_to_hex(&f)) };
+                parse_point(&hex(x)?, &hex(y)?)
+            }
+            Profile::Zkay => zkay::parse_zkay_point(x, y),
+        }
+    }
+
+    /// Same as [`Profile::parse_point`], naming the profile of the point if it is in the encoding of another one.
+    fn point(&self, x: &str, y: &str) -> Result<BabyJubJub, Error> {
+        self.parse_point(x, y).map_err(|e| self.mismatch(x, y).unwrap_or(e))
+    }
+
+    fn check_solver(&self, solver: &Solver) -> Result<(), Error> {
+        let g = solver.generator();
+        if g == self.generator_point() {
+            return Ok(());
+        }
+        let reason = match Profile::ALL.into_iter().find(|profile| profile.generator_point() == g) {
+            Some(profile) => format!("the solver is in base the generator of the {} profile, not the one of {}", profile, self),
+            None => format!("the solver is not in base the generator of the {} profile", self),
+        };
+        Err(Error::ProfileMismatch(reason))
+    }
+
+    fn mismatch(&self, x: &str, y: &str) -> Option<Error> {
+        let detected: Vec<&str> = Profile::detect(x, y).iter().map(Profile::name).collect();
+        if detected.is_empty() {
+            return None;
+        }
+        Some(Error::ProfileMismatch(format!("({}, {}) is in the encoding of the {} profile, not {}", x, y, detected.join(" or "), self)))
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Profile {
+    type Err = Error;
+
+    /// Parses the name of a profile, `circom` being accepted for [`Profile::Circomlib`].
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match s {
+            "circom" => Ok(Profile::Circomlib),
+            _ => Profile::ALL.into_iter().find(|profile| profile.name() == s)
+                .ok_or_else(|| Error::InvalidConfig(format!("unknown profile {:?}, should be noir, circomlib or zkay", s))),
+        }
+    }
+}
+
+const BLAKE512_IV: [u64; 8] = [
+    0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+    0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179,
+];
+
+const BLAKE512_CONSTANTS: [u64; 16] = [
+    0x243f6a8885a308d3, 0x13198a2e03707344, 0xa4093822299f31d0, 0x082efa98ec4e6c89,
+    0x452821e638d01377, 0xbe5466cf34e90c6c, 0xc0ac29b7c97c50dd, 0x3f84d5b5b5470917,
+    0x9216d5d98979fb1b, 0xd1310ba698dfb5ac, 0x2ffd72dbd01adfb7, 0xb8e1afed6a267e96,
+    0xba7c9045f12c7f99, 0x24a19947b3916cf7, 0x0801f2e2858efc16, 0x636920d871574e69,
+];
+
+const BLAKE_SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// BLAKE-512, the SHA-3 finalist used by circomlib, which is not BLAKE2b.
+fn blake512(message: &[u8]) -> [u8; 64] {
+    let bits = message.len() as u128 * 8;
+    let mut padded = message.to_vec();
+    padded.push(0x80);
+    while padded.len() % 128 != 112 {
+        padded.push(0);
+    }
+    *padded.last_mut().unwrap() |= 0x01;
+    padded.extend(bits.to_be_bytes());
+    let mut h = BLAKE512_IV;
+    for (i, block) in padded.chunks_exact(128).enumerate() {
+        // number of bits of the message hashed at the end of the block, or 0 for a block of padding only
+        let offset = i as u128 * 1024;
+        let counter = if offset < bits { bits.min(offset + 1024) } else { 0 };
+        compress(&mut h, block, counter);
+    }
+    let mut digest = [0u8; 64];
+    for (bytes, word) in digest.chunks_exact_mut(8).zip(h) {
+        bytes.copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+fn compress(h: &mut [u64; 8], block: &[u8], counter: u128) {
+    let m: Vec<u64> = block.chunks_exact(8).map(|word| u64::from_be_bytes(word.try_into().unwrap())).collect();
+    let k = BLAKE512_CONSTANTS;
+    let (t0, t1) = (counter as u64, (counter >> 64) as u64);
+    let mut v = [0u64; 16];
+    v[..8].copy_from_slice(h);
+    v[8..12].copy_from_slice(&k[..4]);
+    v[12..].copy_from_slice(&[t0 ^ k[4], t0 ^ k[5], t1 ^ k[6], t1 ^ k[7]]);
+    for round in 0..16 {
+        let s = &BLAKE_SIGMA[round % 10];
+        let mut g = |i: usize, a: usize, b: usize, c: usize, d: usize| {
+            let (x, y) = (s[2 * i], s[2 * i + 1]);
+            v[a] = v[a].wrapping_add(v[b]).wrapping_add(m[x] ^ k[y]);
+            v[d] = (v[d] ^ v[a]).rotate_right(32);
+            v[c] = v[c].wrapping_add(v[d]);
+            v[b] = (v[b] ^ v[c]).rotate_right(25);
+            v[a] = v[a].wrapping_add(v[b]).wrapping_add(m[y] ^ k[x]);
+            v[d] = (v[d] ^ v[a]).rotate_right(16);
+            v[c] = v[c].wrapping_add(v[d]);
+            v[b] = (v[b] ^ v[c]).rotate_right(11);
+        };
+        g(0, 0, 4, 8, 12);
+        g(1, 1, 5, 9, 13);
+        g(2, 2, 6, 10, 14);
+        g(3, 3, 7, 11, 15);
+        g(4, 0, 5, 10, 15);
+        g(5, 1, 6, 11, 12);
+        g(6, 2, 7, 8, 13);
+        g(7, 3, 4, 9, 14);
+    }
+    for i in 0..8 {
+        h[i] ^= v[i] ^ v[i + 8];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ed_on_bn254::Fq;
+    use crate::{exp_elgamal_encrypt, priv_to_pub_key};
+
+    #[test]
+    fn test_blake512() {
+        // test vectors of the specification of BLAKE
+        assert_eq!(hex::encode(blake512(&[0])), "97961587f6d970faba6d2478045de6d1fabd09b61ae50932054d52bc29d31be4ff9102b9f69e2bbdb83be13d4b9c06091e5fa0b48bd081b634058be0ec49beb3");
+        assert_eq!(hex::encode(blake512(&[0; 144])), "313717d608e9cf758dcb1eb0f0c3cf9fc150b2d500fb33f51c52afc99d358a2f1374b8a38bba7974e7f6ef79cab16f22ce1e649d6e01ad9589c213045d545dde");
+        assert_eq!(hex::encode(blake512(b"")), "a8cfbbd73726062df0c6864dda65defe58ef0cc52a5625090fa17601e1eecd1b628e94f396ae402a00acc9eab77b4d4c2e852aaaa25a636d80af3fc7913ef5b8");
+    }
+
+    #[test]
+    fn test_circomlib_profile() {
+        // test vector of the EdDSA of circomlib
+        let private_key = "0x0001020304050607080900010203040506070809000102030405060708090001";
+        let public_key = Profile::Circomlib.public_key(private_key).unwrap();
+        assert_eq!(public_key.0, field_to_hex(&ark_ff::field_new!(Fq, "13277427435165878497778222415993513565335242147425444199013288855685581939618")));
+        assert_eq!(public_key.1, field_to_hex(&ark_ff::field_new!(Fq, "13622229784656158136036771217484571176836296686641868549125388198837476602820")));
+
+        let ciphertext: Ciphertext = exp_elgamal_encrypt((&public_key.0, &public_key.1), 42, "0x05").unwrap().try_into().unwrap();
+        assert_eq!(Profile::Circomlib.decrypt_to_u64(private_key, &ciphertext, &Solver::new(8, 1)), Ok(42));
+        assert_eq!(Profile::Noir.public_key("0x05").unwrap(), priv_to_pub_key("0x05").unwrap());
+        assert!(matches!(Profile::Circomlib.scalar("0x123"), Err(Error::InvalidFormat(_))));
+    }
+
+    #[test]
+    fn test_profiles() {
+        let noir = Profile::Noir.solver(16, 8, 1);
+        let zkay = Profile::Zkay.solver(16, 8, 1);
+        let g = Profile::Zkay.generator();
+        assert_eq!(g, zkay::from_noir(&point_to_hex(&zkay::generator()).0, &point_to_hex(&zkay::generator()).1).unwrap());
+        assert_eq!(Profile::Zkay.solve(&zkay, &g.0, &g.1), Ok(1));
+
+        let p = Profile::Zkay.public_key("0x0123").unwrap();
+        assert_eq!(Profile::Zkay.solve(&zkay, &p.0, &p.1), Ok(0x0123));
+        let converted = Profile::Zkay.to_noir(&p.0, &p.1).unwrap();
+        assert_eq!(Profile::detect(&p.0, &p.1), [Profile::Zkay]);
+        assert_eq!(Profile::Zkay.from_noir(&converted.0, &converted.1), Ok(p.clone()));
+        assert!(matches!(Profile::Noir.to_noir(&p.0, &p.1), Err(Error::ProfileMismatch(_))));
+        // the points of zkay and the solvers in base another generator are mismatches, instead of Discrete Logarithms not found
+        assert!(matches!(Profile::Noir.solve(&noir, &p.0, &p.1), Err(Error::ProfileMismatch(_))));
+        assert!(matches!(Profile::Zkay.solve(&noir, &p.0, &p.1), Err(Error::ProfileMismatch(_))));
+        assert!(matches!(Profile::Noir.solve(&zkay, &converted.0, &converted.1), Err(Error::ProfileMismatch(_))));
+        // a ciphertext of zkay, (r·G, m·G + r·pk) for its generator G
+        let (g, pk) = (zkay::generator(), parse_point(&converted.0, &converted.1).unwrap());
+        let r = parse_scalar("0x05").unwrap();
+        let ciphertext = Ciphertext { c1: g.mul(r).into_affine(), c2: (g.mul(parse_scalar("0x2a").unwrap()) + pk.mul(r)).into_affine() };
+        assert_eq!(Profile::Zkay.decrypt_to_u64("0x0123", &ciphertext, &zkay), Ok(42));
+        let (c1, c2) = Profile::Zkay.encrypt((&p.0, &p.1), 42, "0x05").unwrap();
+        assert_eq!((Profile::Zkay.to_noir(&c1.0, &c1.1).unwrap(), Profile::Zkay.to_noir(&c2.0, &c2.1).unwrap()), (point_to_hex(&ciphertext.c1), point_to_hex(&ciphertext.c2)));
+        assert_eq!(Profile::Zkay.decrypt("0x0123", (&c1.0, &c1.1), (&c2.0, &c2.1)), Ok(Profile::Zkay.encode(&g.mul(parse_scalar("0x2a").unwrap()).into_affine())));
+        assert!(matches!(Profile::Zkay.decrypt_to_u64("0x0123", &ciphertext, &noir), Err(Error::ProfileMismatch(_))));
+
+        // circomlib prints its coordinates as decimal integers, e.g the ones of Base8
+        let base8 = (
+            "5299619240641551281634865583518297030282874472190772894086521144482721001553",
+            "16950150798460657717958625567821834550301663161624707787222815936182638968203",
+        );
+        assert_eq!(Profile::Circomlib.to_noir(base8.0, base8.1), Ok(Profile::Noir.generator()));
+        assert_eq!(Profile::Circomlib.solve(&noir, base8.0, base8.1), Ok(1));
+        assert!(matches!(Profile::Noir.solve(&noir, base8.0, base8.1), Err(Error::ProfileMismatch(_))));
+        let modulus = "21888242871839275222246405745257275088548364400416034343698204186575808495617";
+        assert!(matches!(Profile::Circomlib.to_noir(modulus, base8.1), Err(Error::InvalidFormat(_))));
+        assert_eq!(Profile::Noir.generator(), Profile::Circomlib.generator());
+
+        assert_eq!("circom".parse::<Profile>(), Ok(Profile::Circomlib));
+        assert_eq!(Profile::ALL.map(|profile| profile.to_string().parse::<Profile>()), Profile::ALL.map(Ok));
+        assert!(matches!("arkworks".parse::<Profile>(), Err(Error::InvalidConfig(_))));
+    }
+}
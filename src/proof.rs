@@ -0,0 +1,430 @@
+//! Non-interactive zero-knowledge proofs about ciphertexts, so that decryptions can be published along a proof of their correctness
+//! instead of as bare numbers, e.g by an auditable tally.
+//!
+//! The proofs are Sigma protocols made non-interactive with the Fiat-Shamir transform : the challenge is the Poseidon hash of a domain
+//! separator followed by the Twisted Edwards coordinates of all the points of the statement and the commitments of the prover, see
+//! [`crate::poseidon`], reduced modulo the order of the prime subgroup of Baby Jubjub. Proofs can thus also be checked cheaply in circuits.
+
+use ark_ec::msm::VariableBaseMSM;
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ed_on_bn254::{EdwardsAffine as BabyJubJub, Fr};
+use ark_ff::{BigInteger, PrimeField, Zero};
+use rand::{CryptoRng, RngCore};
+use crate::keys::random_scalar;
+use crate::point::{base_point, field_to_hex, parse_point, parse_scalar, point_coordinates, point_to_hex, HexPoint};
+use crate::{poseidon, Ciphertext, Error};
+#[cfg(feature = "serde")]
+use crate::elgamal::SerializedPoint;
+
+const DECRYPTION_DOMAIN: &[u8] = b"babygiant-alt-bn128/chaum-pedersen/v1";
+const BIT_DOMAIN: &[u8] = b"babygiant-alt-bn128/bit/v1";
+
+/// Chaum-Pedersen proof that a plaintext point `M` is the decryption `C2 - sk·C1` of a ciphertext `(C1, C2)`, for the private key
+/// `sk` of a public key `pk = sk·G`, i.e that `log_G(pk) = log_C1(C2 - M)`, without revealing `sk`.
+///
+/// The proof is the challenge `e` and the response `z = w + e·sk` of the prover, for its commitments `A = w·G` and `B = w·C1`
+/// which the verifier recomputes as `z·G - e·pk` and `z·C1 - e·(C2 - M)`. The proofs of [`prove_decryption`] also carry `A` and `B`,
+/// so that many of them can be checked at once by [`verify_decryptions`], while the compact proofs of [`DecryptionProof::new`] are
+/// checked one by one.
+///
+/// With the `serde` feature, a proof is (de)serialized as `{"challenge": "0x...", "response": "0x...", "commitments": [{"x": "0x...", "y": "0x..."}, ...]}`,
+/// without `commitments` for a compact proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "SerializedProof", into = "SerializedProof"))]
+pub struct DecryptionProof {
+    challenge: Fr,
+    response: Fr,
+    commitments: Option<(BabyJubJub, BabyJubJub)>,
+}
+
+impl DecryptionProof {
+    /// Parses a proof from its challenge and response, in the same format as [`crate::generate_private_key`].
+    pub fn new(challenge: &str, response: &str) -> Result<Self, Error> {
+        Ok(DecryptionProof { challenge: parse_scalar(challenge)?, response: parse_scalar(response)?, commitments: None })
+    }
+
+    /// Parses a proof from its challenge, its response and its commitments `A` and `B`, which should be points of the prime subgroup.
+    pub fn with_commitments(challenge: &str, response: &str, a: (&str, &str), b: (&str, &str)) -> Result<Self, Error> {
+        let commitments = Some((parse_point(a.0, a.1)?, parse_point(b.0, b.1)?));
+        Ok(DecryptionProof { commitments, ..DecryptionProof::new(challenge, response)? })
+    }
+
+    pub fn challenge(&self) -> String {
+        field_to_hex(&self.challenge)
+    }
+
+    pub fn response(&self) -> String {
+        field_to_hex(&self.response)
+    }
+
+    /// Commitments `A` and `B` of the prover, or `None` for a compact proof.
+    pub fn commitments(&self) -> Option<(HexPoint, HexPoint)> {
+        self.commitments.map(|(a, b)| (point_to_hex(&a), point_to_hex(&b)))
+    }
+}
+
+/// Decrypts `ciphertext` into its embedded plaintext, exactly like [`crate::exp_elgamal_decrypt`], and proves the correctness of the decryption.
+///
+/// `rng` draws the commitment of the proof, e.g `rand::rngs::OsRng`, or a seeded generator for reproducible tests.
+pub fn prove_decryption<R: RngCore + CryptoRng>(private_key: &str, ciphertext: &Ciphertext, rng: &mut R) -> Result<(HexPoint, DecryptionProof), Error> {
+    let sk = parse_scalar(private_key)?;
+    let pk = base_point().mul(sk).into_affine();
+    let plaintext = ciphertext.decrypt_point(sk);
+    let w = random_scalar(rng);
+    let a = base_point().mul(w).into_affine();
+    let b = ciphertext.c1.mul(w).into_affine();
+    let challenge = decryption_challenge(&pk, ciphertext, &plaintext, &a, &b);
+    Ok((point_to_hex(&plaintext), DecryptionProof { challenge, response: w + challenge * sk, commitments: Some((a, b)) }))
+}
+
+/// Checks that `proof` proves that `plaintext` is the decryption of `ciphertext` by the private key of `public_key`.
+///
+/// Returns an error if `public_key` or `plaintext` are not valid points, and `false` if the proof is invalid.
+/// Claimed plaintexts published as integers are embedded with [`crate::embed_plaintext`].
+pub fn verify_decryption(public_key: (&str, &str), ciphertext: &Ciphertext, plaintext: (&str, &str), proof: &DecryptionProof) -> Result<bool, Error> {
+    let pk = parse_point(public_key.0, public_key.1)?;
+    let m = parse_point(plaintext.0, plaintext.1)?;
+    let (e, z) = (proof.challenge, proof.response);
+    let a = (base_point().mul(z) - pk.mul(e)).into_affine();
+    let shared_secret = ciphertext.c2.into_projective() - m.into_projective();
+    let b = (ciphertext.c1.mul(z) - shared_secret.into_affine().mul(e)).into_affine();
+    if proof.commitments.is_some_and(|commitments| commitments != (a, b)) {
+        return Ok(false);
+    }
+    Ok(decryption_challenge(&pk, ciphertext, &m, &a, &b) == e)
+}
+
+/// Checks that the proof of each `(ciphertext, plaintext, proof)` of `decryptions` proves that `plaintext` is the decryption of
+/// `ciphertext` by the private key of `public_key`, like [`verify_decryption`], but much faster for many proofs, e.g the published
+/// decryptions of a whole election.
+///
+/// The equations `z·G = A + e·pk` and `z·C1 = B + e·(C2 - M)` of all the proofs carrying their commitments are combined with random
+/// coefficients drawn by `rng` into a single multi-scalar multiplication, which is zero for valid proofs and for invalid ones except
+/// with negligible probability. Only the challenges are still hashed one by one, and compact proofs are checked with [`verify_decryption`].
+///
+/// Returns an error if `public_key` or a plaintext are not valid points, and `false` if any proof is invalid, without telling which one.
+pub fn verify_decryptions<R: RngCore + CryptoRng>(public_key: (&str, &str), decryptions: &[(Ciphertext, HexPoint, DecryptionProof)], rng: &mut R) -> Result<bool, Error> {
+    let pk = parse_point(public_key.0, public_key.1)?;
+    // Σ ρ·(z·G - A - e·pk) + σ·(z·C1 - B - e·C2 + e·M), with the coefficients of G and pk gathered
+    let (mut g_scalar, mut pk_scalar) = (Fr::zero(), Fr::zero());
+    let mut bases = vec![];
+    let mut scalars = vec![];
+    for (ciphertext, plaintext, proof) in decryptions {
+        let m = parse_point(&plaintext.0, &plaintext.1)?;
+        let Some((a, b)) = proof.commitments else {
+            if !verify_decryption(public_key, ciphertext, (&plaintext.0, &plaintext.1), proof)? {
+                return Ok(false);
+            }
+            continue;
+        };
+        let (e, z) = (proof.challenge, proof.response);
+        if decryption_challenge(&pk, ciphertext, &m, &a, &b) != e {
+            return Ok(false);
+        }
+        let (rho, sigma) = (random_scalar(rng), random_scalar(rng));
+        g_scalar += rho * z;
+        pk_scalar -= rho * e;
+        bases.extend([a, ciphertext.c1, b, ciphertext.c2, m]);
+        scalars.extend([-rho, sigma * z, -sigma, -(sigma * e), sigma * e]);
+    }
+    bases.extend([base_point(), pk]);
+    scalars.extend([g_scalar, pk_scalar]);
+    let scalars: Vec<_> = scalars.iter().map(|s| s.into_repr()).collect();
+    Ok(VariableBaseMSM::multi_scalar_mul(&bases, &scalars).is_zero())
+}
+
+/// Proof that a ciphertext encrypts an integer of [`RangeProof::bitwidth`] bits, i.e a plaintext whose Discrete Logarithm can be found,
+/// without revealing it.
+///
+/// The proof decomposes the ciphertext into the encryptions `(r_i·G, b_i·G + r_i·pk)` of the bits `b_i` of the plaintext, whose sum
+/// weighted by `2^i` is the ciphertext, and proves that each of them encrypts `0` or `1` with a disjunctive Chaum-Pedersen proof :
+/// either `log_G(C1_i) = log_pk(C2_i)` or `log_G(C1_i) = log_pk(C2_i - G)`. Its size grows linearly with the bitwidth.
+///
+/// With the `serde` feature, a proof is (de)serialized as `{"bits": [{"ciphertext": ..., "challenges": [e0, e1], "responses": [z0, z1]}, ...]}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RangeProof {
+    bits: Vec<BitProof>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "SerializedBitProof", into = "SerializedBitProof"))]
+struct BitProof {
+    ciphertext: Ciphertext,
+    challenges: [Fr; 2],
+    responses: [Fr; 2],
+}
+
+impl RangeProof {
+    /// Number of bits of the plaintext.
+    pub fn bitwidth(&self) -> u64 {
+        self.bits.len() as u64
+    }
+}
+
+/// Proves that the encryption of `plaintext` for `public_key` with `randomness`, see [`crate::exp_elgamal_encrypt`], is an integer of `max_bitwidth` bits.
+///
+/// `rng` draws the randomness of the encryptions of the bits and of their proofs, see [`prove_decryption`].
+pub fn prove_range<R: RngCore + CryptoRng>(public_key: (&str, &str), plaintext: u64, randomness: &str, max_bitwidth: u64, rng: &mut R) -> Result<RangeProof, Error> {
+    crate::assert_encryptable(plaintext, max_bitwidth)?;
+    let pk = parse_point(public_key.0, public_key.1)?;
+    let r = parse_scalar(randomness)?;
+    let g = base_point();
+    // the randomness of the first bit makes the weighted sum of the randomness of the bits equal to the one of the ciphertext
+    let mut randomness: Vec<Fr> = (1..max_bitwidth).map(|_| random_scalar(rng)).collect();
+    let rest = randomness.iter().enumerate().fold(Fr::from(0u64), |sum, (i, r_i)| sum + Fr::from(2u64 << i) * r_i);
+    randomness.insert(0, r - rest);
+
+    let bits = randomness
+        .into_iter()
+        .enumerate()
+        .map(|(i, r_i)| {
+            let bit = (plaintext >> i) & 1;
+            let c1 = g.mul(r_i).into_affine();
+            let c2 = (pk.mul(r_i) + g.mul(Fr::from(bit))).into_affine();
+            let ciphertext = Ciphertext { c1, c2 };
+            // the branch of the other bit is simulated from a random challenge and response
+            let (fake, real) = (1 - bit as usize, bit as usize);
+            let mut challenges = [Fr::from(0u64); 2];
+            let mut responses = [Fr::from(0u64); 2];
+            let mut commitments = [(g, g); 2];
+            challenges[fake] = random_scalar(rng);
+            responses[fake] = random_scalar(rng);
+            commitments[fake] = bit_commitments(&pk, &ciphertext, fake, challenges[fake], responses[fake]);
+            let w = random_scalar(rng);
+            commitments[real] = (g.mul(w).into_affine(), pk.mul(w).into_affine());
+            let challenge = bit_challenge(i, &pk, &ciphertext, &commitments);
+            challenges[real] = challenge - challenges[fake];
+            responses[real] = w + challenges[real] * r_i;
+            BitProof { ciphertext, challenges, responses }
+        })
+        .collect();
+    Ok(RangeProof { bits })
+}
+
+/// Checks that `proof` proves that `ciphertext`, encrypted for `public_key`, encrypts an integer of at most `max_bitwidth` bits.
+///
+/// Returns an error if `public_key` is not a valid point, and `false` if the proof is invalid or for a larger bitwidth.
+pub fn verify_range(public_key: (&str, &str), ciphertext: &Ciphertext, max_bitwidth: u64, proof: &RangeProof) -> Result<bool, Error> {
+    let pk = parse_point(public_key.0, public_key.1)?;
+    if proof.bits.is_empty() || proof.bitwidth() > max_bitwidth.min(64) {
+        return Ok(false);
+    }
+    let weighted_sum: Ciphertext = proof.bits.iter().enumerate().map(|(i, bit)| bit.ciphertext.mul_plaintext(1 << i)).sum();
+    if weighted_sum != *ciphertext {
+        return Ok(false);
+    }
+    Ok(proof.bits.iter().enumerate().all(|(i, bit)| {
+        let commitments = [0, 1].map(|b| bit_commitments(&pk, &bit.ciphertext, b, bit.challenges[b], bit.responses[b]));
+        bit_challenge(i, &pk, &bit.ciphertext, &commitments) == bit.challenges[0] + bit.challenges[1]
+    }))
+}
+
+/// Commitments `(z·G - e·C1, z·pk - e·(C2 - b·G))` of the branch of the bit `b`, recomputed from its challenge `e` and response `z`.
+fn bit_commitments(pk: &BabyJubJub, ciphertext: &Ciphertext, b: usize, e: Fr, z: Fr) -> (BabyJubJub, BabyJubJub) {
+    let g = base_point();
+    let c2 = if b == 1 { ciphertext.c2.into_projective() - g.into_projective() } else { ciphertext.c2.into_projective() };
+    let a = g.mul(z) - ciphertext.c1.mul(e);
+    let b = pk.mul(z) - c2.into_affine().mul(e);
+    (a.into_affine(), b.into_affine())
+}
+
+fn bit_challenge(index: usize, pk: &BabyJubJub, ciphertext: &Ciphertext, commitments: &[(BabyJubJub, BabyJubJub); 2]) -> Fr {
+    let domain = [BIT_DOMAIN, &(index as u64).to_be_bytes()].concat();
+    let [(a0, b0), (a1, b1)] = commitments;
+    challenge(&domain, &[&base_point(), pk, &ciphertext.c1, &ciphertext.c2, a0, b0, a1, b1])
+}
+
+fn decryption_challenge(pk: &BabyJubJub, ciphertext: &Ciphertext, plaintext: &BabyJubJub, a: &BabyJubJub, b: &BabyJubJub) -> Fr {
+    challenge(DECRYPTION_DOMAIN, &[&base_point(), pk, &ciphertext.c1, &ciphertext.c2, plaintext, a, b])
+}
+
+/// Fiat-Shamir challenge of the transcript `points`, see the [module documentation](self).
+fn challenge(domain: &[u8], points: &[&BabyJubJub]) -> Fr {
+    let coordinates: Vec<_> = points.iter().flat_map(|point| point_coordinates(point)).collect();
+    let hash = poseidon::hash_with_domain(domain, &coordinates);
+    Fr::from_le_bytes_mod_order(&hash.into_repr().to_bytes_le())
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SerializedProof {
+    challenge: String,
+    response: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    commitments: Option<[SerializedPoint; 2]>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SerializedProof> for DecryptionProof {
+    type Error = Error;
+
+    fn try_from(p: SerializedProof) -> Result<Self, Error> {
+        match &p.commitments {
+            None => DecryptionProof::new(&p.challenge, &p.response),
+            Some([a, b]) => DecryptionProof::with_commitments(&p.challenge, &p.response, (&a.x, &a.y), (&b.x, &b.y)),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<DecryptionProof> for SerializedProof {
+    fn from(proof: DecryptionProof) -> Self {
+        let point = |(x, y): HexPoint| SerializedPoint { x, y };
+        let commitments = proof.commitments().map(|(a, b)| [point(a), point(b)]);
+        SerializedProof { challenge: proof.challenge(), response: proof.response(), commitments }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SerializedBitProof {
+    ciphertext: Ciphertext,
+    challenges: [String; 2],
+    responses: [String; 2],
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SerializedBitProof> for BitProof {
+    type Error = Error;
+
+    fn try_from(p: SerializedBitProof) -> Result<Self, Error> {
+        let [e0, e1] = &p.challenges;
+        let [z0, z1] = &p.responses;
+        Ok(BitProof { ciphertext: p.ciphertext, challenges: [parse_scalar(e0)?, parse_scalar(e1)?], responses: [parse_scalar(z0)?, parse_scalar(z1)?] })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<BitProof> for SerializedBitProof {
+    fn from(p: BitProof) -> Self {
+        SerializedBitProof { ciphertext: p.ciphertext, challenges: p.challenges.map(|e| field_to_hex(&e)), responses: p.responses.map(|z| field_to_hex(&z)) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::OsRng;
+    use crate::{embed_plaintext, exp_elgamal_decrypt, exp_elgamal_encrypt, priv_to_pub_key};
+
+    #[test]
+    fn test_decryption_proof() {
+        let private_key = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        let pk = priv_to_pub_key(private_key).unwrap();
+        let pk = (pk.0.as_str(), pk.1.as_str());
+        let (c1, c2) = exp_elgamal_encrypt(pk, 42, "0x03f90f366f9fd55bb1335eac3b11f2190f2ce9ff1769db241edaa7774136099b").unwrap();
+        let ciphertext = Ciphertext::new((&c1.0, &c1.1), (&c2.0, &c2.1)).unwrap();
+
+        let (plaintext, proof) = prove_decryption(private_key, &ciphertext, &mut OsRng).unwrap();
+        assert_eq!(plaintext, exp_elgamal_decrypt(private_key, (&c1.0, &c1.1), (&c2.0, &c2.1)).unwrap());
+        assert_eq!(verify_decryption(pk, &ciphertext, (&plaintext.0, &plaintext.1), &proof), Ok(true));
+        let parsed = DecryptionProof::new(&proof.challenge(), &proof.response()).unwrap();
+        assert_eq!(verify_decryption(pk, &ciphertext, (&plaintext.0, &plaintext.1), &parsed), Ok(true));
+
+        // a wrong plaintext, another ciphertext, another public key, or a tampered proof are rejected
+        let wrong = embed_plaintext(43);
+        assert_eq!(verify_decryption(pk, &ciphertext, (&wrong.0, &wrong.1), &proof), Ok(false));
+        assert_eq!(verify_decryption(pk, &(ciphertext + ciphertext), (&plaintext.0, &plaintext.1), &proof), Ok(false));
+        let other = priv_to_pub_key("0x05").unwrap();
+        assert_eq!(verify_decryption((&other.0, &other.1), &ciphertext, (&plaintext.0, &plaintext.1), &proof), Ok(false));
+        let tampered = DecryptionProof { response: proof.response + Fr::from(1u64), ..proof };
+        assert_eq!(verify_decryption(pk, &ciphertext, (&plaintext.0, &plaintext.1), &tampered), Ok(false));
+        assert_eq!(verify_decryption(pk, &ciphertext, ("0x1", "0x1"), &proof), Err(Error::NotOnCurve));
+        let (a, b) = proof.commitments().unwrap();
+        let with_commitments = DecryptionProof::with_commitments(&proof.challenge(), &proof.response(), (&a.0, &a.1), (&b.0, &b.1));
+        assert_eq!(with_commitments, Ok(proof));
+        let swapped = DecryptionProof::with_commitments(&proof.challenge(), &proof.response(), (&b.0, &b.1), (&a.0, &a.1)).unwrap();
+        assert_eq!(verify_decryption(pk, &ciphertext, (&plaintext.0, &plaintext.1), &swapped), Ok(false));
+    }
+
+    #[test]
+    fn test_batch_decryption_proofs() {
+        let private_key = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        let pk = priv_to_pub_key(private_key).unwrap();
+        let pk = (pk.0.as_str(), pk.1.as_str());
+        let mut decryptions: Vec<_> = (0..8u64)
+            .map(|i| {
+                let ciphertext: Ciphertext = exp_elgamal_encrypt(pk, i * 1000, &format!("{:#x}", i + 3)).unwrap().try_into().unwrap();
+                let (plaintext, proof) = prove_decryption(private_key, &ciphertext, &mut OsRng).unwrap();
+                (ciphertext, plaintext, proof)
+            })
+            .collect();
+        assert_eq!(verify_decryptions(pk, &decryptions, &mut OsRng), Ok(true));
+        assert_eq!(verify_decryptions(pk, &[], &mut OsRng), Ok(true));
+        // compact proofs are checked one by one
+        let compact = DecryptionProof::new(&decryptions[2].2.challenge(), &decryptions[2].2.response()).unwrap();
+        decryptions[2].2 = compact;
+        assert_eq!(verify_decryptions(pk, &decryptions, &mut OsRng), Ok(true));
+
+        // a wrong plaintext, a tampered response or commitment, or another public key are rejected
+        let mut wrong = decryptions.clone();
+        wrong[5].1 = embed_plaintext(5001);
+        assert_eq!(verify_decryptions(pk, &wrong, &mut OsRng), Ok(false));
+        let mut tampered = decryptions.clone();
+        tampered[7].2.response += Fr::from(1u64);
+        assert_eq!(verify_decryptions(pk, &tampered, &mut OsRng), Ok(false));
+        let mut tampered = decryptions.clone();
+        tampered[1].2.commitments = tampered[1].2.commitments.map(|(a, b)| (b, a));
+        assert_eq!(verify_decryptions(pk, &tampered, &mut OsRng), Ok(false));
+        let other = priv_to_pub_key("0x05").unwrap();
+        assert_eq!(verify_decryptions((&other.0, &other.1), &decryptions, &mut OsRng), Ok(false));
+        wrong[0].1 = ("0x1".to_string(), "0x1".to_string());
+        assert_eq!(verify_decryptions(pk, &wrong, &mut OsRng), Err(Error::NotOnCurve));
+    }
+
+    #[test]
+    fn test_range_proof() {
+        let pk = priv_to_pub_key("0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c").unwrap();
+        let pk = (pk.0.as_str(), pk.1.as_str());
+        let randomness = "0x03f90f366f9fd55bb1335eac3b11f2190f2ce9ff1769db241edaa7774136099b";
+        let ciphertext: Ciphertext = exp_elgamal_encrypt(pk, 1000, randomness).unwrap().try_into().unwrap();
+
+        let proof = prove_range(pk, 1000, randomness, 10, &mut OsRng).unwrap();
+        assert_eq!(proof.bitwidth(), 10);
+        assert_eq!(verify_range(pk, &ciphertext, 10, &proof), Ok(true));
+        assert_eq!(verify_range(pk, &ciphertext, 40, &proof), Ok(true));
+        // the proof is for more bits than accepted, for another ciphertext, or for another public key
+        assert_eq!(verify_range(pk, &ciphertext, 9, &proof), Ok(false));
+        assert_eq!(verify_range(pk, &(ciphertext + ciphertext), 10, &proof), Ok(false));
+        let other = priv_to_pub_key("0x05").unwrap();
+        assert_eq!(verify_range((&other.0, &other.1), &ciphertext, 10, &proof), Ok(false));
+
+        // a bit encrypting 2 cannot be proven, even if the weighted sum matches
+        let mut tampered = proof.clone();
+        tampered.bits[0].ciphertext = tampered.bits[0].ciphertext + tampered.bits[1].ciphertext.mul_plaintext(2);
+        tampered.bits[1].ciphertext = Ciphertext::zero();
+        assert_eq!(verify_range(pk, &ciphertext, 10, &tampered), Ok(false));
+
+        assert_eq!(prove_range(pk, 1024, randomness, 10, &mut OsRng), Err(Error::PlaintextOutOfRange { plaintext: 1024, max_bitwidth: 10 }));
+        assert!(prove_range(pk, u64::MAX, randomness, 64, &mut OsRng).is_ok());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_range_proof_serde() {
+        let pk = point_to_hex(&base_point());
+        let ciphertext: Ciphertext = exp_elgamal_encrypt((&pk.0, &pk.1), 5, "0x03").unwrap().try_into().unwrap();
+        let proof = prove_range((&pk.0, &pk.1), 5, "0x03", 4, &mut OsRng).unwrap();
+        let json = serde_json::to_string(&proof).unwrap();
+        let parsed: RangeProof = serde_json::from_str(&json).unwrap();
+        assert_eq!(verify_range((&pk.0, &pk.1), &ciphertext, 4, &parsed), Ok(true));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_decryption_proof_serde() {
+        let ciphertext = Ciphertext::zero();
+        let (_, proof) = prove_decryption("0x05", &ciphertext, &mut OsRng).unwrap();
+        let compact = DecryptionProof::new(&proof.challenge(), &proof.response()).unwrap();
+        let json = serde_json::to_string(&compact).unwrap();
+        assert_eq!(json, format!("{{\"challenge\":\"{}\",\"response\":\"{}\"}}", proof.challenge(), proof.response()));
+        assert_eq!(serde_json::from_str::<DecryptionProof>(&json).unwrap(), compact);
+        let json = serde_json::to_string(&proof).unwrap();
+        let (a, b) = proof.commitments().unwrap();
+        assert!(json.ends_with(&format!("\"commitments\":[{{\"x\":\"{}\",\"y\":\"{}\"}},{{\"x\":\"{}\",\"y\":\"{}\"}}]}}", a.0, a.1, b.0, b.1)));
+        assert_eq!(serde_json::from_str::<DecryptionProof>(&json).unwrap(), proof);
+    }
+}
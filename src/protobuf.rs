@@ -0,0 +1,98 @@
+//! Protobuf messages of the points, the ciphertexts, the solve requests and their results, defined in `proto/messages.proto` shipped
+//! with the crate, so that the [`crate::grpc`] service, the queue workers and the clients in other languages share one wire schema.
+//!
+//! The types are generated by prost, and encoded and decoded with [`prost::Message`], e.g `request.encode_to_vec()`, or [`decode`]
+//! which returns the errors of this crate. A [`SolveRequest`] holds either an embedded plaintext or a ciphertext with its private key,
+//! like the inputs of the [`crate::json`] module, and is solved by [`SolveRequest::solve`].
+
+use crate::point::point_to_hex;
+use crate::{decrypt_to_u64, Error, HexPoint, Solver};
+
+include!(concat!(env!("OUT_DIR"), "/babygiant.v1.rs"));
+
+/// Decodes the protobuf message `bytes`, e.g a [`SolveRequest`] received from a queue.
+pub fn decode<M: prost::Message + Default>(bytes: &[u8]) -> Result<M, Error> {
+    M::decode(bytes).map_err(|e| Error::InvalidProtobuf(e.to_string()))
+}
+
+impl From<HexPoint> for Point {
+    fn from((x, y): HexPoint) -> Self {
+        Point { x, y }
+    }
+}
+
+impl From<Point> for HexPoint {
+    fn from(point: Point) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl From<&crate::Ciphertext> for Ciphertext {
+    fn from(ciphertext: &crate::Ciphertext) -> Self {
+        Ciphertext { c1: Some(point_to_hex(&ciphertext.c1).into()), c2: Some(point_to_hex(&ciphertext.c2).into()) }
+    }
+}
+
+impl TryFrom<&Ciphertext> for crate::Ciphertext {
+    type Error = Error;
+
+    fn try_from(ciphertext: &Ciphertext) -> Result<Self, Error> {
+        let missing = |name: &str| Error::InvalidProtobuf(format!("the ciphertext has no point {}", name));
+        let c1 = ciphertext.c1.as_ref().ok_or_else(|| missing("c1"))?;
+        let c2 = ciphertext.c2.as_ref().ok_or_else(|| missing("c2"))?;
+        crate::Ciphertext::new((&c1.x, &c1.y), (&c2.x, &c2.y))
+    }
+}
+
+impl From<Result<u64, Error>> for DlogResult {
+    fn from(result: Result<u64, Error>) -> Self {
+        let outcome = match result {
+            Ok(dlog) => dlog_result::Outcome::Dlog(dlog),
+            Err(e) => dlog_result::Outcome::Error(e.to_string()),
+        };
+        DlogResult { outcome: Some(outcome) }
+    }
+}
+
+impl SolveRequest {
+    /// Computes the Discrete Logarithm of the embedded plaintext, decrypting it first for a ciphertext.
+    pub fn solve(&self, solver: &Solver) -> Result<u64, Error> {
+        match &self.input {
+            Some(solve_request::Input::Point(point)) => solver.solve(&point.x, &point.y),
+            Some(solve_request::Input::Decrypt(DecryptRequest { private_key, ciphertext: Some(ciphertext) })) => {
+                decrypt_to_u64(private_key, &ciphertext.try_into()?, solver)
+            }
+            Some(solve_request::Input::Decrypt(_)) => Err(Error::InvalidProtobuf("the decrypt request has no ciphertext".to_string())),
+            None => Err(Error::InvalidProtobuf("the solve request has no point or ciphertext".to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost::Message;
+    use crate::{embed_plaintext, exp_elgamal_encrypt, priv_to_pub_key};
+
+    #[test]
+    fn test_protobuf() {
+        let solver = Solver::new(20, 2);
+        let request = SolveRequest { input: Some(solve_request::Input::Point(embed_plaintext(42).into())) };
+        let decoded: SolveRequest = decode(&request.encode_to_vec()).unwrap();
+        assert_eq!(decoded, request);
+        assert_eq!(decoded.solve(&solver), Ok(42));
+
+        let private_key = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        let pk = priv_to_pub_key(private_key).unwrap();
+        let ciphertext: crate::Ciphertext = exp_elgamal_encrypt((&pk.0, &pk.1), 65545, "0x05").unwrap().try_into().unwrap();
+        let message = Ciphertext::from(&ciphertext);
+        assert_eq!(crate::Ciphertext::try_from(&message), Ok(ciphertext));
+        let decrypt = DecryptRequest { private_key: private_key.to_string(), ciphertext: Some(message) };
+        let request = SolveRequest { input: Some(solve_request::Input::Decrypt(decrypt)) };
+        assert_eq!(decode::<SolveRequest>(&request.encode_to_vec()).and_then(|r| r.solve(&solver)), Ok(65545));
+
+        assert_eq!(DlogResult::from(Ok(7)).encode_to_vec(), [0x08, 0x07]);
+        assert!(matches!(SolveRequest::default().solve(&solver), Err(Error::InvalidProtobuf(_))));
+        assert!(matches!(decode::<SolveRequest>(&[0x0a, 0x05]), Err(Error::InvalidProtobuf(_))));
+    }
+}
@@ -0,0 +1,117 @@
+//! Decryption with a private key held by a [`KeyProvider`], e.g a hardware security module reached through PKCS#11 or a cloud KMS,
+//! so that production deployments never load the raw private key into the application process.
+//!
+//! The only operation of a decryption which needs the private key `sk` is the multiplication `sk·C1` of the first point of a
+//! ciphertext, which [`KeyProvider::mul_private_key`] performs. The rest of the decryption, i.e the subtraction from `C2` and the
+//! Baby-step Giant-step search, runs in the application with [`decrypt`] and [`decrypt_to_u64`], and [`shared_secret`] uses the same
+//! multiplication for Diffie-Hellman. [`InMemoryKeyProvider`] keeps the private key in memory, e.g for tests or development, and
+//! external backends implement the trait by forwarding the multiplication to their device or service, reporting their failures
+//! with [`Error::KeyProvider`].
+
+use std::fmt;
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ed_on_bn254::Fr;
+use crate::curve::Point;
+use crate::point::{base_point, parse_point, parse_scalar, point_to_hex};
+use crate::{Ciphertext, Error, HexPoint, Solver};
+
+/// Holder of a private key `sk`, which multiplies points by `sk` without revealing it, see the [module documentation](self).
+pub trait KeyProvider {
+    /// Public key `sk·G` of the private key.
+    fn public_key(&self) -> Result<Point, Error>;
+
+    /// Multiplies `point`, a point of the prime subgroup such as the `C1` of a ciphertext, by the private key.
+    fn mul_private_key(&self, point: &Point) -> Result<Point, Error>;
+}
+
+/// Provider keeping the private key in the memory of the process, like passing it to [`crate::decrypt_to_u64`] directly.
+///
+/// Its `Debug` implementation does not print the private key.
+#[derive(Clone, PartialEq, Eq)]
+pub struct InMemoryKeyProvider {
+    sk: Fr,
+}
+
+impl InMemoryKeyProvider {
+    /// Provider of `private_key`, in the same format as [`crate::generate_private_key`].
+    pub fn new(private_key: &str) -> Result<Self, Error> {
+        Ok(InMemoryKeyProvider { sk: parse_scalar(private_key)? })
+    }
+}
+
+impl fmt::Debug for InMemoryKeyProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InMemoryKeyProvider").finish_non_exhaustive()
+    }
+}
+
+impl KeyProvider for InMemoryKeyProvider {
+    fn public_key(&self) -> Result<Point, Error> {
+        Ok(Point::from(base_point().mul(self.sk).into_affine()))
+    }
+
+    fn mul_private_key(&self, point: &Point) -> Result<Point, Error> {
+        Ok(point.mul_scalar(self.sk))
+    }
+}
+
+/// Decrypts `ciphertext` with the private key of `provider` into its embedded plaintext, like [`crate::exp_elgamal_decrypt`].
+pub fn decrypt<K: KeyProvider + ?Sized>(provider: &K, ciphertext: &Ciphertext) -> Result<HexPoint, Error> {
+    let shared = provider.mul_private_key(&Point::from(ciphertext.c1))?.to_edwards();
+    Ok(point_to_hex(&(ciphertext.c2.into_projective() - shared.into_projective()).into_affine()))
+}
+
+/// Decrypts `ciphertext` with the private key of `provider` down to its plaintext, like [`crate::decrypt_to_u64`].
+pub fn decrypt_to_u64<K: KeyProvider + ?Sized>(provider: &K, ciphertext: &Ciphertext, solver: &Solver) -> Result<u64, Error> {
+    let plaintext = decrypt(provider, ciphertext)?;
+    solver.solve(&plaintext.0, &plaintext.1)
+}
+
+/// Computes the Diffie-Hellman shared secret of the private key of `provider` and `public_key`, like [`crate::shared_secret`].
+pub fn shared_secret<K: KeyProvider + ?Sized>(provider: &K, public_key: (&str, &str)) -> Result<HexPoint, Error> {
+    let pk = Point::from(parse_point(public_key.0, public_key.1)?);
+    if pk.is_identity() {
+        return Err(Error::IdentityPublicKey);
+    }
+    Ok(provider.mul_private_key(&pk)?.to_hex())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{exp_elgamal_decrypt, exp_elgamal_encrypt, priv_to_pub_key};
+
+    /// Provider of a device which is unreachable.
+    struct Unreachable;
+
+    impl KeyProvider for Unreachable {
+        fn public_key(&self) -> Result<Point, Error> {
+            Err(Error::KeyProvider("the device is unreachable".to_string()))
+        }
+
+        fn mul_private_key(&self, _: &Point) -> Result<Point, Error> {
+            self.public_key()
+        }
+    }
+
+    #[test]
+    fn test_key_provider() {
+        let private_key = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        let provider = InMemoryKeyProvider::new(private_key).unwrap();
+        let pk = priv_to_pub_key(private_key).unwrap();
+        assert_eq!(provider.public_key().unwrap().to_hex(), pk);
+        assert!(!format!("{:?}", provider).contains(&format!("{}", provider.sk)));
+
+        let (c1, c2) = exp_elgamal_encrypt((&pk.0, &pk.1), 42, "0x05").unwrap();
+        let ciphertext = Ciphertext::new((&c1.0, &c1.1), (&c2.0, &c2.1)).unwrap();
+        assert_eq!(decrypt(&provider, &ciphertext), exp_elgamal_decrypt(private_key, (&c1.0, &c1.1), (&c2.0, &c2.1)));
+        let boxed: Box<dyn KeyProvider> = Box::new(provider.clone());
+        assert_eq!(decrypt_to_u64(boxed.as_ref(), &ciphertext, &Solver::new(8, 1)), Ok(42));
+
+        let peer = priv_to_pub_key("0x05").unwrap();
+        assert_eq!(shared_secret(&provider, (&peer.0, &peer.1)), crate::shared_secret(private_key, (&peer.0, &peer.1)));
+        let identity = Point::identity().to_hex();
+        assert_eq!(shared_secret(&provider, (&identity.0, &identity.1)), Err(Error::IdentityPublicKey));
+        assert!(matches!(decrypt(&Unreachable, &ciphertext), Err(Error::KeyProvider(_))));
+    }
+}
@@ -0,0 +1,135 @@
+//! Exponential ElGamal on Ristretto255, the prime-order group built on Curve25519 of `curve25519-dalek`, for the stacks without pairings
+//! which encrypt with ElGamal in the exponent, e.g some designs of confidential transfers, with the same Baby-step Giant-step search as
+//! Baby Jubjub.
+//!
+//! The functions are the same as the ones of the other curves, except that a point of Ristretto255 has no affine coordinates of its own :
+//! points are encoded as the 32 bytes of their canonical encoding of RFC 9496 in `0x`-prefixed hexadecimal strings, the generator being
+//! the Ristretto basepoint, see [`GENERATOR`]. Scalars, i.e private keys and the encryption randomness, are integers smaller than the order
+//! `l` of the group, see [`ORDER`].
+
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::Scalar;
+use crate::point::parse_be_bytes;
+use crate::table::BabyStepCurve;
+use crate::Error;
+
+/// Encoding of the Ristretto basepoint `G`.
+pub const GENERATOR: &str = "0xe2f2ae0a6abc4e71a884a961c500515f58e30b6aa582dd8db6a65945e08d2d76";
+
+/// Order `l` of the group, i.e `2^252 + 27742317777372353535851937790883648493`.
+pub const ORDER: &str = "0x1000000000000000000000000000000014def9dea2f79cd65812631a5cf5d3ed";
+
+/// Point of Ristretto255, whose extended coordinates are already suited to the additions of the baby steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Ristretto(RistrettoPoint);
+
+impl BabyStepCurve for Ristretto {
+    type Projective = RistrettoPoint;
+
+    /// Lowest bits of the canonical encoding, as the coordinates of the same element of the group are not unique.
+    fn key(&self) -> u64 {
+        u64::from_le_bytes(self.0.compress().as_bytes()[..8].try_into().unwrap())
+    }
+
+    fn mul_u64(&self, k: u64) -> RistrettoPoint {
+        self.0 * Scalar::from(k)
+    }
+
+    fn add_affine(&mut self, other: &Self) {
+        self.0 += other.0;
+    }
+
+    fn to_projective(&self) -> RistrettoPoint {
+        self.0
+    }
+
+    fn to_affine(p: &RistrettoPoint) -> Self {
+        Ristretto(*p)
+    }
+}
+
+fn parse_point(point: &str) -> Result<RistrettoPoint, Error> {
+    let invalid = || Error::InvalidFormat(point.to_string());
+    let bytes = hex::decode(point.strip_prefix("0x").ok_or_else(invalid)?).map_err(|_| invalid())?;
+    CompressedRistretto::from_slice(&bytes).map_err(|_| invalid())?.decompress().ok_or_else(invalid)
+}
+
+fn to_hex(p: &RistrettoPoint) -> String {
+    format!("0x{}", hex::encode(p.compress().as_bytes()))
+}
+
+fn parse_scalar(s: &str) -> Result<Scalar, Error> {
+    let mut bytes = parse_be_bytes(s)?;
+    bytes.reverse();
+    Option::from(Scalar::from_canonical_bytes(bytes)).ok_or_else(|| Error::InvalidFormat(s.to_string()))
+}
+
+crate::dlog::curve_module!(solver Ristretto, Ristretto(RISTRETTO_BASEPOINT_POINT));
+
+impl Solver {
+    /// Computes the Discrete Logarithm of the encoded point `point` in base [`GENERATOR`].
+    pub fn solve(&self, point: &str) -> Result<u64, Error> {
+        self.inner.search_point(Ristretto(parse_point(point)?)).run()
+    }
+}
+
+/// Computes the public key `sk·G` of the private key `sk`.
+pub fn priv_to_pub_key(private_key: &str) -> Result<String, Error> {
+    Ok(to_hex(&(RISTRETTO_BASEPOINT_POINT * parse_scalar(private_key)?)))
+}
+
+/// Embeds `plaintext` as the point `plaintext·G`.
+pub fn embed_plaintext(plaintext: u64) -> String {
+    to_hex(&(RISTRETTO_BASEPOINT_POINT * Scalar::from(plaintext)))
+}
+
+/// Encrypts `plaintext` for `public_key` with `randomness`, i.e returns `(r·G, m·G + r·pk)`.
+pub fn exp_elgamal_encrypt(public_key: &str, plaintext: u64, randomness: &str) -> Result<(String, String), Error> {
+    let pk = parse_point(public_key)?;
+    let r = parse_scalar(randomness)?;
+    let c2 = RISTRETTO_BASEPOINT_POINT * Scalar::from(plaintext) + pk * r;
+    Ok((to_hex(&(RISTRETTO_BASEPOINT_POINT * r)), to_hex(&c2)))
+}
+
+/// Decrypts the ciphertext `(C1, C2)` with `private_key` into its embedded plaintext `C2 - sk·C1`.
+pub fn exp_elgamal_decrypt(private_key: &str, c1: &str, c2: &str) -> Result<String, Error> {
+    let sk = parse_scalar(private_key)?;
+    Ok(to_hex(&(parse_point(c2)? - parse_point(c1)? * sk)))
+}
+
+/// Decrypts the ciphertext `(C1, C2)` with `private_key` down to its plaintext, searching its Discrete Logarithm with `solver`.
+pub fn decrypt_to_u64(private_key: &str, c1: &str, c2: &str, solver: &Solver) -> Result<u64, Error> {
+    solver.solve(&exp_elgamal_decrypt(private_key, c1, c2)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ristretto() {
+        assert_eq!(embed_plaintext(1), GENERATOR);
+        // the encodings of the small multiples of the basepoint of RFC 9496
+        assert_eq!(embed_plaintext(0), format!("0x{}", "0".repeat(64)));
+        assert_eq!(embed_plaintext(2), "0x6a493210f7499cd17fecb510ae0cea23a110e8d5b901f8acadd3095c73a3b919");
+        assert_eq!(priv_to_pub_key("0x02"), Ok(embed_plaintext(2)));
+
+        let private_key = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        let pk = priv_to_pub_key(private_key).unwrap();
+        let (c1, c2) = exp_elgamal_encrypt(&pk, 65535, "0x05").unwrap();
+        assert_eq!(exp_elgamal_decrypt(private_key, &c1, &c2), Ok(embed_plaintext(65535)));
+        let solver = Solver::new(16, 2);
+        assert_eq!(decrypt_to_u64(private_key, &c1, &c2, &solver), Ok(65535));
+        for plaintext in [0, 1, 255, 256] {
+            assert_eq!(solver.solve(&embed_plaintext(plaintext)), Ok(plaintext));
+        }
+        let too_large = embed_plaintext(65536);
+        assert_eq!(solver.solve(&too_large), Err(Error::DlogNotFound { max_bitwidth: 16 }));
+
+        // the encodings are canonical, e.g with an even first byte, and the scalars smaller than l
+        assert!(matches!(solver.solve("0x01"), Err(Error::InvalidFormat(_))));
+        assert!(matches!(solver.solve(&format!("0x01{}", "0".repeat(62))), Err(Error::InvalidFormat(_))));
+        assert!(matches!(priv_to_pub_key(ORDER), Err(Error::InvalidFormat(_))));
+    }
+}
@@ -0,0 +1,66 @@
+//! Rotation of the key of ciphertexts, so that long-lived encrypted balances survive the replacement of a private key without being
+//! decrypted and encrypted again, which would expose their plaintexts to whoever performs the rotation.
+//!
+//! A ciphertext `(C1, C2) = (r·G, m·G + r·pk_old)` for `pk_old = sk_old·G` is re-encrypted for `pk_new = sk_new·G` with the re-keying
+//! secret `δ = sk_old - sk_new`, as `(C1, C2 - δ·C1) = (r·G, m·G + r·pk_new)`. The re-encryption only needs `δ`, so it can be delegated
+//! to a proxy, e.g the service storing the ciphertexts, which learns neither the plaintexts nor the private keys. The re-keying secret
+//! should however be kept as secret as the keys themselves : together with one of the two private keys, it reveals the other one.
+//!
+//! A re-encrypted ciphertext keeps the `C1` of the original one, so both can be linked. Rerandomizing it for the new public key with
+//! [`Ciphertext::rerandomize`] makes it unlinkable, see [`re_encrypt_unlinkable`].
+
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use rand::{CryptoRng, RngCore};
+use crate::point::{field_to_hex, parse_scalar};
+use crate::{Ciphertext, Error};
+
+/// Re-keying secret `δ = sk_old - sk_new` from `old_private_key` to `new_private_key`, in the same format as [`crate::generate_private_key`].
+pub fn rekey(old_private_key: &str, new_private_key: &str) -> Result<String, Error> {
+    Ok(field_to_hex(&(parse_scalar(old_private_key)? - parse_scalar(new_private_key)?)))
+}
+
+/// Re-encrypts `ciphertext`, encrypted for the old public key of `rekey`, for its new public key, i.e returns `(C1, C2 - δ·C1)`.
+///
+/// Ciphertexts encrypted for another public key are re-encrypted into ciphertexts which decrypt to nothing meaningful; the result
+/// cannot tell them apart.
+pub fn re_encrypt(ciphertext: &Ciphertext, rekey: &str) -> Result<Ciphertext, Error> {
+    let delta = parse_scalar(rekey)?;
+    let c2 = ciphertext.c2.into_projective() - ciphertext.c1.mul(delta);
+    Ok(Ciphertext { c1: ciphertext.c1, c2: c2.into_affine() })
+}
+
+/// Re-encrypts `ciphertext` like [`re_encrypt`], and rerandomizes the result for `new_public_key` with `rng`, so that it cannot be linked
+/// to `ciphertext`.
+pub fn re_encrypt_unlinkable<R: RngCore + CryptoRng>(ciphertext: &Ciphertext, rekey: &str, new_public_key: (&str, &str), rng: &mut R) -> Result<Ciphertext, Error> {
+    re_encrypt(ciphertext, rekey)?.rerandomize(new_public_key, rng)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::OsRng;
+    use crate::{decrypt_to_u64, priv_to_pub_key, Solver};
+
+    #[test]
+    fn test_key_rotation() {
+        let (old, new) = ("0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c", "0x05");
+        let (old_pk, new_pk) = (priv_to_pub_key(old).unwrap(), priv_to_pub_key(new).unwrap());
+        let solver = Solver::new(16, 1);
+        let a = Ciphertext::encrypt((&old_pk.0, &old_pk.1), 1000, &mut OsRng).unwrap();
+        let b = Ciphertext::encrypt((&old_pk.0, &old_pk.1), 234, &mut OsRng).unwrap();
+
+        let secret = rekey(old, new).unwrap();
+        let rotated = re_encrypt(&a, &secret).unwrap();
+        assert_eq!(rotated.c1, a.c1);
+        assert_eq!(decrypt_to_u64(new, &rotated, &solver), Ok(1000));
+        assert!(decrypt_to_u64(old, &rotated, &solver).is_err());
+        // the re-encryption is homomorphic, so rotated balances can still be aggregated
+        assert_eq!(re_encrypt(&(a + b), &secret), Ok(rotated + re_encrypt(&b, &secret).unwrap()));
+
+        let unlinkable = re_encrypt_unlinkable(&a, &secret, (&new_pk.0, &new_pk.1), &mut OsRng).unwrap();
+        assert_ne!(unlinkable.c1, a.c1);
+        assert_eq!(decrypt_to_u64(new, &unlinkable, &solver), Ok(1000));
+        assert_eq!(re_encrypt(&a, &rekey(old, old).unwrap()), Ok(a));
+        assert!(matches!(re_encrypt(&a, "0xzz"), Err(Error::InvalidHex { .. })));
+    }
+}
@@ -0,0 +1,133 @@
+//! Exponential ElGamal and small-range Discrete Logarithms on secp256k1, for the amount-recovery schemes based on ECDH, e.g the amount
+//! hints of stealth payments, which reduce to a Discrete Logarithm of less than 40 bits, with the same Baby-step Giant-step search as
+//! Baby Jubjub. The arithmetic of the curve is the one of the `k256` crate.
+//!
+//! secp256k1 is the short Weierstrass curve `y^2 = x^3 + 7` of prime order `n`, generated by the standard generator `G`, see
+//! [`GENERATOR`]. Points are encoded as their affine coordinates `(x, y)` in `0x`-prefixed hexadecimal strings, the point at infinity
+//! being `(0, 0)`, or as their 33 bytes SEC1 compressed encoding with [`pack_point`], and scalars, i.e private keys and the encryption
+//! randomness, are integers smaller than `n`.
+
+use k256::elliptic_curve::point::AffineCoordinates;
+use k256::elliptic_curve::sec1::{Coordinates, FromEncodedPoint, ToEncodedPoint};
+use k256::elliptic_curve::PrimeField;
+use k256::{AffinePoint, EncodedPoint, ProjectivePoint, Scalar};
+use crate::dlog::{self, DlogCurve};
+use crate::point::{parse_be_bytes, parse_coordinates};
+use crate::table::BabyStepCurve;
+use crate::{Error, HexPoint};
+
+/// Coordinates of the standard generator `G` of secp256k1.
+pub const GENERATOR: (&str, &str) = (
+    "0x79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+    "0x483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+);
+
+/// Point of secp256k1, in the affine representation of `k256`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Secp256k1(AffinePoint);
+
+impl BabyStepCurve for Secp256k1 {
+    type Projective = ProjectivePoint;
+
+    fn key(&self) -> u64 {
+        let x = self.0.x();
+        u64::from_be_bytes(x[24..].try_into().unwrap())
+    }
+
+    fn mul_u64(&self, k: u64) -> ProjectivePoint {
+        ProjectivePoint::from(self.0) * Scalar::from(k)
+    }
+
+    fn add_affine(&mut self, other: &Self) {
+        self.0 = (ProjectivePoint::from(self.0) + other.0).to_affine();
+    }
+
+    fn to_projective(&self) -> ProjectivePoint {
+        ProjectivePoint::from(self.0)
+    }
+
+    fn to_affine(p: &ProjectivePoint) -> Self {
+        Secp256k1(p.to_affine())
+    }
+}
+
+impl DlogCurve for Secp256k1 {
+    type Scalar = Scalar;
+
+    fn generator() -> Self {
+        Secp256k1(AffinePoint::GENERATOR)
+    }
+
+    fn mul_scalar(&self, s: &Scalar) -> ProjectivePoint {
+        ProjectivePoint::from(self.0) * s
+    }
+
+    /// Parses the coordinates `(x, y)` of a point of secp256k1, `(0, 0)` being the point at infinity.
+    fn parse_point(x: &str, y: &str) -> Result<Self, Error> {
+        let (x, y) = parse_coordinates(x, y, parse_be_bytes)?;
+        if x == [0; 32] && y == [0; 32] {
+            return Ok(Secp256k1(AffinePoint::IDENTITY));
+        }
+        let encoded = EncodedPoint::from_affine_coordinates(&x.into(), &y.into(), false);
+        Option::from(AffinePoint::from_encoded_point(&encoded)).map(Secp256k1).ok_or(Error::NotOnCurve)
+    }
+
+    fn to_hex(&self) -> HexPoint {
+        match self.0.to_encoded_point(false).coordinates() {
+            Coordinates::Uncompressed { x, y } => (format!("0x{}", hex::encode(x)), format!("0x{}", hex::encode(y))),
+            _ => (format!("0x{}", "0".repeat(64)), format!("0x{}", "0".repeat(64))),
+        }
+    }
+
+    fn parse_scalar(s: &str) -> Result<Scalar, Error> {
+        Option::from(Scalar::from_repr(parse_be_bytes(s)?.into())).ok_or_else(|| Error::InvalidFormat(s.to_string()))
+    }
+}
+
+/// Packs the point `(x, y)` in its 33 bytes SEC1 compressed encoding, or the single byte `0x00` of the point at infinity.
+pub fn pack_point(x: &str, y: &str) -> Result<Vec<u8>, Error> {
+    Ok(Secp256k1::parse_point(x, y)?.0.to_encoded_point(true).as_bytes().to_vec())
+}
+
+/// Unpacks a point in its SEC1 encoding, compressed or not, e.g as packed by [`pack_point`].
+pub fn unpack_point(packed: &[u8]) -> Result<HexPoint, Error> {
+    let encoded = EncodedPoint::from_bytes(packed).map_err(|_| Error::InvalidFormat(format!("0x{}", hex::encode(packed))))?;
+    let p: Option<AffinePoint> = AffinePoint::from_encoded_point(&encoded).into();
+    Ok(Secp256k1(p.ok_or(Error::NotOnCurve)?).to_hex())
+}
+
+dlog::curve_module!(Secp256k1);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secp256k1() {
+        assert_eq!(embed_plaintext(1), (GENERATOR.0.to_string(), GENERATOR.1.to_string()));
+        // the public key of the private key 1 is G, and the one of 2 is the well-known 2·G
+        assert_eq!(priv_to_pub_key("0x01"), Ok(embed_plaintext(1)));
+        assert_eq!(embed_plaintext(2).0, "0xc6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5");
+
+        let private_key = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        let pk = priv_to_pub_key(private_key).unwrap();
+        let (c1, c2) = exp_elgamal_encrypt((&pk.0, &pk.1), 65535, "0x05").unwrap();
+        assert_eq!(exp_elgamal_decrypt(private_key, (&c1.0, &c1.1), (&c2.0, &c2.1)), Ok(embed_plaintext(65535)));
+        let solver = Solver::new(16, 2);
+        assert_eq!(decrypt_to_u64(private_key, (&c1.0, &c1.1), (&c2.0, &c2.1), &solver), Ok(65535));
+        for plaintext in [0, 1, 255, 256] {
+            let p = embed_plaintext(plaintext);
+            assert_eq!(solver.solve(&p.0, &p.1), Ok(plaintext));
+        }
+        let too_large = embed_plaintext(65536);
+        assert_eq!(solver.solve(&too_large.0, &too_large.1), Err(Error::DlogNotFound { max_bitwidth: 16 }));
+
+        for p in [&pk, &c1, &embed_plaintext(0)] {
+            assert_eq!(unpack_point(&pack_point(&p.0, &p.1).unwrap()).as_ref(), Ok(p));
+        }
+        assert_eq!(pack_point(&pk.0, &pk.1).unwrap().len(), 33);
+        assert_eq!(Secp256k1::parse_point("0x01", "0x01"), Err(Error::NotOnCurve));
+        let order = "0xfffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+        assert!(matches!(priv_to_pub_key(order), Err(Error::InvalidFormat(_))));
+    }
+}
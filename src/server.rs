@@ -0,0 +1,281 @@
+//! HTTP decryption service, exposing the [`Solver`] as a JSON API.
+//!
+//! The table of baby steps is built once when the service starts and shared by all the requests,
+//! which are solved on the blocking thread pool of the tokio runtime :
+//!
+//! - `POST /dlog` with a body `{"x": "0x...", "y": "0x..."}` returns `{"dlog": 42}`, or `{"error": "..."}` with a `4xx` status code.
+//! - `POST /dlog/batch` with a JSON array of points returns an array with one result per point, in the same order.
+//! - `POST /rpc` serves the same computations over JSON-RPC 2.0, see [`jsonrpc`].
+//! - `POST /shard`, `POST /shard/cancel` and `GET /shard/info` run a part of a search distributed between several machines, see [`shard`].
+//! - `GET /metrics` exposes the request counts and latencies of these routes to Prometheus, see [`metrics`].
+//!
+//! The results can also be stored in a persistent [`cache::Cache`], shared by all the requests through a [`Backend`],
+//! and the solving routes can be protected by per-client rate limits and a cap on the number of concurrent solves, see [`limits`].
+//! Each search of the backend can also be recorded in an [`AuditLog`], with the IP address of its client.
+
+use axum::extract::{ConnectInfo, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::middleware;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use crate::audit::AuditLog;
+use crate::{Error, Solver};
+
+pub mod cache;
+pub mod jsonrpc;
+pub mod limits;
+pub mod metrics;
+pub mod shard;
+
+use cache::Cache;
+use limits::{Limiter, Limits, Rejection, Slot};
+
+/// Solver shared by all the requests of the service, with an optional persistent cache of its results.
+#[derive(Clone)]
+pub struct Backend {
+    solver: Arc<Solver>,
+    cache: Option<Arc<Cache>>,
+    audit_log: Option<Arc<AuditLog>>,
+    limiter: Arc<Limiter>,
+}
+
+impl Backend {
+    pub fn new(solver: Arc<Solver>) -> Self {
+        Backend { solver, cache: None, audit_log: None, limiter: Arc::new(Limiter::new(Limits::default())) }
+    }
+
+    /// Looks up the results in `cache` before solving them, and stores the new ones.
+    pub fn with_cache(mut self, cache: Arc<Cache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Records every search in `audit_log`, including the ones answered by the cache.
+    pub fn with_audit_log(mut self, audit_log: Arc<AuditLog>) -> Self {
+        self.audit_log = Some(audit_log);
+        self
+    }
+
+    /// Enforces `limits` on the requests of all the transports sharing the backend, see [`limits`].
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limiter = Arc::new(Limiter::new(limits));
+        self
+    }
+
+    pub fn solver(&self) -> &Solver {
+        &self.solver
+    }
+
+    pub fn cache(&self) -> Option<&Cache> {
+        self.cache.as_deref()
+    }
+
+    /// Computes the Discrete Logarithm of the point `(x, y)`, using the cache if there is one.
+    pub fn solve(&self, x: &str, y: &str) -> Result<u64, Error> {
+        self.solve_for(None, x, y)
+    }
+
+    /// Same as [`Backend::solve`] on behalf of the client `client`, which is named in the audit log if there is one.
+    pub fn solve_for(&self, client: Option<SocketAddr>, x: &str, y: &str) -> Result<u64, Error> {
+        self.audit(client, "dlog", &[x, y], || match &self.cache {
+            Some(cache) => cache.solve(&self.solver, x, y),
+            None => self.solver.solve(x, y),
+        })
+    }
+
+    /// Number of requests being solved, waiting for a thread of the blocking pool or running.
+    pub fn in_flight(&self) -> u64 {
+        self.limiter.in_flight()
+    }
+
+    /// Admits a request of `client` solving `points` points under the limits of the backend, waiting for a solving slot if needed.
+    pub(crate) async fn admit(&self, client: Option<SocketAddr>, points: usize) -> Result<Slot, Rejection> {
+        self.limiter.admit(client.map(|c| c.ip()), points).await
+    }
+
+    /// Runs `f` on the blocking thread pool once the request of `client` solving `points` points is admitted, holding its solving slot
+    /// until `f` returns even if the request is dropped in the meantime.
+    pub(crate) async fn run_blocking<T: Send + 'static>(&self, client: Option<SocketAddr>, points: usize, f: impl FnOnce(&Backend) -> T + Send + 'static) -> Result<T, Rejection> {
+        let slot = self.admit(client, points).await?;
+        let backend = self.clone();
+        Ok(tokio::task::spawn_blocking(move || {
+            let _slot = slot;
+            f(&backend)
+        }).await.unwrap())
+    }
+
+    /// Runs the search `f` of `operation` on `coordinates`, recording it in the audit log if there is one.
+    pub(crate) fn audit(&self, client: Option<SocketAddr>, operation: &str, coordinates: &[&str], f: impl FnOnce() -> Result<u64, Error>) -> Result<u64, Error> {
+        match &self.audit_log {
+            Some(audit_log) => audit_log.record(client.map(|c| c.ip().to_string()).as_deref(), operation, coordinates, f),
+            None => f(),
+        }
+    }
+}
+
+impl From<Arc<Solver>> for Backend {
+    fn from(solver: Arc<Solver>) -> Self {
+        Backend::new(solver)
+    }
+}
+
+/// Coordinates of an embedded plaintext, in the same format as the inputs of [`crate::do_compute_dlog`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PointRequest {
+    pub x: String,
+    pub y: String,
+}
+
+/// Result of a Discrete Logarithm computation : either the `dlog`, or the `error` which prevented its computation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DlogResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dlog: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl From<&Result<u64, Error>> for DlogResult {
+    fn from(result: &Result<u64, Error>) -> Self {
+        match result {
+            Ok(dlog) => DlogResult { dlog: Some(*dlog), error: None },
+            Err(e) => DlogResult { dlog: None, error: Some(e.to_string()) },
+        }
+    }
+}
+
+/// Returns the routes of the service, sharing `backend` (or a solver without cache) between all the requests, within its limits.
+pub fn router(backend: impl Into<Backend>) -> Router {
+    let backend = backend.into();
+    let metrics = Arc::new(metrics::Metrics::new(&backend));
+    let (run_shard, cancel_shard) = shard::routes(backend.clone());
+    Router::new()
+        .route("/dlog", post(dlog))
+        .route("/dlog/batch", post(dlog_batch))
+        .route("/rpc", post(jsonrpc::rpc))
+        .with_state(backend)
+        .merge(run_shard)
+        .merge(cancel_shard)
+        .route_layer(middleware::from_fn_with_state(metrics.clone(), metrics::track))
+        .route("/metrics", get(metrics::metrics).with_state(metrics))
+}
+
+/// Same as [`router`], enforcing `limits` on the solving routes instead of the limits of the backend.
+///
+/// Clients are identified by their IP address, so the service should be served with
+/// [`Router::into_make_service_with_connect_info`] for the rate limits to be per client.
+/// To share the limits with the gRPC service, set them with [`Backend::with_limits`] instead.
+pub fn router_with_limits(backend: impl Into<Backend>, limits: Limits) -> Router {
+    router(backend.into().with_limits(limits))
+}
+
+fn status_code(e: &Error) -> StatusCode {
+    match e {
+        Error::InvalidFormat(_) | Error::InvalidHex { .. } | Error::InvalidBase64 { .. } | Error::NotInField { .. } | Error::InvalidCoordinate { .. } | Error::NotOnCurve | Error::NotInSubgroup | Error::InvalidRange { .. } => StatusCode::BAD_REQUEST,
+        Error::DlogNotFound { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+        Error::Cancelled => StatusCode::CONFLICT,
+        Error::Remote(_) => StatusCode::BAD_GATEWAY,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+async fn dlog(State(backend): State<Backend>, client: Option<ConnectInfo<SocketAddr>>, Json(point): Json<PointRequest>) -> Response {
+    let client = client.map(|c| c.0);
+    let result = match backend.run_blocking(client, 1, move |backend| backend.solve_for(client, &point.x, &point.y)).await {
+        Ok(result) => result,
+        Err(rejection) => return rejection.into_response(),
+    };
+    let status = result.as_ref().err().map_or(StatusCode::OK, status_code);
+    (status, Json(DlogResult::from(&result))).into_response()
+}
+
+async fn dlog_batch(State(backend): State<Backend>, client: Option<ConnectInfo<SocketAddr>>, Json(points): Json<Vec<PointRequest>>) -> Response {
+    let client = client.map(|c| c.0);
+    let results = backend.run_blocking(client, points.len(), move |backend| {
+        points.iter().map(|p| DlogResult::from(&backend.solve_for(client, &p.x, &p.y))).collect::<Vec<_>>()
+    }).await;
+    match results {
+        Ok(results) => Json(results).into_response(),
+        Err(rejection) => rejection.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    async fn post_json(router: Router, uri: &str, body: &str) -> (StatusCode, String) {
+        let request = Request::post(uri).header("content-type", "application/json").body(Body::from(body.to_string())).unwrap();
+        let response = router.oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_dlog_routes() {
+        let router = router(Arc::new(Solver::new(16, 2)));
+        let point = r#"{"x": "0x0bb77a6ad63e739b4eacb2e09d6277c12ab8d8010534e0b62893f3f6bb957051", "y": "0x25797203f7a0b24925572e1cd16bf9edfce0051fb9e133774b3c257a872d7d8b"}"#;
+        assert_eq!(post_json(router.clone(), "/dlog", point).await, (StatusCode::OK, r#"{"dlog":1}"#.to_string()));
+
+        let (status, _) = post_json(router.clone(), "/dlog", r#"{"x": "0x1", "y": "0x1"}"#).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, body) = post_json(router, "/dlog/batch", &format!(r#"[{}, {{"x": "0x0", "y": "0x1"}}, {{"x": "0x1", "y": "0x1"}}]"#, point)).await;
+        assert_eq!(status, StatusCode::OK);
+        let results: Vec<DlogResult> = serde_json::from_str(&body).unwrap();
+        assert_eq!(results[0].dlog, Some(1));
+        assert_eq!(results[1].dlog, Some(0));
+        assert!(results[2].error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_limits() {
+        let limits = Limits { rate: Some(limits::RateLimit { per_second: 0.001, burst: 1.0 }), ..Default::default() };
+        let router = router_with_limits(Arc::new(Solver::new(16, 2)), limits);
+        assert_eq!(post_json(router.clone(), "/dlog", r#"{"x": "0x0", "y": "0x1"}"#).await.0, StatusCode::OK);
+        assert_eq!(post_json(router.clone(), "/dlog", r#"{"x": "0x0", "y": "0x1"}"#).await.0, StatusCode::TOO_MANY_REQUESTS);
+        // each point of a batch takes a token, so a batch larger than the burst is never admitted
+        assert_eq!(post_json(router.clone(), "/dlog/batch", r#"[{"x": "0x0", "y": "0x1"}, {"x": "0x0", "y": "0x1"}]"#).await.0, StatusCode::PAYLOAD_TOO_LARGE);
+        // the metrics are not rate limited
+        let response = router.oneshot(Request::get("/metrics").body(Body::empty()).unwrap()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_metrics() {
+        let router = router(Arc::new(Solver::new(16, 2)));
+        post_json(router.clone(), "/dlog", r#"{"x": "0x0", "y": "0x1"}"#).await;
+        post_json(router.clone(), "/dlog", r#"{"x": "0x1", "y": "0x1"}"#).await;
+        let response = router.oneshot(Request::get("/metrics").body(Body::empty()).unwrap()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = String::from_utf8(to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()).unwrap();
+        assert!(body.contains("babygiant_requests_total{route=\"/dlog\",status=\"200\"} 1\n"));
+        assert!(body.contains("babygiant_requests_total{route=\"/dlog\",status=\"400\"} 1\n"));
+        assert!(body.contains("babygiant_request_duration_seconds_count{route=\"/dlog\"} 2\n"));
+        assert!(body.contains("babygiant_in_flight_solves 0\n"));
+    }
+
+    #[tokio::test]
+    async fn test_audit_log() {
+        let path = std::env::temp_dir().join(format!("babygiant-audit-{}.jsonl", std::process::id()));
+        let backend = Backend::new(Arc::new(Solver::new(16, 2))).with_audit_log(Arc::new(AuditLog::open(&path).unwrap()));
+        let router = router(backend);
+        post_json(router.clone(), "/dlog", r#"{"x": "0x0", "y": "0x1"}"#).await;
+        post_json(router.clone(), "/dlog/batch", r#"[{"x": "0x1", "y": "0x1"}]"#).await;
+        post_json(router.clone(), "/rpc", r#"{"jsonrpc": "2.0", "method": "babygiant_computeDlog", "params": [{"x": "0x0", "y": "0x1"}], "id": 1}"#).await;
+        post_json(router, "/shard", r#"{"id": "a", "x": "0x0", "y": "0x1", "max_bitwidth": 16, "table_bitwidth": 8, "start": 0, "end": 256}"#).await;
+        let records: Vec<crate::audit::Record> = std::fs::read_to_string(&path).unwrap().lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(records.iter().map(|r| r.operation.as_str()).collect::<Vec<_>>(), ["dlog", "dlog", "dlog", "shard"]);
+        assert_eq!((records[0].operation.as_str(), records[0].coordinates.clone(), records[0].dlog), ("dlog", vec!["0x0".to_string(), "0x1".to_string()], Some(0)));
+        assert_eq!((records[1].ok, records[1].error.clone()), (false, Some(Error::NotOnCurve.to_string())));
+    }
+}
@@ -0,0 +1,105 @@
+//! Persistent cache of the results of the service, backed by a [sled](https://docs.rs/sled) database.
+//!
+//! The Discrete Logarithm of a point does not depend on the solver, so the results are keyed by the canonical encoding of the points
+//! only, and previously solved points are returned without any search, even after a restart of the service.
+
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use crate::point::{canonical_key, parse_coordinates, ParseMode};
+use crate::{Error, Solver};
+
+pub struct Cache {
+    db: sled::Db,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl Cache {
+    /// Opens the database at `path`, creating it if it does not exist.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, sled::Error> {
+        Ok(Cache { db: sled::open(path)?, hits: AtomicU64::new(0), misses: AtomicU64::new(0) })
+    }
+
+    /// Returns the cached Discrete Logarithm of the point `(x, y)`, or computes it with `solver` and caches it.
+    ///
+    /// Failed computations are not cached, since they depend on the bitwidth of the solver.
+    pub fn solve(&self, solver: &Solver, x: &str, y: &str) -> Result<u64, Error> {
+        if let Some(dlog) = self.get(x, y, solver.parse_mode())? {
+            return Ok(dlog);
+        }
+        let dlog = solver.solve(x, y)?;
+        self.insert(x, y, dlog)?;
+        Ok(dlog)
+    }
+
+    /// Looks up the Discrete Logarithm of the point `(x, y)`, counting the lookup as a hit or a miss.
+    ///
+    /// The coordinates are first checked with `parse_mode`, the one of the solver, so that the inputs it rejects are also rejected
+    /// when their point is cached.
+    pub fn get(&self, x: &str, y: &str, parse_mode: ParseMode) -> Result<Option<u64>, Error> {
+        parse_coordinates(x, y, |s| parse_mode.check(s))?;
+        let key = canonical_key(x, y)?;
+        // a failing database only slows the service down, so its errors are treated as cache misses
+        let dlog = self.db.get(&key).ok().flatten().and_then(|value| <[u8; 8]>::try_from(value.as_ref()).ok()).map(u64::from_le_bytes);
+        let counter = if dlog.is_some() { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+        Ok(dlog)
+    }
+
+    /// Stores `dlog` as the Discrete Logarithm of the point `(x, y)`.
+    pub fn insert(&self, x: &str, y: &str, dlog: u64) -> Result<(), Error> {
+        let key = canonical_key(x, y)?;
+        let _ = self.db.insert(&key, &dlog.to_le_bytes());
+        Ok(())
+    }
+
+    /// Number of lookups which found their point in the cache, since it was opened.
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of lookups which did not find their point in the cache, since it was opened.
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Number of points in the cache.
+    pub fn len(&self) -> usize {
+        self.db.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.db.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache() {
+        let dir = std::env::temp_dir().join(format!("babygiant-cache-{}", std::process::id()));
+        let solver = Solver::new(16, 1);
+        {
+            let cache = Cache::open(&dir).unwrap();
+            assert_eq!(cache.solve(&solver, "0x0", "0x1"), Ok(0));
+            assert_eq!(cache.solve(&solver, "0x00", "0x01"), Ok(0));
+            assert_eq!(cache.solve(&solver, "0x1", "0x1"), Err(Error::NotOnCurve));
+            assert_eq!((cache.hits(), cache.misses(), cache.len()), (1, 1, 1));
+        }
+        // the results survive the reopening of the database
+        let cache = Cache::open(&dir).unwrap();
+        assert_eq!(cache.solve(&solver, "0x0", "0x1"), Ok(0));
+        assert_eq!(cache.hits(), 1);
+
+        // the strict mode rejects the non-canonical encodings of a cached point, like the solver
+        let strict = Solver::new(16, 1).with_parse_mode(ParseMode::Strict);
+        assert!(matches!(cache.solve(&strict, "0x0", "1"), Err(Error::InvalidCoordinate { .. })));
+        let (x, y) = ("0x".to_string() + &"0".repeat(64), "0x".to_string() + &"0".repeat(63) + "1");
+        assert_eq!(cache.solve(&strict, &x, &y), Ok(0));
+        assert_eq!(cache.hits(), 2);
+        drop(cache);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,166 @@
+//! [JSON-RPC 2.0](https://www.jsonrpc.org/specification) transport of the service, served on `POST /rpc`.
+//!
+//! Parameters are positional, like the methods of Ethereum nodes, and points are `{"x": "0x...", "y": "0x..."}` objects :
+//!
+//! - `babygiant_computeDlog` with params `[point]` returns the Discrete Logarithm of `point`.
+//! - `babygiant_decrypt` with params `[privateKey, c1, c2]` decrypts the ciphertext `(c1, c2)` and returns the plaintext.
+//!
+//! Batches and notifications are supported as described in the specification.
+
+use axum::extract::{ConnectInfo, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::net::SocketAddr;
+use super::{Backend, PointRequest};
+use crate::{exp_elgamal_decrypt, Error};
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+pub const INTERNAL_ERROR: i64 = -32603;
+/// Server error returned when the Discrete Logarithm is not among the integers of `max_bitwidth` bits.
+pub const DLOG_NOT_FOUND: i64 = -32000;
+
+#[derive(Debug, Deserialize)]
+struct RpcRequest {
+    jsonrpc: String,
+    method: String,
+    #[serde(default)]
+    params: Value,
+    id: Option<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RpcResponse {
+    pub jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+    pub id: Value,
+}
+
+impl RpcResponse {
+    fn new(id: Value, result: Result<Value, RpcError>) -> Self {
+        let (result, error) = match result {
+            Ok(result) => (Some(result), None),
+            Err(error) => (None, Some(error)),
+        };
+        RpcResponse { jsonrpc: "2.0".to_string(), result, error, id }
+    }
+}
+
+fn rpc_error(code: i64, message: impl Into<String>) -> RpcError {
+    RpcError { code, message: message.into() }
+}
+
+impl From<Error> for RpcError {
+    fn from(e: Error) -> Self {
+        let code = match e {
+            Error::InvalidFormat(_) | Error::InvalidHex { .. } | Error::InvalidBase64 { .. } | Error::NotInField { .. } | Error::InvalidCoordinate { .. } | Error::NotOnCurve | Error::NotInSubgroup => INVALID_PARAMS,
+            Error::DlogNotFound { .. } => DLOG_NOT_FOUND,
+            _ => INTERNAL_ERROR,
+        };
+        rpc_error(code, e.to_string())
+    }
+}
+
+fn params<T: serde::de::DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    serde_json::from_value(params).map_err(|e| rpc_error(INVALID_PARAMS, e.to_string()))
+}
+
+fn call(backend: &Backend, client: Option<SocketAddr>, method: &str, p: Value) -> Result<Value, RpcError> {
+    let dlog = match method {
+        "babygiant_computeDlog" => {
+            let (point,): (PointRequest,) = params(p)?;
+            backend.solve_for(client, &point.x, &point.y)?
+        }
+        "babygiant_decrypt" => {
+            let (private_key, c1, c2): (String, PointRequest, PointRequest) = params(p)?;
+            let (x, y) = exp_elgamal_decrypt(&private_key, (&c1.x, &c1.y), (&c2.x, &c2.y))?;
+            backend.solve_for(client, &x, &y)?
+        }
+        _ => return Err(rpc_error(METHOD_NOT_FOUND, format!("Method not found : {}", method))),
+    };
+    Ok(Value::from(dlog))
+}
+
+/// Handles one element of a request of `client`, returning `None` for notifications.
+fn handle(backend: &Backend, client: Option<SocketAddr>, request: Value) -> Option<RpcResponse> {
+    let request: RpcRequest = match serde_json::from_value(request) {
+        Ok(request) => request,
+        Err(e) => return Some(RpcResponse::new(Value::Null, Err(rpc_error(INVALID_REQUEST, e.to_string())))),
+    };
+    if request.jsonrpc != "2.0" {
+        return Some(RpcResponse::new(request.id.unwrap_or(Value::Null), Err(rpc_error(INVALID_REQUEST, "jsonrpc must be \"2.0\""))));
+    }
+    let result = call(backend, client, &request.method, request.params);
+    request.id.map(|id| RpcResponse::new(id, result))
+}
+
+pub(super) async fn rpc(State(backend): State<Backend>, client: Option<ConnectInfo<SocketAddr>>, body: String) -> Response {
+    let client = client.map(|c| c.0);
+    let request: Value = match serde_json::from_str(&body) {
+        Ok(request) => request,
+        Err(e) => return Json(RpcResponse::new(Value::Null, Err(rpc_error(PARSE_ERROR, e.to_string())))).into_response(),
+    };
+    // each call of a batch is charged as one point by the rate limit
+    let points = match &request {
+        Value::Array(batch) => batch.len(),
+        _ => 1,
+    };
+    let response = backend.run_blocking(client, points, move |backend| match request {
+        Value::Array(batch) if batch.is_empty() => {
+            Some(serde_json::to_value(RpcResponse::new(Value::Null, Err(rpc_error(INVALID_REQUEST, "Empty batch")))).unwrap())
+        }
+        Value::Array(batch) => {
+            let responses: Vec<RpcResponse> = batch.into_iter().filter_map(|r| handle(backend, client, r)).collect();
+            (!responses.is_empty()).then(|| serde_json::to_value(responses).unwrap())
+        }
+        request => handle(backend, client, request).map(|r| serde_json::to_value(r).unwrap()),
+    }).await;
+    match response {
+        Ok(Some(response)) => Json(response).into_response(),
+        Ok(None) => StatusCode::NO_CONTENT.into_response(),
+        Err(rejection) => rejection.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{exp_elgamal_encrypt, priv_to_pub_key, Solver};
+    use std::sync::Arc;
+    use serde_json::json;
+
+    #[test]
+    fn test_handle() {
+        let solver = Backend::new(Arc::new(Solver::new(16, 2)));
+        let g = json!({"x": "0x0bb77a6ad63e739b4eacb2e09d6277c12ab8d8010534e0b62893f3f6bb957051", "y": "0x25797203f7a0b24925572e1cd16bf9edfce0051fb9e133774b3c257a872d7d8b"});
+        let response = handle(&solver, None, json!({"jsonrpc": "2.0", "method": "babygiant_computeDlog", "params": [g], "id": 1})).unwrap();
+        assert_eq!(response, RpcResponse::new(json!(1), Ok(json!(1))));
+
+        let sk = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        let pk = priv_to_pub_key(sk).unwrap();
+        let (c1, c2) = exp_elgamal_encrypt((&pk.0, &pk.1), 12345, "0x03f90f366f9fd55bb1335eac3b11f2190f2ce9ff1769db241edaa7774136099b").unwrap();
+        let params = json!([sk, {"x": c1.0, "y": c1.1}, {"x": c2.0, "y": c2.1}]);
+        let response = handle(&solver, None, json!({"jsonrpc": "2.0", "method": "babygiant_decrypt", "params": params, "id": "a"})).unwrap();
+        assert_eq!(response.result, Some(json!(12345)));
+
+        let response = handle(&solver, None, json!({"jsonrpc": "2.0", "method": "babygiant_computeDlog", "params": [{"x": "0x1", "y": "0x1"}], "id": 2})).unwrap();
+        assert_eq!(response.error.unwrap().code, INVALID_PARAMS);
+        let response = handle(&solver, None, json!({"jsonrpc": "2.0", "method": "eth_chainId", "id": 3})).unwrap();
+        assert_eq!(response.error.unwrap().code, METHOD_NOT_FOUND);
+        assert_eq!(handle(&solver, None, json!({"jsonrpc": "2.0", "method": "babygiant_computeDlog", "params": [g]})), None);
+    }
+}
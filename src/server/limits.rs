@@ -0,0 +1,187 @@
+//! Per-client rate limits and global cap on the number of concurrent solves of the service.
+//!
+//! The limits are held by the [`super::Backend`], so that they are shared by all the transports serving it, HTTP and gRPC. Each point
+//! of a request takes a token from the bucket of its client, and each request solves its points one after the other in one solving
+//! slot : capping the number of slots caps the number of concurrent searches, the other requests waiting in a queue for a free slot
+//! and being rejected once the queue is full. A slot is held by the thread running the search, so that it is only released once the
+//! search is done, even if its client went away in the meantime.
+
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Number of clients tracked by the rate limiter before the ones which are back to a full bucket are forgotten.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// Token bucket rate limit : each client can send `burst` points at once, refilled at `per_second` points per second.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    pub per_second: f64,
+    pub burst: f64,
+}
+
+/// Limits of a [`super::Backend`], all disabled by default.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Limits {
+    /// Rate limit of each client, identified by its IP address.
+    pub rate: Option<RateLimit>,
+    /// Maximum number of requests solved at the same time.
+    pub max_concurrent_solves: Option<usize>,
+    /// Maximum number of requests waiting for a solving slot, beyond which requests are rejected [default: unbounded].
+    pub max_queued: Option<usize>,
+}
+
+/// Reason why a request was not admitted by the [`Limits`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Rejection {
+    /// The client has no token left, and gets the next one in `retry_after` seconds.
+    RateLimited { retry_after: u64 },
+    /// The request has more points than the burst of the rate limit, so it would never be admitted.
+    TooManyPoints { burst: u64 },
+    QueueFull,
+}
+
+impl std::fmt::Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::RateLimited { .. } => write!(f, "Rate limit exceeded"),
+            Rejection::TooManyPoints { burst } => write!(f, "Too many points : a request can have at most {} points", burst),
+            Rejection::QueueFull => write!(f, "Too many requests are waiting to be solved"),
+        }
+    }
+}
+
+impl IntoResponse for Rejection {
+    fn into_response(self) -> Response {
+        match self {
+            Rejection::RateLimited { retry_after } => (StatusCode::TOO_MANY_REQUESTS, [(header::RETRY_AFTER, retry_after.to_string())], self.to_string()).into_response(),
+            Rejection::TooManyPoints { .. } => (StatusCode::PAYLOAD_TOO_LARGE, self.to_string()).into_response(),
+            Rejection::QueueFull => (StatusCode::SERVICE_UNAVAILABLE, self.to_string()).into_response(),
+        }
+    }
+}
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+pub(crate) struct Limiter {
+    limits: Limits,
+    buckets: Mutex<HashMap<Option<IpAddr>, Bucket>>,
+    slots: Option<Arc<Semaphore>>,
+    queued: AtomicUsize,
+    in_flight: AtomicU64,
+}
+
+/// Solving slot of an admitted request, released when dropped.
+pub(crate) struct Slot {
+    limiter: Arc<Limiter>,
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.limiter.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Decrements the length of the queue even if the request is dropped while waiting.
+struct Queued<'a>(&'a AtomicUsize);
+
+impl Drop for Queued<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl Limiter {
+    pub(crate) fn new(limits: Limits) -> Self {
+        let slots = limits.max_concurrent_solves.map(|n| Arc::new(Semaphore::new(n)));
+        Limiter { limits, buckets: Mutex::new(HashMap::new()), slots, queued: AtomicUsize::new(0), in_flight: AtomicU64::new(0) }
+    }
+
+    /// Number of requests admitted whose slot is not released yet.
+    pub(crate) fn in_flight(&self) -> u64 {
+        self.in_flight.load(Ordering::Relaxed)
+    }
+
+    /// Takes `points` tokens from the bucket of `client`, or returns the reason why they cannot be taken.
+    fn take_tokens(&self, client: Option<IpAddr>, points: usize) -> Result<(), Rejection> {
+        let Some(rate) = self.limits.rate else { return Ok(()) };
+        let cost = points as f64;
+        if cost > rate.burst {
+            return Err(Rejection::TooManyPoints { burst: rate.burst as u64 });
+        }
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() >= MAX_TRACKED_CLIENTS {
+            buckets.retain(|_, b| b.tokens + now.duration_since(b.updated).as_secs_f64() * rate.per_second < rate.burst);
+        }
+        let bucket = buckets.entry(client).or_insert(Bucket { tokens: rate.burst, updated: now });
+        bucket.tokens = (bucket.tokens + now.duration_since(bucket.updated).as_secs_f64() * rate.per_second).min(rate.burst);
+        bucket.updated = now;
+        if bucket.tokens < cost {
+            return Err(Rejection::RateLimited { retry_after: ((cost - bucket.tokens) / rate.per_second).ceil() as u64 });
+        }
+        bucket.tokens -= cost;
+        Ok(())
+    }
+
+    /// Admits a request of `client` solving `points` points, waiting for a free slot if they are all taken.
+    pub(crate) async fn admit(self: &Arc<Self>, client: Option<IpAddr>, points: usize) -> Result<Slot, Rejection> {
+        self.take_tokens(client, points)?;
+        let permit = match &self.slots {
+            None => None,
+            Some(slots) => Some(match slots.clone().try_acquire_owned() {
+                Ok(permit) => permit,
+                Err(_) => {
+                    let queued = self.queued.fetch_add(1, Ordering::Relaxed);
+                    let _queued = Queued(&self.queued);
+                    if self.limits.max_queued.is_some_and(|max| queued >= max) {
+                        return Err(Rejection::QueueFull);
+                    }
+                    slots.clone().acquire_owned().await.unwrap()
+                }
+            }),
+        };
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+        Ok(Slot { limiter: self.clone(), _permit: permit })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limit() {
+        let limiter = Limiter::new(Limits { rate: Some(RateLimit { per_second: 0.5, burst: 2.0 }), ..Default::default() });
+        let client = Some(IpAddr::from([127, 0, 0, 1]));
+        assert_eq!(limiter.take_tokens(client, 1), Ok(()));
+        assert_eq!(limiter.take_tokens(client, 1), Ok(()));
+        assert_eq!(limiter.take_tokens(client, 1), Err(Rejection::RateLimited { retry_after: 2 }));
+        // the other clients have their own bucket, from which each point of a batch takes a token
+        let other = Some(IpAddr::from([10, 0, 0, 1]));
+        assert_eq!(limiter.take_tokens(other, 3), Err(Rejection::TooManyPoints { burst: 2 }));
+        assert_eq!(limiter.take_tokens(other, 2), Ok(()));
+        assert_eq!(limiter.take_tokens(other, 1), Err(Rejection::RateLimited { retry_after: 2 }));
+    }
+
+    #[tokio::test]
+    async fn test_slots() {
+        let limiter = Arc::new(Limiter::new(Limits { max_concurrent_solves: Some(1), max_queued: Some(0), ..Default::default() }));
+        let slot = limiter.admit(None, 1).await.unwrap();
+        assert_eq!(limiter.in_flight(), 1);
+        assert!(matches!(limiter.admit(None, 1).await, Err(Rejection::QueueFull)));
+        // the slot is released by the thread holding it, e.g the one of a search whose client went away
+        std::thread::spawn(move || drop(slot)).join().unwrap();
+        assert_eq!(limiter.in_flight(), 0);
+        assert!(limiter.admit(None, 1).await.is_ok());
+    }
+}
@@ -0,0 +1,105 @@
+//! [Prometheus](https://prometheus.io/docs/instrumenting/exposition_formats/) metrics of the service, served on `GET /metrics`.
+
+use axum::extract::{MatchedPath, Request, State};
+use axum::http::header;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use super::Backend;
+
+/// Upper bounds of the buckets of the latency histograms, in seconds.
+const LATENCY_BUCKETS: [f64; 12] = [0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 10.0, 30.0, 60.0, 300.0];
+
+#[derive(Default)]
+struct Histogram {
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, value: f64) {
+        for (bucket, bound) in self.buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if value <= bound {
+                *bucket += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+/// Metrics shared between all the requests of a [`super::router`].
+pub struct Metrics {
+    requests: Mutex<BTreeMap<(String, u16), u64>>,
+    latencies: Mutex<BTreeMap<String, Histogram>>,
+    backend: Backend,
+}
+
+impl Metrics {
+    pub fn new(backend: &Backend) -> Self {
+        Metrics {
+            requests: Mutex::new(BTreeMap::new()),
+            latencies: Mutex::new(BTreeMap::new()),
+            backend: backend.clone(),
+        }
+    }
+
+    fn record(&self, route: &str, status: u16, latency: Duration) {
+        *self.requests.lock().unwrap().entry((route.to_string(), status)).or_default() += 1;
+        self.latencies.lock().unwrap().entry(route.to_string()).or_default().observe(latency.as_secs_f64());
+    }
+
+    /// Renders the metrics in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        out.push_str("# HELP babygiant_requests_total Number of handled requests.\n# TYPE babygiant_requests_total counter\n");
+        for ((route, status), count) in self.requests.lock().unwrap().iter() {
+            writeln!(out, "babygiant_requests_total{{route=\"{}\",status=\"{}\"}} {}", route, status, count).unwrap();
+        }
+        out.push_str("# HELP babygiant_request_duration_seconds Latency of the requests.\n# TYPE babygiant_request_duration_seconds histogram\n");
+        for (route, histogram) in self.latencies.lock().unwrap().iter() {
+            for (count, bound) in histogram.buckets.iter().zip(LATENCY_BUCKETS) {
+                writeln!(out, "babygiant_request_duration_seconds_bucket{{route=\"{}\",le=\"{}\"}} {}", route, bound, count).unwrap();
+            }
+            writeln!(out, "babygiant_request_duration_seconds_bucket{{route=\"{}\",le=\"+Inf\"}} {}", route, histogram.count).unwrap();
+            writeln!(out, "babygiant_request_duration_seconds_sum{{route=\"{}\"}} {}", route, histogram.sum).unwrap();
+            writeln!(out, "babygiant_request_duration_seconds_count{{route=\"{}\"}} {}", route, histogram.count).unwrap();
+        }
+        out.push_str("# HELP babygiant_in_flight_solves Number of requests currently being solved.\n# TYPE babygiant_in_flight_solves gauge\n");
+        writeln!(out, "babygiant_in_flight_solves {}", self.backend.in_flight()).unwrap();
+        out.push_str("# HELP babygiant_table_size_bytes Approximate memory used by the table of baby steps.\n# TYPE babygiant_table_size_bytes gauge\n");
+        writeln!(out, "babygiant_table_size_bytes {}", self.backend.solver().table_size_bytes()).unwrap();
+        let memory = self.backend.solver().memory_usage();
+        out.push_str("# HELP babygiant_search_memory_bytes Memory used by the searches currently running.\n# TYPE babygiant_search_memory_bytes gauge\n");
+        writeln!(out, "babygiant_search_memory_bytes {}", memory.search_bytes).unwrap();
+        out.push_str("# HELP babygiant_peak_memory_bytes Highest memory used by the solver since the start.\n# TYPE babygiant_peak_memory_bytes gauge\n");
+        writeln!(out, "babygiant_peak_memory_bytes {}", memory.peak_bytes).unwrap();
+        out.push_str("# HELP babygiant_max_bitwidth Maximum bitwidth of the plaintexts solved by the service.\n# TYPE babygiant_max_bitwidth gauge\n");
+        writeln!(out, "babygiant_max_bitwidth {}", self.backend.solver().max_bitwidth()).unwrap();
+        if let Some(cache) = self.backend.cache() {
+            out.push_str("# HELP babygiant_cache_hits_total Number of points found in the cache of results.\n# TYPE babygiant_cache_hits_total counter\n");
+            writeln!(out, "babygiant_cache_hits_total {}", cache.hits()).unwrap();
+            out.push_str("# HELP babygiant_cache_misses_total Number of points not found in the cache of results.\n# TYPE babygiant_cache_misses_total counter\n");
+            writeln!(out, "babygiant_cache_misses_total {}", cache.misses()).unwrap();
+            out.push_str("# HELP babygiant_cache_entries Number of points in the cache of results.\n# TYPE babygiant_cache_entries gauge\n");
+            writeln!(out, "babygiant_cache_entries {}", cache.len()).unwrap();
+        }
+        out
+    }
+}
+
+pub(super) async fn track(State(metrics): State<Arc<Metrics>>, request: Request, next: Next) -> Response {
+    let route = request.extensions().get::<MatchedPath>().map_or("unmatched", |p| p.as_str()).to_string();
+    let start = Instant::now();
+    let response = next.run(request).await;
+    metrics.record(&route, response.status().as_u16(), start.elapsed());
+    response
+}
+
+pub(super) async fn metrics(State(metrics): State<Arc<Metrics>>) -> Response {
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], metrics.render()).into_response()
+}
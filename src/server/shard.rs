@@ -0,0 +1,335 @@
+//! Distributed search, sharding the giant steps of one point between several machines running the service.
+//!
+//! The [`Coordinator`] splits the range of giant steps into shards, and sends each of them to a worker with `POST /shard`,
+//! which answers with the Discrete Logarithm if it is in the shard (a hit), or an error otherwise.
+//! As soon as one shard is a hit, the shards still running are cancelled with `POST /shard/cancel`.
+//! The shards of unreachable workers are given to the other workers, so the search survives the loss of some of them.
+//!
+//! All the workers must use the same bitwidth as the coordinator, and the same table of baby steps, since its size is the one of a giant step.
+//! The coordinator reads both from the workers with `GET /shard/info` before sending the shards of a search.
+
+use axum::extract::{ConnectInfo, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tokio::task::JoinSet;
+use super::{status_code, Backend, DlogResult};
+use crate::point::parse_point;
+use crate::Error;
+
+/// Interval at which a running shard checks whether it was cancelled.
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Shard of the search for the Discrete Logarithm of `(x, y)`, covering the giant steps `[start, end)`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShardRequest {
+    /// Identifier of the shard, used to cancel it.
+    pub id: String,
+    pub x: String,
+    pub y: String,
+    pub max_bitwidth: u64,
+    /// Bitwidth of the table of baby steps of the workers, which determines the number of giant steps of the search.
+    pub table_bitwidth: u64,
+    pub start: u64,
+    pub end: u64,
+}
+
+/// Bitwidths of the plaintexts solved by a worker and of its table of baby steps, returned by `GET /shard/info`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShardInfo {
+    pub max_bitwidth: u64,
+    pub table_bitwidth: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CancelRequest {
+    pub id: String,
+}
+
+/// State of the worker routes : the cancellation flags of the shards, by identifier.
+#[derive(Clone)]
+struct ShardState {
+    backend: Backend,
+    cancelled: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
+}
+
+/// Returns the route running shards, and the ones cancelling them and describing the worker, which must not be subject to the limits
+/// of the service since they are needed to free its slots, and to start a search while its slots are taken.
+pub(super) fn routes(backend: Backend) -> (Router, Router) {
+    let state = ShardState { backend, cancelled: Arc::new(Mutex::new(HashMap::new())) };
+    let run = Router::new().route("/shard", post(run_shard)).with_state(state.clone());
+    let cancel = Router::new().route("/shard/cancel", post(cancel_shard)).route("/shard/info", get(shard_info)).with_state(state);
+    (run, cancel)
+}
+
+async fn run_shard(State(state): State<ShardState>, client: Option<ConnectInfo<SocketAddr>>, Json(shard): Json<ShardRequest>) -> Response {
+    let client = client.map(|c| c.0);
+    let solver = state.backend.solver();
+    let error = if shard.max_bitwidth != solver.max_bitwidth() {
+        Some(format!("the worker solves plaintexts of {} bits, not {}", solver.max_bitwidth(), shard.max_bitwidth))
+    } else if shard.table_bitwidth != solver.table_bitwidth() {
+        Some(format!("the worker uses a table of {} bits, not {}", solver.table_bitwidth(), shard.table_bitwidth))
+    } else {
+        None
+    };
+    if let Some(error) = error {
+        return (StatusCode::BAD_REQUEST, Json(DlogResult { dlog: None, error: Some(error) })).into_response();
+    }
+    // the cancellation may arrive before the shard itself
+    let cancelled = state.cancelled.lock().unwrap().entry(shard.id.clone()).or_default().clone();
+    let result = state.backend.run_blocking(client, 1, move |backend| backend.audit(client, "shard", &[&shard.x, &shard.y], || {
+        let search = backend.solver().search_range(&shard.x, &shard.y, shard.start, shard.end)?;
+        let (done_tx, done_rx) = channel::<()>();
+        thread::scope(|s| {
+            let search = &search;
+            s.spawn(move || {
+                while let Err(RecvTimeoutError::Timeout) = done_rx.recv_timeout(CANCEL_POLL_INTERVAL) {
+                    if cancelled.load(Ordering::Relaxed) {
+                        search.cancel();
+                    }
+                }
+            });
+            let result = search.run();
+            drop(done_tx);
+            result
+        })
+    })).await;
+    state.cancelled.lock().unwrap().remove(&shard.id);
+    let result = match result {
+        Ok(result) => result,
+        Err(rejection) => return rejection.into_response(),
+    };
+    let status = result.as_ref().err().map_or(StatusCode::OK, status_code);
+    (status, Json(DlogResult::from(&result))).into_response()
+}
+
+async fn cancel_shard(State(state): State<ShardState>, Json(request): Json<CancelRequest>) -> StatusCode {
+    state.cancelled.lock().unwrap().entry(request.id).or_default().store(true, Ordering::Relaxed);
+    StatusCode::NO_CONTENT
+}
+
+async fn shard_info(State(state): State<ShardState>) -> Json<ShardInfo> {
+    let solver = state.backend.solver();
+    Json(ShardInfo { max_bitwidth: solver.max_bitwidth(), table_bitwidth: solver.table_bitwidth() })
+}
+
+/// Coordinator of a search sharded between the services listening on `workers`, e.g `http://10.0.0.1:8080`.
+pub struct Coordinator {
+    workers: Vec<String>,
+    max_bitwidth: u64,
+    shards_per_worker: u64,
+    client: reqwest::Client,
+}
+
+type InFlight = Arc<Mutex<HashMap<String, String>>>;
+
+impl Coordinator {
+    pub fn new(workers: Vec<String>, max_bitwidth: u64) -> Self {
+        assert!(!workers.is_empty(), "a coordinator needs at least one worker");
+        assert!((1..=64).contains(&max_bitwidth), "max_bitwidth should be between 1 and 64");
+        let workers = workers.into_iter().map(|w| w.trim_end_matches('/').to_string()).collect();
+        Coordinator { workers, max_bitwidth, shards_per_worker: 4, client: reqwest::Client::new() }
+    }
+
+    /// Sets the number of shards per worker (`4` by default). Smaller shards balance the load better between heterogeneous machines.
+    pub fn with_shards_per_worker(mut self, shards_per_worker: u64) -> Self {
+        assert!(shards_per_worker > 0, "shards_per_worker should be positive");
+        self.shards_per_worker = shards_per_worker;
+        self
+    }
+
+    /// Computes the Discrete Logarithm of the point `(x, y)` on the workers.
+    pub async fn solve(&self, x: &str, y: &str) -> Result<u64, Error> {
+        // invalid points are rejected before sending any shard
+        let _ = parse_point(x, y)?;
+        let table_bitwidth = self.table_bitwidth().await?;
+        // a search of 64 bits with a table of 0 bits would have 2^64 giant steps
+        let total = self.max_bitwidth.checked_sub(table_bitwidth).and_then(|bits| 1u64.checked_shl(bits as u32)).ok_or_else(|| {
+            Error::Remote(format!("the workers use a table of {} bits, which cannot search plaintexts of {} bits", table_bitwidth, self.max_bitwidth))
+        })?;
+        let num_shards = (self.workers.len() as u64 * self.shards_per_worker).min(total);
+        let prefix = format!("{:016x}", rand::random::<u64>());
+        let shards: VecDeque<ShardRequest> = (0..num_shards)
+            .map(|idx| ShardRequest {
+                id: format!("{}-{}", prefix, idx),
+                x: x.to_string(),
+                y: y.to_string(),
+                max_bitwidth: self.max_bitwidth,
+                table_bitwidth,
+                start: idx * total / num_shards,
+                end: (idx + 1) * total / num_shards,
+            })
+            .collect();
+        let queue = Arc::new(Mutex::new(shards));
+        let in_flight: InFlight = Arc::new(Mutex::new(HashMap::new()));
+
+        let mut tasks = JoinSet::new();
+        for worker in &self.workers {
+            tasks.spawn(run_worker(self.client.clone(), worker.clone(), queue.clone(), in_flight.clone()));
+        }
+        let mut last_error = None;
+        while let Some(result) = tasks.join_next().await {
+            match result.unwrap() {
+                Ok(Some(dlog)) => {
+                    tasks.abort_all();
+                    self.cancel_in_flight(&in_flight).await;
+                    return Ok(dlog);
+                }
+                Ok(None) => {}
+                Err(e) => last_error = Some(e),
+            }
+        }
+        match last_error {
+            // the shards of a failed worker were not all taken over by the other ones
+            Some(e) if !queue.lock().unwrap().is_empty() => Err(e),
+            _ => Err(Error::DlogNotFound { max_bitwidth: self.max_bitwidth }),
+        }
+    }
+
+    /// Reads the bitwidth of the table of the reachable workers, which must be the same for all of them, and checks that they solve
+    /// plaintexts of the bitwidth of the coordinator.
+    async fn table_bitwidth(&self) -> Result<u64, Error> {
+        let mut table_bitwidth = None;
+        let mut last_error = None;
+        for worker in &self.workers {
+            let info = match fetch_info(&self.client, worker).await {
+                Ok(info) => info,
+                // the shards of the unreachable workers are given to the other ones
+                Err(e) => {
+                    last_error = Some(e);
+                    continue;
+                }
+            };
+            if info.max_bitwidth != self.max_bitwidth {
+                return Err(Error::Remote(format!("{} : the worker solves plaintexts of {} bits, not {}", worker, info.max_bitwidth, self.max_bitwidth)));
+            }
+            match table_bitwidth {
+                Some(bitwidth) if bitwidth != info.table_bitwidth => {
+                    return Err(Error::Remote(format!("{} : the worker uses a table of {} bits, not {} like the other workers", worker, info.table_bitwidth, bitwidth)));
+                }
+                _ => table_bitwidth = Some(info.table_bitwidth),
+            }
+        }
+        table_bitwidth.ok_or_else(|| last_error.unwrap())
+    }
+
+    async fn cancel_in_flight(&self, in_flight: &InFlight) {
+        let shards: Vec<(String, String)> = in_flight.lock().unwrap().drain().collect();
+        for (id, worker) in shards {
+            // a worker which cannot be reached is not running the shard anymore
+            let _ = self.client.post(format!("{}/shard/cancel", worker)).json(&CancelRequest { id }).send().await;
+        }
+    }
+}
+
+/// Sends the shards of `queue` to `worker` until one of them is a hit, or until the queue is empty.
+///
+/// If the worker fails, its current shard is put back in the queue for the other workers.
+async fn run_worker(client: reqwest::Client, worker: String, queue: Arc<Mutex<VecDeque<ShardRequest>>>, in_flight: InFlight) -> Result<Option<u64>, Error> {
+    loop {
+        let Some(shard) = queue.lock().unwrap().pop_front() else { return Ok(None) };
+        in_flight.lock().unwrap().insert(shard.id.clone(), worker.clone());
+        let response = send_shard(&client, &worker, &shard).await;
+        in_flight.lock().unwrap().remove(&shard.id);
+        match response {
+            Ok(Some(dlog)) => return Ok(Some(dlog)),
+            Ok(None) => {}
+            Err(e) => {
+                queue.lock().unwrap().push_back(shard);
+                return Err(e);
+            }
+        }
+    }
+}
+
+async fn fetch_info(client: &reqwest::Client, worker: &str) -> Result<ShardInfo, Error> {
+    let remote = |e: reqwest::Error| Error::Remote(format!("{} : {}", worker, e));
+    let response = client.get(format!("{}/shard/info", worker)).send().await.map_err(remote)?;
+    response.error_for_status().map_err(remote)?.json().await.map_err(remote)
+}
+
+async fn send_shard(client: &reqwest::Client, worker: &str, shard: &ShardRequest) -> Result<Option<u64>, Error> {
+    let remote = |e: reqwest::Error| Error::Remote(format!("{} : {}", worker, e));
+    let response = client.post(format!("{}/shard", worker)).json(shard).send().await.map_err(remote)?;
+    let status = response.status();
+    let result: DlogResult = response.json().await.map_err(remote)?;
+    match (status, result) {
+        (_, DlogResult { dlog: Some(dlog), .. }) => Ok(Some(dlog)),
+        (StatusCode::UNPROCESSABLE_ENTITY, _) => Ok(None),
+        (status, DlogResult { error, .. }) => Err(Error::Remote(format!("{} : {} {}", worker, status, error.unwrap_or_default()))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point::{base_point, point_to_hex};
+    use crate::Solver;
+    use ark_ec::{AffineCurve, ProjectiveCurve};
+    use ark_ff::BigInteger256;
+
+    #[tokio::test]
+    async fn test_coordinator() {
+        let mut workers = Vec::new();
+        for _ in 0..2 {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            workers.push(format!("http://{}", listener.local_addr().unwrap()));
+            let router = super::super::router(Arc::new(Solver::new(16, 1)));
+            tokio::spawn(async move { axum::serve(listener, router).await });
+        }
+        // a worker which is not reachable
+        workers.push("http://127.0.0.1:1".to_string());
+
+        let coordinator = Coordinator::new(workers, 16);
+        let generator = ("0x0bb77a6ad63e739b4eacb2e09d6277c12ab8d8010534e0b62893f3f6bb957051", "0x25797203f7a0b24925572e1cd16bf9edfce0051fb9e133774b3c257a872d7d8b");
+        assert_eq!(coordinator.solve(generator.0, generator.1).await, Ok(1));
+        let (x, y) = point_to_hex(&base_point().mul(BigInteger256::from(40000)).into_affine());
+        assert_eq!(coordinator.solve(&x, &y).await, Ok(40000));
+        // the neutral element, in the first shard
+        assert_eq!(coordinator.solve("0x0", "0x1").await, Ok(0));
+        assert_eq!(coordinator.solve("0x1", "0x1").await, Err(Error::NotOnCurve));
+    }
+
+    async fn spawn_worker(solver: Solver) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let worker = format!("http://{}", listener.local_addr().unwrap());
+        let router = super::super::router(Arc::new(solver));
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        worker
+    }
+
+    #[tokio::test]
+    async fn test_coordinator_table_bitwidth() {
+        // tables smaller and larger than the default one of 8 bits, whose giant steps are more and fewer
+        for table_bitwidth in [5, 12] {
+            let workers = vec![
+                spawn_worker(Solver::with_table_bitwidth(16, table_bitwidth, 1)).await,
+                spawn_worker(Solver::with_table_bitwidth(16, table_bitwidth, 1)).await,
+            ];
+            let coordinator = Coordinator::new(workers, 16);
+            for dlog in [0u64, 40000, 65535] {
+                let (x, y) = point_to_hex(&base_point().mul(BigInteger256::from(dlog)).into_affine());
+                assert_eq!(coordinator.solve(&x, &y).await, Ok(dlog));
+            }
+        }
+
+        let workers = vec![spawn_worker(Solver::with_table_bitwidth(16, 5, 1)).await, spawn_worker(Solver::new(16, 1)).await];
+        let (x, y) = point_to_hex(&base_point().mul(BigInteger256::from(40000)).into_affine());
+        assert!(matches!(Coordinator::new(workers.clone(), 16).solve(&x, &y).await, Err(Error::Remote(e)) if e.contains("uses a table of 8 bits, not 5")));
+        assert!(matches!(Coordinator::new(workers, 20).solve(&x, &y).await, Err(Error::Remote(e)) if e.contains("plaintexts of 16 bits, not 20")));
+
+        // a shard sized for another table is rejected instead of covering a part of the range
+        let shard = ShardRequest { id: "a".to_string(), x, y, max_bitwidth: 16, table_bitwidth: 8, start: 0, end: 256 };
+        let response = reqwest::Client::new().post(format!("{}/shard", spawn_worker(Solver::with_table_bitwidth(16, 5, 1)).await)).json(&shard).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,121 @@
+//! Encryption of signed amounts, e.g balances which can become negative, with the same convention on the encrypting and the decrypting side.
+//!
+//! An amount `v` of `b` bits, i.e between `-2^(b-1)` and `2^(b-1) - 1`, is embedded with one of two conventions, see [`Convention`] :
+//!
+//! - [`Convention::Offset`] : the plaintext is the unsigned integer `v + 2^(b-1)`, which can be encrypted by any encryptor of unsigned
+//!   plaintexts, such as the `exp_elgamal_encrypt` Noir function. Sums of `n` ciphertexts are offset by `n·2^(b-1)` however.
+//! - [`Convention::Complement`] : the embedded point is `v·G`, i.e a negative `v` is the plaintext `l + v` modulo the order `l` of the prime
+//!   subgroup, which is what the difference of two [`Ciphertext`]s gives. Sums of ciphertexts keep this convention.
+//!
+//! Both conventions are decrypted with a single search of `b` bits, from `0` for the offset convention, and from `-2^(b-1)` for the
+//! complement one after shifting the embedded point by `2^(b-1)·G`. A [`SignedEncoding`] is also a [`crate::codec::PlaintextCodec`].
+
+use crate::{codec, Ciphertext, Error, HexPoint, Solver};
+
+/// How a signed amount is embedded into a plaintext, see the [module documentation](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Convention {
+    Offset,
+    Complement,
+}
+
+/// Encoding of the signed amounts of `bitwidth` bits with a [`Convention`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignedEncoding {
+    convention: Convention,
+    bitwidth: u64,
+}
+
+impl SignedEncoding {
+    /// Encoding of the signed amounts of `bitwidth` bits, which is between `1` and `64`.
+    pub fn new(convention: Convention, bitwidth: u64) -> Self {
+        assert!((1..=64).contains(&bitwidth), "bitwidth should be between 1 and 64");
+        SignedEncoding { convention, bitwidth }
+    }
+
+    pub fn convention(&self) -> Convention {
+        self.convention
+    }
+
+    /// Bitwidth of the amounts, which is also the `max_bitwidth` of the [`Solver`] decrypting them.
+    pub fn bitwidth(&self) -> u64 {
+        self.bitwidth
+    }
+
+    /// Smallest amount, `-2^(bitwidth-1)`.
+    pub fn min(&self) -> i64 {
+        i64::MIN >> (64 - self.bitwidth)
+    }
+
+    /// Largest amount, `2^(bitwidth-1) - 1`.
+    pub fn max(&self) -> i64 {
+        i64::MAX >> (64 - self.bitwidth)
+    }
+
+    /// Unsigned plaintext of `amount` with the offset convention, whatever the convention of this encoding.
+    pub fn offset_plaintext(&self, amount: i64) -> Result<u64, Error> {
+        self.check(amount)?;
+        Ok(amount.wrapping_sub(self.min()) as u64)
+    }
+
+    /// Embeds `amount` as a point, i.e the point recovered by [`crate::exp_elgamal_decrypt`] from its encryptions.
+    pub fn embed(&self, amount: i64) -> Result<HexPoint, Error> {
+        codec::embed(self, &amount)
+    }
+
+    /// Encrypts `amount` for `public_key` with `randomness`, like [`crate::exp_elgamal_encrypt`] does for unsigned plaintexts.
+    pub fn encrypt(&self, public_key: (&str, &str), amount: i64, randomness: &str) -> Result<Ciphertext, Error> {
+        codec::encrypt(self, public_key, &amount, randomness)
+    }
+
+    /// Decrypts `ciphertext` with `private_key` down to its amount, with `solver`, whose maximum bitwidth should be at least [`SignedEncoding::bitwidth`].
+    pub fn decrypt(&self, private_key: &str, ciphertext: &Ciphertext, solver: &Solver) -> Result<i64, Error> {
+        codec::decrypt(self, private_key, ciphertext, solver)
+    }
+
+    /// Recovers the amount embedded in the point `(x, y)`, e.g returned by the `exp_elgamal_decrypt` Noir function, like [`crate::do_compute_dlog`].
+    pub fn solve(&self, x: &str, y: &str, solver: &Solver) -> Result<i64, Error> {
+        codec::solve(self, x, y, solver)
+    }
+
+    fn check(&self, amount: i64) -> Result<(), Error> {
+        if amount < self.min() || amount > self.max() {
+            return Err(Error::AmountOutOfRange { bitwidth: self.bitwidth });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::priv_to_pub_key;
+
+    #[test]
+    fn test_signed_encoding() {
+        let sk = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        let pk = priv_to_pub_key(sk).unwrap();
+        let pk = (pk.0.as_str(), pk.1.as_str());
+        let solver = Solver::new(12, 1);
+        for convention in [Convention::Offset, Convention::Complement] {
+            let encoding = SignedEncoding::new(convention, 12);
+            assert_eq!((encoding.min(), encoding.max()), (-2048, 2047));
+            for amount in [-2048, -42, -1, 0, 1, 42, 2047] {
+                let ciphertext = encoding.encrypt(pk, amount, "0x05").unwrap();
+                assert_eq!(encoding.decrypt(sk, &ciphertext, &solver), Ok(amount), "{:?} {}", convention, amount);
+                let embedded = encoding.embed(amount).unwrap();
+                assert_eq!(encoding.solve(&embedded.0, &embedded.1, &solver), Ok(amount));
+            }
+            assert_eq!(encoding.encrypt(pk, 2048, "0x05"), Err(Error::AmountOutOfRange { bitwidth: 12 }));
+            assert_eq!(encoding.embed(-2049), Err(Error::AmountOutOfRange { bitwidth: 12 }));
+        }
+
+        // the difference of two ciphertexts follows the complement convention
+        let complement = SignedEncoding::new(Convention::Complement, 12);
+        let a = complement.encrypt(pk, 10, "0x05").unwrap();
+        let b = complement.encrypt(pk, 52, "0x07").unwrap();
+        assert_eq!(complement.decrypt(sk, &(a - b), &solver), Ok(-42));
+        assert_eq!(SignedEncoding::new(Convention::Offset, 12).offset_plaintext(-42), Ok(2006));
+        assert_eq!(SignedEncoding::new(Convention::Offset, 64).offset_plaintext(i64::MIN), Ok(0));
+    }
+}
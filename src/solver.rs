@@ -0,0 +1,794 @@
+//! Multi-threaded baby-step giant-step solver.
+//!
+//! The baby steps `j·G` for `j` in `[0, m)` are computed once, in parallel, and stored in a table shared by every search.
+//! The giant steps `P - i·m·G` are then split between the workers, each of them covering its own contiguous part of the range,
+//! so that the progress of a search can be observed while it is running.
+
+#[cfg(test)]
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ed_on_bn254::EdwardsAffine as BabyJubJub;
+#[cfg(test)]
+use ark_ff::BigInteger256;
+use std::io::{self, Read, Write};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::point::{base_point, parse_coordinates, parse_field_be_bytes, parse_hex_ascii, parse_point, point_from_coordinates, point_to_hex, HexPoint, ParseMode};
+use crate::dlog::DlogCurve;
+use crate::table::{BabyStepCurve, BabyStepTable, Lookup, TableKey};
+use crate::Error;
+
+/// Solver for the Discrete Logarithm of embedded plaintexts of at most `max_bitwidth` bits.
+///
+/// Building a `Solver` computes its table of baby steps, which is then reused by all the calls to [`Solver::solve`].
+///
+/// The solvers of the other curves, e.g [`crate::grumpkin::Solver`], run the same search on their own points, see [`crate::dlog`].
+pub struct Solver<C: BabyStepCurve = BabyJubJub, K: TableKey = u64> {
+    max_bitwidth: u64,
+    table_bitwidth: u64,
+    num_threads: u64,
+    parse_mode: ParseMode,
+    progress_interval: Option<u64>,
+    table: BabyStepTable<C, K>,
+    table_build_time: Duration,
+    last_stats: Mutex<Option<SolveStats>>,
+    /// Memory allocated by the running searches, and highest memory used by the solver, see [`Solver::memory_usage`].
+    search_bytes: AtomicU64,
+    peak_bytes: AtomicU64,
+}
+
+impl Solver {
+    /// Builds the table of baby steps for plaintexts of at most `max_bitwidth` bits (between `1` and `64`), using `num_threads` threads.
+    pub fn new(max_bitwidth: u64, num_threads: u64) -> Self {
+        Self::with_table_bitwidth(max_bitwidth, max_bitwidth / 2, num_threads)
+    }
+
+    /// Same as [`Solver::new`], with a table of `2^table_bitwidth` baby steps instead of `2^(max_bitwidth / 2)`.
+    ///
+    /// A larger table uses more memory and takes longer to build, but makes each search faster, which pays off when it is reused for many searches.
+    /// A search of 64 bits needs a table of at least 1 bit, since its `2^64` giant steps could not be counted otherwise.
+    pub fn with_table_bitwidth(max_bitwidth: u64, table_bitwidth: u64, num_threads: u64) -> Self {
+        Self::with_generator(base_point(), max_bitwidth, table_bitwidth, num_threads)
+    }
+
+    /// Computes the Discrete Logarithm of the point of Montgomery coordinates `(u, v)`, see [`crate::curve::montgomery_to_noir`].
+    pub fn solve_montgomery(&self, u: &str, v: &str) -> Result<u64, Error> {
+        let (x, y) = crate::curve::montgomery_to_noir(u, v)?;
+        self.solve(&x, &y)
+    }
+
+    /// Computes the Discrete Logarithm of the point of short Weierstrass coordinates `(x, y)`, see [`crate::curve::weierstrass_to_noir`].
+    pub fn solve_weierstrass(&self, x: &str, y: &str) -> Result<u64, Error> {
+        let (x, y) = crate::curve::weierstrass_to_noir(x, y)?;
+        self.solve(&x, &y)
+    }
+
+    /// Computes the Discrete Logarithm of the point whose coordinates `(x, y)` are base64 encodings of their big-endian bytes, see
+    /// [`crate::base64_to_hex`].
+    pub fn solve_base64(&self, x: &str, y: &str) -> Result<u64, Error> {
+        self.solve(&crate::base64_to_hex(x)?, &crate::base64_to_hex(y)?)
+    }
+
+    /// Computes the Discrete Logarithm of the point whose coordinates `(x, y)` are `Field`s in any format displayed by Noir, e.g decimal,
+    /// see [`crate::noir_field_to_hex`].
+    pub fn solve_noir(&self, x: &str, y: &str) -> Result<u64, Error> {
+        let (x, y) = parse_coordinates(x, y, crate::noir_field_to_hex)?;
+        self.solve(&x, &y)
+    }
+
+    /// Same as [`Solver::solve`] for coordinates given as the bytes of their hexadecimal strings, e.g slices of a network buffer, which
+    /// are parsed without allocating any intermediate string or buffer.
+    pub fn solve_hex_bytes(&self, x: &[u8], y: &[u8]) -> Result<u64, Error> {
+        parse_coordinates(x, y, |s| self.parse_mode.check_ascii(s))?;
+        let (x, y) = parse_coordinates(x, y, parse_hex_ascii)?;
+        self.solve_be_bytes(&x, &y)
+    }
+
+    /// Same as [`Solver::solve`] for coordinates given as their 32 big-endian bytes, e.g read from a binary record.
+    pub fn solve_be_bytes(&self, x: &[u8; 32], y: &[u8; 32]) -> Result<u64, Error> {
+        let (x, y) = parse_coordinates(x, y, parse_field_be_bytes)?;
+        let point = point_from_coordinates(x, y)?;
+        self.search_point(point).run()
+    }
+
+    /// Loads the table of baby steps from a file written by [`Solver::write_table`] instead of building it, for plaintexts of at most
+    /// `max_bitwidth` bits, which should be at least the bitwidth of the table.
+    pub fn from_table<R: Read>(r: R, max_bitwidth: u64, num_threads: u64) -> Result<Self, Error> {
+        assert!((1..=64).contains(&max_bitwidth), "max_bitwidth should be between 1 and 64");
+        assert!(num_threads > 0, "num_threads should be positive");
+        let start = Instant::now();
+        let table = BabyStepTable::read_from(r, base_point())?;
+        let table_build_time = start.elapsed();
+        let table_bitwidth = u64::from(table.len().trailing_zeros());
+        if table_bitwidth > max_bitwidth {
+            return Err(Error::InvalidTable(format!("a table of {} bits is too large for plaintexts of {} bits", table_bitwidth, max_bitwidth)));
+        }
+        if max_bitwidth - table_bitwidth == 64 {
+            return Err(Error::InvalidTable("a search of 64 bits needs a table of at least 1 bit".to_string()));
+        }
+        Ok(Solver::from_parts(max_bitwidth, table_bitwidth, num_threads, table, table_build_time))
+    }
+
+    /// Writes the table of baby steps, see the [`crate::table`] module for its format.
+    pub fn write_table<W: Write>(&self, w: W) -> io::Result<()> {
+        // the keys of the baby steps are sorted in a buffer before being written
+        let _buffer = self.allocate(8 * self.table.len());
+        self.table.write_to(w)
+    }
+
+    /// Resumes a search from a [`Checkpoint`], e.g one saved when a previous process was interrupted.
+    ///
+    /// The checkpoint keeps the split of the giant steps between workers of the original search, regardless of the number of threads of this solver.
+    pub fn resume(&self, checkpoint: &Checkpoint) -> Result<Search<'_>, Error> {
+        if checkpoint.max_bitwidth != self.max_bitwidth {
+            return Err(Error::InvalidCheckpoint(format!("the checkpoint is for a search of {} bits, not {}", checkpoint.max_bitwidth, self.max_bitwidth)));
+        }
+        if checkpoint.table_bitwidth != self.table_bitwidth {
+            return Err(Error::InvalidCheckpoint(format!("the checkpoint is for a table of {} bits, not {}", checkpoint.table_bitwidth, self.table_bitwidth)));
+        }
+        let point = parse_point(&checkpoint.point.0, &checkpoint.point.1)?;
+        let giant_steps = self.giant_steps();
+        let mut workers = Vec::with_capacity(checkpoint.workers.len());
+        for &(start, end, position) in &checkpoint.workers {
+            if start > position || position > end || end > giant_steps {
+                return Err(Error::InvalidCheckpoint("the position of a worker is out of the range of giant steps".to_string()));
+            }
+            workers.push(Worker { start, end, position: AtomicU64::new(position) });
+        }
+        Ok(Search::new(self, point, workers))
+    }
+
+}
+
+impl<C: DlogCurve> Solver<C> {
+    /// Same as [`Solver::with_table_bitwidth`], on the curve `C` and in base its generator, e.g a curve implemented outside of this crate.
+    pub fn for_curve(max_bitwidth: u64, table_bitwidth: u64, num_threads: u64) -> Self {
+        Self::with_generator(C::generator(), max_bitwidth, table_bitwidth, num_threads)
+    }
+
+    /// Computes the Discrete Logarithm of the point `(x, y)`, with the same input format as [`crate::do_compute_dlog`] on Baby Jubjub.
+    pub fn solve(&self, x: &str, y: &str) -> Result<u64, Error> {
+        self.search(x, y)?.run()
+    }
+
+    /// Prepares the search for the Discrete Logarithm of the point `(x, y)`, without running it.
+    ///
+    /// The returned [`Search`] can be shared with other threads, e.g to report its [`Search::progress`] while [`Search::run`] is executing.
+    pub fn search(&self, x: &str, y: &str) -> Result<Search<'_, C>, Error> {
+        parse_coordinates(x, y, |s| self.parse_mode.check(s))?;
+        let point = C::parse_point(x, y)?;
+        Ok(self.search_point(point))
+    }
+
+    /// Prepares a search restricted to the giant steps `[start, end)`, e.g one shard of a search distributed between several machines.
+    ///
+    /// The Discrete Logarithms covered by the giant step `i` are `[i·m, (i + 1)·m)`, where `m` is the number of baby steps.
+    pub fn search_range(&self, x: &str, y: &str, start: u64, end: u64) -> Result<Search<'_, C>, Error> {
+        parse_coordinates(x, y, |s| self.parse_mode.check(s))?;
+        let point = C::parse_point(x, y)?;
+        if start > end || end > self.giant_steps() {
+            return Err(Error::InvalidRange { start, end, giant_steps: self.giant_steps() });
+        }
+        Ok(self.search_point_range(point, start, end))
+    }
+}
+
+impl<C: BabyStepCurve, K: TableKey> Solver<C, K> {
+    /// Same as [`Solver::with_table_bitwidth`], for the Discrete Logarithms in base `generator` instead of the generator of noir-elgamal.
+    pub(crate) fn with_generator(generator: C, max_bitwidth: u64, table_bitwidth: u64, num_threads: u64) -> Self {
+        assert!((1..=64).contains(&max_bitwidth), "max_bitwidth should be between 1 and 64");
+        assert!(table_bitwidth <= max_bitwidth.min(32), "table_bitwidth should be at most max_bitwidth and 32");
+        assert!(max_bitwidth - table_bitwidth < 64, "a search of 64 bits needs a table of at least 1 bit");
+        assert!(num_threads > 0, "num_threads should be positive");
+        let start = Instant::now();
+        let table = BabyStepTable::build(generator, 1u64 << table_bitwidth, num_threads);
+        let table_build_time = start.elapsed();
+        Solver::from_parts(max_bitwidth, table_bitwidth, num_threads, table, table_build_time)
+    }
+
+    fn from_parts(max_bitwidth: u64, table_bitwidth: u64, num_threads: u64, table: BabyStepTable<C, K>, table_build_time: Duration) -> Self {
+        let peak_bytes = AtomicU64::new(table.peak_bytes());
+        Solver {
+            max_bitwidth,
+            table_bitwidth,
+            num_threads,
+            parse_mode: ParseMode::default(),
+            progress_interval: None,
+            table,
+            table_build_time,
+            last_stats: Mutex::new(None),
+            search_bytes: AtomicU64::new(0),
+            peak_bytes,
+        }
+    }
+
+    /// Generator of the table of baby steps, i.e the base of the Discrete Logarithms solved by the solver.
+    #[cfg(feature = "elgamal")]
+    pub(crate) fn generator(&self) -> C {
+        self.table.generator()
+    }
+
+    /// Sets the number of threads of each search, e.g to build the table with all the cores and then run several searches at the same time.
+    pub fn with_num_threads(mut self, num_threads: u64) -> Self {
+        assert!(num_threads > 0, "num_threads should be positive");
+        self.num_threads = num_threads;
+        self
+    }
+
+    /// Sets the policy of acceptance of the hexadecimal coordinates, [`ParseMode::Lenient`] by default.
+    pub fn with_parse_mode(mut self, parse_mode: ParseMode) -> Self {
+        self.parse_mode = parse_mode;
+        self
+    }
+
+    pub fn parse_mode(&self) -> ParseMode {
+        self.parse_mode
+    }
+
+    /// With the `tracing` feature, makes each worker of the searches emit a `tracing` event every `giant_steps` giant steps, with the
+    /// range of the worker, the part of it covered so far and its counts of hits and misses in the table, so that the long searches
+    /// are followed live, e.g in a log aggregator. There are no periodic events by default.
+    ///
+    /// The hits are the giant steps whose truncated key is in the table, including the `false_hits` rejected by recomputing the baby
+    /// step. They are counted by the current run of the search, so that `resumed + hits + misses = covered` for the searches resumed
+    /// from a checkpoint, where `resumed` is the part of the range covered before the checkpoint.
+    pub fn with_progress_interval(mut self, giant_steps: u64) -> Self {
+        assert!(giant_steps > 0, "the progress interval should be positive");
+        self.progress_interval = Some(giant_steps);
+        self
+    }
+
+    pub fn progress_interval(&self) -> Option<u64> {
+        self.progress_interval
+    }
+
+    pub fn max_bitwidth(&self) -> u64 {
+        self.max_bitwidth
+    }
+
+    pub fn table_bitwidth(&self) -> u64 {
+        self.table_bitwidth
+    }
+
+    pub fn num_threads(&self) -> u64 {
+        self.num_threads
+    }
+
+    /// Approximate memory used by the table of baby steps, in bytes.
+    pub fn table_size_bytes(&self) -> u64 {
+        self.table.size_bytes()
+    }
+
+    /// Statistics of the last search run by the solver, i.e of the last one to finish when several run at the same time, or `None`
+    /// before the first one.
+    pub fn last_stats(&self) -> Option<SolveStats> {
+        *self.last_stats.lock().unwrap()
+    }
+
+    /// Memory allocated by the solver : its table of baby steps and the buffers of its running searches, with the highest total
+    /// reached since the solver was built, including the buffers used while the table was built or loaded. The stacks of the threads
+    /// of the workers are not included.
+    ///
+    /// This allows to enforce a quota before starting more searches, and to compare the configurations of the solver, see
+    /// [`MemoryUsage::estimate`] for the ones which are not built yet.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage {
+            table_bytes: self.table_size_bytes(),
+            search_bytes: self.search_bytes.load(Ordering::Relaxed),
+            peak_bytes: self.peak_bytes.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Records the allocation of `bytes` by a search, until the returned guard is dropped.
+    fn allocate(&self, bytes: u64) -> Allocation<'_, C, K> {
+        let search_bytes = self.search_bytes.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        self.peak_bytes.fetch_max(self.table_size_bytes() + search_bytes, Ordering::Relaxed);
+        Allocation { solver: self, bytes }
+    }
+
+    pub(crate) fn search_point(&self, point: C) -> Search<'_, C, K> {
+        self.search_point_range(point, 0, self.giant_steps())
+    }
+
+    fn search_point_range(&self, point: C, start: u64, end: u64) -> Search<'_, C, K> {
+        let chunk_size = (end - start) / self.num_threads;
+        let workers = (0..self.num_threads)
+            .map(|idx| {
+                let worker_start = start + idx * chunk_size;
+                let worker_end = if idx == self.num_threads - 1 { end } else { worker_start + chunk_size };
+                Worker { start: worker_start, end: worker_end, position: AtomicU64::new(worker_start) }
+            })
+            .collect();
+        Search::new(self, point, workers)
+    }
+
+    /// Number of giant steps of a full search, i.e `2^(max_bitwidth - table_bitwidth)`.
+    pub fn giant_steps(&self) -> u64 {
+        1u64 << (self.max_bitwidth - self.table_bitwidth)
+    }
+}
+
+/// Statistics of a search and of the table of its [`Solver`], see [`Solver::last_stats`], e.g for capacity planning and the tracking of
+/// performance regressions in production.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SolveStats {
+    /// Time taken to build the table of baby steps, or to load it with [`Solver::from_table`].
+    pub table_build_time: Duration,
+    /// Number of baby steps computed, or loaded, per second.
+    pub baby_steps_per_second: f64,
+    /// Approximate memory used by the table of baby steps, see [`Solver::table_size_bytes`].
+    pub table_bytes: u64,
+    /// Time taken by the giant steps of the search.
+    pub search_time: Duration,
+    /// Number of giant steps done by all the workers of the search.
+    pub giant_steps: u64,
+    pub giant_steps_per_second: f64,
+    /// Fraction of the search time during which the threads of the workers were running, between `0` and `1`, which is lower when the
+    /// parts of the range of the workers are unbalanced.
+    pub thread_utilization: f64,
+}
+
+/// Memory allocated by a [`Solver`], in bytes, see [`Solver::memory_usage`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// Approximate memory used by the table of baby steps, see [`Solver::table_size_bytes`].
+    pub table_bytes: u64,
+    /// Memory used by the state of the searches currently running or prepared.
+    pub search_bytes: u64,
+    /// Highest memory used by the solver since it was built.
+    pub peak_bytes: u64,
+}
+
+impl MemoryUsage {
+    /// Expected memory of a [`Solver`] with a table of `table_bitwidth` bits, built with [`Solver::with_table_bitwidth`] before any search.
+    pub fn estimate(table_bitwidth: u64) -> Self {
+        assert!(table_bitwidth <= 32, "table_bitwidth should be at most 32");
+        let m = 1u64 << table_bitwidth;
+        // the buckets of the map are a power of two, at most 7/8 of which are used
+        let buckets = if m < 8 { (m + 1).next_power_of_two().max(4) } else { (m * 8 / 7).next_power_of_two() };
+        let capacity = if buckets < 8 { buckets - 1 } else { buckets / 8 * 7 };
+        let entry_size = std::mem::size_of::<(u64, u32)>() as u64;
+        let table_bytes = (entry_size + 1) * capacity;
+        // the baby steps are computed in buffers of the threads, all kept until they are inserted into the map
+        MemoryUsage { table_bytes, search_bytes: 0, peak_bytes: table_bytes + entry_size * m }
+    }
+
+    /// Memory currently used, i.e by the table and the searches.
+    pub fn total_bytes(&self) -> u64 {
+        self.table_bytes + self.search_bytes
+    }
+}
+
+/// Memory of a search counted in [`Solver::memory_usage`] while it is alive.
+struct Allocation<'a, C: BabyStepCurve, K: TableKey> {
+    solver: &'a Solver<C, K>,
+    bytes: u64,
+}
+
+impl<C: BabyStepCurve, K: TableKey> Drop for Allocation<'_, C, K> {
+    fn drop(&mut self) {
+        self.solver.search_bytes.fetch_sub(self.bytes, Ordering::Relaxed);
+    }
+}
+
+/// Progress of one of the workers of a [`Search`], in number of giant steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkerProgress {
+    pub done: u64,
+    pub total: u64,
+}
+
+/// A search for a Discrete Logarithm, whose giant steps are split between the workers of the [`Solver`].
+pub struct Search<'a, C: BabyStepCurve = BabyJubJub, K: TableKey = u64> {
+    solver: &'a Solver<C, K>,
+    point: C,
+    workers: Vec<Worker>,
+    found: AtomicBool,
+    cancelled: AtomicBool,
+    _allocation: Allocation<'a, C, K>,
+}
+
+struct Worker {
+    start: u64,
+    end: u64,
+    position: AtomicU64,
+}
+
+impl<'a, C: BabyStepCurve, K: TableKey> Search<'a, C, K> {
+    fn new(solver: &'a Solver<C, K>, point: C, workers: Vec<Worker>) -> Self {
+        let allocation = solver.allocate((std::mem::size_of::<Self>() + workers.capacity() * std::mem::size_of::<Worker>()) as u64);
+        Search { solver, point, workers, found: AtomicBool::new(false), cancelled: AtomicBool::new(false), _allocation: allocation }
+    }
+
+    /// Returns the number of giant steps done by each worker so far, out of the size of its part of the range.
+    pub fn progress(&self) -> Vec<WorkerProgress> {
+        self.workers
+            .iter()
+            .map(|w| WorkerProgress { done: w.position.load(Ordering::Relaxed) - w.start, total: w.end - w.start })
+            .collect()
+    }
+
+    /// Stops the workers of a running search, which then returns [`Error::Cancelled`].
+    ///
+    /// The state of the search can still be saved with [`Search::checkpoint`] and resumed later with [`Solver::resume`].
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Runs the giant steps on all the workers, until one of them finds the Discrete Logarithm or they all reach the end of their range.
+    ///
+    /// Each worker starts from its current position, so a search should only be run once.
+    pub fn run(&self) -> Result<u64, Error> {
+        let m = self.solver.table.len();
+        let g = self.solver.table.generator();
+        let am = g.mul_u64(m);
+        let start = Instant::now();
+        let _buffers = self.solver.allocate((self.workers.len() * std::mem::size_of::<(u64, Option<u64>, Duration)>()) as u64);
+        let positions: Vec<u64> = self.workers.iter().map(|w| w.position.load(Ordering::Relaxed)).collect();
+        let results: Vec<(Option<u64>, Duration)> = thread::scope(|s| {
+            let handles: Vec<_> = self.workers
+                .iter()
+                .enumerate()
+                .map(|(idx, w)| s.spawn(move || (self.run_worker(idx, w, &am), start.elapsed())))
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        let search_time = start.elapsed();
+        let giant_steps = self.workers.iter().zip(positions).map(|(w, position)| w.position.load(Ordering::Relaxed) - position).sum();
+        let busy: f64 = results.iter().map(|(_, time)| time.as_secs_f64()).sum();
+        let solver = self.solver;
+        let stats = SolveStats {
+            table_build_time: solver.table_build_time,
+            baby_steps_per_second: m as f64 / solver.table_build_time.as_secs_f64(),
+            table_bytes: solver.table_size_bytes(),
+            search_time,
+            giant_steps,
+            giant_steps_per_second: giant_steps as f64 / search_time.as_secs_f64(),
+            thread_utilization: (busy / (results.len() as f64 * search_time.as_secs_f64())).min(1.0),
+        };
+        *solver.last_stats.lock().unwrap() = Some(stats);
+        let result = results.into_iter().find_map(|(dlog, _)| dlog);
+        #[cfg(feature = "tracing")]
+        tracing::info!(found = result.is_some(), giant_steps, search_ms = search_time.as_millis() as u64, "search finished");
+        match result {
+            Some(dlog) => Ok(dlog),
+            None if self.cancelled.load(Ordering::Relaxed) => Err(Error::Cancelled),
+            None => Err(Error::DlogNotFound { max_bitwidth: self.solver.max_bitwidth }),
+        }
+    }
+
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables, unused_assignments))]
+    fn run_worker(&self, idx: usize, worker: &Worker, am: &C::Projective) -> Option<u64> {
+        let m = self.solver.table.len();
+        let start = worker.position.load(Ordering::Relaxed);
+        let mut gamma = self.point.to_projective() - C::to_affine(am).mul_u64(start);
+        // giant steps whose key is in the table, and the ones of them rejected by recomputing the baby step
+        let (mut hits, mut false_hits) = (0u64, 0u64);
+        for i in start..worker.end { // giant_steps
+            if self.found.load(Ordering::Relaxed) || self.cancelled.load(Ordering::Relaxed) {
+                return None;
+            }
+            match self.solver.table.lookup(&C::to_affine(&gamma)) {
+                Lookup::Hit(j) => {
+                    self.found.store(true, Ordering::Relaxed);
+                    worker.position.store(i + 1, Ordering::Relaxed);
+                    #[cfg(feature = "tracing")]
+                    tracing::info!(
+                        worker = idx,
+                        start = worker.start,
+                        end = worker.end,
+                        position = i + 1,
+                        resumed = start - worker.start,
+                        hits = hits + 1,
+                        false_hits,
+                        misses = i - start - hits,
+                        "worker found the discrete logarithm"
+                    );
+                    return Some(i * m + j);
+                }
+                Lookup::FalseMatch => {
+                    hits += 1;
+                    false_hits += 1;
+                }
+                Lookup::Miss => {}
+            }
+            gamma -= am;
+            worker.position.store(i + 1, Ordering::Relaxed);
+            #[cfg(feature = "tracing")]
+            if self.solver.progress_interval.is_some_and(|interval| (i + 1 - worker.start) % interval == 0) {
+                tracing::info!(
+                    worker = idx,
+                    start = worker.start,
+                    end = worker.end,
+                    position = i + 1,
+                    covered = i + 1 - worker.start,
+                    remaining = worker.end - i - 1,
+                    resumed = start - worker.start,
+                    hits,
+                    false_hits,
+                    misses = i + 1 - start - hits,
+                    "search progress"
+                );
+            }
+        }
+        None
+    }
+}
+
+impl Search<'_> {
+    /// Returns the current state of the search. Taken after the search was cancelled, it allows to resume it without repeating any giant step.
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            max_bitwidth: self.solver.max_bitwidth,
+            table_bitwidth: self.solver.table_bitwidth,
+            point: point_to_hex(&self.point),
+            workers: self.workers.iter().map(|w| (w.start, w.end, w.position.load(Ordering::Relaxed))).collect(),
+        }
+    }
+}
+
+/// Serializable state of a [`Search`]: the searched point, and the part of the giant steps remaining for each worker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checkpoint {
+    max_bitwidth: u64,
+    table_bitwidth: u64,
+    point: HexPoint,
+    workers: Vec<(u64, u64, u64)>,
+}
+
+const CHECKPOINT_MAGIC: &[u8; 4] = b"BGCK";
+const CHECKPOINT_VERSION: u8 = 2;
+
+impl Checkpoint {
+    pub fn max_bitwidth(&self) -> u64 {
+        self.max_bitwidth
+    }
+
+    pub fn table_bitwidth(&self) -> u64 {
+        self.table_bitwidth
+    }
+
+    /// Writes the checkpoint in a small binary format: a magic number and a version, the bitwidths of the search and of its table,
+    /// the coordinates of the point as 32 bytes big-endian integers, then the start, end and current position of each worker.
+    pub fn write_to<W: Write>(&self, mut w: W) -> io::Result<()> {
+        w.write_all(CHECKPOINT_MAGIC)?;
+        w.write_all(&[CHECKPOINT_VERSION, self.max_bitwidth as u8, self.table_bitwidth as u8])?;
+        for coordinate in [&self.point.0, &self.point.1] {
+            w.write_all(&hex::decode(&coordinate[2..]).unwrap())?;
+        }
+        w.write_all(&(self.workers.len() as u32).to_le_bytes())?;
+        for &(start, end, position) in &self.workers {
+            for n in [start, end, position] {
+                w.write_all(&n.to_le_bytes())?;
+            }
+        }
+        w.flush()
+    }
+
+    /// Reads a checkpoint written by [`Checkpoint::write_to`], including the ones of the first version, which always used the default table.
+    pub fn read_from<R: Read>(mut r: R) -> Result<Self, Error> {
+        let invalid = |e: io::Error| Error::InvalidCheckpoint(e.to_string());
+        let mut header = [0u8; 6];
+        r.read_exact(&mut header).map_err(invalid)?;
+        if &header[..4] != CHECKPOINT_MAGIC || !(1..=CHECKPOINT_VERSION).contains(&header[4]) {
+            return Err(Error::InvalidCheckpoint("not a checkpoint file, or written by an incompatible version".to_string()));
+        }
+        let max_bitwidth = u64::from(header[5]);
+        let table_bitwidth = if header[4] == 1 {
+            max_bitwidth / 2
+        } else {
+            let mut table_bitwidth = [0u8; 1];
+            r.read_exact(&mut table_bitwidth).map_err(invalid)?;
+            u64::from(table_bitwidth[0])
+        };
+        let mut coordinates = [[0u8; 32]; 2];
+        for c in coordinates.iter_mut() {
+            r.read_exact(c).map_err(invalid)?;
+        }
+        let point = (format!("0x{}", hex::encode(coordinates[0])), format!("0x{}", hex::encode(coordinates[1])));
+        let mut len = [0u8; 4];
+        r.read_exact(&mut len).map_err(invalid)?;
+        let mut workers = Vec::new();
+        for _ in 0..u32::from_le_bytes(len) {
+            let mut n = [[0u8; 8]; 3];
+            for b in n.iter_mut() {
+                r.read_exact(b).map_err(invalid)?;
+            }
+            workers.push((u64::from_le_bytes(n[0]), u64::from_le_bytes(n[1]), u64::from_le_bytes(n[2])));
+        }
+        Ok(Checkpoint { max_bitwidth, table_bitwidth, point, workers })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solver_small_bitwidth() {
+        let solver = Solver::new(17, 3);
+        for dlog in [0u64, 1, 255, 256, 65535, 131071] {
+            let p = base_point().mul(BigInteger256::from(dlog)).into_affine();
+            assert_eq!(solver.search_point(p).run(), Ok(dlog));
+        }
+        let p = base_point().mul(BigInteger256::from(131072)).into_affine();
+        assert_eq!(solver.search_point(p).run(), Err(Error::DlogNotFound { max_bitwidth: 17 }));
+    }
+
+    #[test]
+    fn test_search_progress() {
+        let solver = Solver::new(16, 3);
+        let search = solver.search_point(base_point().mul(BigInteger256::from(65535)).into_affine());
+        let totals: Vec<u64> = search.progress().iter().map(|p| p.total).collect();
+        assert_eq!(totals, vec![85, 85, 86]);
+        assert!(search.progress().iter().all(|p| p.done == 0));
+        assert_eq!(solver.last_stats(), None);
+        assert_eq!(search.run(), Ok(65535));
+        assert_eq!(search.progress()[2].done, 86);
+
+        let stats = solver.last_stats().unwrap();
+        assert_eq!(stats.giant_steps, search.progress().iter().map(|p| p.done).sum::<u64>());
+        assert!((86..=256).contains(&stats.giant_steps));
+        assert_eq!(stats.table_bytes, solver.table_size_bytes());
+        assert!(stats.baby_steps_per_second > 0.0 && stats.giant_steps_per_second > 0.0);
+        assert!(stats.thread_utilization > 0.0 && stats.thread_utilization <= 1.0);
+    }
+
+    #[test]
+    fn test_memory_usage() {
+        let solver = Solver::with_table_bitwidth(16, 10, 2);
+        let usage = solver.memory_usage();
+        assert_eq!(usage, MemoryUsage::estimate(10));
+        assert_eq!((usage.table_bytes, usage.search_bytes), (solver.table_size_bytes(), 0));
+        assert!(usage.peak_bytes >= usage.total_bytes() + 12 * 1024);
+
+        let search = solver.search_point(base_point().mul(BigInteger256::from(1000)).into_affine());
+        let search_bytes = solver.memory_usage().search_bytes;
+        assert!(search_bytes >= 2 * std::mem::size_of::<Worker>() as u64);
+        assert_eq!(search.run(), Ok(1000));
+        drop(search);
+        assert_eq!(solver.memory_usage().search_bytes, 0);
+
+        let mut bytes = Vec::new();
+        solver.write_table(&mut bytes).unwrap();
+        assert_eq!(solver.memory_usage().search_bytes, 0);
+        let loaded = Solver::from_table(bytes.as_slice(), 16, 1).unwrap();
+        assert!(loaded.memory_usage().peak_bytes >= loaded.table_size_bytes());
+    }
+
+    #[test]
+    fn test_checkpoint_resume() {
+        let solver = Solver::new(16, 2);
+        let search = solver.search_point(base_point().mul(BigInteger256::from(40000)).into_affine());
+        search.cancel();
+        assert_eq!(search.run(), Err(Error::Cancelled));
+
+        let mut bytes = Vec::new();
+        search.checkpoint().write_to(&mut bytes).unwrap();
+        let checkpoint = Checkpoint::read_from(bytes.as_slice()).unwrap();
+        assert_eq!(checkpoint, search.checkpoint());
+        assert_eq!(solver.resume(&checkpoint).unwrap().run(), Ok(40000));
+        assert!(matches!(Solver::new(14, 2).resume(&checkpoint), Err(Error::InvalidCheckpoint(_))));
+        assert!(matches!(Checkpoint::read_from(&bytes[..20]), Err(Error::InvalidCheckpoint(_))));
+        assert!(matches!(Solver::with_table_bitwidth(16, 9, 2).resume(&checkpoint), Err(Error::InvalidCheckpoint(_))));
+
+        // the first version of the format has no table bitwidth
+        let mut v1 = bytes[..5].to_vec();
+        v1[4] = 1;
+        v1.push(16);
+        v1.extend_from_slice(&bytes[7..]);
+        assert_eq!(Checkpoint::read_from(v1.as_slice()).unwrap(), checkpoint);
+    }
+
+    #[test]
+    fn test_table_bitwidth() {
+        let solver = Solver::with_table_bitwidth(16, 12, 2);
+        assert_eq!(solver.giant_steps(), 16);
+        for dlog in [0u64, 4095, 4096, 65535] {
+            let p = base_point().mul(BigInteger256::from(dlog)).into_affine();
+            assert_eq!(solver.search_point(p).run(), Ok(dlog));
+        }
+        // the table built with 2 threads is searched with 3
+        let solver = solver.with_num_threads(3);
+        let search = solver.search_point(base_point().mul(BigInteger256::from(65535u64)).into_affine());
+        assert_eq!(search.progress().len(), 3);
+        assert_eq!(search.run(), Ok(65535));
+
+        // the saved table is loaded for a larger bitwidth
+        let mut bytes = Vec::new();
+        solver.write_table(&mut bytes).unwrap();
+        let loaded = Solver::from_table(bytes.as_slice(), 18, 2).unwrap();
+        assert_eq!((loaded.table_bitwidth(), loaded.giant_steps()), (12, 64));
+        assert_eq!(loaded.search_point(base_point().mul(BigInteger256::from(200000u64)).into_affine()).run(), Ok(200000));
+        assert!(matches!(Solver::from_table(bytes.as_slice(), 8, 2), Err(Error::InvalidTable(_))));
+
+        // the 2^64 giant steps of a search of 64 bits without baby steps are rejected instead of wrapping around
+        let mut bytes = Vec::new();
+        Solver::with_table_bitwidth(8, 0, 1).write_table(&mut bytes).unwrap();
+        assert!(matches!(Solver::from_table(bytes.as_slice(), 64, 1), Err(Error::InvalidTable(e)) if e.contains("at least 1 bit")));
+        assert_eq!(Solver::from_table(bytes.as_slice(), 63, 1).unwrap().giant_steps(), 1 << 63);
+    }
+
+    #[test]
+    fn test_search_range() {
+        let solver = Solver::new(16, 2);
+        let (x, y) = point_to_hex(&base_point().mul(BigInteger256::from(40000)).into_affine());
+        // 40000 = 156·256 + 64
+        assert_eq!(solver.search_range(&x, &y, 0, 156).unwrap().run(), Err(Error::DlogNotFound { max_bitwidth: 16 }));
+        assert_eq!(solver.search_range(&x, &y, 156, 157).unwrap().run(), Ok(40000));
+        assert_eq!(solver.search_range(&x, &y, 100, 256).unwrap().run(), Ok(40000));
+        assert!(matches!(solver.search_range(&x, &y, 0, 257), Err(Error::InvalidRange { .. })));
+    }
+
+    /// Integer fields of an event.
+    #[cfg(feature = "tracing")]
+    type Fields = Vec<(String, u64)>;
+
+    /// Collects the fields of the progress events of every thread.
+    #[cfg(feature = "tracing")]
+    struct Progress(std::sync::Arc<Mutex<Vec<Fields>>>);
+
+    #[cfg(feature = "tracing")]
+    impl tracing::Subscriber for Progress {
+        fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, _: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            tracing::span::Id::from_u64(1)
+        }
+        fn record(&self, _: &tracing::span::Id, _: &tracing::span::Record<'_>) {}
+        fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+        fn event(&self, event: &tracing::Event<'_>) {
+            struct Visitor(Fields, bool);
+            impl tracing::field::Visit for Visitor {
+                fn record_u64(&mut self, field: &tracing::field::Field, value: u64) {
+                    self.0.push((field.name().to_string(), value));
+                }
+                fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+                    self.1 |= field.name() == "message" && format!("{:?}", value) == "search progress";
+                }
+            }
+            let mut fields = Visitor(Vec::new(), false);
+            event.record(&mut fields);
+            if fields.1 {
+                self.0.lock().unwrap().push(fields.0);
+            }
+        }
+        fn enter(&self, _: &tracing::span::Id) {}
+        fn exit(&self, _: &tracing::span::Id) {}
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_progress_events() {
+        // the events of the workers are emitted by their threads, so they are collected by the global subscriber
+        let events = std::sync::Arc::new(Mutex::new(Vec::new()));
+        tracing::subscriber::set_global_default(Progress(events.clone())).unwrap();
+        let solver = Solver::new(16, 1).with_progress_interval(7);
+        assert_eq!(solver.search_point(base_point().mul(BigInteger256::from(65535)).into_affine()).run(), Ok(65535));
+        let field = |event: &Fields, name: &str| event.iter().find(|(n, _)| n == name).unwrap().1;
+        {
+            let events = events.lock().unwrap();
+            assert_eq!(events.len(), 36);
+            for (i, event) in events.iter().enumerate() {
+                let steps = 7 * (i as u64 + 1);
+                assert_eq!((field(event, "worker"), field(event, "end"), field(event, "covered")), (0, 256, steps));
+                assert_eq!((field(event, "hits"), field(event, "misses"), field(event, "remaining")), (0, steps, 256 - steps));
+            }
+        }
+
+        // a resumed search keeps the events at the same positions, and counts the giant steps done before it apart
+        events.lock().unwrap().clear();
+        let point = base_point().mul(BigInteger256::from(65535)).into_affine();
+        let checkpoint = Checkpoint { max_bitwidth: 16, table_bitwidth: 8, point: point_to_hex(&point), workers: vec![(0, 256, 100)] };
+        assert_eq!(solver.resume(&checkpoint).unwrap().run(), Ok(65535));
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 36 - 14);
+        for event in events.iter() {
+            assert_eq!(field(event, "covered") % 7, 0);
+            assert_eq!(field(event, "resumed") + field(event, "hits") + field(event, "misses"), field(event, "covered"));
+            assert_eq!(field(event, "resumed"), 100);
+        }
+    }
+}
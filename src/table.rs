@@ -0,0 +1,397 @@
+//! Table of the baby steps of the [`crate::Solver`], and the file format used to save it, so that a large table is built once and
+//! then loaded by every run instead of being rebuilt.
+//!
+//! A table file starts with a header : a magic number and a version, the bitwidth of the table, the name of the curve and the generator
+//! `G` of the baby steps, and the number of entries `m`. It is followed by the keys of the baby steps `j·G` for `j` in `[0, m)`, in order,
+//! and ends with the SHA-256 checksum of all the previous bytes, so that a corrupted file is detected before it is trusted.
+
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ed_on_bn254::EdwardsAffine as BabyJubJub;
+use ark_ff::PrimeField;
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use std::collections::hash_map::{Entry, HashMap};
+use std::hash::Hash;
+use std::io::{self, Read, Write};
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::thread;
+use crate::point::{base_point, point_to_hex, HexPoint};
+use crate::Error;
+
+const TABLE_MAGIC: &[u8; 4] = b"BGTB";
+const TABLE_VERSION: u8 = 1;
+
+/// Number of entries reserved when a table file starts to be loaded, the map then growing with the entries actually read, so that the
+/// header of a truncated or hostile file cannot make the loading allocate much more memory than the size of the file.
+const MAX_RESERVED_ENTRIES: u64 = 1 << 16;
+
+/// Name of the curve of the baby steps in table files.
+pub const CURVE: &str = "babyjubjub";
+
+/// Header and checksum of a table file, see [`inspect_table`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableInfo {
+    pub table_bitwidth: u64,
+    pub curve: String,
+    /// Generator of the baby steps, in Twisted Edwards form.
+    pub generator: HexPoint,
+    /// Number of baby steps, i.e `2^table_bitwidth`.
+    pub entries: u64,
+    /// SHA-256 checksum of the file, as an hexadecimal string.
+    pub checksum: String,
+}
+
+/// Reads a table file written by [`crate::Solver::write_table`] and checks its checksum, without loading the table in memory.
+pub fn inspect_table<R: Read>(r: R) -> Result<TableInfo, Error> {
+    read_table(r, |_, _, _| {})
+}
+
+/// Same as [`inspect_table`], also checking that the file is a table of the curve and generator of noir-elgamal, and comparing `samples`
+/// baby steps chosen at random, as well as the first and the last ones, with their recomputation.
+pub fn verify_table<R: Read>(r: R, samples: u64) -> Result<TableInfo, Error> {
+    let g = base_point();
+    let mut checked: Vec<u64> = Vec::new();
+    let mut mismatch = None;
+    let info = read_table(r, |entries, j, k| {
+        if j == 0 {
+            let mut rng = rand::thread_rng();
+            checked = (0..samples).map(|_| rng.gen_range(0, entries)).chain([0, entries - 1]).collect();
+            checked.sort_unstable_by(|a, b| b.cmp(a));
+            checked.dedup();
+        }
+        if checked.last() == Some(&j) {
+            checked.pop();
+            if mismatch.is_none() && g.mul_u64(j).into_affine().key() != k {
+                mismatch = Some(j);
+            }
+        }
+    })?;
+    check_generator(&info, &g)?;
+    match mismatch {
+        Some(j) => Err(Error::InvalidTable(format!("the baby step {} does not match its recomputation", j))),
+        None => Ok(info),
+    }
+}
+
+fn check_generator(info: &TableInfo, g: &BabyJubJub) -> Result<(), Error> {
+    if info.curve != CURVE || info.generator != point_to_hex(g) {
+        return Err(Error::InvalidTable(format!("the table is for the generator {:?} of the curve {}, not {:?}", info.generator, info.curve, point_to_hex(g))));
+    }
+    Ok(())
+}
+
+/// Hashes the bytes read from `inner`.
+struct HashingReader<R> {
+    inner: R,
+    hasher: Sha256,
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.hasher.update(&buf[..n]);
+        Ok(n)
+    }
+}
+
+/// Reads a table file, calling `f` with the number of entries, the index `j` and the key of each baby step, and checks its checksum.
+fn read_table<R: Read>(r: R, mut f: impl FnMut(u64, u64, u64)) -> Result<TableInfo, Error> {
+    let invalid = |e: io::Error| Error::InvalidTable(e.to_string());
+    let mut r = HashingReader { inner: r, hasher: Sha256::new() };
+    let mut header = [0u8; 7];
+    r.read_exact(&mut header).map_err(invalid)?;
+    if &header[..4] != TABLE_MAGIC || header[4] != TABLE_VERSION {
+        return Err(Error::InvalidTable("not a table file, or written by an incompatible version".to_string()));
+    }
+    let table_bitwidth = u64::from(header[5]);
+    if table_bitwidth > 32 {
+        return Err(Error::InvalidTable(format!("a table of {} bits is larger than the maximum of 32 bits", table_bitwidth)));
+    }
+    let mut curve = vec![0u8; usize::from(header[6])];
+    r.read_exact(&mut curve).map_err(invalid)?;
+    let curve = String::from_utf8(curve).map_err(|_| Error::InvalidTable("the name of the curve is not valid UTF-8".to_string()))?;
+    let mut generator = [0u8; 64];
+    r.read_exact(&mut generator).map_err(invalid)?;
+    let generator = (format!("0x{}", hex::encode(&generator[..32])), format!("0x{}", hex::encode(&generator[32..])));
+    let mut entries = [0u8; 8];
+    r.read_exact(&mut entries).map_err(invalid)?;
+    let entries = u64::from_le_bytes(entries);
+    if entries != 1 << table_bitwidth {
+        return Err(Error::InvalidTable(format!("a table of {} bits should have {} entries, not {}", table_bitwidth, 1u64 << table_bitwidth, entries)));
+    }
+
+    let mut k = [0u8; 8];
+    for j in 0..entries {
+        r.read_exact(&mut k).map_err(invalid)?;
+        f(entries, j, u64::from_le_bytes(k));
+    }
+    let checksum = r.hasher.finalize();
+    let mut stored = [0u8; 32];
+    r.inner.read_exact(&mut stored).map_err(invalid)?;
+    if checksum.as_slice() != stored {
+        return Err(Error::InvalidTable("the checksum does not match the content of the file, which is corrupted".to_string()));
+    }
+    Ok(TableInfo { table_bitwidth, curve, generator, entries, checksum: hex::encode(stored) })
+}
+
+/// Points of the curve whose Discrete Logarithms are searched by a [`crate::Solver`], i.e Baby Jubjub, or the curve of another backend,
+/// e.g of the `grumpkin` and `secp256k1` features, in the affine representation in which they are compared and keyed.
+pub trait BabyStepCurve: Copy + Eq + Send + Sync {
+    /// Representation of the points in which the giant steps are computed, e.g their projective coordinates.
+    type Projective: Copy + Send + Sync + Add<Output = Self::Projective> + Sub<Output = Self::Projective> + for<'a> SubAssign<&'a Self::Projective>;
+
+    /// Key of the point in the table of baby steps, e.g the lowest 64 bits of its `x` coordinate.
+    fn key(&self) -> u64;
+
+    /// Multiplies the point by the integer `k`.
+    fn mul_u64(&self, k: u64) -> Self::Projective;
+
+    /// Adds `other` to the point, e.g to compute the next baby step.
+    fn add_affine(&mut self, other: &Self);
+
+    fn to_projective(&self) -> Self::Projective;
+
+    fn to_affine(p: &Self::Projective) -> Self;
+}
+
+/// Integer type of the keys of a table of baby steps, i.e the lowest bits of [`BabyStepCurve::key`] : `u64` for the tables of any size, or
+/// `u32` for the smaller tables of a `FixedSolver` (with the `fixed` feature), whose rare false matches are discarded when they are confirmed.
+pub trait TableKey: Copy + Eq + Hash + Send + Sync + 'static {
+    fn truncate(key: u64) -> Self;
+}
+
+impl TableKey for u64 {
+    fn truncate(key: u64) -> Self {
+        key
+    }
+}
+
+impl TableKey for u32 {
+    fn truncate(key: u64) -> Self {
+        key as u32
+    }
+}
+
+/// Curve of arkworks, whose points are keyed by the lowest 64 bits of their `x` coordinate.
+pub trait ArkworksCurve: AffineCurve + for<'a> AddAssign<&'a Self> {
+    /// Lowest 64 bits of the `x` coordinate of the point.
+    fn x_low_bits(&self) -> u64;
+}
+
+impl<C: ArkworksCurve> BabyStepCurve for C {
+    type Projective = C::Projective;
+
+    fn key(&self) -> u64 {
+        self.x_low_bits()
+    }
+
+    fn mul_u64(&self, k: u64) -> C::Projective {
+        self.mul(<C::ScalarField as PrimeField>::BigInt::from(k))
+    }
+
+    fn add_affine(&mut self, other: &Self) {
+        *self += other;
+    }
+
+    fn to_projective(&self) -> C::Projective {
+        self.into_projective()
+    }
+
+    fn to_affine(p: &C::Projective) -> Self {
+        p.into_affine()
+    }
+}
+
+impl ArkworksCurve for BabyJubJub {
+    fn x_low_bits(&self) -> u64 {
+        self.x.into_repr().0[0]
+    }
+}
+
+/// Table of the baby steps `j·G`, for `j` in `[0, m)`.
+///
+/// Points are keyed by the lowest 64 bits of their `x` coordinate, or 32 bits with `K = u32`, which identifies them uniquely in the prime
+/// subgroup with overwhelming probability. The rare keys shared by several baby steps are kept aside in `collisions`, and every match is confirmed by recomputing `j·G`.
+pub(crate) struct BabyStepTable<C: BabyStepCurve = BabyJubJub, K: TableKey = u64> {
+    entries: HashMap<K, u32>,
+    collisions: Vec<(K, u32)>,
+    m: u64,
+    g: C,
+    peak_bytes: u64,
+}
+
+/// Outcome of the lookup of a point in a [`BabyStepTable`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Lookup {
+    /// No baby step has the key of the point.
+    Miss,
+    /// Baby steps have the key of the point, but none of them is the point.
+    FalseMatch,
+    /// The point is the baby step `j·G`.
+    Hit(u64),
+}
+
+impl<C: BabyStepCurve, K: TableKey> BabyStepTable<C, K> {
+    pub(crate) fn build(g: C, m: u64, num_threads: u64) -> Self {
+        let chunk_size = m / num_threads;
+        let chunks: Vec<Vec<(K, u32)>> = thread::scope(|s| {
+            let handles: Vec<_> = (0..num_threads)
+                .map(|idx| {
+                    s.spawn(move || {
+                        let start = idx * chunk_size;
+                        let end = if idx == num_threads - 1 { m } else { start + chunk_size };
+                        // NOTE: equality and hashing do not perform as expected for projective representation
+                        // (because coordinates are ambiguous), so switching to affine coordinates here
+                        let mut v = C::to_affine(&g.mul_u64(start));
+                        let mut chunk = Vec::with_capacity((end - start) as usize);
+                        for j in start..end { // baby_steps
+                            chunk.push((K::truncate(v.key()), j as u32));
+                            v.add_affine(&g); // original zkay version was doing scalar multiplication inside the loop, we replaced it by constant increment, because addition is faster than scalar multiplication on the elliptic curve
+                        }
+                        chunk
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        let chunks_bytes = chunks.iter().map(|chunk| entries_bytes::<K>(0, chunk.capacity())).sum::<u64>();
+        let mut entries = HashMap::with_capacity(m as usize);
+        let mut collisions = Vec::new();
+        for (k, j) in chunks.into_iter().flatten() {
+            match entries.entry(k) {
+                Entry::Occupied(_) => collisions.push((k, j)),
+                Entry::Vacant(e) => {
+                    e.insert(j);
+                }
+            }
+        }
+        // the chunks of baby steps are all allocated until the map is filled
+        let peak_bytes = chunks_bytes + entries_bytes::<K>(entries.capacity(), collisions.capacity());
+        BabyStepTable { entries, collisions, m, g, peak_bytes }
+    }
+
+    pub(crate) fn len(&self) -> u64 {
+        self.m
+    }
+
+    /// Generator `G` of the baby steps.
+    pub(crate) fn generator(&self) -> C {
+        self.g
+    }
+
+    pub(crate) fn size_bytes(&self) -> u64 {
+        entries_bytes::<K>(self.entries.capacity(), self.collisions.capacity())
+    }
+
+    /// Highest memory used while the table was built or loaded, including the buffers of the baby steps and the growth of the map.
+    pub(crate) fn peak_bytes(&self) -> u64 {
+        self.peak_bytes
+    }
+
+    pub(crate) fn lookup(&self, p: &C) -> Lookup {
+        let k = K::truncate(p.key());
+        let Some(&j) = self.entries.get(&k) else { return Lookup::Miss };
+        std::iter::once(j)
+            .chain(self.collisions.iter().filter(|(c, _)| *c == k).map(|(_, j)| *j))
+            .map(u64::from)
+            .find(|j| C::to_affine(&self.g.mul_u64(*j)) == *p)
+            .map_or(Lookup::FalseMatch, Lookup::Hit)
+    }
+}
+
+impl BabyStepTable {
+    /// Writes the table in the format described in the [module documentation](self).
+    pub(crate) fn write_to<W: Write>(&self, mut w: W) -> io::Result<()> {
+        let mut keys = vec![0u64; self.m as usize];
+        for (&k, &j) in self.entries.iter().chain(self.collisions.iter().map(|(k, j)| (k, j))) {
+            keys[j as usize] = k;
+        }
+        let mut hasher = Sha256::new();
+        let mut write = |bytes: &[u8]| {
+            hasher.update(bytes);
+            w.write_all(bytes)
+        };
+        write(TABLE_MAGIC)?;
+        write(&[TABLE_VERSION, self.m.trailing_zeros() as u8, CURVE.len() as u8])?;
+        write(CURVE.as_bytes())?;
+        let (gx, gy) = point_to_hex(&self.g);
+        for coordinate in [&gx, &gy] {
+            write(&hex::decode(&coordinate[2..]).unwrap())?;
+        }
+        write(&self.m.to_le_bytes())?;
+        for k in keys {
+            write(&k.to_le_bytes())?;
+        }
+        let checksum = hasher.finalize();
+        w.write_all(&checksum)?;
+        w.flush()
+    }
+
+    /// Reads a table written by [`BabyStepTable::write_to`], checking that it uses the generator `g`.
+    pub(crate) fn read_from<R: Read>(r: R, g: BabyJubJub) -> Result<Self, Error> {
+        let mut entries = HashMap::new();
+        let mut collisions = Vec::new();
+        let mut peak_bytes = 0;
+        let info = read_table(r, |m, j, k| {
+            if j == 0 {
+                entries.reserve(m.min(MAX_RESERVED_ENTRIES) as usize);
+            }
+            let capacity = entries.capacity();
+            match entries.entry(k) {
+                Entry::Occupied(_) => collisions.push((k, j as u32)),
+                Entry::Vacant(e) => {
+                    e.insert(j as u32);
+                }
+            }
+            // the previous buckets of the map are only freed once they are moved to the larger ones
+            if entries.capacity() != capacity {
+                peak_bytes = peak_bytes.max(entries_bytes::<u64>(capacity + entries.capacity(), collisions.capacity()));
+            }
+        })?;
+        check_generator(&info, &g)?;
+        let peak_bytes = peak_bytes.max(entries_bytes::<u64>(entries.capacity(), collisions.capacity()));
+        Ok(BabyStepTable { entries, collisions, m: info.entries, g, peak_bytes })
+    }
+}
+
+/// Memory used by a map of `map_capacity` baby steps and a list of `list_capacity` ones, in bytes.
+fn entries_bytes<K: TableKey>(map_capacity: usize, list_capacity: usize) -> u64 {
+    // a `HashMap` also stores one control byte per bucket
+    let entry_size = std::mem::size_of::<(K, u32)>();
+    ((entry_size + 1) * map_capacity + entry_size * list_capacity) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_file() {
+        let table = BabyStepTable::build(base_point(), 1 << 10, 2);
+        let mut bytes = Vec::new();
+        table.write_to(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), 7 + CURVE.len() + 64 + 8 + 8 * 1024 + 32);
+
+        let info = inspect_table(bytes.as_slice()).unwrap();
+        assert_eq!((info.table_bitwidth, info.curve.as_str(), info.entries), (10, CURVE, 1024));
+        assert_eq!(info.generator, point_to_hex(&base_point()));
+        assert_eq!(verify_table(bytes.as_slice(), 100), Ok(info));
+        let loaded = BabyStepTable::read_from(bytes.as_slice(), base_point()).unwrap();
+        assert_eq!((loaded.entries, loaded.collisions, loaded.m), (table.entries, table.collisions, table.m));
+        assert!(matches!(BabyStepTable::read_from(bytes.as_slice(), -base_point()), Err(Error::InvalidTable(_))));
+
+        // a corrupted entry is detected by the checksum
+        let mut corrupted = bytes.clone();
+        corrupted[100] ^= 1;
+        assert!(matches!(inspect_table(corrupted.as_slice()), Err(Error::InvalidTable(_))));
+        assert!(matches!(BabyStepTable::read_from(&bytes[..bytes.len() - 1], base_point()), Err(Error::InvalidTable(_))));
+
+        // the header of a truncated file claiming 2^32 entries does not allocate the memory of the whole table
+        let mut hostile = bytes[..7 + CURVE.len() + 64].to_vec();
+        hostile[5] = 32;
+        hostile.extend((1u64 << 32).to_le_bytes());
+        hostile.extend([0; 16]);
+        assert!(matches!(BabyStepTable::read_from(hostile.as_slice(), base_point()), Err(Error::InvalidTable(_))));
+    }
+}
@@ -0,0 +1,224 @@
+//! Tally of elections whose ballots are encrypted with exponential ElGamal, from the collection of the ballots to a report that
+//! anyone can audit.
+//!
+//! - Each ballot encrypts one vote counter per option, `1` for the chosen options and `0` for the others, for the public key of the
+//!   election. [`Tally::add_ballot`] adds it to the encrypted count of each option, and [`Tally::add_verified_ballot`] first checks the
+//!   range proofs of its counters, so that a ballot cannot add more than one vote to an option.
+//! - [`Tally::decrypt`] recovers the count of each option with the private key of the election. Each count is at most the number of
+//!   voters, which bounds the Discrete Logarithms to solve, so a single small table of baby steps is built and reused for all the options.
+//! - The returned [`TallyReport`] proves that each count is the decryption of the encrypted count of its option, see
+//!   [`crate::proof::prove_decryption`], and is signed with the same key, see [`crate::eddsa`], so that it can be published and checked
+//!   with [`TallyReport::verify`] and the public key of the election only.
+//!
+//! The signed message is the Poseidon hash of the domain `babygiant-alt-bn128/tally/v1`, the number of voters, the counts, and the
+//! Twisted Edwards coordinates of the encrypted counts `C1.x, C1.y, C2.x, C2.y`, see [`TallyReport::message`].
+
+use ark_ed_on_bn254::{EdwardsAffine as BabyJubJub, Fq};
+use rand::{CryptoRng, RngCore};
+use crate::eddsa::{self, Signature};
+use crate::point::{field_to_hex, parse_point, point_coordinates, point_to_hex};
+use crate::proof::{prove_decryption, verify_decryption, verify_range, DecryptionProof, RangeProof};
+use crate::{embed_plaintext, poseidon, priv_to_pub_key, Ciphertext, Error, Solver};
+
+const TALLY_DOMAIN: &[u8] = b"babygiant-alt-bn128/tally/v1";
+
+/// Encrypted counts of the options of an election, see the [module documentation](self).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tally {
+    public_key: BabyJubJub,
+    aggregates: Vec<Ciphertext>,
+    voters: u64,
+}
+
+/// Decrypted counts of a [`Tally`], with the proofs of their decryption and the signature of the tallier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TallyReport {
+    voters: u64,
+    counts: Vec<u64>,
+    aggregates: Vec<Ciphertext>,
+    proofs: Vec<DecryptionProof>,
+    signature: Signature,
+}
+
+impl Tally {
+    /// Starts the tally of an election of `options` options, whose ballots are encrypted for `public_key`.
+    pub fn new(public_key: (&str, &str), options: usize) -> Result<Self, Error> {
+        let public_key = parse_point(public_key.0, public_key.1)?;
+        Ok(Tally { public_key, aggregates: vec![Ciphertext::zero(); options], voters: 0 })
+    }
+
+    /// Adds the encrypted vote counters of a ballot, one per option.
+    ///
+    /// The counters are not checked, so a malicious voter can add any number of votes to an option : when ballots are not checked
+    /// before they reach the tally, use [`Tally::add_verified_ballot`] instead.
+    pub fn add_ballot(&mut self, ballot: &[Ciphertext]) -> Result<(), Error> {
+        if ballot.len() != self.aggregates.len() {
+            return Err(Error::InvalidTally(format!("a ballot has {} counters for {} options", ballot.len(), self.aggregates.len())));
+        }
+        for (aggregate, counter) in self.aggregates.iter_mut().zip(ballot) {
+            *aggregate = *aggregate + *counter;
+        }
+        self.voters += 1;
+        Ok(())
+    }
+
+    /// Adds the encrypted vote counters of a ballot like [`Tally::add_ballot`], after checking that each of them comes with a
+    /// [`RangeProof`] of `1` bit, i.e encrypts `0` or `1`, see [`crate::proof::prove_range`].
+    pub fn add_verified_ballot(&mut self, ballot: &[(Ciphertext, RangeProof)]) -> Result<(), Error> {
+        let public_key = point_to_hex(&self.public_key);
+        for (option, (counter, proof)) in ballot.iter().enumerate() {
+            if !verify_range((&public_key.0, &public_key.1), counter, 1, proof)? {
+                return Err(Error::InvalidTally(format!("the range proof of the counter of the option {} is invalid", option)));
+            }
+        }
+        self.add_ballot(&ballot.iter().map(|(counter, _)| *counter).collect::<Vec<Ciphertext>>())
+    }
+
+    pub fn options(&self) -> usize {
+        self.aggregates.len()
+    }
+
+    /// Number of ballots added to the tally.
+    pub fn voters(&self) -> u64 {
+        self.voters
+    }
+
+    /// Encrypted count of each option.
+    pub fn aggregates(&self) -> &[Ciphertext] {
+        &self.aggregates
+    }
+
+    /// Decrypts the count of each option with `private_key`, the private key of the election, using `num_threads` threads, and returns
+    /// the signed report of the tally, whose proofs of decryption are drawn with `rng`, e.g `rand::rngs::OsRng`.
+    pub fn decrypt<R: RngCore + CryptoRng>(&self, private_key: &str, num_threads: u64, rng: &mut R) -> Result<TallyReport, Error> {
+        if priv_to_pub_key(private_key)? != point_to_hex(&self.public_key) {
+            return Err(Error::InvalidTally("the private key is not the one of the public key of the election".to_string()));
+        }
+        // the count of an option is at most the number of voters
+        let max_bitwidth = u64::from(64 - self.voters.leading_zeros()).max(1);
+        let solver = Solver::new(max_bitwidth, num_threads);
+        let mut counts = Vec::with_capacity(self.aggregates.len());
+        let mut proofs = Vec::with_capacity(self.aggregates.len());
+        for aggregate in &self.aggregates {
+            let (plaintext, proof) = prove_decryption(private_key, aggregate, rng)?;
+            counts.push(solver.solve(&plaintext.0, &plaintext.1)?);
+            proofs.push(proof);
+        }
+        let message = tally_message(self.voters, &counts, &self.aggregates);
+        let signature = eddsa::sign(private_key, &field_to_hex(&message))?;
+        Ok(TallyReport { voters: self.voters, counts, aggregates: self.aggregates.clone(), proofs, signature })
+    }
+}
+
+impl TallyReport {
+    /// Rebuilds a published report, e.g to [`TallyReport::verify`] it.
+    pub fn new(voters: u64, counts: Vec<u64>, aggregates: Vec<Ciphertext>, proofs: Vec<DecryptionProof>, signature: Signature) -> Result<Self, Error> {
+        if counts.len() != aggregates.len() || proofs.len() != aggregates.len() {
+            return Err(Error::InvalidTally(format!("{} counts and {} proofs for {} options", counts.len(), proofs.len(), aggregates.len())));
+        }
+        Ok(TallyReport { voters, counts, aggregates, proofs, signature })
+    }
+
+    /// Checks the proofs of decryption of the counts and the signature of the report for the public key of the election.
+    ///
+    /// Returns an error if `public_key` is invalid, and `false` if a proof or the signature is invalid, or if a count is larger than the
+    /// number of voters. The encrypted counts should also be checked against the ballots, e.g by recomputing them with a [`Tally`].
+    pub fn verify(&self, public_key: (&str, &str)) -> Result<bool, Error> {
+        for ((count, aggregate), proof) in self.counts.iter().zip(&self.aggregates).zip(&self.proofs) {
+            let plaintext = embed_plaintext(*count);
+            if *count > self.voters || !verify_decryption(public_key, aggregate, (&plaintext.0, &plaintext.1), proof)? {
+                return Ok(false);
+            }
+        }
+        eddsa::verify(public_key, &self.message(), &self.signature)
+    }
+
+    pub fn voters(&self) -> u64 {
+        self.voters
+    }
+
+    /// Decrypted count of each option.
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    /// Encrypted count of each option, as returned by [`Tally::aggregates`].
+    pub fn aggregates(&self) -> &[Ciphertext] {
+        &self.aggregates
+    }
+
+    /// Proof of decryption of the count of each option.
+    pub fn proofs(&self) -> &[DecryptionProof] {
+        &self.proofs
+    }
+
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    /// `Field` message signed by the tallier, see the [module documentation](self).
+    pub fn message(&self) -> String {
+        field_to_hex(&tally_message(self.voters, &self.counts, &self.aggregates))
+    }
+}
+
+fn tally_message(voters: u64, counts: &[u64], aggregates: &[Ciphertext]) -> Fq {
+    let mut elements = vec![Fq::from(voters)];
+    elements.extend(counts.iter().map(|count| Fq::from(*count)));
+    for aggregate in aggregates {
+        elements.extend(point_coordinates(&aggregate.c1));
+        elements.extend(point_coordinates(&aggregate.c2));
+    }
+    poseidon::hash_with_domain(TALLY_DOMAIN, &elements)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::OsRng;
+    use crate::exp_elgamal_encrypt;
+    use crate::proof::prove_range;
+
+    #[test]
+    fn test_tally() {
+        let sk = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        let pk = priv_to_pub_key(sk).unwrap();
+        let pk = (pk.0.as_str(), pk.1.as_str());
+        let mut tally = Tally::new(pk, 3).unwrap();
+        let mut randomness = 1u64;
+        let mut ballot = |choice: usize| -> Vec<(Ciphertext, RangeProof)> {
+            (0..3)
+                .map(|option| {
+                    randomness += 1;
+                    let r = format!("{:#x}", randomness);
+                    let vote = u64::from(option == choice);
+                    let counter = exp_elgamal_encrypt(pk, vote, &r).unwrap().try_into().unwrap();
+                    (counter, prove_range(pk, vote, &r, 1, &mut OsRng).unwrap())
+                })
+                .collect()
+        };
+        for choice in [0, 2, 2, 1, 2] {
+            tally.add_verified_ballot(&ballot(choice)).unwrap();
+        }
+        assert_eq!(tally.voters(), 5);
+        let report = tally.decrypt(sk, 1, &mut OsRng).unwrap();
+        assert_eq!(report.counts(), [1, 1, 3]);
+        assert_eq!(report.verify(pk), Ok(true));
+        let published = TallyReport::new(5, report.counts().to_vec(), report.aggregates().to_vec(), report.proofs().to_vec(), *report.signature());
+        assert_eq!(published.as_ref(), Ok(&report));
+
+        // a forged count, or a report checked with another key
+        let forged = TallyReport::new(5, vec![1, 2, 2], report.aggregates().to_vec(), report.proofs().to_vec(), *report.signature()).unwrap();
+        assert_eq!(forged.verify(pk), Ok(false));
+        let other = priv_to_pub_key("0x05").unwrap();
+        assert_eq!(report.verify((&other.0, &other.1)), Ok(false));
+        assert!(matches!(tally.decrypt("0x05", 1, &mut OsRng), Err(Error::InvalidTally(_))));
+
+        // a ballot with two votes for the same option, or with a missing option
+        let mut invalid = ballot(0);
+        invalid[0].0 = invalid[0].0 + invalid[0].0;
+        assert!(matches!(tally.add_verified_ballot(&invalid), Err(Error::InvalidTally(_))));
+        assert!(matches!(tally.add_ballot(&[Ciphertext::zero(); 2]), Err(Error::InvalidTally(_))));
+        assert_eq!(tally.voters(), 5);
+    }
+}
@@ -0,0 +1,99 @@
+//! `proptest` strategies generating the inputs of this crate, so that the applications embedding it property-test their integration,
+//! e.g their encoders, storage layers and endpoints, without reimplementing the encryption and the embedding of the plaintexts.
+//!
+//! The valid inputs are generated by [`plaintext`], [`scalar`], [`point`] and [`test_vector`], which are consistent with each other :
+//! the Discrete Logarithm of the [`point`] of a plaintext is the plaintext, and the decryption of the ciphertext of a [`test_vector`]
+//! is its embedded plaintext. The malformed inputs of [`near_miss_point`] differ from valid points by a small change, and are all
+//! rejected by [`crate::validate_point`] and the solver, each with the error of its change.
+
+use ark_ff::{BigInteger, BigInteger256, Field, PrimeField};
+use ark_ed_on_bn254::Fq;
+use proptest::prelude::*;
+use crate::point::{field_to_hex, parse_field};
+use crate::vectors::{edge_plaintexts, TestVector};
+use crate::{embed_plaintext, exp_elgamal_decrypt, exp_elgamal_encrypt, priv_to_pub_key, private_key_from_seed, validate_point, HexPoint};
+
+/// Plaintexts of at most `max_bitwidth` bits, between `1` and `64`, half of them being the [`edge_plaintexts`] of `max_bitwidth` bits.
+pub fn plaintext(max_bitwidth: u64) -> impl Strategy<Value = u64> {
+    assert!((1..=64).contains(&max_bitwidth), "max_bitwidth should be between 1 and 64");
+    prop_oneof![prop::sample::select(edge_plaintexts(max_bitwidth)), 0..=u64::MAX >> (64 - max_bitwidth)]
+}
+
+/// Scalars usable as private keys or as the randomness of an encryption, derived from 32 random bytes by [`private_key_from_seed`].
+pub fn scalar() -> impl Strategy<Value = String> {
+    any::<[u8; 32]>().prop_map(|seed| private_key_from_seed(&seed))
+}
+
+/// Plaintexts of at most `max_bitwidth` bits with their embedding, i.e the point whose Discrete Logarithm is the plaintext.
+pub fn point(max_bitwidth: u64) -> impl Strategy<Value = (u64, HexPoint)> {
+    plaintext(max_bitwidth).prop_map(|plaintext| (plaintext, embed_plaintext(plaintext)))
+}
+
+/// Encryptions of plaintexts of at most `max_bitwidth` bits for random key pairs, with their decryption, see [`TestVector`].
+pub fn test_vector(max_bitwidth: u64) -> impl Strategy<Value = TestVector> {
+    (plaintext(max_bitwidth), scalar(), scalar()).prop_map(|(plaintext, private_key, randomness)| {
+        let public_key = priv_to_pub_key(&private_key).unwrap();
+        let (c1, c2) = exp_elgamal_encrypt((&public_key.0, &public_key.1), plaintext, &randomness).unwrap();
+        let embedded = exp_elgamal_decrypt(&private_key, (&c1.0, &c1.1), (&c2.0, &c2.1)).unwrap();
+        TestVector { plaintext, private_key, public_key, randomness, c1, c2, embedded }
+    })
+}
+
+/// Malformed points differing from the [`point`] of a plaintext of at most `max_bitwidth` bits by one of the changes :
+///
+/// - an hexadecimal digit of a coordinate is changed, so that the point is not on the curve,
+/// - the coordinates are swapped, which is also not on the curve,
+/// - the modulus is added to a coordinate, which is then not smaller than the modulus, see [`crate::Error::NotInField`],
+/// - both coordinates are negated, which adds the point `(0, -1)` of order `2`, so that the point is on the curve but not in the prime
+///   subgroup,
+/// - a character which is not an hexadecimal digit is appended to a coordinate.
+pub fn near_miss_point(max_bitwidth: u64) -> impl Strategy<Value = HexPoint> {
+    let changed_digit = (point(max_bitwidth), any::<bool>(), 2..66usize, 1..16u8).prop_map(|((_, (x, y)), on_x, i, delta)| {
+        let change = |s: &str| {
+            let mut digits = s.as_bytes().to_vec();
+            let digit = u8::from_str_radix(&s[i..=i], 16).unwrap();
+            digits[i] = b"0123456789abcdef"[usize::from((digit + delta) % 16)];
+            String::from_utf8(digits).unwrap()
+        };
+        if on_x { (change(&x), y) } else { (x, change(&y)) }
+    });
+    let swapped = point(max_bitwidth).prop_map(|(_, (x, y))| (y, x));
+    let not_in_field = (point(max_bitwidth), any::<bool>()).prop_map(|((_, (x, y)), on_x)| {
+        let add_modulus = |s: &str| {
+            let mut repr = parse_field(s).unwrap().into_repr();
+            repr.add_nocarry(&BigInteger256::new(<[u64; 4]>::try_from(Fq::characteristic()).unwrap()));
+            format!("0x{}", hex::encode(repr.to_bytes_be()))
+        };
+        if on_x { (add_modulus(&x), y) } else { (x, add_modulus(&y)) }
+    });
+    let not_in_subgroup = point(max_bitwidth).prop_map(|(_, (x, y))| (field_to_hex(&-parse_field(&x).unwrap()), field_to_hex(&-parse_field(&y).unwrap())));
+    let invalid_hex = (point(max_bitwidth), "[g-zG-Z /.]").prop_map(|((_, (x, y)), c)| (x, format!("{}{}", y, c)));
+    prop_oneof![changed_digit, swapped, not_in_field, not_in_subgroup, invalid_hex]
+        .prop_filter("the changed point should be rejected", |(x, y)| validate_point(x, y).is_err())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Error, Solver};
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn test_strategies(
+            (plaintext, (x, y)) in point(16),
+            vector in test_vector(16),
+            (near_x, near_y) in near_miss_point(16),
+        ) {
+            let solver = Solver::new(16, 1);
+            prop_assert_eq!(solver.solve(&x, &y), Ok(plaintext));
+            prop_assert_eq!(solver.solve(&vector.embedded.0, &vector.embedded.1), Ok(vector.plaintext));
+            let error = solver.solve(&near_x, &near_y).unwrap_err();
+            prop_assert!(
+                matches!(error, Error::NotOnCurve | Error::NotInSubgroup | Error::InvalidCoordinate { .. }),
+                "{:?} for ({}, {})", error, near_x, near_y
+            );
+        }
+    }
+}
@@ -0,0 +1,396 @@
+//! Threshold exponential ElGamal, where the private key is split between `n` share holders so that any `t` of them can decrypt
+//! a ciphertext together, while fewer cannot, e.g for the tally of an election.
+//!
+//! - A trusted dealer splits a random private key `sk` with Shamir's secret sharing, see [`deal`] : the share holder `i` gets
+//!   `sk_i = f(i)` for a random polynomial `f` of degree `t - 1` with `f(0) = sk`, and its verification key `sk_i·G` is public.
+//!   Without a trusted dealer, the share holders run a distributed key generation instead, see [`Dealing`].
+//! - Each share holder publishes its partial decryption `sk_i·C1` of a ciphertext `(C1, C2)`, along a Chaum-Pedersen proof that it
+//!   used its share, see [`KeyShare::decrypt_share`].
+//! - A combiner checks the proofs of `t` partial decryptions, interpolates `sk·C1 = Σ λ_i·sk_i·C1` with the Lagrange coefficients `λ_i` at `0`,
+//!   and recovers the plaintext from `C2 - sk·C1` like a single-key decryption, see [`ThresholdPublicKey::combine`].
+
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ed_on_bn254::{EdwardsAffine as BabyJubJub, EdwardsProjective, Fr};
+use ark_ff::{Field, PrimeField, Zero};
+use rand::{CryptoRng, RngCore};
+#[cfg(feature = "ark-serialize")]
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
+use crate::keys::random_scalar;
+use crate::point::{base_point, field_to_hex, parse_point, parse_scalar, point_to_hex, HexPoint};
+use crate::proof::{prove_decryption, verify_decryption, DecryptionProof};
+use crate::{Ciphertext, Error, Solver};
+
+/// Share of the private key held by the share holder [`KeyShare::index`].
+///
+/// With the `ark-serialize` feature, it implements the traits of arkworks as its index, a little-endian `u64`, followed by its private key.
+#[derive(Clone, PartialEq, Eq)]
+pub struct KeyShare {
+    index: u64,
+    private_key: Fr,
+}
+
+/// Partial decryption `sk_i·C1` of a ciphertext by the share holder `i`, with its proof of correctness.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecryptionShare {
+    index: u64,
+    share: BabyJubJub,
+    proof: DecryptionProof,
+}
+
+/// Public key of a threshold key, to which ciphertexts are encrypted, with the verification keys of its share holders.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThresholdPublicKey {
+    threshold: u64,
+    public_key: BabyJubJub,
+    verification_keys: Vec<BabyJubJub>,
+}
+
+/// Splits a fresh random private key into `parties` shares, indexed from `1` to `parties`, any `threshold` of which can decrypt.
+///
+/// The dealer learns the private key, and should thus be trusted to forget it. `rng` draws the polynomial, e.g `rand::rngs::OsRng`.
+pub fn deal<R: RngCore + CryptoRng>(threshold: u64, parties: u64, rng: &mut R) -> Result<(ThresholdPublicKey, Vec<KeyShare>), Error> {
+    check_threshold(threshold, parties)?;
+    let dealing = Dealing::new(threshold, rng)?;
+    let shares: Vec<KeyShare> = (1..=parties).map(|index| KeyShare { index, private_key: dealing.evaluate(index) }).collect();
+    let g = base_point();
+    let public_key = ThresholdPublicKey {
+        threshold,
+        public_key: g.mul(dealing.coefficients[0]).into_affine(),
+        verification_keys: shares.iter().map(|share| g.mul(share.private_key).into_affine()).collect(),
+    };
+    Ok((public_key, shares))
+}
+
+/// Contribution of a share holder to a distributed key generation, which replaces the trusted dealer of [`deal`] by the share holders themselves,
+/// following the joint Feldman protocol of Pedersen :
+///
+/// 1. Each share holder creates a [`Dealing`], i.e a random polynomial `f` of degree `t - 1`, and broadcasts its [`Dealing::commitments`] `a_k·G`
+///    to the coefficients `a_k` of `f`.
+/// 2. It sends privately the [`Dealing::share`] `f(j)` to each share holder `j`, including itself.
+/// 3. Each share holder checks the shares it received against the commitments of their dealers with [`verify_dealt_share`], and combines
+///    them with [`combine_dealings`] into its [`KeyShare`] and the [`ThresholdPublicKey`], whose private key is the sum of the secrets `f(0)`
+///    of all the dealings and is never known by anyone.
+///
+/// Complaints are left to the application : a share holder receiving an invalid share should publish it, so that its dealer is excluded
+/// and the protocol restarted without it. All the share holders should combine the dealings of the same dealers.
+pub struct Dealing {
+    coefficients: Vec<Fr>,
+}
+
+impl Dealing {
+    /// Draws a random polynomial of degree `threshold - 1` with `rng`, e.g `rand::rngs::OsRng`.
+    pub fn new<R: RngCore + CryptoRng>(threshold: u64, rng: &mut R) -> Result<Self, Error> {
+        if threshold == 0 {
+            return Err(Error::InvalidShare("the threshold should be at least 1".to_string()));
+        }
+        Ok(Dealing { coefficients: (0..threshold).map(|_| random_scalar(rng)).collect() })
+    }
+
+    /// Public commitments to the coefficients of the polynomial, to broadcast to all the share holders.
+    pub fn commitments(&self) -> Vec<HexPoint> {
+        self.coefficients.iter().map(|c| point_to_hex(&base_point().mul(*c).into_affine())).collect()
+    }
+
+    /// Secret share of the share holder `index`, to send to it over a private channel.
+    pub fn share(&self, index: u64) -> Result<String, Error> {
+        if index == 0 {
+            return Err(Error::InvalidShare("the indices of the shares start at 1".to_string()));
+        }
+        Ok(field_to_hex(&self.evaluate(index)))
+    }
+
+    fn evaluate(&self, index: u64) -> Fr {
+        // Horner evaluation of the polynomial at `index`
+        self.coefficients.iter().rev().fold(Fr::zero(), |acc, c| acc * Fr::from(index) + c)
+    }
+}
+
+/// The coefficients are not printed, so that they do not leak into logs.
+impl std::fmt::Debug for Dealing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Dealing").field("threshold", &self.coefficients.len()).finish_non_exhaustive()
+    }
+}
+
+/// Checks that `share`, received by the share holder `index`, is the evaluation of the polynomial committed to by `commitments`,
+/// i.e that `share·G = Σ index^k·commitments[k]`.
+pub fn verify_dealt_share(commitments: &[HexPoint], index: u64, share: &str) -> Result<bool, Error> {
+    let commitments = parse_points(commitments)?;
+    Ok(base_point().mul(parse_scalar(share)?).into_affine() == evaluate_commitments(&commitments, index))
+}
+
+/// Combines the `dealings` received by the share holder `index` among `parties`, each of them the commitments of a dealer with the share
+/// it sent to `index`, into the share of `index` and the threshold public key, see [`Dealing`].
+///
+/// All the dealings should have the same threshold. An error names the first dealing, by its position in `dealings`, whose share is invalid.
+pub fn combine_dealings(index: u64, parties: u64, dealings: &[(Vec<HexPoint>, String)]) -> Result<(ThresholdPublicKey, KeyShare), Error> {
+    let threshold = dealings.first().map_or(0, |(commitments, _)| commitments.len() as u64);
+    check_threshold(threshold, parties)?;
+    if index == 0 || index > parties {
+        return Err(Error::InvalidShare(format!("the index {} is not one of the {} parties", index, parties)));
+    }
+    let mut private_key = Fr::zero();
+    let mut sums = vec![EdwardsProjective::zero(); threshold as usize];
+    for (position, (commitments, share)) in dealings.iter().enumerate() {
+        if commitments.len() as u64 != threshold {
+            return Err(Error::InvalidShare(format!("the dealing {} has a threshold of {} instead of {}", position, commitments.len(), threshold)));
+        }
+        if !verify_dealt_share(commitments, index, share)? {
+            return Err(Error::InvalidShare(format!("the share of the dealing {} does not match its commitments", position)));
+        }
+        private_key += parse_scalar(share)?;
+        for (sum, commitment) in sums.iter_mut().zip(parse_points(commitments)?) {
+            *sum += commitment.into_projective();
+        }
+    }
+    // the commitments of the sum of the polynomials
+    let commitments: Vec<BabyJubJub> = sums.into_iter().map(|sum| sum.into_affine()).collect();
+    let public_key = ThresholdPublicKey {
+        threshold,
+        public_key: commitments[0],
+        verification_keys: (1..=parties).map(|j| evaluate_commitments(&commitments, j)).collect(),
+    };
+    Ok((public_key, KeyShare { index, private_key }))
+}
+
+fn check_threshold(threshold: u64, parties: u64) -> Result<(), Error> {
+    if threshold == 0 || threshold > parties {
+        return Err(Error::InvalidShare(format!("the threshold should be between 1 and the {} parties", parties)));
+    }
+    Ok(())
+}
+
+fn parse_points(points: &[HexPoint]) -> Result<Vec<BabyJubJub>, Error> {
+    points.iter().map(|(x, y)| parse_point(x, y)).collect()
+}
+
+/// `Σ index^k·commitments[k]`, i.e the commitment to the evaluation at `index` of the committed polynomial.
+fn evaluate_commitments(commitments: &[BabyJubJub], index: u64) -> BabyJubJub {
+    commitments.iter().rev().fold(EdwardsProjective::zero(), |acc, c| acc.mul(Fr::from(index).into_repr()) + c.into_projective()).into_affine()
+}
+
+impl KeyShare {
+    /// Parses the share `index` of a private key, in the same format as [`crate::generate_private_key`].
+    pub fn new(index: u64, private_key: &str) -> Result<Self, Error> {
+        if index == 0 {
+            return Err(Error::InvalidShare("the indices of the shares start at 1".to_string()));
+        }
+        Ok(KeyShare { index, private_key: parse_scalar(private_key)? })
+    }
+
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    pub fn private_key(&self) -> String {
+        field_to_hex(&self.private_key)
+    }
+
+    /// Computes the partial decryption of `ciphertext` with this share, drawing the commitment of its proof with `rng`.
+    pub fn decrypt_share<R: RngCore + CryptoRng>(&self, ciphertext: &Ciphertext, rng: &mut R) -> DecryptionShare {
+        // `C2 - sk_i·C1` and its proof of decryption are a proof of `log_G(sk_i·G) = log_C1(sk_i·C1)`
+        let (partial, proof) = prove_decryption(&self.private_key(), ciphertext, rng).unwrap();
+        let partial = parse_point(&partial.0, &partial.1).unwrap();
+        let share = (ciphertext.c2.into_projective() - partial.into_projective()).into_affine();
+        DecryptionShare { index: self.index, share, proof }
+    }
+}
+
+/// The share is not printed, so that it does not leak into logs.
+impl std::fmt::Debug for KeyShare {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyShare").field("index", &self.index).finish_non_exhaustive()
+    }
+}
+
+impl DecryptionShare {
+    /// Parses the partial decryption `share` of the share holder `index`, e.g received from another process.
+    pub fn new(index: u64, share: (&str, &str), proof: DecryptionProof) -> Result<Self, Error> {
+        Ok(DecryptionShare { index, share: parse_point(share.0, share.1)?, proof })
+    }
+
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    pub fn share(&self) -> HexPoint {
+        point_to_hex(&self.share)
+    }
+
+    pub fn proof(&self) -> &DecryptionProof {
+        &self.proof
+    }
+}
+
+impl ThresholdPublicKey {
+    /// Parses a threshold public key, where `verification_keys[i - 1]` is the verification key of the share holder `i`.
+    pub fn new(threshold: u64, public_key: (&str, &str), verification_keys: &[HexPoint]) -> Result<Self, Error> {
+        check_threshold(threshold, verification_keys.len() as u64)?;
+        Ok(ThresholdPublicKey { threshold, public_key: parse_point(public_key.0, public_key.1)?, verification_keys: parse_points(verification_keys)? })
+    }
+
+    pub fn threshold(&self) -> u64 {
+        self.threshold
+    }
+
+    /// Public key to which ciphertexts are encrypted.
+    pub fn public_key(&self) -> HexPoint {
+        point_to_hex(&self.public_key)
+    }
+
+    /// Verification key `sk_i·G` of the share holder `index`.
+    pub fn verification_key(&self, index: u64) -> Option<HexPoint> {
+        self.key(index).map(point_to_hex)
+    }
+
+    /// Checks the proof of a partial decryption of `ciphertext`.
+    pub fn verify_share(&self, ciphertext: &Ciphertext, share: &DecryptionShare) -> bool {
+        let Some(key) = self.verification_key(share.index) else { return false };
+        let partial = point_to_hex(&(ciphertext.c2.into_projective() - share.share.into_projective()).into_affine());
+        verify_decryption((&key.0, &key.1), ciphertext, (&partial.0, &partial.1), &share.proof) == Ok(true)
+    }
+
+    /// Recovers the embedded plaintext of `ciphertext` from the partial decryptions of at least [`ThresholdPublicKey::threshold`]
+    /// share holders, after checking their proofs.
+    pub fn combine_point(&self, ciphertext: &Ciphertext, shares: &[DecryptionShare]) -> Result<HexPoint, Error> {
+        let mut indices: Vec<u64> = Vec::new();
+        for share in shares {
+            if !self.verify_share(ciphertext, share) {
+                return Err(Error::InvalidShare(format!("the partial decryption of the share holder {} is invalid", share.index)));
+            }
+            if indices.contains(&share.index) {
+                return Err(Error::InvalidShare(format!("several partial decryptions of the share holder {}", share.index)));
+            }
+            indices.push(share.index);
+        }
+        if (shares.len() as u64) < self.threshold {
+            return Err(Error::InvalidShare(format!("{} partial decryptions for a threshold of {}", shares.len(), self.threshold)));
+        }
+        let shares = &shares[..self.threshold as usize];
+        let indices = &indices[..self.threshold as usize];
+        let shared_secret = shares.iter().fold(EdwardsProjective::zero(), |acc, share| acc + share.share.mul(lagrange_at_zero(share.index, indices)));
+        Ok(point_to_hex(&(ciphertext.c2.into_projective() - shared_secret).into_affine()))
+    }
+
+    /// Decrypts `ciphertext` down to its plaintext from the partial decryptions of the share holders, see [`ThresholdPublicKey::combine_point`],
+    /// computing the Discrete Logarithm of the embedded plaintext with `solver`.
+    pub fn combine(&self, ciphertext: &Ciphertext, shares: &[DecryptionShare], solver: &Solver) -> Result<u64, Error> {
+        let (x, y) = self.combine_point(ciphertext, shares)?;
+        solver.solve(&x, &y)
+    }
+
+    fn key(&self, index: u64) -> Option<&BabyJubJub> {
+        index.checked_sub(1).and_then(|i| self.verification_keys.get(i as usize))
+    }
+}
+
+/// Lagrange coefficient `Π j / (j - i)` of the share holder `i` among `indices`, interpolating the polynomial at `0`.
+fn lagrange_at_zero(i: u64, indices: &[u64]) -> Fr {
+    indices.iter().filter(|&&j| j != i).fold(Fr::from(1u64), |acc, &j| {
+        acc * Fr::from(j) * (Fr::from(j) - Fr::from(i)).inverse().unwrap()
+    })
+}
+
+#[cfg(feature = "ark-serialize")]
+impl CanonicalSerialize for KeyShare {
+    fn serialize<W: Write>(&self, mut writer: W) -> Result<(), SerializationError> {
+        self.index.serialize(&mut writer)?;
+        self.private_key.serialize(&mut writer)
+    }
+
+    fn serialized_size(&self) -> usize {
+        self.index.serialized_size() + self.private_key.serialized_size()
+    }
+}
+
+#[cfg(feature = "ark-serialize")]
+impl CanonicalDeserialize for KeyShare {
+    fn deserialize<R: Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let index = u64::deserialize(&mut reader)?;
+        let private_key = Fr::deserialize(&mut reader)?;
+        if index == 0 {
+            return Err(SerializationError::InvalidData);
+        }
+        Ok(KeyShare { index, private_key })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::OsRng;
+    use crate::exp_elgamal_encrypt;
+
+    #[test]
+    fn test_threshold_decryption() {
+        let (public_key, shares) = deal(3, 5, &mut OsRng).unwrap();
+        let pk = public_key.public_key();
+        let ciphertext: Ciphertext = exp_elgamal_encrypt((&pk.0, &pk.1), 1234, "0x05").unwrap().try_into().unwrap();
+        let partials: Vec<DecryptionShare> = shares.iter().map(|share| share.decrypt_share(&ciphertext, &mut OsRng)).collect();
+        assert!(partials.iter().all(|partial| public_key.verify_share(&ciphertext, partial)));
+
+        let solver = Solver::new(16, 1);
+        assert_eq!(public_key.combine(&ciphertext, &partials[..3], &solver), Ok(1234));
+        let others = [partials[4].clone(), partials[1].clone(), partials[3].clone(), partials[0].clone()];
+        assert_eq!(public_key.combine(&ciphertext, &others, &solver), Ok(1234));
+
+        // fewer shares than the threshold, duplicated shares, or a share of another ciphertext
+        assert!(matches!(public_key.combine_point(&ciphertext, &partials[..2]), Err(Error::InvalidShare(_))));
+        assert!(matches!(public_key.combine_point(&ciphertext, &[partials[0].clone(), partials[0].clone(), partials[1].clone()]), Err(Error::InvalidShare(_))));
+        let forged = shares[2].decrypt_share(&(ciphertext + ciphertext), &mut OsRng);
+        assert!(!public_key.verify_share(&ciphertext, &forged));
+        assert!(matches!(public_key.combine_point(&ciphertext, &[partials[0].clone(), partials[1].clone(), forged]), Err(Error::InvalidShare(_))));
+
+        // the shares and keys can be exchanged as strings
+        let keys: Vec<HexPoint> = (1..=5).map(|i| public_key.verification_key(i).unwrap()).collect();
+        assert_eq!(ThresholdPublicKey::new(3, (&pk.0, &pk.1), &keys), Ok(public_key.clone()));
+        assert_eq!(KeyShare::new(2, &shares[1].private_key()).unwrap(), shares[1]);
+        let share = partials[1].share();
+        assert_eq!(DecryptionShare::new(2, (&share.0, &share.1), *partials[1].proof()), Ok(partials[1].clone()));
+
+        assert!(matches!(deal(0, 5, &mut OsRng), Err(Error::InvalidShare(_))));
+        assert!(matches!(deal(6, 5, &mut OsRng), Err(Error::InvalidShare(_))));
+        let (single, shares) = deal(1, 1, &mut OsRng).unwrap();
+        let pk = single.public_key();
+        let ciphertext: Ciphertext = exp_elgamal_encrypt((&pk.0, &pk.1), 7, "0x05").unwrap().try_into().unwrap();
+        assert_eq!(single.combine(&ciphertext, &[shares[0].decrypt_share(&ciphertext, &mut OsRng)], &solver), Ok(7));
+    }
+
+    #[test]
+    fn test_distributed_key_generation() {
+        let (threshold, parties) = (2, 3);
+        let dealings: Vec<Dealing> = (0..parties).map(|_| Dealing::new(threshold, &mut OsRng).unwrap()).collect();
+        let commitments: Vec<Vec<HexPoint>> = dealings.iter().map(Dealing::commitments).collect();
+        let received = |index: u64| -> Vec<(Vec<HexPoint>, String)> {
+            dealings.iter().zip(&commitments).map(|(dealing, c)| (c.clone(), dealing.share(index).unwrap())).collect()
+        };
+        assert!(received(2).iter().all(|(c, share)| verify_dealt_share(c, 2, share) == Ok(true)));
+        let (public_keys, shares): (Vec<ThresholdPublicKey>, Vec<KeyShare>) = (1..=parties).map(|i| combine_dealings(i, parties, &received(i)).unwrap()).unzip();
+        // all the share holders agree on the public key
+        assert!(public_keys.iter().all(|key| *key == public_keys[0]));
+
+        let pk = public_keys[0].public_key();
+        let ciphertext: Ciphertext = exp_elgamal_encrypt((&pk.0, &pk.1), 4321, "0x09").unwrap().try_into().unwrap();
+        let partials = [shares[2].decrypt_share(&ciphertext, &mut OsRng), shares[0].decrypt_share(&ciphertext, &mut OsRng)];
+        assert_eq!(public_keys[1].combine(&ciphertext, &partials, &Solver::new(16, 1)), Ok(4321));
+
+        // a share which does not match the commitments of its dealer
+        let mut tampered = received(1);
+        tampered[1].1 = dealings[1].share(2).unwrap();
+        assert_eq!(verify_dealt_share(&tampered[1].0, 1, &tampered[1].1), Ok(false));
+        assert!(matches!(combine_dealings(1, parties, &tampered), Err(Error::InvalidShare(reason)) if reason.contains("dealing 1")));
+        assert!(matches!(combine_dealings(4, parties, &received(4)), Err(Error::InvalidShare(_))));
+        assert!(matches!(combine_dealings(1, parties, &[]), Err(Error::InvalidShare(_))));
+    }
+
+    #[cfg(feature = "ark-serialize")]
+    #[test]
+    fn test_key_share_ark_serialize() {
+        let share = KeyShare::new(3, "0x05").unwrap();
+        let mut bytes = Vec::new();
+        share.serialize(&mut bytes).unwrap();
+        assert_eq!(KeyShare::deserialize(bytes.as_slice()).unwrap(), share);
+        bytes[0] = 0;
+        assert!(KeyShare::deserialize(bytes.as_slice()).is_err());
+    }
+}
@@ -0,0 +1,141 @@
+//! Diagnostics of an embedded plaintext before its Discrete Logarithm is searched, so that the submissions of an API are rejected
+//! cheaply and with actionable messages, e.g by a gateway in front of a [`crate::server`].
+//!
+//! [`validate_embedding`] runs the checks of [`crate::compute_dlog`] one after the other, and reports the outcome of each of them in a
+//! [`ValidationReport`] instead of only the first error. The checks after a failed one are skipped, as their inputs are not defined.
+
+use std::fmt;
+use crate::point::{parse_coordinates, parse_field_be_bytes, parse_hex, point_from_coordinates};
+use crate::Error;
+
+/// Outcome of one check of a [`ValidationReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Passed,
+    Failed(Error),
+    /// A previous check failed.
+    Skipped,
+}
+
+/// Findings of [`validate_embedding`], in the order of the checks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// Both coordinates are hexadecimal strings of at most 32 bytes, see [`Error::InvalidHex`].
+    pub parse: Check,
+    /// Both coordinates are smaller than the modulus of the base field, i.e are canonical field elements, see [`Error::NotInField`].
+    pub canonical: Check,
+    /// The point is on Baby Jubjub in Twisted Edwards form.
+    pub on_curve: Check,
+    /// The point is in the prime subgroup generated by the base point of noir-elgamal.
+    pub subgroup: Check,
+    /// The point is the embedding `m·G` of a plaintext `m`, like all the points of the prime subgroup : whether `m` is small enough
+    /// for its Discrete Logarithm to be found is only known by searching it.
+    pub likely_encodable: Check,
+}
+
+impl ValidationReport {
+    /// Whether all the checks passed, i.e the point is a valid input of [`crate::compute_dlog`].
+    pub fn is_valid(&self) -> bool {
+        self.checks().into_iter().all(|(_, check)| *check == Check::Passed)
+    }
+
+    /// Error of the failed check, if any.
+    pub fn error(&self) -> Option<&Error> {
+        self.checks().into_iter().find_map(|(_, check)| match check {
+            Check::Failed(e) => Some(e),
+            _ => None,
+        })
+    }
+
+    /// Names and outcomes of the checks, in their order.
+    pub fn checks(&self) -> [(&'static str, &Check); 5] {
+        [
+            ("parse", &self.parse),
+            ("canonical", &self.canonical),
+            ("on curve", &self.on_curve),
+            ("subgroup", &self.subgroup),
+            ("likely encodable", &self.likely_encodable),
+        ]
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (name, check) in self.checks() {
+            match check {
+                Check::Passed => writeln!(f, "{} : passed", name)?,
+                Check::Failed(e) => writeln!(f, "{} : failed, {}", name, e)?,
+                Check::Skipped => writeln!(f, "{} : skipped", name)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks the embedded plaintext `(x, y)` without solving its Discrete Logarithm, see the [module documentation](self).
+pub fn validate_embedding(x: &str, y: &str) -> ValidationReport {
+    let mut report = ValidationReport {
+        parse: Check::Skipped,
+        canonical: Check::Skipped,
+        on_curve: Check::Skipped,
+        subgroup: Check::Skipped,
+        likely_encodable: Check::Skipped,
+    };
+    let (x, y) = match parse_coordinates(x, y, parse_hex) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            report.parse = Check::Failed(e);
+            return report;
+        }
+    };
+    report.parse = Check::Passed;
+    let (x, y) = match parse_coordinates(&x, &y, parse_field_be_bytes) {
+        Ok(coordinates) => coordinates,
+        Err(e) => {
+            report.canonical = Check::Failed(e);
+            return report;
+        }
+    };
+    report.canonical = Check::Passed;
+    match point_from_coordinates(x, y) {
+        Ok(_) => {
+            report.on_curve = Check::Passed;
+            report.subgroup = Check::Passed;
+            report.likely_encodable = Check::Passed;
+        }
+        Err(Error::NotOnCurve) => report.on_curve = Check::Failed(Error::NotOnCurve),
+        Err(e) => {
+            report.on_curve = Check::Passed;
+            report.subgroup = Check::Failed(e);
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embed_plaintext;
+
+    #[test]
+    fn test_validate_embedding() {
+        let p = embed_plaintext(42);
+        let report = validate_embedding(&p.0, &p.1);
+        assert!(report.is_valid() && report.error().is_none());
+        assert_eq!(report.to_string(), "parse : passed\ncanonical : passed\non curve : passed\nsubgroup : passed\nlikely encodable : passed\n");
+
+        let report = validate_embedding(&p.0, "0x1g");
+        assert!(matches!(&report.parse, Check::Failed(Error::InvalidCoordinate { coordinate, .. }) if coordinate == "y"));
+        assert_eq!((&report.canonical, &report.likely_encodable), (&Check::Skipped, &Check::Skipped));
+        let modulus = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+        let report = validate_embedding(modulus, &p.1);
+        assert_eq!(report.parse, Check::Passed);
+        assert!(matches!(report.error(), Some(Error::InvalidCoordinate { error, .. }) if matches!(**error, Error::NotInField { .. })));
+        assert_eq!(validate_embedding("0x1", "0x1").on_curve, Check::Failed(Error::NotOnCurve));
+        // (0, -1) is the point of order 2 of Baby Jubjub
+        let report = validate_embedding("0x0", "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000");
+        assert_eq!((report.on_curve, report.subgroup), (Check::Passed, Check::Failed(Error::NotInSubgroup)));
+        // the neutral element is the embedding of 0
+        assert!(validate_embedding("0x0", "0x1").is_valid());
+    }
+}
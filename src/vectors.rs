@@ -0,0 +1,168 @@
+//! Deterministic test vectors, so that the tests of Noir circuits, Solidity contracts and other integrations check their encryptions
+//! and decryptions against the same fixtures as this crate, instead of copying a few hardcoded values.
+//!
+//! A suite of [`TestVector`]s is generated from a seed by [`generate`] : every value of the suite is derived from `SHA-512` of the seed
+//! followed by a label, see [`crate::private_key_from_seed`], so the same seed gives the same suite on every platform and with every
+//! version of this crate. All the vectors of a suite share the private key derived from the seed, and their plaintexts are the edge
+//! cases of [`edge_plaintexts`] followed by pseudo-random plaintexts.
+//!
+//! [`generate_all`] generates the same suites for every curve and every profile of Baby Jubjub enabled by the features, see [`Suite`],
+//! so that the wrappers of this crate in other languages are checked against one canonical corpus.
+//!
+//! The `babygiant vectors` command prints the suite as JSON, see [`TestVector`], or the whole corpus with `--all`.
+
+use sha2::{Digest, Sha512};
+use crate::dlog::Curve;
+use crate::profile::Profile;
+use crate::{exp_elgamal_decrypt, exp_elgamal_encrypt, priv_to_pub_key, private_key_from_seed, Error, HexPoint};
+
+/// Encryption of `plaintext` for the public key of `private_key` with `randomness`, as returned by [`crate::exp_elgamal_encrypt`],
+/// and its decryption `embedded`, as returned by [`crate::exp_elgamal_decrypt`], whose Discrete Logarithm is `plaintext`.
+///
+/// With the `serde` feature, a vector is (de)serialized as `{"plaintext": 42, "private_key": "0x...", "public_key": ["0x...", "0x..."],
+/// "randomness": "0x...", "c1": [...], "c2": [...], "embedded": [...]}`, each point being the array of its coordinates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TestVector {
+    pub plaintext: u64,
+    pub private_key: String,
+    pub public_key: HexPoint,
+    pub randomness: String,
+    pub c1: HexPoint,
+    pub c2: HexPoint,
+    pub embedded: HexPoint,
+}
+
+/// Suite of [`TestVector`]s of a curve, in the encoding of its module, or of a [`Profile`] of Baby Jubjub, in the encoding of the profile.
+///
+/// With the `serde` feature, a suite is (de)serialized as `{"curve": "babyjubjub", "profile": "zkay", "vectors": [...]}`, the profile
+/// being `null` for the other curves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Suite {
+    pub curve: String,
+    pub profile: Option<String>,
+    pub vectors: Vec<TestVector>,
+}
+
+/// Edge cases of the plaintexts of `max_bitwidth` bits, between `1` and `64`, in increasing order : `0`, `1`, `2`, the integers around
+/// the boundaries of the first and second giant steps and the first integer of the last giant step of a [`crate::Solver::new`] of
+/// `max_bitwidth` bits, `2^32 - 1` and `2^40 - 1` when they fit, and the largest plaintext `2^max_bitwidth - 1`.
+pub fn edge_plaintexts(max_bitwidth: u64) -> Vec<u64> {
+    assert!((1..=64).contains(&max_bitwidth), "max_bitwidth should be between 1 and 64");
+    let max = u64::MAX >> (64 - max_bitwidth);
+    // number of baby steps, i.e the number of integers covered by a giant step
+    let m = 1u64 << (max_bitwidth / 2);
+    let mut plaintexts = vec![0, 1, 2, m - 1, m, m + 1, 2 * m - 1, 2 * m, max - m + 1, (1 << 32) - 1, (1 << 40) - 1, max];
+    plaintexts.retain(|&p| p <= max);
+    plaintexts.sort_unstable();
+    plaintexts.dedup();
+    plaintexts
+}
+
+/// Generates the suite of `seed`, with the [`edge_plaintexts`] of `max_bitwidth` bits followed by `random` pseudo-random plaintexts
+/// of at most `max_bitwidth` bits, which is between `1` and `64`.
+pub fn generate(seed: &[u8], max_bitwidth: u64, random: u64) -> Result<Vec<TestVector>, Error> {
+    generate_with(seed, max_bitwidth, random, priv_to_pub_key, exp_elgamal_encrypt, exp_elgamal_decrypt)
+}
+
+/// Generates the suites of `seed` like [`generate`] for every [`Profile`] of Baby Jubjub, then for every other [`Curve`] enabled by the
+/// features, in the order of [`Curve::all`].
+///
+/// The suites share their private key, randomness and plaintexts, which are scalars of every curve. With [`Profile::Circomlib`],
+/// the private key is the byte string hashed into the scalar.
+pub fn generate_all(seed: &[u8], max_bitwidth: u64, random: u64) -> Result<Vec<Suite>, Error> {
+    let curve = Curve::BabyJubJub.to_string();
+    let mut suites = Profile::ALL.into_iter().map(|profile| {
+        let vectors = generate_with(seed, max_bitwidth, random, |sk| profile.public_key(sk), |pk, m, r| profile.encrypt(pk, m, r), |sk, c1, c2| profile.decrypt(sk, c1, c2))?;
+        Ok(Suite { curve: curve.clone(), profile: Some(profile.to_string()), vectors })
+    }).collect::<Result<Vec<Suite>, Error>>()?;
+    for curve in Curve::all().into_iter().filter(|&curve| curve != Curve::BabyJubJub) {
+        let vectors = generate_with(seed, max_bitwidth, random, |sk| curve.priv_to_pub_key(sk), |pk, m, r| curve.encrypt(pk, m, r), |sk, c1, c2| curve.decrypt(sk, c1, c2))?;
+        suites.push(Suite { curve: curve.to_string(), profile: None, vectors });
+    }
+    Ok(suites)
+}
+
+fn generate_with(
+    seed: &[u8],
+    max_bitwidth: u64,
+    random: u64,
+    public_key: impl Fn(&str) -> Result<HexPoint, Error>,
+    encrypt: impl Fn((&str, &str), u64, &str) -> Result<(HexPoint, HexPoint), Error>,
+    decrypt: impl Fn(&str, (&str, &str), (&str, &str)) -> Result<HexPoint, Error>,
+) -> Result<Vec<TestVector>, Error> {
+    let private_key = private_key_from_seed(&derive(seed, "private-key", 0));
+    let public_key = public_key(&private_key)?;
+    let edges = edge_plaintexts(max_bitwidth);
+    let max = u64::MAX >> (64 - max_bitwidth);
+    let random_plaintexts = (0..random).map(|i| {
+        let digest = derive(seed, "plaintext", i);
+        u64::from_le_bytes(digest[..8].try_into().unwrap()) & max
+    });
+    edges
+        .into_iter()
+        .chain(random_plaintexts)
+        .enumerate()
+        .map(|(i, plaintext)| {
+            let randomness = private_key_from_seed(&derive(seed, "randomness", i as u64));
+            let (c1, c2) = encrypt((&public_key.0, &public_key.1), plaintext, &randomness)?;
+            let embedded = decrypt(&private_key, (&c1.0, &c1.1), (&c2.0, &c2.1))?;
+            Ok(TestVector { plaintext, private_key: private_key.clone(), public_key: public_key.clone(), randomness, c1, c2, embedded })
+        })
+        .collect()
+}
+
+/// `SHA-512(seed || "/" || label || "/" || index)`, with `index` as 8 big-endian bytes.
+fn derive(seed: &[u8], label: &str, index: u64) -> Vec<u8> {
+    let input = [seed, b"/", label.as_bytes(), b"/", &index.to_be_bytes()].concat();
+    Sha512::digest(input).to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{embed_plaintext, Solver};
+
+    #[test]
+    fn test_vectors() {
+        assert_eq!(edge_plaintexts(8), vec![0, 1, 2, 15, 16, 17, 31, 32, 240, 255]);
+        assert_eq!(edge_plaintexts(1), vec![0, 1]);
+        let edges = edge_plaintexts(40);
+        assert!(edges.contains(&((1 << 32) - 1)) && edges.contains(&((1 << 20) - 1)) && edges.contains(&((1 << 40) - (1 << 20))));
+        assert_eq!(edges.last(), Some(&((1 << 40) - 1)));
+
+        let suite = generate(b"babygiant", 16, 4).unwrap();
+        assert_eq!(suite.len(), edge_plaintexts(16).len() + 4);
+        assert_eq!(suite, generate(b"babygiant", 16, 4).unwrap());
+        assert_ne!(suite[0].randomness, suite[1].randomness);
+        assert_ne!(suite[0].private_key, generate(b"other", 16, 4).unwrap()[0].private_key);
+        let solver = Solver::new(16, 1);
+        for vector in &suite {
+            assert_eq!(vector.embedded, embed_plaintext(vector.plaintext));
+            assert_eq!(solver.solve(&vector.embedded.0, &vector.embedded.1), Ok(vector.plaintext));
+        }
+        // the derivation is fixed, so that fixtures generated with other versions stay valid
+        assert_eq!(suite[0].private_key, "0x012b87ddd8d109a863faf3cdc0a655a1842690287606fa4cc49ce8ebb692ce00");
+    }
+
+    #[test]
+    fn test_all_vectors() {
+        let suites = generate_all(b"babygiant", 8, 2).unwrap();
+        assert_eq!(suites.len(), Profile::ALL.len() + Curve::all().len() - 1);
+        assert_eq!(suites[0].vectors, generate(b"babygiant", 8, 2).unwrap());
+        for suite in &suites {
+            for vector in &suite.vectors {
+                let (x, y) = (&vector.embedded.0, &vector.embedded.1);
+                let dlog = match &suite.profile {
+                    Some(profile) => profile.parse::<Profile>().and_then(|profile| profile.solve(&profile.solver(8, 4, 1), x, y)),
+                    None => crate::compute_dlog_on(suite.curve.parse().unwrap(), x, y, &crate::SolveConfig::new(8, 1)),
+                };
+                assert_eq!(dlog, Ok(vector.plaintext), "{} {:?}", suite.curve, suite.profile);
+            }
+        }
+        // the circomlib public key is the one of the hashed private key, and the points of zkay are not in the encoding of Noir
+        assert_ne!(suites[1].vectors[0].public_key, suites[0].vectors[0].public_key);
+        assert!(Profile::Noir.to_noir(&suites[2].vectors[0].c1.0, &suites[2].vectors[0].c1.1).is_err());
+    }
+}
@@ -0,0 +1,227 @@
+//! Directory watcher decrypting the ciphertext files dropped in a directory, for machines which cannot run a service, e.g air-gapped ones.
+//!
+//! Each input file `<name>.jsonl` holds one job per line : either a ciphertext `{"c1": {"x": ..., "y": ...}, "c2": {"x": ..., "y": ...}}`,
+//! decrypted with the private key of the [`Watcher`], or an embedded plaintext `{"x": ..., "y": ...}`. Its results are written to
+//! `<name>.results.jsonl`, one line `{"dlog": 42}` or `{"error": "..."}` per job, in the same order. The files which are not valid
+//! UTF-8 get a single error line, since their jobs cannot be told apart.
+//!
+//! - Files modified less than a settle time ago are left for a later poll, since they may still be being written.
+//!   Producers can also write their files under another name and rename them to `<name>.jsonl` once complete.
+//! - A file is locked by atomically creating `<name>.jsonl.lock`, so that several watchers can share a directory. A lock left by a
+//!   crashed watcher must be removed by hand, which makes the file be processed again.
+//! - A file which cannot be read or whose results cannot be written is reported on the standard error and retried at the next poll,
+//!   without stopping the watcher.
+//! - The results are written to a temporary file, which is then renamed to `<name>.results.jsonl`, so that consumers never read
+//!   partial results : a file is done as soon as its results exist.
+
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime};
+use crate::{decrypt_to_u64, Ciphertext, Solver};
+
+const INPUT_EXTENSION: &str = ".jsonl";
+const RESULTS_EXTENSION: &str = ".results.jsonl";
+
+#[derive(Deserialize)]
+struct Point {
+    x: String,
+    y: String,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Job {
+    Ciphertext { c1: Point, c2: Point },
+    Plaintext(Point),
+}
+
+#[derive(Serialize)]
+struct JobResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dlog: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Watcher of the ciphertext files of a directory, see the [module documentation](self).
+pub struct Watcher<'a> {
+    dir: PathBuf,
+    solver: &'a Solver,
+    private_key: Option<String>,
+    settle_time: Duration,
+}
+
+impl<'a> Watcher<'a> {
+    pub fn new(dir: impl Into<PathBuf>, solver: &'a Solver) -> Self {
+        Watcher { dir: dir.into(), solver, private_key: None, settle_time: Duration::from_secs(1) }
+    }
+
+    /// Sets the private key decrypting the ciphertexts. Without it, only embedded plaintexts can be solved.
+    pub fn with_private_key(mut self, private_key: String) -> Self {
+        self.private_key = Some(private_key);
+        self
+    }
+
+    /// Sets the time since their last modification after which files are processed (`1s` by default).
+    pub fn with_settle_time(mut self, settle_time: Duration) -> Self {
+        self.settle_time = settle_time;
+        self
+    }
+
+    /// Processes the files of the directory which are ready, until `stop` is set, and returns the paths of the results written.
+    ///
+    /// `stop` is checked between two files, so the file being processed is always completed. Only the errors listing the directory
+    /// are returned, the ones of its files are reported on the standard error.
+    pub fn poll(&self, stop: &AtomicBool) -> io::Result<Vec<PathBuf>> {
+        let mut inputs: Vec<PathBuf> = fs::read_dir(&self.dir)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.to_str().is_some_and(|p| p.ends_with(INPUT_EXTENSION) && !p.ends_with(RESULTS_EXTENSION)))
+            .collect();
+        inputs.sort();
+        let mut written = Vec::new();
+        for input in inputs {
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
+            match self.process_if_ready(&input) {
+                Ok(Some(results)) => written.push(results),
+                Ok(None) => {}
+                // the lock was released, so the file is retried at the next poll
+                Err(e) => eprintln!("Error: unable to process {} : {}", input.display(), e),
+            }
+        }
+        Ok(written)
+    }
+
+    /// Polls the directory every `interval` until `stop` is set, calling `on_results` with the path of each results file written.
+    ///
+    /// `stop` is checked between two files, as in [`Watcher::poll`].
+    pub fn run(&self, interval: Duration, stop: &AtomicBool, mut on_results: impl FnMut(&Path)) -> io::Result<()> {
+        loop {
+            for results in self.poll(stop)? {
+                on_results(&results);
+            }
+            if stop.load(Ordering::Relaxed) {
+                return Ok(());
+            }
+            std::thread::sleep(interval);
+        }
+    }
+
+    fn process_if_ready(&self, input: &Path) -> io::Result<Option<PathBuf>> {
+        let name = input.to_str().unwrap().strip_suffix(INPUT_EXTENSION).unwrap();
+        let results = PathBuf::from(format!("{}{}", name, RESULTS_EXTENSION));
+        let lock = PathBuf::from(format!("{}{}.lock", name, INPUT_EXTENSION));
+        if results.exists() {
+            return Ok(None);
+        }
+        let modified = fs::metadata(input)?.modified()?;
+        if SystemTime::now().duration_since(modified).unwrap_or_default() < self.settle_time {
+            return Ok(None);
+        }
+        match OpenOptions::new().write(true).create_new(true).open(&lock) {
+            Ok(mut file) => writeln!(file, "{}", std::process::id())?,
+            // another watcher is processing the file
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => return Ok(None),
+            Err(e) => return Err(e),
+        }
+        // another watcher may have processed the file between the first check and the lock
+        if results.exists() {
+            fs::remove_file(&lock)?;
+            return Ok(None);
+        }
+        let processed = self.process(input, &results);
+        fs::remove_file(&lock)?;
+        processed.map(|()| Some(results))
+    }
+
+    fn process(&self, input: &Path, results: &Path) -> io::Result<()> {
+        let lines: Vec<Result<u64, String>> = match String::from_utf8(fs::read(input)?) {
+            Ok(contents) => contents.lines().map(str::trim).filter(|line| !line.is_empty()).map(|line| self.solve(line)).collect(),
+            Err(_) => vec![Err("Invalid input file : it is not valid UTF-8".to_string())],
+        };
+        let mut output = String::new();
+        for line in lines {
+            let result = match line {
+                Ok(dlog) => JobResult { dlog: Some(dlog), error: None },
+                Err(error) => JobResult { dlog: None, error: Some(error) },
+            };
+            output.push_str(&serde_json::to_string(&result).unwrap());
+            output.push('\n');
+        }
+        let tmp = results.with_extension("jsonl.tmp");
+        let mut file = File::create(&tmp)?;
+        file.write_all(output.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&tmp, results)
+    }
+
+    fn solve(&self, line: &str) -> Result<u64, String> {
+        match serde_json::from_str::<Job>(line).map_err(|_| "Invalid job : expected a ciphertext {\"c1\", \"c2\"} or a point {\"x\", \"y\"}".to_string())? {
+            Job::Plaintext(p) => self.solver.solve(&p.x, &p.y),
+            Job::Ciphertext { c1, c2 } => {
+                let private_key = self.private_key.as_deref().ok_or("A private key is needed to decrypt ciphertexts")?;
+                Ciphertext::new((&c1.x, &c1.y), (&c2.x, &c2.y)).and_then(|ciphertext| decrypt_to_u64(private_key, &ciphertext, self.solver))
+            }
+        }
+        .map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{exp_elgamal_encrypt, priv_to_pub_key};
+
+    #[test]
+    fn test_watcher() {
+        let dir = std::env::temp_dir().join(format!("babygiant-watch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let sk = "0x04d73359c9166e49aafaf9a4852eaa4dceb2c26878196b10e9048004ff5cc20c";
+        let pk = priv_to_pub_key(sk).unwrap();
+        let (c1, c2) = exp_elgamal_encrypt((&pk.0, &pk.1), 1234, "0x03").unwrap();
+        let jobs = format!(
+            "{{\"c1\": {{\"x\": \"{}\", \"y\": \"{}\"}}, \"c2\": {{\"x\": \"{}\", \"y\": \"{}\"}}}}\n\n{{\"x\": \"0x0\", \"y\": \"0x1\"}}\n{{\"x\": \"0x1\", \"y\": \"0x1\"}}\nnot json\n",
+            c1.0, c1.1, c2.0, c2.1
+        );
+        fs::write(dir.join("tally.jsonl"), jobs).unwrap();
+
+        let solver = Solver::new(16, 1);
+        let (running, stopped) = (AtomicBool::new(false), AtomicBool::new(true));
+        // the file was just written
+        assert_eq!(Watcher::new(&dir, &solver).poll(&running).unwrap(), Vec::<PathBuf>::new());
+        let watcher = Watcher::new(&dir, &solver).with_private_key(sk.to_string()).with_settle_time(Duration::ZERO);
+        // a stopped watcher does not start any file
+        assert_eq!(watcher.poll(&stopped).unwrap(), Vec::<PathBuf>::new());
+        assert_eq!(watcher.poll(&running).unwrap(), vec![dir.join("tally.results.jsonl")]);
+        let results = fs::read_to_string(dir.join("tally.results.jsonl")).unwrap();
+        let lines: Vec<&str> = results.lines().collect();
+        assert_eq!(lines[..2], ["{\"dlog\":1234}", "{\"dlog\":0}"]);
+        assert!(lines[2].starts_with("{\"error\":\"(x,y) is not a valid point"));
+        assert!(lines[3].starts_with("{\"error\":\"Invalid job"));
+        assert!(!dir.join("tally.jsonl.lock").exists());
+        // the files are only processed once
+        assert_eq!(watcher.poll(&running).unwrap(), Vec::<PathBuf>::new());
+
+        // a file which is not valid UTF-8 is reported in its results, without stopping the watcher
+        fs::write(dir.join("binary.jsonl"), b"{\"x\": \"0x0\", \"y\": \"0x1\"}\n\xff\xfe\n").unwrap();
+        fs::write(dir.join("valid.jsonl"), "{\"x\": \"0x0\", \"y\": \"0x1\"}\n").unwrap();
+        assert_eq!(watcher.poll(&running).unwrap(), vec![dir.join("binary.results.jsonl"), dir.join("valid.results.jsonl")]);
+        assert_eq!(fs::read_to_string(dir.join("binary.results.jsonl")).unwrap(), "{\"error\":\"Invalid input file : it is not valid UTF-8\"}\n");
+
+        // a locked file is left to the watcher holding the lock
+        fs::write(dir.join("locked.jsonl"), "{\"x\": \"0x0\", \"y\": \"0x1\"}\n").unwrap();
+        fs::write(dir.join("locked.jsonl.lock"), "1").unwrap();
+        assert_eq!(watcher.poll(&running).unwrap(), Vec::<PathBuf>::new());
+
+        // a file which cannot be read does not stop the watcher from processing the other ones
+        fs::remove_file(dir.join("locked.jsonl.lock")).unwrap();
+        fs::create_dir(dir.join("unreadable.jsonl")).unwrap();
+        assert_eq!(watcher.poll(&running).unwrap(), vec![dir.join("locked.results.jsonl")]);
+        assert!(!dir.join("unreadable.jsonl.lock").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,177 @@
+//! Queue worker, consuming decryption jobs from a Redis list or a NATS JetStream subject and publishing their results.
+//!
+//! A job is a point `{"x": "0x...", "y": "0x..."}`, and its result is the same point with either its `dlog` or an `error`.
+//! Jobs are only acknowledged once their result has been published, so they are processed at least once,
+//! and the Discrete Logarithm of each point is stored in a shared store, so that redelivered jobs are not solved twice.
+
+use async_nats::jetstream::{self, consumer::pull, kv, stream};
+use futures_util::StreamExt;
+use redis::{AsyncCommands, Direction};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use crate::point::canonical_key;
+use crate::server::{DlogResult, PointRequest};
+use crate::Solver;
+
+/// Errors of the Redis or NATS connections.
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Result published for each job.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobResult {
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub point: Option<PointRequest>,
+    #[serde(flatten)]
+    pub result: DlogResult,
+}
+
+/// Solves the job `payload`, whose `point` was parsed by [`parse_job`], and returns its result serialized as JSON.
+async fn process(solver: &Arc<Solver>, payload: &[u8], point: Option<PointRequest>) -> Vec<u8> {
+    let result = match point {
+        Some(point) => {
+            let solver = solver.clone();
+            let p = point.clone();
+            let result = tokio::task::spawn_blocking(move || solver.solve(&p.x, &p.y)).await.unwrap();
+            JobResult { point: Some(point), result: DlogResult::from(&result) }
+        }
+        None => {
+            let error = serde_json::from_slice::<PointRequest>(payload).err().map(|e| format!("Invalid job : {}", e));
+            JobResult { point: None, result: DlogResult { dlog: None, error } }
+        }
+    };
+    serde_json::to_vec(&result).unwrap()
+}
+
+/// Parses the point of a job, and its key in the store of results.
+///
+/// Invalid points have no key, since there is nothing to save by storing their result.
+fn parse_job(payload: &[u8]) -> (Option<PointRequest>, Option<String>) {
+    let point = serde_json::from_slice::<PointRequest>(payload).ok();
+    let key = point.as_ref().and_then(|p| canonical_key(&p.x, &p.y).ok());
+    (point, key)
+}
+
+/// Names of the Redis keys used by [`run_redis`].
+#[derive(Debug, Clone)]
+pub struct RedisConfig {
+    pub url: String,
+    /// List the jobs are popped from.
+    pub jobs: String,
+    /// List the results are pushed to.
+    pub results: String,
+    /// Identifier of the worker, naming its list `<jobs>:processing:<worker_id>` of jobs being processed.
+    pub worker_id: String,
+}
+
+/// Processes the jobs of a Redis list forever.
+///
+/// Each job is atomically moved to the processing list of the worker, and removed from it once its result has been pushed,
+/// so the jobs left in this list by a crashed worker are requeued when it restarts.
+/// The results are also stored in the hash `<results>:by-point`, keyed by point.
+pub async fn run_redis(solver: Arc<Solver>, config: &RedisConfig) -> Result<(), BoxError> {
+    let mut con = redis::Client::open(config.url.as_str())?.get_multiplexed_async_connection().await?;
+    let processing = format!("{}:processing:{}", config.jobs, config.worker_id);
+    let by_point = format!("{}:by-point", config.results);
+    loop {
+        let requeued: Option<Vec<u8>> = con.lmove(&processing, &config.jobs, Direction::Right, Direction::Right).await?;
+        if requeued.is_none() {
+            break;
+        }
+    }
+    loop {
+        let payload: Option<Vec<u8>> = con.blmove(&config.jobs, &processing, Direction::Right, Direction::Left, 0.0).await?;
+        let Some(payload) = payload else { continue };
+        let (point, key) = parse_job(&payload);
+        let cached: Option<Vec<u8>> = match &key {
+            Some(key) => con.hget(&by_point, key).await?,
+            None => None,
+        };
+        let result = match cached {
+            Some(result) => result,
+            None => {
+                let result = process(&solver, &payload, point).await;
+                if let Some(key) = &key {
+                    let _: () = con.hset(&by_point, key, &result).await?;
+                }
+                result
+            }
+        };
+        let _: () = con.lpush(&config.results, &result).await?;
+        let _: () = con.lrem(&processing, 1, &payload).await?;
+    }
+}
+
+/// Names of the NATS JetStream resources used by [`run_nats`].
+#[derive(Debug, Clone)]
+pub struct NatsConfig {
+    pub url: String,
+    /// Stream capturing the subject of the jobs, created if it does not exist.
+    pub stream: String,
+    /// Subject the jobs are published on.
+    pub jobs: String,
+    /// Subject the results are published on.
+    pub results: String,
+    /// Durable consumer shared by all the workers.
+    pub consumer: String,
+    /// Key-value bucket storing the results by point.
+    pub bucket: String,
+}
+
+/// Processes the jobs of a NATS JetStream subject forever.
+///
+/// The jobs are acknowledged once their result has been published, so the jobs of a crashed worker are redelivered to another one.
+pub async fn run_nats(solver: Arc<Solver>, config: &NatsConfig) -> Result<(), BoxError> {
+    let client = async_nats::connect(config.url.as_str()).await?;
+    let context = jetstream::new(client.clone());
+    let store = context.create_key_value(kv::Config { bucket: config.bucket.clone(), ..Default::default() }).await?;
+    let stream = context
+        .get_or_create_stream(stream::Config { name: config.stream.clone(), subjects: vec![config.jobs.clone()], ..Default::default() })
+        .await?;
+    let consumer = stream
+        .get_or_create_consumer(&config.consumer, pull::Config { durable_name: Some(config.consumer.clone()), ..Default::default() })
+        .await?;
+    let mut messages = consumer.messages().await?;
+    while let Some(message) = messages.next().await {
+        let message = message?;
+        let (point, key) = parse_job(&message.payload);
+        let cached = match &key {
+            Some(key) => store.get(key).await?.map(|b| b.to_vec()),
+            None => None,
+        };
+        let result = match cached {
+            Some(result) => result,
+            None => {
+                let result = process(&solver, &message.payload, point).await;
+                if let Some(key) = &key {
+                    store.put(key, result.clone().into()).await?;
+                }
+                result
+            }
+        };
+        client.publish(config.results.clone(), result.into()).await?;
+        client.flush().await?;
+        message.ack().await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_process() {
+        let solver = Arc::new(Solver::new(16, 1));
+        let payload = br#"{"x": "0xbb77a6ad63e739b4eacb2e09d6277c12ab8d8010534e0b62893f3f6bb957051", "y": "0x25797203f7a0b24925572e1cd16bf9edfce0051fb9e133774b3c257a872d7d8b"}"#;
+        let (point, key) = parse_job(payload);
+        // the key does not depend on the padding of the coordinates
+        assert_eq!(key.unwrap(), "0x0bb77a6ad63e739b4eacb2e09d6277c12ab8d8010534e0b62893f3f6bb957051_0x25797203f7a0b24925572e1cd16bf9edfce0051fb9e133774b3c257a872d7d8b");
+        let result: JobResult = serde_json::from_slice(&process(&solver, payload, point.clone()).await).unwrap();
+        assert_eq!(result, JobResult { point, result: DlogResult { dlog: Some(1), error: None } });
+
+        let (point, key) = parse_job(b"not a point");
+        assert_eq!(key, None);
+        let result: JobResult = serde_json::from_slice(&process(&solver, b"not a point", point).await).unwrap();
+        assert!(result.result.error.unwrap().starts_with("Invalid job"));
+    }
+}
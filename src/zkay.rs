@@ -0,0 +1,149 @@
+//! Compatibility with the [babygiant-lib](https://github.com/eth-sri/zkay/blob/master/babygiant-lib/src/lib.rs) of zkay, which this crate
+//! descends from, so that projects migrating from zkay to Noir can still decrypt their historical ciphertexts.
+//!
+//! zkay also uses exponential ElGamal on Baby Jubjub, but differs from noir-elgamal in three ways :
+//!
+//! - its coordinates are the ones of the Edwards form of arkworks instead of the Twisted Edwards form, see [`crate::curve`],
+//! - its generator is another point of the prime subgroup, [`GENERATOR`], so the plaintext `m` is embedded as `m·G` for this generator,
+//! - its coordinates are written as 64 hexadecimal digits without prefix of their 32 little-endian bytes, and its plaintexts are `u32`s.
+//!
+//! A [`ZkaySolver`] takes points and ciphertexts in the format of zkay and solves their Discrete Logarithms in base [`GENERATOR`].
+//! Points can also be converted between the two formats with [`to_noir`] and [`from_noir`], e.g to reuse the public keys of zkay, but converted
+//! ciphertexts still embed their plaintexts with the generator of zkay, and should be decrypted with a [`ZkaySolver`].
+
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ed_on_bn254::{EdwardsAffine as BabyJubJub, Fq};
+use ark_ff::{field_new, BigInteger, BigInteger256, PrimeField};
+use crate::point::{parse_point, parse_scalar, point_to_hex};
+use crate::{Error, HexPoint, Solver};
+
+/// Edwards coordinates of the generator of zkay, as decimal integers.
+pub const GENERATOR: (&str, &str) = (
+    "11904062828411472290643689191857696496057424932476499415469791423656658550213",
+    "9356450144216313082194365820021861619676443907964402770398322487858544118183",
+);
+
+/// Bitwidth of the plaintexts of zkay, i.e the `max_bitwidth` of its `baby_giant` call.
+pub const MAX_BITWIDTH: u64 = 32;
+
+pub(crate) fn generator() -> BabyJubJub {
+    BabyJubJub::new(
+        field_new!(Fq, "11904062828411472290643689191857696496057424932476499415469791423656658550213"),
+        field_new!(Fq, "9356450144216313082194365820021861619676443907964402770398322487858544118183"),
+    )
+}
+
+/// Solver for the Discrete Logarithms in base [`GENERATOR`] of the points of zkay.
+pub struct ZkaySolver {
+    solver: Solver,
+}
+
+impl ZkaySolver {
+    /// Builds the table of baby steps for plaintexts of at most `max_bitwidth` bits, e.g [`MAX_BITWIDTH`], using `num_threads` threads.
+    pub fn new(max_bitwidth: u64, num_threads: u64) -> Self {
+        ZkaySolver { solver: Solver::with_generator(generator(), max_bitwidth, max_bitwidth / 2, num_threads) }
+    }
+
+    pub fn max_bitwidth(&self) -> u64 {
+        self.solver.max_bitwidth()
+    }
+
+    /// Computes the Discrete Logarithm of the point `(x, y)` in the format of zkay, like its `do_compute_dlog`.
+    pub fn solve(&self, x: &str, y: &str) -> Result<u64, Error> {
+        self.solver.search_point(parse_zkay_point(x, y)?).run()
+    }
+
+    /// Decrypts the ciphertext `(c1, c2)` of zkay with `private_key` down to its plaintext, i.e solves `c2 - sk·c1`.
+    ///
+    /// `private_key` is the integer of the private key of zkay, in the same format as the other inputs of this crate, e.g `0x05`.
+    pub fn decrypt(&self, private_key: &str, c1: (&str, &str), c2: (&str, &str)) -> Result<u64, Error> {
+        let sk = parse_scalar(private_key)?;
+        let c1 = parse_zkay_point(c1.0, c1.1)?;
+        let c2 = parse_zkay_point(c2.0, c2.1)?;
+        self.solver.search_point((c2.into_projective() - c1.mul(sk)).into_affine()).run()
+    }
+}
+
+/// Computes the Discrete Logarithm of a `u32` plaintext embedded in the point `(x, y)` in the format of zkay, with the same inputs as
+/// `do_compute_dlog` in zkay, using `num_threads` threads.
+pub fn do_compute_dlog(x: &str, y: &str, num_threads: u64) -> Result<u64, Error> {
+    ZkaySolver::new(MAX_BITWIDTH, num_threads).solve(x, y)
+}
+
+/// Converts the point `(x, y)` in the format of zkay to its Twisted Edwards coordinates, in the format of noir-elgamal.
+pub fn to_noir(x: &str, y: &str) -> Result<HexPoint, Error> {
+    Ok(point_to_hex(&parse_zkay_point(x, y)?))
+}
+
+/// Converts the point `(x, y)` in the format of noir-elgamal to its coordinates in the format of zkay.
+pub fn from_noir(x: &str, y: &str) -> Result<(String, String), Error> {
+    let p = parse_point(x, y)?;
+    Ok((to_le_hex(&p.x), to_le_hex(&p.y)))
+}
+
+/// Parses the Edwards coordinates of a point of the prime subgroup, as written by zkay.
+pub(crate) fn parse_zkay_point(x: &str, y: &str) -> Result<BabyJubJub, Error> {
+    let p = BabyJubJub::new(parse_le_hex(x)?, parse_le_hex(y)?);
+    if !p.is_on_curve() {
+        return Err(Error::NotOnCurve);
+    }
+    if !p.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(Error::NotInSubgroup);
+    }
+    Ok(p)
+}
+
+/// Parses a field element written as the 64 hexadecimal digits of its little-endian bytes, like `parse_le_bytes_str` in zkay.
+fn parse_le_hex(s: &str) -> Result<Fq, Error> {
+    let invalid = || Error::InvalidFormat(s.to_string());
+    let bytes = hex::decode(s).map_err(|_| invalid())?;
+    if bytes.len() != 32 {
+        return Err(invalid());
+    }
+    let mut limbs = [0u64; 4];
+    for (limb, word) in limbs.iter_mut().zip(bytes.chunks_exact(8)) {
+        *limb = u64::from_le_bytes(word.try_into().unwrap());
+    }
+    Fq::from_repr(BigInteger256::new(limbs)).ok_or_else(invalid)
+}
+
+fn to_le_hex(f: &Fq) -> String {
+    hex::encode(f.into_repr().to_bytes_le())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ed_on_bn254::Fr;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_zkay() {
+        let g = generator();
+        assert!(g.is_on_curve() && g.is_in_correct_subgroup_assuming_on_curve());
+        assert_eq!((g.x, g.y), (Fq::from_str(GENERATOR.0).unwrap(), Fq::from_str(GENERATOR.1).unwrap()));
+        let (gx, gy) = (to_le_hex(&g.x), to_le_hex(&g.y));
+        assert_eq!(gy, "a7099eb9f4b811bbd4ea1643e449bd1551d732d9ebc81833e5e33a3c2890af14");
+
+        let solver = ZkaySolver::new(16, 2);
+        let p = g.mul(Fr::from(40000u64)).into_affine();
+        assert_eq!(solver.solve(&to_le_hex(&p.x), &to_le_hex(&p.y)), Ok(40000));
+        assert_eq!(solver.solve(&gx, &gy), Ok(1));
+
+        // c1 = r·G, c2 = m·G + r·pk, for pk = sk·G
+        let (sk, r) = (Fr::from(5u64), Fr::from(7u64));
+        let pk = g.mul(sk);
+        let c1 = g.mul(r).into_affine();
+        let c2 = (g.mul(Fr::from(1234u64)) + pk.into_affine().mul(r)).into_affine();
+        let c1 = (to_le_hex(&c1.x), to_le_hex(&c1.y));
+        let c2 = (to_le_hex(&c2.x), to_le_hex(&c2.y));
+        assert_eq!(solver.decrypt("0x05", (&c1.0, &c1.1), (&c2.0, &c2.1)), Ok(1234));
+
+        // the generator of zkay is another point than the one of noir-elgamal
+        let noir = to_noir(&gx, &gy).unwrap();
+        assert_ne!(noir, point_to_hex(&crate::point::base_point()));
+        assert_eq!(from_noir(&noir.0, &noir.1), Ok((gx.clone(), gy.clone())));
+        assert_eq!(solver.solve(&format!("0x{}", gx), &gy), Err(Error::InvalidFormat(format!("0x{}", gx))));
+        assert_eq!(solver.solve(&gx, &gx), Err(Error::NotOnCurve));
+    }
+}